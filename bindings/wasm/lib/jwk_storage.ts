@@ -84,6 +84,20 @@ export class JwkMemStore implements JwkStorage {
         return this._keys.has(keyId);
     }
 
+    public async getPublicKey(keyId: string): Promise<Jwk> {
+        const jwk = this._keys.get(keyId);
+        if (!jwk) {
+            throw new Error(`key with id ${keyId} not found`);
+        }
+
+        const publicJWK = jwk.toPublic();
+        if (!publicJWK) {
+            throw new Error(`JWK is not a public key`);
+        }
+
+        return publicJWK;
+    }
+
     public count(): number {
         return this._keys.size;
     }
@@ -116,10 +116,10 @@ impl WasmCredential {
       .wasm_result()
   }
 
-  /// Returns a copy of the timestamp of when the {@link Credential} becomes valid.
+  /// Returns a copy of the timestamp of when the {@link Credential} becomes valid, as defined by VC Data Model v1.1.
   #[wasm_bindgen(js_name = "issuanceDate")]
-  pub fn issuance_date(&self) -> WasmTimestamp {
-    WasmTimestamp::from(self.0.issuance_date)
+  pub fn issuance_date(&self) -> Option<WasmTimestamp> {
+    self.0.issuance_date.map(WasmTimestamp::from)
   }
 
   /// Returns a copy of the timestamp of when the {@link Credential} should no longer be considered valid.
@@ -128,6 +128,31 @@ impl WasmCredential {
     self.0.expiration_date.map(WasmTimestamp::from)
   }
 
+  /// Returns a copy of the timestamp of when the {@link Credential} becomes valid, as defined by VC Data Model v2.0.
+  #[wasm_bindgen(js_name = "validFrom")]
+  pub fn valid_from(&self) -> Option<WasmTimestamp> {
+    self.0.valid_from.map(WasmTimestamp::from)
+  }
+
+  /// Returns a copy of the timestamp of when the {@link Credential} should no longer be considered valid, as defined
+  /// by VC Data Model v2.0.
+  #[wasm_bindgen(js_name = "validUntil")]
+  pub fn valid_until(&self) -> Option<WasmTimestamp> {
+    self.0.valid_until.map(WasmTimestamp::from)
+  }
+
+  /// Returns a copy of the human-readable name of the {@link Credential}.
+  #[wasm_bindgen]
+  pub fn name(&self) -> Option<String> {
+    self.0.name.clone()
+  }
+
+  /// Returns a copy of the human-readable description of the {@link Credential}.
+  #[wasm_bindgen]
+  pub fn description(&self) -> Option<String> {
+    self.0.description.clone()
+  }
+
   /// Returns a copy of the information used to determine the current status of the {@link Credential}.
   #[wasm_bindgen(js_name = "credentialStatus")]
   pub fn credential_status(&self) -> Result<ArrayStatus> {
@@ -7,6 +7,7 @@ use identity_iota::core::OneOrMany;
 use identity_iota::core::Timestamp;
 use identity_iota::core::Url;
 use identity_iota::credential::CredentialBuilder;
+use identity_iota::credential::DataModelVersion;
 use identity_iota::credential::Evidence;
 use identity_iota::credential::Issuer;
 use identity_iota::credential::Policy;
@@ -33,6 +34,10 @@ impl TryFrom<ICredential> for CredentialBuilder {
       issuer,
       issuance_date,
       expiration_date,
+      valid_from,
+      valid_until,
+      name,
+      description,
       credential_status,
       credential_schema,
       refresh_service,
@@ -47,7 +52,10 @@ impl TryFrom<ICredential> for CredentialBuilder {
 
     if let Some(context) = context {
       for value in context.into_vec() {
-        builder = builder.context(value);
+        builder = match DataModelVersion::from_base_context(&value) {
+          Some(version) => builder.data_model_version(version),
+          None => builder.context(value),
+        };
       }
     }
     if let Some(id) = id {
@@ -72,6 +80,18 @@ impl TryFrom<ICredential> for CredentialBuilder {
     if let Some(expiration_date) = expiration_date {
       builder = builder.expiration_date(expiration_date);
     }
+    if let Some(valid_from) = valid_from {
+      builder = builder.valid_from(valid_from);
+    }
+    if let Some(valid_until) = valid_until {
+      builder = builder.valid_until(valid_until);
+    }
+    if let Some(name) = name {
+      builder = builder.name(name);
+    }
+    if let Some(description) = description {
+      builder = builder.description(description);
+    }
     if let Some(credential_status) = credential_status {
       builder = builder.status(credential_status);
     }
@@ -132,12 +152,26 @@ struct ICredentialHelper {
   /// A reference to the issuer of the {@link Credential}.
   #[typescript(optional = false, type = "string | CoreDID | IotaDID | Issuer")]
   issuer: Option<Issuer>,
-  /// A timestamp of when the {@link Credential} becomes valid. Defaults to the current datetime.
+  /// A timestamp of when the {@link Credential} becomes valid. Defaults to the current datetime for VC Data Model v1.1
+  /// credentials.
   #[typescript(name = "issuanceDate", type = "Timestamp")]
   issuance_date: Option<Timestamp>,
   /// A timestamp of when the {@link Credential} should no longer be considered valid.
   #[typescript(name = "expirationDate", type = "Timestamp")]
   expiration_date: Option<Timestamp>,
+  /// A timestamp of when the {@link Credential} becomes valid, as defined by VC Data Model v2.0.
+  #[typescript(name = "validFrom", type = "Timestamp")]
+  valid_from: Option<Timestamp>,
+  /// A timestamp of when the {@link Credential} should no longer be considered valid, as defined by VC Data Model
+  /// v2.0.
+  #[typescript(name = "validUntil", type = "Timestamp")]
+  valid_until: Option<Timestamp>,
+  /// A human-readable name of the {@link Credential}, as defined by VC Data Model v2.0.
+  #[typescript(type = "string")]
+  name: Option<String>,
+  /// A human-readable description of the {@link Credential}, as defined by VC Data Model v2.0.
+  #[typescript(type = "string")]
+  description: Option<String>,
   /// Information used to determine the current status of the {@link Credential}.
   #[typescript(name = "credentialStatus", type = "Status")]
   credential_status: Option<Status>,
@@ -111,13 +111,17 @@ impl From<&ServiceEndpoint> for UServiceEndpoint {
         }
         js_map.unchecked_into::<UServiceEndpoint>()
       }
+      // Map<string, any>
+      ServiceEndpoint::Object(object) => MapStringAny::try_from(object)
+        .expect("a JSON object can be converted to a JS map")
+        .unchecked_into::<UServiceEndpoint>(),
     }
   }
 }
 
 #[wasm_bindgen]
 extern "C" {
-  #[wasm_bindgen(typescript_type = "string | string[] | Map<string, string[]>")]
+  #[wasm_bindgen(typescript_type = "string | string[] | Map<string, string[]> | Map<string, any>")]
   pub type UServiceEndpoint;
 }
 
@@ -160,11 +164,12 @@ interface IService {
     readonly type: string | string[];
 
     /**
-     * A URL, set of URLs, or map of URL sets.
+     * A URL, set of URLs, map of URL sets, or any other map, e.g. a DIDComm v2 endpoint.
      *
-     * NOTE: throws an error if any entry is not a valid URL string. List entries must be unique.
+     * NOTE: throws an error if any entry of a URL or map of URL sets is not a valid URL string. List entries must be
+     * unique.
      */
-    readonly serviceEndpoint: string | string[] | Map<string, string[]> | Record<string, string[]>;
+    readonly serviceEndpoint: string | string[] | Map<string, string[]> | Record<string, string[]> | Map<string, any> | Record<string, any>;
 
     /**
      * Additional custom properties to embed in the service.
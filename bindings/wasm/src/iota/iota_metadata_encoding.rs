@@ -11,12 +11,18 @@ use wasm_bindgen::prelude::*;
 #[repr(u8)]
 pub enum WasmStateMetadataEncoding {
   Json = 0,
+  JsonDeflate = 1,
+  JsonBrotli = 2,
+  Cbor = 3,
 }
 
 impl From<WasmStateMetadataEncoding> for StateMetadataEncoding {
   fn from(encoding: WasmStateMetadataEncoding) -> Self {
     match encoding {
       WasmStateMetadataEncoding::Json => Self::Json,
+      WasmStateMetadataEncoding::JsonDeflate => Self::JsonDeflate,
+      WasmStateMetadataEncoding::JsonBrotli => Self::JsonBrotli,
+      WasmStateMetadataEncoding::Cbor => Self::Cbor,
     }
   }
 }
@@ -50,6 +50,9 @@ extern "C" {
 
   #[wasm_bindgen(method)]
   pub fn exists(this: &WasmJwkStorage, key_id: String) -> PromiseBool;
+
+  #[wasm_bindgen(method, catch, js_name = getPublicKey)]
+  pub fn get_public_key(this: &WasmJwkStorage, key_id: String) -> Result<PromiseJwk, JsValue>;
 }
 
 #[async_trait::async_trait(?Send)]
@@ -88,6 +91,18 @@ impl JwkStorage for WasmJwkStorage {
     let result: JsValueResult = JsFuture::from(promise).await.into();
     result.into()
   }
+
+  async fn get_public_key(&self, key_id: &KeyId) -> KeyStorageResult<Jwk> {
+    // `getPublicKey` is optional, calling it on storages that do not implement it throws.
+    let promise: Promise = WasmJwkStorage::get_public_key(self, key_id.clone().into())
+      .map(|promise| Promise::resolve(&promise))
+      .map_err(|err| {
+        KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+          .with_custom_message(format!("the storage does not support retrieving public keys: {err:?}"))
+      })?;
+    let result: JsValueResult = JsFuture::from(promise).await.into();
+    result.into()
+  }
 }
 
 #[wasm_bindgen(typescript_custom_section)]
@@ -112,6 +127,10 @@ interface JwkStorage {
   delete: (keyId: string) => Promise<void>;
   /** Returns `true` if the key with the given `keyId` exists in storage, `false` otherwise. */
   exists: (keyId: string) => Promise<boolean>;
+  /** Returns the public key corresponding to the key identified by `keyId`.
+   * 
+   * Optional, storages that cannot retrieve public keys may omit it. */
+  getPublicKey?: (keyId: string) => Promise<Jwk>;
 }"#;
 
 fn uint8array_to_bytes(value: JsValue) -> KeyStorageResult<Vec<u8>> {
@@ -12,11 +12,11 @@ use identity_iota::did::DIDUrl;
 use identity_iota::did::DID;
 use identity_iota::document::Service;
 use identity_iota::iota::block::address::Address;
-use identity_iota::iota::block::output::RentStructure;
 use identity_iota::iota::IotaClientExt;
 use identity_iota::iota::IotaDID;
 use identity_iota::iota::IotaDocument;
 use identity_iota::iota::IotaIdentityClientExt;
+use identity_iota::iota::StorageDepositChange;
 use identity_iota::storage::JwkDocumentExt;
 use identity_iota::storage::JwkMemStore;
 use identity_iota::storage::KeyIdMemstore;
@@ -28,7 +28,6 @@ use iota_sdk::client::secret::SecretManager;
 use iota_sdk::client::Client;
 use iota_sdk::client::Password;
 use iota_sdk::types::block::output::AliasOutput;
-use iota_sdk::types::block::output::AliasOutputBuilder;
 
 /// Demonstrates how to update a DID document in an existing Alias Output.
 #[tokio::main]
@@ -86,14 +85,15 @@ async fn main() -> anyhow::Result<()> {
   document.purge_method(&storage, &original_method).await.unwrap();
 
   // Resolve the latest output and update it with the given document.
-  let alias_output: AliasOutput = client.update_did_output(document.clone()).await?;
-
-  // Because the size of the DID document increased, we have to increase the allocated storage deposit.
-  // This increases the deposit amount to the new minimum.
-  let rent_structure: RentStructure = client.get_rent_structure().await?;
-  let alias_output: AliasOutput = AliasOutputBuilder::from(&alias_output)
-    .with_minimum_storage_deposit(rent_structure)
-    .finish()?;
+  // Because the size of the DID document changed, the allocated storage deposit is set to the new minimum.
+  let (alias_output, deposit_change): (AliasOutput, StorageDepositChange) = client
+    .update_did_output_with_storage_deposit(document.clone(), None)
+    .await?;
+  println!(
+    "Storage deposit: {} required, {} freed",
+    deposit_change.required(),
+    deposit_change.freed()
+  );
 
   // Publish the updated Alias Output.
   let updated: IotaDocument = client.publish_did_output(&secret_manager, alias_output).await?;
@@ -1,7 +1,6 @@
 // Copyright 2020-2023 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::Context;
 use examples::create_did;
 use examples::random_stronghold_path;
 use examples::MemStorage;
@@ -16,7 +15,7 @@ use identity_iota::iota::block::output::RentStructure;
 use identity_iota::iota::IotaClientExt;
 use identity_iota::iota::IotaDID;
 use identity_iota::iota::IotaDocument;
-use identity_iota::iota::IotaIdentityClient;
+use identity_iota::iota::IotaDocumentVersion;
 use identity_iota::iota::IotaIdentityClientExt;
 use identity_iota::storage::JwkMemStore;
 use identity_iota::storage::KeyIdMemstore;
@@ -25,18 +24,10 @@ use iota_sdk::client::secret::stronghold::StrongholdSecretManager;
 use iota_sdk::client::secret::SecretManager;
 use iota_sdk::client::Client;
 use iota_sdk::client::Password;
-use iota_sdk::types::block::input::Input;
-use iota_sdk::types::block::output::AliasId;
 use iota_sdk::types::block::output::AliasOutput;
 use iota_sdk::types::block::output::AliasOutputBuilder;
-use iota_sdk::types::block::output::Output;
-use iota_sdk::types::block::output::OutputId;
-use iota_sdk::types::block::output::OutputMetadata;
-use iota_sdk::types::block::payload::transaction::TransactionEssence;
-use iota_sdk::types::block::payload::Payload;
-use iota_sdk::types::block::Block;
 
-/// Demonstrates how to obtain the alias output history.
+/// Demonstrates how to obtain the history of a DID document from its Alias Output.
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
   // Create a new client to interact with the IOTA ledger.
@@ -86,90 +77,26 @@ async fn main() -> anyhow::Result<()> {
     client.publish_did_output(&secret_manager, alias_output).await?;
   }
 
-  // ====================================
-  // Retrieving the Alias Output History
-  // ====================================
-  let mut alias_history: Vec<AliasOutput> = Vec::new();
-
-  // Step 0 - Get the latest Alias Output
-  let alias_id: AliasId = AliasId::from(client.resolve_did(&did).await?.id());
-  let (mut output_id, mut alias_output): (OutputId, AliasOutput) = client.get_alias_output(alias_id).await?;
-
-  while alias_output.state_index() != 0 {
-    // Step 1 - Get the current block
-    let block: Block = current_block(&client, &output_id).await?;
-    // Step 2 - Get the OutputId of the previous block
-    output_id = previous_output_id(&block)?;
-    // Step 3 - Get the Alias Output from the block
-    alias_output = block_alias_output(&block, &alias_id)?;
-    alias_history.push(alias_output.clone());
+  // ===========================================
+  // Retrieving the history of the DID document
+  // ===========================================
+
+  // Every state of the Alias Output contains a version of the DID document.
+  let history: Vec<IotaDocumentVersion> = client.resolve_did_history(&did).await?;
+  for version in history.iter() {
+    println!(
+      "Version {} published in output {} at {}: {:#}",
+      version.state_index, version.output_id, version.timestamp, version.document
+    );
   }
 
-  println!("Alias History: {alias_history:?}");
+  // Resolve the document as it was before the service was added, e.g. to verify
+  // a credential against the methods that were valid when it was issued.
+  let initial_document: IotaDocument = client.resolve_did_at_state_index(&did, 0).await?;
+  assert!(initial_document.service().is_empty());
+  let creation_time: Timestamp = history[0].timestamp;
+  let initial_document_by_time: IotaDocument = client.resolve_did_at_time(&did, creation_time).await?;
+  assert_eq!(initial_document, initial_document_by_time);
 
   Ok(())
 }
-
-async fn current_block(client: &Client, output_id: &OutputId) -> anyhow::Result<Block> {
-  let output_metadata: OutputMetadata = client.get_output_metadata(output_id).await?;
-  let block: Block = client.get_block(output_metadata.block_id()).await?;
-  Ok(block)
-}
-
-fn previous_output_id(block: &Block) -> anyhow::Result<OutputId> {
-  match block
-    .payload()
-    .context("expected a transaction payload, but no payload was found")?
-  {
-    Payload::Transaction(transaction_payload) => match transaction_payload.essence() {
-      TransactionEssence::Regular(regular_transaction_essence) => {
-        match regular_transaction_essence
-          .inputs()
-          .first()
-          .context("expected an utxo for the block, but no input was found")?
-        {
-          Input::Utxo(utxo_input) => Ok(*utxo_input.output_id()),
-          Input::Treasury(_) => {
-            anyhow::bail!("expected an utxo input, found a treasury input");
-          }
-        }
-      }
-    },
-    Payload::Milestone(_) | Payload::TreasuryTransaction(_) | Payload::TaggedData(_) => {
-      anyhow::bail!("expected a transaction payload");
-    }
-  }
-}
-
-fn block_alias_output(block: &Block, alias_id: &AliasId) -> anyhow::Result<AliasOutput> {
-  match block
-    .payload()
-    .context("expected a transaction payload, but no payload was found")?
-  {
-    Payload::Transaction(transaction_payload) => match transaction_payload.essence() {
-      TransactionEssence::Regular(regular_transaction_essence) => {
-        for (index, output) in regular_transaction_essence.outputs().iter().enumerate() {
-          match output {
-            Output::Alias(alias_output) => {
-              if &alias_output.alias_id().or_from_output_id(
-                &OutputId::new(
-                  transaction_payload.id(),
-                  index.try_into().context("output index must fit into a u16")?,
-                )
-                .context("failed to create OutputId")?,
-              ) == alias_id
-              {
-                return Ok(alias_output.clone());
-              }
-            }
-            Output::Basic(_) | Output::Foundry(_) | Output::Nft(_) | Output::Treasury(_) => continue,
-          }
-        }
-      }
-    },
-    Payload::Milestone(_) | Payload::TreasuryTransaction(_) | Payload::TaggedData(_) => {
-      anyhow::bail!("expected a transaction payload");
-    }
-  }
-  anyhow::bail!("no alias output has been found");
-}
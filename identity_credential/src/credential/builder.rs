@@ -8,6 +8,7 @@ use identity_core::common::Url;
 use identity_core::common::Value;
 
 use crate::credential::Credential;
+use crate::credential::DataModelVersion;
 use crate::credential::Evidence;
 use crate::credential::Issuer;
 use crate::credential::Policy;
@@ -29,6 +30,10 @@ pub struct CredentialBuilder<T = Object> {
   pub(crate) issuer: Option<Issuer>,
   pub(crate) issuance_date: Option<Timestamp>,
   pub(crate) expiration_date: Option<Timestamp>,
+  pub(crate) valid_from: Option<Timestamp>,
+  pub(crate) valid_until: Option<Timestamp>,
+  pub(crate) name: Option<String>,
+  pub(crate) description: Option<String>,
   pub(crate) status: Option<Status>,
   pub(crate) schema: Vec<Schema>,
   pub(crate) refresh_service: Vec<RefreshService>,
@@ -50,6 +55,10 @@ impl<T> CredentialBuilder<T> {
       issuer: None,
       issuance_date: None,
       expiration_date: None,
+      valid_from: None,
+      valid_until: None,
+      name: None,
+      description: None,
       status: None,
       schema: Vec::new(),
       refresh_service: Vec::new(),
@@ -61,6 +70,15 @@ impl<T> CredentialBuilder<T> {
     }
   }
 
+  /// Sets the version of the VC Data Model of the `Credential` by replacing its base context.
+  ///
+  /// Defaults to [`DataModelVersion::V1`].
+  #[must_use]
+  pub fn data_model_version(mut self, version: DataModelVersion) -> Self {
+    self.context[0] = version.base_context().clone();
+    self
+  }
+
   /// Adds a value to the `Credential` context set.
   #[must_use]
   pub fn context(mut self, value: impl Into<Context>) -> Self {
@@ -119,6 +137,34 @@ impl<T> CredentialBuilder<T> {
     self
   }
 
+  /// Sets the value of the `Credential` `validFrom`, as defined by VC Data Model v2.0.
+  #[must_use]
+  pub fn valid_from(mut self, value: Timestamp) -> Self {
+    self.valid_from = Some(value);
+    self
+  }
+
+  /// Sets the value of the `Credential` `validUntil`, as defined by VC Data Model v2.0.
+  #[must_use]
+  pub fn valid_until(mut self, value: Timestamp) -> Self {
+    self.valid_until = Some(value);
+    self
+  }
+
+  /// Sets the value of the `Credential` `name`, as defined by VC Data Model v2.0.
+  #[must_use]
+  pub fn name(mut self, value: impl Into<String>) -> Self {
+    self.name = Some(value.into());
+    self
+  }
+
+  /// Sets the value of the `Credential` `description`, as defined by VC Data Model v2.0.
+  #[must_use]
+  pub fn description(mut self, value: impl Into<String>) -> Self {
+    self.description = Some(value.into());
+    self
+  }
+
   /// Adds a value to the `credentialStatus` set.
   #[must_use]
   pub fn status(mut self, value: impl Into<Status>) -> Self {
@@ -221,8 +267,10 @@ mod tests {
 
   use crate::credential::Credential;
   use crate::credential::CredentialBuilder;
+  use crate::credential::DataModelVersion;
   use crate::credential::Proof;
   use crate::credential::Subject;
+  use crate::Error;
 
   fn subject() -> Subject {
     let json: Value = json!({
@@ -266,7 +314,7 @@ mod tests {
     assert_eq!(credential.types.get(1).unwrap(), "UniversityDegreeCredential");
     assert_eq!(credential.credential_subject.len(), 1);
     assert_eq!(credential.issuer.url(), "did:example:issuer");
-    assert_eq!(credential.issuance_date.to_string(), "2010-01-01T00:00:00Z");
+    assert_eq!(credential.issuance_date.unwrap().to_string(), "2010-01-01T00:00:00Z");
     assert_eq!(
       credential.credential_subject.get(0).unwrap().id.as_ref().unwrap(),
       "did:example:ebfeb1f712ebc6f1c276e12ec21"
@@ -282,6 +330,45 @@ mod tests {
     assert_eq!(credential.proof.unwrap().type_, "test-type");
   }
 
+  #[test]
+  fn test_credential_builder_v2() {
+    let valid_from: Timestamp = Timestamp::parse("2010-01-01T00:00:00Z").unwrap();
+    let valid_until: Timestamp = Timestamp::parse("2030-01-01T00:00:00Z").unwrap();
+    let credential: Credential = CredentialBuilder::default()
+      .data_model_version(DataModelVersion::V2)
+      .context(Url::parse("https://www.w3.org/ns/credentials/examples/v2").unwrap())
+      .type_("ExampleDegreeCredential")
+      .subject(subject())
+      .issuer(issuer())
+      .valid_from(valid_from)
+      .valid_until(valid_until)
+      .name("Example University Degree")
+      .description("A degree awarded by Example University.")
+      .build()
+      .unwrap();
+
+    assert_eq!(credential.data_model_version(), Some(DataModelVersion::V2));
+    assert_eq!(credential.context.get(0).unwrap(), DataModelVersion::V2.base_context());
+    assert_eq!(credential.context.len(), 2);
+    assert_eq!(credential.issuance_date, None);
+    assert_eq!(credential.valid_from, Some(valid_from));
+    assert_eq!(credential.valid_until, Some(valid_until));
+    assert_eq!(credential.name.as_deref(), Some("Example University Degree"));
+    assert_eq!(
+      credential.description.as_deref(),
+      Some("A degree awarded by Example University.")
+    );
+
+    // The v1.1 issuanceDate cannot be used in a v2.0 credential.
+    let result = CredentialBuilder::<Object>::default()
+      .data_model_version(DataModelVersion::V2)
+      .subject(subject())
+      .issuer(issuer())
+      .issuance_date(valid_from)
+      .build();
+    assert!(matches!(result, Err(Error::UnsupportedDataModelProperty(_))));
+  }
+
   #[test]
   #[should_panic = "MissingSubject"]
   fn test_builder_missing_subjects() {
@@ -5,7 +5,6 @@ use core::fmt::Display;
 use core::fmt::Formatter;
 
 use identity_core::convert::ToJson;
-use once_cell::sync::Lazy;
 use serde::Deserialize;
 use serde::Serialize;
 
@@ -17,6 +16,7 @@ use identity_core::common::Url;
 use identity_core::convert::FmtJson;
 
 use crate::credential::CredentialBuilder;
+use crate::credential::DataModelVersion;
 use crate::credential::Evidence;
 use crate::credential::Issuer;
 use crate::credential::Policy;
@@ -30,9 +30,6 @@ use crate::error::Result;
 use super::jwt_serialization::CredentialJwtClaims;
 use super::Proof;
 
-static BASE_CONTEXT: Lazy<Context> =
-  Lazy::new(|| Context::Url(Url::parse("https://www.w3.org/2018/credentials/v1").unwrap()));
-
 /// Represents a set of claims describing an entity.
 #[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct Credential<T = Object> {
@@ -50,12 +47,24 @@ pub struct Credential<T = Object> {
   pub credential_subject: OneOrMany<Subject>,
   /// A reference to the issuer of the `Credential`.
   pub issuer: Issuer,
-  /// A timestamp of when the `Credential` becomes valid.
-  #[serde(rename = "issuanceDate")]
-  pub issuance_date: Timestamp,
-  /// A timestamp of when the `Credential` should no longer be considered valid.
+  /// A timestamp of when the `Credential` becomes valid, required by VC Data Model v1.1.
+  #[serde(rename = "issuanceDate", skip_serializing_if = "Option::is_none")]
+  pub issuance_date: Option<Timestamp>,
+  /// A timestamp of when the `Credential` should no longer be considered valid, as defined by VC Data Model v1.1.
   #[serde(rename = "expirationDate", skip_serializing_if = "Option::is_none")]
   pub expiration_date: Option<Timestamp>,
+  /// A timestamp of when the `Credential` becomes valid, as defined by VC Data Model v2.0.
+  #[serde(rename = "validFrom", skip_serializing_if = "Option::is_none")]
+  pub valid_from: Option<Timestamp>,
+  /// A timestamp of when the `Credential` should no longer be considered valid, as defined by VC Data Model v2.0.
+  #[serde(rename = "validUntil", skip_serializing_if = "Option::is_none")]
+  pub valid_until: Option<Timestamp>,
+  /// A human-readable name of the `Credential`, as defined by VC Data Model v2.0.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub name: Option<String>,
+  /// A human-readable description of the `Credential`, as defined by VC Data Model v2.0.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub description: Option<String>,
   /// Information used to determine the current status of the `Credential`.
   #[serde(default, rename = "credentialStatus", skip_serializing_if = "Option::is_none")]
   pub credential_status: Option<Status>,
@@ -84,9 +93,11 @@ pub struct Credential<T = Object> {
 }
 
 impl<T> Credential<T> {
-  /// Returns the base JSON-LD context.
+  /// Returns the base JSON-LD context of VC Data Model v1.1.
+  ///
+  /// See [`DataModelVersion::base_context`] for the base context of other versions.
   pub fn base_context() -> &'static Context {
-    &BASE_CONTEXT
+    DataModelVersion::V1.base_context()
   }
 
   /// Returns the base type.
@@ -102,15 +113,25 @@ impl<T> Credential<T> {
   }
 
   /// Returns a new `Credential` based on the `CredentialBuilder` configuration.
+  ///
+  /// The `issuanceDate` of a VC Data Model v1.1 credential defaults to the current time if it is not set.
   pub fn from_builder(builder: CredentialBuilder<T>) -> Result<Self> {
+    let issuance_date: Option<Timestamp> = match builder.context.first().and_then(DataModelVersion::from_base_context) {
+      Some(DataModelVersion::V1) => Some(builder.issuance_date.unwrap_or_default()),
+      _ => builder.issuance_date,
+    };
     let this: Self = Self {
       context: builder.context.into(),
       id: builder.id,
       types: builder.types.into(),
       credential_subject: builder.subject.into(),
       issuer: builder.issuer.ok_or(Error::MissingIssuer)?,
-      issuance_date: builder.issuance_date.unwrap_or_default(),
+      issuance_date,
       expiration_date: builder.expiration_date,
+      valid_from: builder.valid_from,
+      valid_until: builder.valid_until,
+      name: builder.name,
+      description: builder.description,
       credential_status: builder.status,
       credential_schema: builder.schema.into(),
       refresh_service: builder.refresh_service.into(),
@@ -126,12 +147,38 @@ impl<T> Credential<T> {
     Ok(this)
   }
 
+  /// Returns the version of the VC Data Model indicated by the base context of the `Credential`, or `None` if the
+  /// first context is not a known base context.
+  pub fn data_model_version(&self) -> Option<DataModelVersion> {
+    self.context.get(0).and_then(DataModelVersion::from_base_context)
+  }
+
+  /// Returns the timestamp from which the `Credential` is valid, i.e. its `validFrom` property, or its
+  /// `issuanceDate` for VC Data Model v1.1 credentials.
+  pub fn validity_start(&self) -> Option<Timestamp> {
+    self.valid_from.or(self.issuance_date)
+  }
+
+  /// Returns the timestamp after which the `Credential` is no longer valid, i.e. its `validUntil` property, or its
+  /// `expirationDate` for VC Data Model v1.1 credentials.
+  pub fn validity_end(&self) -> Option<Timestamp> {
+    self.valid_until.or(self.expiration_date)
+  }
+
   /// Validates the semantic structure of the `Credential`.
   pub fn check_structure(&self) -> Result<()> {
     // Ensure the base context is present and in the correct location
-    match self.context.get(0) {
-      Some(context) if context == Self::base_context() => {}
-      Some(_) | None => return Err(Error::MissingBaseContext),
+    let version: DataModelVersion = self.data_model_version().ok_or(Error::MissingBaseContext)?;
+
+    // Ensure the validity period is expressed with the properties of the data model version
+    match version {
+      DataModelVersion::V1 if self.issuance_date.is_none() => return Err(Error::MissingIssuanceDate),
+      DataModelVersion::V2 if self.issuance_date.is_some() || self.expiration_date.is_some() => {
+        return Err(Error::UnsupportedDataModelProperty(
+          "issuanceDate and expirationDate are replaced by validFrom and validUntil in VC Data Model v2.0",
+        ));
+      }
+      DataModelVersion::V1 | DataModelVersion::V2 => {}
     }
 
     // The set of types MUST contain the base type
@@ -164,6 +211,9 @@ impl<T> Credential<T> {
   /// Serializes the [`Credential`] as a JWT claims set
   /// in accordance with [VC Data Model v1.1](https://www.w3.org/TR/vc-data-model/#json-web-token).
   ///
+  /// The `validFrom` and `validUntil` properties of VC Data Model v2.0 credentials are mapped to the `nbf` and `exp`
+  /// claims, like `issuanceDate` and `expirationDate`.
+  ///
   /// The resulting string can be used as the payload of a JWS when issuing the credential.  
   pub fn serialize_jwt(&self, custom_claims: Option<Object>) -> Result<String>
   where
@@ -187,9 +237,13 @@ where
 
 #[cfg(test)]
 mod tests {
+  use identity_core::common::Timestamp;
   use identity_core::convert::FromJson;
+  use identity_core::convert::ToJson;
 
   use crate::credential::Credential;
+  use crate::credential::DataModelVersion;
+  use crate::Error;
 
   const JSON1: &str = include_str!("../../tests/fixtures/credential-1.json");
   const JSON2: &str = include_str!("../../tests/fixtures/credential-2.json");
@@ -203,6 +257,7 @@ mod tests {
   const JSON10: &str = include_str!("../../tests/fixtures/credential-10.json");
   const JSON11: &str = include_str!("../../tests/fixtures/credential-11.json");
   const JSON12: &str = include_str!("../../tests/fixtures/credential-12.json");
+  const JSON13: &str = include_str!("../../tests/fixtures/credential-13.json");
 
   #[test]
   fn test_from_json() {
@@ -218,5 +273,54 @@ mod tests {
     let _credential: Credential = Credential::from_json(JSON10).unwrap();
     let _credential: Credential = Credential::from_json(JSON11).unwrap();
     let _credential: Credential = Credential::from_json(JSON12).unwrap();
+    let _credential: Credential = Credential::from_json(JSON13).unwrap();
+  }
+
+  #[test]
+  fn test_data_model_v2() {
+    let credential: Credential = Credential::from_json(JSON13).unwrap();
+    assert_eq!(credential.data_model_version(), Some(DataModelVersion::V2));
+    assert!(credential.check_structure().is_ok());
+    assert_eq!(credential.issuance_date, None);
+    assert_eq!(
+      credential.validity_start(),
+      Some(Timestamp::parse("2010-01-01T00:00:00Z").unwrap())
+    );
+    assert_eq!(
+      credential.validity_end(),
+      Some(Timestamp::parse("2030-01-01T00:00:00Z").unwrap())
+    );
+    assert_eq!(credential.name.as_deref(), Some("Example University Degree"));
+    assert_eq!(
+      credential.description.as_deref(),
+      Some("A degree awarded by Example University.")
+    );
+    assert!(!credential.properties.contains_key("name"));
+    assert_eq!(
+      Credential::from_json(&credential.to_json().unwrap()).unwrap(),
+      credential
+    );
+
+    // The v1.1 validity properties are not defined in v2.0.
+    let mut invalid: Credential = credential.clone();
+    invalid.issuance_date = credential.valid_from;
+    assert!(matches!(
+      invalid.check_structure().unwrap_err(),
+      Error::UnsupportedDataModelProperty(_)
+    ));
+  }
+
+  #[test]
+  fn test_data_model_v1_requires_issuance_date() {
+    let mut credential: Credential = Credential::from_json(JSON1).unwrap();
+    assert_eq!(credential.data_model_version(), Some(DataModelVersion::V1));
+    assert!(credential.check_structure().is_ok());
+    assert_eq!(credential.validity_start(), credential.issuance_date);
+
+    credential.issuance_date = None;
+    assert!(matches!(
+      credential.check_structure().unwrap_err(),
+      Error::MissingIssuanceDate
+    ));
   }
 }
@@ -0,0 +1,42 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Context;
+use identity_core::common::Url;
+use once_cell::sync::Lazy;
+
+static BASE_CONTEXT_V1: Lazy<Context> =
+  Lazy::new(|| Context::Url(Url::parse("https://www.w3.org/2018/credentials/v1").unwrap()));
+static BASE_CONTEXT_V2: Lazy<Context> =
+  Lazy::new(|| Context::Url(Url::parse("https://www.w3.org/ns/credentials/v2").unwrap()));
+
+/// The version of the W3C Verifiable Credentials Data Model a [`Credential`](crate::credential::Credential) or
+/// [`Presentation`](crate::presentation::Presentation) conforms to, as indicated by its base context.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum DataModelVersion {
+  /// [VC Data Model v1.1](https://www.w3.org/TR/vc-data-model/), using the `issuanceDate` and `expirationDate`
+  /// properties.
+  #[default]
+  V1,
+  /// [VC Data Model v2.0](https://www.w3.org/TR/vc-data-model-2.0/), using the `validFrom` and `validUntil`
+  /// properties.
+  V2,
+}
+
+impl DataModelVersion {
+  /// Returns the base JSON-LD context of this version, which must be the first context of a credential or
+  /// presentation.
+  pub fn base_context(self) -> &'static Context {
+    match self {
+      Self::V1 => &BASE_CONTEXT_V1,
+      Self::V2 => &BASE_CONTEXT_V2,
+    }
+  }
+
+  /// Returns the version whose base context is `context`, if any.
+  pub fn from_base_context(context: &Context) -> Option<Self> {
+    [Self::V1, Self::V2]
+      .into_iter()
+      .find(|version| version.base_context() == context)
+  }
+}
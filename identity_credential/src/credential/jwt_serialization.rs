@@ -14,6 +14,7 @@ use identity_core::common::Url;
 use serde::de::DeserializeOwned;
 
 use crate::credential::Credential;
+use crate::credential::DataModelVersion;
 use crate::credential::Evidence;
 use crate::credential::Issuer;
 use crate::credential::Policy;
@@ -27,6 +28,9 @@ use crate::Result;
 
 /// Implementation of JWT Encoding/Decoding according to [VC Data Model v1.1](https://www.w3.org/TR/vc-data-model/#json-web-token).
 ///
+/// VC Data Model v2.0 credentials are encoded in the same way, with `validFrom` and `validUntil` taking the place of
+/// `issuanceDate` and `expirationDate`.
+///
 /// This type is opinionated in the following ways:
 /// 1. Serialization tries to duplicate as little as possible between the required registered claims and the `vc` entry.
 /// 2. Only allows serializing/deserializing claims "exp, iss, nbf &/or iat, jti, sub and vc". Other custom properties
@@ -37,13 +41,13 @@ where
   T: ToOwned + Serialize,
   <T as ToOwned>::Owned: DeserializeOwned,
 {
-  /// Represents the expirationDate encoded as a UNIX timestamp.  
+  /// Represents the expirationDate, or validUntil, encoded as a UNIX timestamp.
   #[serde(skip_serializing_if = "Option::is_none")]
   exp: Option<i64>,
   /// Represents the issuer.
   pub(crate) iss: Cow<'credential, Issuer>,
 
-  /// Represents the issuanceDate encoded as a UNIX timestamp.
+  /// Represents the issuanceDate, or validFrom, encoded as a UNIX timestamp.
   #[serde(flatten)]
   issuance_date: IssuanceDateClaims,
 
@@ -74,6 +78,10 @@ where
       issuer,
       issuance_date,
       expiration_date,
+      valid_from,
+      valid_until,
+      name,
+      description,
       credential_status,
       credential_schema,
       refresh_service,
@@ -87,10 +95,19 @@ where
       return Err(Error::MoreThanOneSubjectInJwt);
     };
 
+    // The registered claims represent the validity period of the data model version of the credential,
+    // other timestamps are kept in the `vc` object.
+    let is_v2: bool = credential.data_model_version() == Some(DataModelVersion::V2);
+    let (not_before, not_after, vc_issuance_date, vc_expiration_date, vc_valid_from, vc_valid_until) = if is_v2 {
+      (*valid_from, *valid_until, *issuance_date, *expiration_date, None, None)
+    } else {
+      (*issuance_date, *expiration_date, None, None, *valid_from, *valid_until)
+    };
+
     Ok(Self {
-      exp: expiration_date.map(|value| Timestamp::to_unix(&value)),
+      exp: not_after.map(|value| Timestamp::to_unix(&value)),
       iss: Cow::Borrowed(issuer),
-      issuance_date: IssuanceDateClaims::new(*issuance_date),
+      issuance_date: not_before.map(IssuanceDateClaims::new).unwrap_or_default(),
       jti: id.as_ref().map(Cow::Borrowed),
       sub: subject.id.as_ref().map(Cow::Borrowed),
       vc: InnerCredential {
@@ -98,8 +115,12 @@ where
         id: None,
         types: Cow::Borrowed(types),
         credential_subject: InnerCredentialSubject::new(subject),
-        issuance_date: None,
-        expiration_date: None,
+        issuance_date: vc_issuance_date,
+        expiration_date: vc_expiration_date,
+        valid_from: vc_valid_from,
+        valid_until: vc_valid_until,
+        name: name.as_deref().map(Cow::Borrowed),
+        description: description.as_deref().map(Cow::Borrowed),
         issuer: None,
         credential_schema: Cow::Borrowed(credential_schema),
         credential_status: credential_status.as_ref().map(Cow::Borrowed),
@@ -135,28 +156,53 @@ where
       return Err(Error::InconsistentCredentialJwtClaims("inconsistent issuer"));
     };
 
-    // Check consistency of issuanceDate
-    let issuance_date_from_claims = self.issuance_date.to_issuance_date()?;
-    if !self
-      .vc
-      .issuance_date
-      .map(|value| value == issuance_date_from_claims)
-      .unwrap_or(true)
-    {
-      return Err(Error::InconsistentCredentialJwtClaims("inconsistent issuanceDate"));
-    };
-
-    // Check consistency of expirationDate
-    if !self
-      .vc
-      .expiration_date
-      .map(|value| self.exp.filter(|exp| *exp == value.to_unix()).is_some())
-      .unwrap_or(true)
-    {
-      return Err(Error::InconsistentCredentialJwtClaims(
-        "inconsistent credential expirationDate",
-      ));
-    };
+    if self.is_v2() {
+      // Check consistency of validFrom
+      let valid_from_from_claims: Option<Timestamp> = self.issuance_date.to_timestamp()?;
+      if !self
+        .vc
+        .valid_from
+        .map(|value| Some(value) == valid_from_from_claims)
+        .unwrap_or(true)
+      {
+        return Err(Error::InconsistentCredentialJwtClaims("inconsistent validFrom"));
+      };
+
+      // Check consistency of validUntil
+      if !self
+        .vc
+        .valid_until
+        .map(|value| self.exp.filter(|exp| *exp == value.to_unix()).is_some())
+        .unwrap_or(true)
+      {
+        return Err(Error::InconsistentCredentialJwtClaims(
+          "inconsistent credential validUntil",
+        ));
+      };
+    } else {
+      // Check consistency of issuanceDate
+      let issuance_date_from_claims = self.issuance_date.to_issuance_date()?;
+      if !self
+        .vc
+        .issuance_date
+        .map(|value| value == issuance_date_from_claims)
+        .unwrap_or(true)
+      {
+        return Err(Error::InconsistentCredentialJwtClaims("inconsistent issuanceDate"));
+      };
+
+      // Check consistency of expirationDate
+      if !self
+        .vc
+        .expiration_date
+        .map(|value| self.exp.filter(|exp| *exp == value.to_unix()).is_some())
+        .unwrap_or(true)
+      {
+        return Err(Error::InconsistentCredentialJwtClaims(
+          "inconsistent credential expirationDate",
+        ));
+      };
+    }
 
     // Check consistency of id
     if !self
@@ -184,12 +230,18 @@ where
     Ok(())
   }
 
+  /// Returns whether the `vc` object is a VC Data Model v2.0 credential.
+  fn is_v2(&self) -> bool {
+    self.vc.context.get(0).and_then(DataModelVersion::from_base_context) == Some(DataModelVersion::V2)
+  }
+
   /// Converts the JWT representation into a [`Credential`].
   ///
   /// # Errors
   /// Errors if either timestamp conversion or [`Self::check_consistency`] fails.
   pub(crate) fn try_into_credential(self) -> Result<Credential<T>> {
     self.check_consistency()?;
+    let is_v2: bool = self.is_v2();
 
     let Self {
       exp,
@@ -214,11 +266,35 @@ where
       non_transferable,
       properties,
       proof,
-      issuance_date: _,
+      issuance_date: vc_issuance_date,
       issuer: _,
-      expiration_date: _,
+      expiration_date: vc_expiration_date,
+      valid_from: vc_valid_from,
+      valid_until: vc_valid_until,
+      name,
+      description,
     } = vc;
 
+    let not_after: Option<Timestamp> = exp
+      .map(Timestamp::from_unix)
+      .transpose()
+      .map_err(|_| Error::TimestampConversionError)?;
+    let (issuance_date, expiration_date, valid_from, valid_until) = if is_v2 {
+      (
+        vc_issuance_date,
+        vc_expiration_date,
+        issuance_date.to_timestamp()?,
+        not_after,
+      )
+    } else {
+      (
+        Some(issuance_date.to_issuance_date()?),
+        not_after,
+        vc_valid_from,
+        vc_valid_until,
+      )
+    };
+
     Ok(Credential {
       context: context.into_owned(),
       id: jti.map(Cow::into_owned),
@@ -230,11 +306,12 @@ where
         })
       },
       issuer: iss.into_owned(),
-      issuance_date: issuance_date.to_issuance_date()?,
-      expiration_date: exp
-        .map(Timestamp::from_unix)
-        .transpose()
-        .map_err(|_| Error::TimestampConversionError)?,
+      issuance_date,
+      expiration_date,
+      valid_from,
+      valid_until,
+      name: name.map(Cow::into_owned),
+      description: description.map(Cow::into_owned),
       credential_status: credential_status.map(Cow::into_owned),
       credential_schema: credential_schema.into_owned(),
       refresh_service: refresh_service.into_owned(),
@@ -250,7 +327,7 @@ where
 /// The [VC Data Model v1.1](https://www.w3.org/TR/vc-data-model/#json-web-token) states that issuanceDate
 /// corresponds to the registered `nbf` claim, but `iat` is also used in the ecosystem.
 /// This type aims to take care of this discrepancy on a best effort basis.
-#[derive(Serialize, Deserialize, Clone, Copy)]
+#[derive(Serialize, Deserialize, Clone, Copy, Default)]
 pub(crate) struct IssuanceDateClaims {
   #[serde(skip_serializing_if = "Option::is_none")]
   pub(crate) iat: Option<i64>,
@@ -269,17 +346,19 @@ impl IssuanceDateClaims {
   /// otherwise falls back to `iat`. If none of these values are set an error is returned.
   #[cfg(feature = "validator")]
   pub(crate) fn to_issuance_date(self) -> Result<Timestamp> {
-    if let Some(timestamp) = self
+    self.to_timestamp()?.ok_or(Error::TimestampConversionError)
+  }
+
+  /// Produces the timestamp from `nbf` if it is set, otherwise falls back to `iat`.
+  /// Returns `None` if none of these values are set.
+  #[cfg(feature = "validator")]
+  pub(crate) fn to_timestamp(self) -> Result<Option<Timestamp>> {
+    self
       .nbf
+      .or(self.iat)
       .map(Timestamp::from_unix)
       .transpose()
-      .map_err(|_| Error::TimestampConversionError)?
-    {
-      Ok(timestamp)
-    } else {
-      Timestamp::from_unix(self.iat.ok_or(Error::TimestampConversionError)?)
-        .map_err(|_| Error::TimestampConversionError)
-    }
+      .map_err(|_| Error::TimestampConversionError)
   }
 }
 
@@ -333,6 +412,18 @@ where
   /// A timestamp of when the `Credential` should no longer be considered valid.
   #[serde(rename = "expirationDate", skip_serializing_if = "Option::is_none")]
   expiration_date: Option<Timestamp>,
+  /// A timestamp of when the `Credential` becomes valid, as defined by VC Data Model v2.0.
+  #[serde(rename = "validFrom", skip_serializing_if = "Option::is_none")]
+  valid_from: Option<Timestamp>,
+  /// A timestamp of when the `Credential` should no longer be considered valid, as defined by VC Data Model v2.0.
+  #[serde(rename = "validUntil", skip_serializing_if = "Option::is_none")]
+  valid_until: Option<Timestamp>,
+  /// A human-readable name of the `Credential`.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  name: Option<Cow<'credential, str>>,
+  /// A human-readable description of the `Credential`.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  description: Option<Cow<'credential, str>>,
   /// Information used to determine the current status of the `Credential`.
   #[serde(default, rename = "credentialStatus", skip_serializing_if = "Option::is_none")]
   credential_status: Option<Cow<'credential, Status>>,
@@ -433,6 +524,107 @@ mod tests {
     assert_eq!(credential, retrieved_credential);
   }
 
+  #[test]
+  fn roundtrip_v2() {
+    let credential_json: &str = r#"
+    {
+      "@context": [
+        "https://www.w3.org/ns/credentials/v2",
+        "https://www.w3.org/ns/credentials/examples/v2"
+      ],
+      "id": "http://university.example/credentials/3732",
+      "type": ["VerifiableCredential", "ExampleDegreeCredential"],
+      "issuer": "https://university.example/issuers/565049",
+      "validFrom": "2010-01-01T19:23:24Z",
+      "validUntil": "2025-09-13T15:56:23Z",
+      "name": "Example University Degree",
+      "credentialSubject": {
+        "id": "did:example:ebfeb1f712ebc6f1c276e12ec21",
+        "degree": {
+          "type": "ExampleBachelorDegree",
+          "name": "Bachelor of Science and Arts"
+        }
+      }
+    }"#;
+
+    let expected_serialization_json: &str = r#"
+    {
+      "sub": "did:example:ebfeb1f712ebc6f1c276e12ec21",
+      "jti": "http://university.example/credentials/3732",
+      "iss": "https://university.example/issuers/565049",
+      "nbf": 1262373804,
+      "exp": 1757778983,
+      "vc": {
+        "@context": [
+          "https://www.w3.org/ns/credentials/v2",
+          "https://www.w3.org/ns/credentials/examples/v2"
+        ],
+        "type": ["VerifiableCredential", "ExampleDegreeCredential"],
+        "name": "Example University Degree",
+        "credentialSubject": {
+          "degree": {
+            "type": "ExampleBachelorDegree",
+            "name": "Bachelor of Science and Arts"
+          }
+        }
+      }
+    }"#;
+
+    let credential: Credential = Credential::from_json(credential_json).unwrap();
+    let jwt_credential_claims_serialized: String =
+      CredentialJwtClaims::new(&credential, None).unwrap().to_json().unwrap();
+    assert_eq!(
+      Object::from_json(expected_serialization_json).unwrap(),
+      Object::from_json(&jwt_credential_claims_serialized).unwrap()
+    );
+
+    let retrieved_credential: Credential =
+      CredentialJwtClaims::<'static, Object>::from_json(&jwt_credential_claims_serialized)
+        .unwrap()
+        .try_into_credential()
+        .unwrap();
+    assert_eq!(credential, retrieved_credential);
+
+    // A credential without validFrom has no `nbf` claim.
+    let mut credential: Credential = credential;
+    credential.valid_from = None;
+    let claims: Object =
+      Object::from_json(&CredentialJwtClaims::new(&credential, None).unwrap().to_json().unwrap()).unwrap();
+    assert!(!claims.contains_key("nbf") && !claims.contains_key("iat"));
+    let retrieved_credential: Credential =
+      CredentialJwtClaims::<'static, Object>::from_json(&claims.to_json().unwrap())
+        .unwrap()
+        .try_into_credential()
+        .unwrap();
+    assert_eq!(credential, retrieved_credential);
+  }
+
+  #[test]
+  fn inconsistent_valid_until() {
+    let claims_json: &str = r#"
+    {
+      "iss": "https://university.example/issuers/565049",
+      "exp": 1757778983,
+      "vc": {
+        "@context": "https://www.w3.org/ns/credentials/v2",
+        "type": "VerifiableCredential",
+        "validUntil": "2026-09-13T15:56:23Z",
+        "credentialSubject": {
+          "name": "Alice"
+        }
+      }
+    }"#;
+
+    let credential_from_claims_result: Result<Credential, _> =
+      CredentialJwtClaims::<'_, Object>::from_json(&claims_json)
+        .unwrap()
+        .try_into_credential();
+    assert!(matches!(
+      credential_from_claims_result.unwrap_err(),
+      Error::InconsistentCredentialJwtClaims("inconsistent credential validUntil")
+    ));
+  }
+
   #[test]
   fn claims_duplication() {
     let credential_json: &str = r#"
@@ -105,7 +105,7 @@ impl LinkedDomainService {
         }
         Ok(())
       }
-      ServiceEndpoint::Set(_) => Err(DomainLinkageError(
+      ServiceEndpoint::Set(_) | ServiceEndpoint::Object(_) => Err(DomainLinkageError(
         "service endpoints must be either a string or an object containing an `origins` property".into(),
       )),
       ServiceEndpoint::Map(endpoint) => {
@@ -135,8 +135,8 @@ impl LinkedDomainService {
   pub fn domains(&self) -> &[Url] {
     match self.service.service_endpoint() {
       ServiceEndpoint::One(endpoint) => std::slice::from_ref(endpoint),
-      ServiceEndpoint::Set(_) => {
-        unreachable!("the service endpoint is never a set per the `LinkedDomainService` type invariant")
+      ServiceEndpoint::Set(_) | ServiceEndpoint::Object(_) => {
+        unreachable!("the service endpoint is never a set or an object per the `LinkedDomainService` type invariant")
       }
       ServiceEndpoint::Map(endpoint) => endpoint
         .get("origins")
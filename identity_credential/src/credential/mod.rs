@@ -7,6 +7,7 @@
 
 mod builder;
 mod credential;
+mod data_model;
 mod evidence;
 mod issuer;
 mod jws;
@@ -24,6 +25,7 @@ mod subject;
 
 pub use self::builder::CredentialBuilder;
 pub use self::credential::Credential;
+pub use self::data_model::DataModelVersion;
 pub use self::evidence::Evidence;
 pub use self::issuer::Issuer;
 pub use self::jws::Jws;
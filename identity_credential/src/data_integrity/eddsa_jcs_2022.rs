@@ -0,0 +1,502 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crypto::hashes::sha::SHA256;
+use crypto::hashes::sha::SHA256_LEN;
+use identity_core::common::Object;
+use identity_core::common::Timestamp;
+use identity_core::common::Value;
+use identity_core::convert::BaseEncoding;
+use identity_core::convert::FromJson;
+use identity_core::convert::ToJson;
+use identity_did::DIDUrl;
+use identity_did::DID;
+use identity_document::document::CoreDocument;
+use identity_verification::jwk::Jwk;
+use identity_verification::jws::JwsAlgorithm;
+use identity_verification::jws::JwsVerifier;
+use identity_verification::jws::VerificationInput;
+use identity_verification::MethodScope;
+use identity_verification::VerificationMethod;
+use serde::Serialize;
+
+use super::DataIntegrityError;
+use super::DataIntegrityProof;
+use super::DataIntegrityProofOptions;
+use super::ProofPurpose;
+
+type DataIntegrityResult<T> = Result<T, DataIntegrityError>;
+
+/// The [`eddsa-jcs-2022`](https://www.w3.org/TR/vc-di-eddsa/#eddsa-jcs-2022) cryptosuite, which signs documents
+/// canonicalized with the [JSON Canonicalization Scheme](https://www.rfc-editor.org/rfc/rfc8785) using Ed25519.
+///
+/// Use [`EddsaJcs2022Verifier`] to verify proofs created with this cryptosuite.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct EddsaJcs2022;
+
+impl EddsaJcs2022 {
+  /// The identifier of the cryptosuite.
+  pub const CRYPTOSUITE: &'static str = "eddsa-jcs-2022";
+
+  /// Serializes `document` to a JSON object and removes its `proof` property, which is returned separately.
+  pub fn unsecure<T>(document: &T) -> DataIntegrityResult<(Object, Option<Value>)>
+  where
+    T: Serialize + ?Sized,
+  {
+    let mut unsecured_document: Object = document
+      .to_json_value()
+      .and_then(Object::from_json_value)
+      .map_err(|err| DataIntegrityError::SerializationError(err.into()))?;
+    let proof: Option<Value> = unsecured_document.remove("proof");
+    Ok((unsecured_document, proof))
+  }
+
+  /// Creates the proof configuration for signing `unsecured_document` with the verification method identified by
+  /// `verification_method`.
+  ///
+  /// The `@context` of the proof configuration is set to that of the document, if present.
+  pub fn create_proof_configuration(
+    unsecured_document: &Object,
+    verification_method: &DIDUrl,
+    proof_purpose: ProofPurpose,
+    options: &DataIntegrityProofOptions,
+  ) -> DataIntegrityResult<DataIntegrityProof> {
+    let context = unsecured_document
+      .get("@context")
+      .cloned()
+      .map(FromJson::from_json_value)
+      .transpose()
+      .map_err(DataIntegrityError::InvalidProof)?;
+
+    Ok(DataIntegrityProof {
+      context,
+      type_: DataIntegrityProof::TYPE.to_owned(),
+      cryptosuite: Self::CRYPTOSUITE.to_owned(),
+      created: Some(options.created.unwrap_or_else(Timestamp::now_utc).to_rfc3339()),
+      expires: options.expires.map(|expires| expires.to_rfc3339()),
+      verification_method: verification_method.to_string(),
+      proof_purpose,
+      challenge: options.challenge.clone(),
+      domain: options.domain.clone(),
+      nonce: options.nonce.clone(),
+      proof_value: None,
+      properties: Object::new(),
+    })
+  }
+
+  /// Computes the data to sign or verify, which is the concatenation of the SHA-256 hashes of the canonicalized
+  /// `proof_configuration` and the canonicalized `unsecured_document`.
+  ///
+  /// `proof_configuration` is either a [`DataIntegrityProof`] or the JSON object of a received proof, whose
+  /// `proofValue` is ignored.
+  pub fn hash_data<P>(unsecured_document: &Object, proof_configuration: &P) -> DataIntegrityResult<[u8; 2 * SHA256_LEN]>
+  where
+    P: Serialize + ?Sized,
+  {
+    let mut proof_configuration: Object = proof_configuration
+      .to_json_value()
+      .and_then(Object::from_json_value)
+      .map_err(|err| DataIntegrityError::SerializationError(err.into()))?;
+    proof_configuration.remove("proofValue");
+
+    let canonical_proof_configuration: Vec<u8> =
+      serde_jcs::to_vec(&proof_configuration).map_err(|err| DataIntegrityError::SerializationError(err.into()))?;
+    let canonical_document: Vec<u8> =
+      serde_jcs::to_vec(unsecured_document).map_err(|err| DataIntegrityError::SerializationError(err.into()))?;
+
+    let mut hash_data: [u8; 2 * SHA256_LEN] = [0; 2 * SHA256_LEN];
+    let (proof_configuration_hash, document_hash) = hash_data.split_at_mut(SHA256_LEN);
+    SHA256(
+      &canonical_proof_configuration,
+      proof_configuration_hash
+        .try_into()
+        .expect("slice has the length of a SHA-256 digest"),
+    );
+    SHA256(
+      &canonical_document,
+      document_hash
+        .try_into()
+        .expect("slice has the length of a SHA-256 digest"),
+    );
+    Ok(hash_data)
+  }
+
+  /// Encodes an Ed25519 `signature` as a `proofValue`, using the base58-btc multibase encoding.
+  pub fn encode_proof_value(signature: &[u8]) -> String {
+    BaseEncoding::encode_multibase(signature, None)
+  }
+
+  /// Decodes a `proofValue` encoded with [`EddsaJcs2022::encode_proof_value`].
+  pub fn decode_proof_value(proof_value: &str) -> DataIntegrityResult<Vec<u8>> {
+    if !proof_value.starts_with('z') {
+      return Err(DataIntegrityError::InvalidProofValue(None));
+    }
+    BaseEncoding::decode_multibase(proof_value).map_err(|err| DataIntegrityError::InvalidProofValue(Some(err)))
+  }
+}
+
+/// A type for verifying [`DataIntegrityProof`]s created with the [`EddsaJcs2022`] cryptosuite.
+#[non_exhaustive]
+pub struct EddsaJcs2022Verifier<V: JwsVerifier>(V);
+
+impl<V: JwsVerifier> EddsaJcs2022Verifier<V> {
+  /// Create a new [`EddsaJcs2022Verifier`] that delegates cryptographic signature verification to the given
+  /// `signature_verifier`, which must support [`JwsAlgorithm::EdDSA`].
+  pub fn with_signature_verifier(signature_verifier: V) -> Self {
+    Self(signature_verifier)
+  }
+
+  /// Verifies the proof embedded in `credential` using the DID Document of a trusted issuer.
+  ///
+  /// `credential` is the JSON representation of the credential as it was received, since serializing a
+  /// [`Credential`](crate::credential::Credential) again may not reproduce the signed data.
+  ///
+  /// The proof must have the `assertionMethod` purpose and its verification method must belong to the issuer of the
+  /// credential. The verified proof is returned upon success.
+  ///
+  /// # Warning
+  /// Only the proof is verified, the properties of the credential must be validated separately.
+  pub fn verify_credential<DOC>(
+    &self,
+    credential: &Value,
+    trusted_issuers: &[DOC],
+  ) -> DataIntegrityResult<DataIntegrityProof>
+  where
+    DOC: AsRef<CoreDocument>,
+  {
+    self.verify_signed_by(
+      credential,
+      credential.get("issuer"),
+      trusted_issuers,
+      ProofPurpose::AssertionMethod,
+    )
+  }
+
+  /// Verifies the proof embedded in `presentation` using the DID Document of a trusted holder.
+  ///
+  /// `presentation` is the JSON representation of the presentation as it was received, since serializing a
+  /// [`Presentation`](crate::presentation::Presentation) again may not reproduce the signed data.
+  ///
+  /// The proof must have the `authentication` purpose and its verification method must belong to the holder of the
+  /// presentation. If set, the proof's challenge must equal `challenge` and its domain must equal `domain`. The
+  /// verified proof is returned upon success.
+  ///
+  /// # Warning
+  /// Only the proof of the presentation is verified, neither the presentation nor its credentials are validated.
+  pub fn verify_presentation<DOC>(
+    &self,
+    presentation: &Value,
+    trusted_holders: &[DOC],
+    challenge: Option<&str>,
+    domain: Option<&str>,
+  ) -> DataIntegrityResult<DataIntegrityProof>
+  where
+    DOC: AsRef<CoreDocument>,
+  {
+    let proof: DataIntegrityProof = self.verify_signed_by(
+      presentation,
+      presentation.get("holder"),
+      trusted_holders,
+      ProofPurpose::Authentication,
+    )?;
+    if challenge.is_some() && proof.challenge.as_deref() != challenge {
+      return Err(DataIntegrityError::ChallengeMismatch);
+    }
+    if domain.is_some() && proof.domain.as_deref() != domain {
+      return Err(DataIntegrityError::DomainMismatch);
+    }
+    Ok(proof)
+  }
+
+  /// Verifies the proof of `document`, whose verification method must belong to `signer`, the value of its
+  /// `issuer` or `holder` property.
+  fn verify_signed_by<DOC>(
+    &self,
+    document: &Value,
+    signer: Option<&Value>,
+    trusted_signers: &[DOC],
+    proof_purpose: ProofPurpose,
+  ) -> DataIntegrityResult<DataIntegrityProof>
+  where
+    DOC: AsRef<CoreDocument>,
+  {
+    // The signer is either a URL or an object with an `id`, as is the case for the issuer of credentials.
+    let signer: &str = match signer {
+      Some(Value::String(signer)) => signer,
+      Some(Value::Object(signer)) => signer
+        .get("id")
+        .and_then(Value::as_str)
+        .ok_or(DataIntegrityError::SignerMismatch)?,
+      _ => return Err(DataIntegrityError::SignerMismatch),
+    };
+
+    let proof: DataIntegrityProof = self.verify(document, trusted_signers, proof_purpose)?;
+    // The verification method was resolved from a DID Document, so its identifier is a valid DID Url.
+    let method_id: DIDUrl =
+      DIDUrl::parse(&proof.verification_method).map_err(|_| DataIntegrityError::SignerMismatch)?;
+    if method_id.did().as_str() != signer {
+      return Err(DataIntegrityError::SignerMismatch);
+    }
+    Ok(proof)
+  }
+
+  /// Verifies the [`DataIntegrityProof`] embedded in the `proof` property of `document` using the DID Document of a
+  /// trusted signer.
+  ///
+  /// `document` is the JSON representation of the secured document as it was received. The proof is verified over
+  /// its canonical form, so that properties unknown to this library or alternative representations of values, e.g.
+  /// timestamps with fractional seconds, are preserved.
+  ///
+  /// The proof must have the given `proof_purpose` and must not have expired. Its verification method is resolved
+  /// from the DID Document in `trusted_signers` matching its DID, and must be authorized for the verification
+  /// relationship corresponding to `proof_purpose`. The verified proof is returned upon success.
+  pub fn verify<DOC>(
+    &self,
+    document: &Value,
+    trusted_signers: &[DOC],
+    proof_purpose: ProofPurpose,
+  ) -> DataIntegrityResult<DataIntegrityProof>
+  where
+    DOC: AsRef<CoreDocument>,
+  {
+    let (unsecured_document, proof_json) = EddsaJcs2022::unsecure(document)?;
+    let proof_json: Value = proof_json.ok_or(DataIntegrityError::MissingProof)?;
+    if proof_json.is_array() {
+      return Err(DataIntegrityError::MultipleProofs);
+    }
+    let proof: DataIntegrityProof =
+      DataIntegrityProof::from_json_value(proof_json.clone()).map_err(DataIntegrityError::InvalidProof)?;
+
+    if proof.type_ != DataIntegrityProof::TYPE || proof.cryptosuite != EddsaJcs2022::CRYPTOSUITE {
+      return Err(DataIntegrityError::UnsupportedProof(format!(
+        "{} {}",
+        proof.type_, proof.cryptosuite
+      )));
+    }
+    if proof.proof_purpose != proof_purpose {
+      return Err(DataIntegrityError::ProofPurposeMismatch);
+    }
+    if let Some(expires) = proof.expires.as_deref() {
+      if Timestamp::parse(expires).map_err(DataIntegrityError::InvalidProof)? < Timestamp::now_utc() {
+        return Err(DataIntegrityError::ProofExpired);
+      }
+    }
+    if let Some(context) = proof_json.get("@context") {
+      let proof_context: Vec<Value> = context_values(context.clone());
+      let document_context: Vec<Value> = unsecured_document
+        .get("@context")
+        .cloned()
+        .map(context_values)
+        .unwrap_or_default();
+      if !document_context.starts_with(&proof_context) {
+        return Err(DataIntegrityError::ContextMismatch);
+      }
+    }
+
+    let signature: Vec<u8> = EddsaJcs2022::decode_proof_value(
+      proof
+        .proof_value
+        .as_deref()
+        .ok_or(DataIntegrityError::InvalidProofValue(None))?,
+    )?;
+    let hash_data: [u8; 2 * SHA256_LEN] = EddsaJcs2022::hash_data(&unsecured_document, &proof_json)?;
+
+    let public_key: &Jwk = resolve_public_key(&proof.verification_method, trusted_signers, proof_purpose)?;
+    self
+      .0
+      .verify(
+        VerificationInput {
+          alg: JwsAlgorithm::EdDSA,
+          signing_input: hash_data.into(),
+          decoded_signature: signature.into_boxed_slice(),
+        },
+        public_key,
+      )
+      .map_err(DataIntegrityError::Signature)?;
+
+    Ok(proof)
+  }
+}
+
+/// Returns the entries of a JSON-LD `@context` value.
+fn context_values(context: Value) -> Vec<Value> {
+  match context {
+    Value::Array(values) => values,
+    value => vec![value],
+  }
+}
+
+/// Resolves the public key of the verification method identified by `verification_method` from the matching
+/// document in `trusted_signers`.
+fn resolve_public_key<'a, DOC>(
+  verification_method: &str,
+  trusted_signers: &'a [DOC],
+  proof_purpose: ProofPurpose,
+) -> DataIntegrityResult<&'a Jwk>
+where
+  DOC: AsRef<CoreDocument>,
+{
+  let method_id: DIDUrl =
+    DIDUrl::parse(verification_method).map_err(|err| DataIntegrityError::MethodDataLookupError {
+      source: Some(err.into()),
+      message: "could not parse verificationMethod as a DID Url",
+    })?;
+
+  let signer: &CoreDocument = trusted_signers
+    .iter()
+    .map(AsRef::as_ref)
+    .find(|document| <CoreDocument>::id(document) == method_id.did())
+    .ok_or(DataIntegrityError::SignerMismatch)?;
+
+  let method: &VerificationMethod = signer
+    .resolve_method(
+      &method_id,
+      Some(MethodScope::VerificationRelationship(proof_purpose.into())),
+    )
+    .ok_or(DataIntegrityError::MethodDataLookupError {
+      source: None,
+      message: "could not find a verification method authorized for the proof purpose",
+    })?;
+
+  method
+    .data()
+    .try_public_key_jwk()
+    .map_err(|err| DataIntegrityError::MethodDataLookupError {
+      source: Some(err.into()),
+      message: "the verification method does not contain a public key in the JWK format",
+    })
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_core::common::Context;
+  use identity_core::common::OneOrMany;
+  use identity_core::common::Url;
+
+  use super::*;
+
+  #[test]
+  fn test_proof_configuration() {
+    let (unsecured_document, proof) = EddsaJcs2022::unsecure(&serde_json::json!({
+      "@context": ["https://www.w3.org/ns/credentials/v2", "https://www.w3.org/ns/credentials/examples/v2"],
+      "type": ["VerifiableCredential"],
+      "proof": {}
+    }))
+    .unwrap();
+    assert!(proof.is_some());
+    assert!(!unsecured_document.contains_key("proof"));
+
+    let method_id: DIDUrl = DIDUrl::parse("did:example:123#key-1").unwrap();
+    let mut proof: DataIntegrityProof = EddsaJcs2022::create_proof_configuration(
+      &unsecured_document,
+      &method_id,
+      ProofPurpose::AssertionMethod,
+      &DataIntegrityProofOptions::new().created(Timestamp::parse("2023-02-24T23:36:38Z").unwrap()),
+    )
+    .unwrap();
+    assert_eq!(
+      proof.context,
+      Some(OneOrMany::Many(vec![
+        Context::Url(Url::parse("https://www.w3.org/ns/credentials/v2").unwrap()),
+        Context::Url(Url::parse("https://www.w3.org/ns/credentials/examples/v2").unwrap()),
+      ]))
+    );
+    assert_eq!(proof.verification_method, "did:example:123#key-1");
+
+    // The proof value is not part of the signed data.
+    let hash_data = EddsaJcs2022::hash_data(&unsecured_document, &proof).unwrap();
+    proof.proof_value = Some(EddsaJcs2022::encode_proof_value(&[1, 2, 3]));
+    assert_eq!(EddsaJcs2022::hash_data(&unsecured_document, &proof).unwrap(), hash_data);
+  }
+
+  /// Creates the DID Document of a did:key for the multibase-encoded Ed25519 `public_key`, authorized for all
+  /// proof purposes.
+  fn did_key_document(public_key: &str) -> CoreDocument {
+    use identity_did::CoreDID;
+    use identity_verification::jwk::EdCurve;
+    use identity_verification::jwk::JwkParamsOkp;
+    use identity_verification::jwu;
+
+    // Strip the multicodec prefix of Ed25519 public keys.
+    let public_key_bytes: Vec<u8> = BaseEncoding::decode_multibase(public_key).unwrap();
+    let mut params: JwkParamsOkp = JwkParamsOkp::new();
+    params.crv = EdCurve::Ed25519.name().to_owned();
+    params.x = jwu::encode_b64(&public_key_bytes[2..]);
+
+    let did: CoreDID = CoreDID::parse(format!("did:key:{public_key}")).unwrap();
+    let method: VerificationMethod =
+      VerificationMethod::new_from_jwk(did.clone(), Jwk::from_params(params), Some(public_key)).unwrap();
+    let method_id: DIDUrl = method.id().clone();
+    CoreDocument::builder(Object::new())
+      .id(did)
+      .verification_method(method)
+      .authentication(method_id.clone())
+      .assertion_method(method_id)
+      .build()
+      .unwrap()
+  }
+
+  #[test]
+  fn test_w3c_test_vector() {
+    use identity_eddsa_verifier::EdDSAJwsVerifier;
+
+    // The eddsa-jcs-2022 test vector of https://www.w3.org/TR/vc-di-eddsa/#representation-eddsa-jcs-2022.
+    let mut credential: Value = serde_json::json!({
+      "@context": [
+        "https://www.w3.org/ns/credentials/v2",
+        "https://www.w3.org/ns/credentials/examples/v2"
+      ],
+      "id": "urn:uuid:58172aac-d8ba-11ed-83dd-0b3aef56cc33",
+      "type": ["VerifiableCredential", "AlumniCredential"],
+      "name": "Alumni Credential",
+      "description": "A minimum viable example of an Alumni Credential.",
+      "issuer": "https://vc.example/issuers/5678",
+      "validFrom": "2023-01-01T00:00:00Z",
+      "credentialSubject": {
+        "id": "did:example:abcdefgh",
+        "alumniOf": "The School of Examples"
+      },
+      "proof": {
+        "type": "DataIntegrityProof",
+        "cryptosuite": "eddsa-jcs-2022",
+        "created": "2023-02-24T23:36:38Z",
+        "verificationMethod": "did:key:z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2#z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2",
+        "proofPurpose": "assertionMethod",
+        "@context": [
+          "https://www.w3.org/ns/credentials/v2",
+          "https://www.w3.org/ns/credentials/examples/v2"
+        ],
+        "proofValue": "z2HnFSSPPBzR36zdDgK8PbEHeXbR56YF24jwMpt3R1eHXQzJDMWS93FCzpvJpwTWd3GAVFuUfjoJdcnTMuVor51aX"
+      }
+    });
+    let signer: CoreDocument = did_key_document("z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2");
+
+    let verifier = EddsaJcs2022Verifier::with_signature_verifier(EdDSAJwsVerifier::default());
+    let proof: DataIntegrityProof = verifier
+      .verify(&credential, &[&signer], ProofPurpose::AssertionMethod)
+      .unwrap();
+    assert_eq!(proof.created.as_deref(), Some("2023-02-24T23:36:38Z"));
+
+    // The proof covers the received representation of its timestamps.
+    credential["proof"]["created"] = Value::from("2023-02-24T23:36:38.000Z");
+    assert!(matches!(
+      verifier
+        .verify(&credential, &[&signer], ProofPurpose::AssertionMethod)
+        .unwrap_err(),
+      DataIntegrityError::Signature(_)
+    ));
+  }
+
+  #[test]
+  fn test_proof_value_encoding() {
+    let proof_value: String = EddsaJcs2022::encode_proof_value(&[0xde, 0xad, 0xbe, 0xef]);
+    assert!(proof_value.starts_with('z'));
+    assert_eq!(
+      EddsaJcs2022::decode_proof_value(&proof_value).unwrap(),
+      [0xde, 0xad, 0xbe, 0xef]
+    );
+    // Only base58-btc is allowed.
+    assert!(EddsaJcs2022::decode_proof_value("uAQID").is_err());
+  }
+}
@@ -0,0 +1,59 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_verification::jws::SignatureVerificationError;
+
+/// Errors that can occur when creating or verifying a
+/// [`DataIntegrityProof`](crate::data_integrity::DataIntegrityProof).
+#[derive(Debug, thiserror::Error, strum::IntoStaticStr)]
+#[non_exhaustive]
+pub enum DataIntegrityError {
+  /// Caused by a document without a `proof` property.
+  #[error("missing proof")]
+  MissingProof,
+  /// Caused by a document containing more than a single proof.
+  #[error("multiple proofs are not supported")]
+  MultipleProofs,
+  /// Caused by a malformed proof.
+  #[error("invalid proof")]
+  InvalidProof(#[source] identity_core::Error),
+  /// Caused by a proof of an unsupported type or cryptosuite.
+  #[error("unsupported proof type or cryptosuite: {0}")]
+  UnsupportedProof(String),
+  /// Caused by a proof whose purpose differs from the expected one.
+  #[error("unexpected proof purpose")]
+  ProofPurposeMismatch,
+  /// Caused by a proof whose challenge differs from the expected one.
+  #[error("unexpected proof challenge")]
+  ChallengeMismatch,
+  /// Caused by a proof whose domain differs from the expected one.
+  #[error("unexpected proof domain")]
+  DomainMismatch,
+  /// Caused by a proof that expired.
+  #[error("the proof has expired")]
+  ProofExpired,
+  /// Caused by a proof configuration whose `@context` does not match the beginning of the secured document's.
+  #[error("the proof context does not match the document context")]
+  ContextMismatch,
+  /// Caused by a failure to serialize the document or the proof configuration.
+  #[error("could not serialize the document or the proof configuration")]
+  SerializationError(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
+  /// Caused by a missing or malformed `proofValue`.
+  #[error("invalid proof value")]
+  InvalidProofValue(#[source] Option<identity_core::Error>),
+  /// Caused by a failure to obtain the public key of the verification method referenced by the proof.
+  #[error("could not find verification material: {message}")]
+  MethodDataLookupError {
+    /// The source of the error if set.
+    #[source]
+    source: Option<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    /// A message providing more context.
+    message: &'static str,
+  },
+  /// Caused by a proof whose verification method does not belong to the expected signer, e.g. the credential issuer.
+  #[error("the verification method does not belong to the expected signer")]
+  SignerMismatch,
+  /// Caused by a failure to verify the signature.
+  #[error("could not verify the signature")]
+  Signature(#[source] SignatureVerificationError),
+}
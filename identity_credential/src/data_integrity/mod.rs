@@ -0,0 +1,15 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Implementation of [Verifiable Credential Data Integrity](https://www.w3.org/TR/vc-data-integrity/) proofs
+//! using the [`eddsa-jcs-2022`](https://www.w3.org/TR/vc-di-eddsa/#eddsa-jcs-2022) cryptosuite.
+
+mod eddsa_jcs_2022;
+mod error;
+mod proof;
+mod proof_options;
+
+pub use self::eddsa_jcs_2022::*;
+pub use self::error::*;
+pub use self::proof::*;
+pub use self::proof_options::*;
@@ -0,0 +1,150 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Context;
+use identity_core::common::Object;
+use identity_core::common::OneOrMany;
+use identity_core::convert::FromJson;
+use identity_core::convert::ToJson;
+use identity_verification::MethodRelationship;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::DataIntegrityError;
+use crate::credential::Proof;
+
+/// The purpose of a [`DataIntegrityProof`], which determines the verification relationship the verification
+/// method must be authorized for.
+///
+/// [More Info](https://www.w3.org/TR/vc-data-integrity/#proof-purposes)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ProofPurpose {
+  /// Used to authenticate the controller of the verification method, e.g. the holder of a presentation.
+  Authentication,
+  /// Used to assert claims, e.g. those of a credential by its issuer.
+  AssertionMethod,
+  /// Used for key agreement protocols.
+  KeyAgreement,
+  /// Used to delegate capabilities.
+  CapabilityDelegation,
+  /// Used to invoke capabilities.
+  CapabilityInvocation,
+}
+
+impl From<ProofPurpose> for MethodRelationship {
+  fn from(purpose: ProofPurpose) -> Self {
+    match purpose {
+      ProofPurpose::Authentication => Self::Authentication,
+      ProofPurpose::AssertionMethod => Self::AssertionMethod,
+      ProofPurpose::KeyAgreement => Self::KeyAgreement,
+      ProofPurpose::CapabilityDelegation => Self::CapabilityDelegation,
+      ProofPurpose::CapabilityInvocation => Self::CapabilityInvocation,
+    }
+  }
+}
+
+/// A [Data Integrity proof](https://www.w3.org/TR/vc-data-integrity/#proofs) of type `DataIntegrityProof`.
+///
+/// A proof without a `proofValue` is a proof configuration, from which the value is derived.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataIntegrityProof {
+  /// The JSON-LD context(s) of the proof configuration, which must match the beginning of the secured document's
+  /// context.
+  #[serde(rename = "@context", default, skip_serializing_if = "Option::is_none")]
+  pub context: Option<OneOrMany<Context>>,
+  /// The type of the proof, always `DataIntegrityProof`.
+  #[serde(rename = "type")]
+  pub type_: String,
+  /// The cryptosuite used to produce the proof.
+  pub cryptosuite: String,
+  /// The time at which the proof was created, as an XML Schema `dateTimeStamp`.
+  ///
+  /// The value is kept as received, since it is part of the signed data.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub created: Option<String>,
+  /// The time after which the proof is no longer valid, as an XML Schema `dateTimeStamp`.
+  ///
+  /// The value is kept as received, since it is part of the signed data.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub expires: Option<String>,
+  /// The identifier of the verification method that can be used to verify the proof.
+  pub verification_method: String,
+  /// The purpose of the proof.
+  pub proof_purpose: ProofPurpose,
+  /// A challenge provided by the verifier, to mitigate replay attacks.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub challenge: Option<String>,
+  /// The domain the proof is restricted to.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub domain: Option<String>,
+  /// A nonce supplied by the creator of the proof.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub nonce: Option<String>,
+  /// The multibase-encoded value of the proof.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub proof_value: Option<String>,
+  /// Additional properties of the proof.
+  #[serde(flatten)]
+  pub properties: Object,
+}
+
+impl DataIntegrityProof {
+  /// The type of Data Integrity proofs.
+  pub const TYPE: &'static str = "DataIntegrityProof";
+}
+
+impl TryFrom<&Proof> for DataIntegrityProof {
+  type Error = DataIntegrityError;
+
+  fn try_from(proof: &Proof) -> Result<Self, Self::Error> {
+    if proof.type_ != Self::TYPE {
+      return Err(DataIntegrityError::UnsupportedProof(proof.type_.clone()));
+    }
+    proof
+      .to_json_value()
+      .and_then(Self::from_json_value)
+      .map_err(DataIntegrityError::InvalidProof)
+  }
+}
+
+impl From<DataIntegrityProof> for Proof {
+  fn from(proof: DataIntegrityProof) -> Self {
+    // A `DataIntegrityProof` always serializes to a JSON object with a `type` property.
+    proof
+      .to_json_value()
+      .and_then(Proof::from_json_value)
+      .expect("a data integrity proof is a valid proof")
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const PROOF: &str = r#"{
+    "type": "DataIntegrityProof",
+    "cryptosuite": "eddsa-jcs-2022",
+    "created": "2023-02-24T23:36:38Z",
+    "verificationMethod": "did:example:123#key-1",
+    "proofPurpose": "assertionMethod",
+    "proofValue": "z2YwC8z3ap7yx1nZYCg4L3j3ApHsF8kgPdSb5xoS1VR7vPG3F561B52hYnQF9iseabecm3ijx4K1FBTQsCZahKZme"
+  }"#;
+
+  #[test]
+  fn test_proof_conversion() {
+    let proof: Proof = Proof::from_json(PROOF).unwrap();
+    let data_integrity_proof: DataIntegrityProof = DataIntegrityProof::try_from(&proof).unwrap();
+    assert_eq!(data_integrity_proof.cryptosuite, "eddsa-jcs-2022");
+    assert_eq!(data_integrity_proof.proof_purpose, ProofPurpose::AssertionMethod);
+    assert!(data_integrity_proof.properties.is_empty());
+    assert_eq!(Proof::from(data_integrity_proof), proof);
+
+    let proof: Proof = Proof::new("RsaSignature2018".to_owned(), Object::new());
+    assert!(matches!(
+      DataIntegrityProof::try_from(&proof).unwrap_err(),
+      DataIntegrityError::UnsupportedProof(_)
+    ));
+  }
+}
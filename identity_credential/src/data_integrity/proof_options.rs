@@ -0,0 +1,70 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Timestamp;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Options for creating a [`DataIntegrityProof`](crate::data_integrity::DataIntegrityProof).
+#[non_exhaustive]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataIntegrityProofOptions {
+  /// The time at which the proof is created.
+  ///
+  /// Default: the current datetime.
+  pub created: Option<Timestamp>,
+  /// The time after which the proof is no longer valid.
+  ///
+  /// Default: `None`.
+  pub expires: Option<Timestamp>,
+  /// A challenge provided by the verifier, to mitigate replay attacks.
+  ///
+  /// Default: `None`.
+  pub challenge: Option<String>,
+  /// The domain the proof is restricted to.
+  ///
+  /// Default: `None`.
+  pub domain: Option<String>,
+  /// A nonce to include in the proof.
+  ///
+  /// Default: `None`.
+  pub nonce: Option<String>,
+}
+
+impl DataIntegrityProofOptions {
+  /// Creates a new [`DataIntegrityProofOptions`] with all options set to their defaults.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Sets the time at which the proof is created.
+  pub fn created(mut self, created: Timestamp) -> Self {
+    self.created = Some(created);
+    self
+  }
+
+  /// Sets the time after which the proof is no longer valid.
+  pub fn expires(mut self, expires: Timestamp) -> Self {
+    self.expires = Some(expires);
+    self
+  }
+
+  /// Sets the challenge provided by the verifier.
+  pub fn challenge(mut self, challenge: impl Into<String>) -> Self {
+    self.challenge = Some(challenge.into());
+    self
+  }
+
+  /// Sets the domain the proof is restricted to.
+  pub fn domain(mut self, domain: impl Into<String>) -> Self {
+    self.domain = Some(domain.into());
+    self
+  }
+
+  /// Sets the nonce to include in the proof.
+  pub fn nonce(mut self, nonce: impl Into<String>) -> Self {
+    self.nonce = Some(nonce.into());
+    self
+  }
+}
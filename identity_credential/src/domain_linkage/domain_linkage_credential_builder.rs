@@ -98,8 +98,12 @@ impl DomainLinkageCredentialBuilder {
       ]),
       credential_subject: OneOrMany::One(Subject::with_id_and_properties(issuer.clone(), properties)),
       issuer: Issuer::Url(issuer),
-      issuance_date: self.issuance_date.unwrap_or_else(Timestamp::now_utc),
+      issuance_date: Some(self.issuance_date.unwrap_or_else(Timestamp::now_utc)),
       expiration_date: Some(self.expiration_date.ok_or(Error::MissingExpirationDate)?),
+      valid_from: None,
+      valid_until: None,
+      name: None,
+      description: None,
       credential_status: None,
       credential_schema: Vec::new().into(),
       refresh_service: Vec::new().into(),
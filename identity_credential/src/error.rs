@@ -22,6 +22,12 @@ pub enum Error {
   /// Caused when constructing a credential without a subject.
   #[error("missing credential subject")]
   MissingSubject,
+  /// Caused when constructing a VC Data Model v1.1 credential without an issuance date.
+  #[error("missing issuance date")]
+  MissingIssuanceDate,
+  /// Caused when constructing a credential with a property that is not defined by its data model version.
+  #[error("unsupported data model property: {0}")]
+  UnsupportedDataModelProperty(&'static str),
   /// Caused when constructing a Domain Linkage credential without an expiration date.
   #[error("missing expiration date")]
   MissingExpirationDate,
@@ -34,6 +40,10 @@ pub enum Error {
   /// Caused when trying to construct an invalid status.
   #[error("invalid credential status: {0}")]
   InvalidStatus(String),
+  /// Caused when a credential schema cannot be used to validate a credential, or the credential does not conform to
+  /// it.
+  #[error("invalid credential schema: {0}")]
+  InvalidCredentialSchema(String),
   /// Caused when constructing an invalid `LinkedDomainService` or `DomainLinkageConfiguration`.
   #[error("domain linkage error")]
   DomainLinkageError(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
@@ -68,4 +78,8 @@ pub enum Error {
   /// JSON.
   #[error("could not deserialize JWT claims set")]
   JwtClaimsSetDeserializationError(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
+
+  /// Caused by a failure to deserialize a credential from its JSON representation.
+  #[error("could not deserialize credential")]
+  CredentialDeserializationError(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
 }
@@ -16,11 +16,15 @@
 
 #[cfg(feature = "credential")]
 pub mod credential;
+#[cfg(feature = "data-integrity")]
+pub mod data_integrity;
 #[cfg(feature = "domain-linkage")]
 pub mod domain_linkage;
 pub mod error;
 #[cfg(feature = "presentation")]
 pub mod presentation;
+#[cfg(feature = "presentation-exchange")]
+pub mod presentation_exchange;
 #[cfg(feature = "revocation-bitmap")]
 pub mod revocation;
 mod utils;
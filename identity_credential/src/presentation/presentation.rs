@@ -15,7 +15,7 @@ use identity_core::common::Url;
 use identity_core::convert::FmtJson;
 use identity_core::convert::ToJson;
 
-use crate::credential::Credential;
+use crate::credential::DataModelVersion;
 use crate::credential::Policy;
 use crate::credential::Proof;
 use crate::credential::RefreshService;
@@ -26,7 +26,7 @@ use super::jwt_serialization::PresentationJwtClaims;
 use super::JwtPresentationOptions;
 use super::PresentationBuilder;
 
-/// Represents a bundle of one or more [`Credential`]s.
+/// Represents a bundle of one or more [`Credential`](crate::credential::Credential)s.
 #[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct Presentation<CRED, T = Object> {
   /// The JSON-LD context(s) applicable to the `Presentation`.
@@ -44,7 +44,7 @@ pub struct Presentation<CRED, T = Object> {
   pub verifiable_credential: Vec<CRED>,
   /// The entity that generated the `Presentation`.
   pub holder: Url,
-  /// Service(s) used to refresh an expired [`Credential`] in the `Presentation`.
+  /// Service(s) used to refresh an expired [`Credential`](crate::credential::Credential) in the `Presentation`.
   #[serde(default, rename = "refreshService", skip_serializing_if = "OneOrMany::is_empty")]
   pub refresh_service: OneOrMany<RefreshService>,
   /// Terms-of-use specified by the `Presentation` holder.
@@ -71,9 +71,11 @@ where
 }
 
 impl<CRED, T> Presentation<CRED, T> {
-  /// Returns the base JSON-LD context for `Presentation`s.
+  /// Returns the base JSON-LD context for VC Data Model v1.1 `Presentation`s.
+  ///
+  /// See [`DataModelVersion::base_context`] for the base context of other versions.
   pub fn base_context() -> &'static Context {
-    Credential::<Object>::base_context()
+    DataModelVersion::V1.base_context()
   }
 
   /// Returns the base type for `Presentation`s.
@@ -106,6 +108,12 @@ impl<CRED, T> Presentation<CRED, T> {
     Ok(this)
   }
 
+  /// Returns the version of the VC Data Model indicated by the base context of the `Presentation`, or `None` if
+  /// the first context is not a known base context.
+  pub fn data_model_version(&self) -> Option<DataModelVersion> {
+    self.context.get(0).and_then(DataModelVersion::from_base_context)
+  }
+
   /// Validates the semantic structure of the `Presentation`.
   ///
   /// # Warning
@@ -114,9 +122,8 @@ impl<CRED, T> Presentation<CRED, T> {
   /// signature validation on the credentials as they are encoded as JWTs.
   pub fn check_structure(&self) -> Result<()> {
     // Ensure the base context is present and in the correct location
-    match self.context.get(0) {
-      Some(context) if context == Self::base_context() => {}
-      Some(_) | None => return Err(Error::MissingBaseContext),
+    if self.data_model_version().is_none() {
+      return Err(Error::MissingBaseContext);
     }
 
     // The set of types MUST contain the base type
@@ -164,9 +171,12 @@ mod tests {
   use std::error::Error;
 
   use identity_core::common::Object;
+  use identity_core::common::Url;
   use identity_core::convert::FromJson;
 
+  use crate::credential::DataModelVersion;
   use crate::presentation::Presentation;
+  use crate::presentation::PresentationBuilder;
 
   #[test]
   fn test_presentation_deserialization() {
@@ -214,6 +224,34 @@ mod tests {
     .is_ok());
   }
 
+  #[test]
+  fn test_presentation_data_model_version() {
+    let presentation: Presentation<Object> = Presentation::from_json_value(json!({
+      "@context": "https://www.w3.org/ns/credentials/v2",
+      "holder": "did:test:abc1",
+      "type": "VerifiablePresentation"
+    }))
+    .unwrap();
+    assert_eq!(presentation.data_model_version(), Some(DataModelVersion::V2));
+    assert!(presentation.check_structure().is_ok());
+
+    let presentation: Presentation<Object> =
+      PresentationBuilder::new(Url::parse("did:test:abc1").unwrap(), Object::new())
+        .data_model_version(DataModelVersion::V2)
+        .build()
+        .unwrap();
+    assert_eq!(presentation.context.get(0), Some(DataModelVersion::V2.base_context()));
+
+    let presentation: Presentation<Object> = Presentation::from_json_value(json!({
+      "@context": "https://www.w3.org/ns/credentials/examples/v2",
+      "holder": "did:test:abc1",
+      "type": "VerifiablePresentation"
+    }))
+    .unwrap();
+    assert_eq!(presentation.data_model_version(), None);
+    assert!(presentation.check_structure().is_err());
+  }
+
   #[test]
   fn test_presentation_deserialization_without_credentials() {
     // Deserializing a Presentation without `verifiableCredential' property is allowed.
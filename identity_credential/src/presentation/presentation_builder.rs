@@ -6,6 +6,7 @@ use identity_core::common::Object;
 use identity_core::common::Url;
 use identity_core::common::Value;
 
+use crate::credential::DataModelVersion;
 use crate::credential::Policy;
 use crate::credential::RefreshService;
 use crate::error::Result;
@@ -40,6 +41,15 @@ impl<CRED, T> PresentationBuilder<CRED, T> {
     }
   }
 
+  /// Sets the version of the VC Data Model of the presentation by replacing its base context.
+  ///
+  /// Defaults to [`DataModelVersion::V1`].
+  #[must_use]
+  pub fn data_model_version(mut self, version: DataModelVersion) -> Self {
+    self.context[0] = version.base_context().clone();
+    self
+  }
+
   /// Adds a value to the `context` set.
   #[must_use]
   pub fn context(mut self, value: impl Into<Context>) -> Self {
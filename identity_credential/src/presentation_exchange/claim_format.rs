@@ -0,0 +1,113 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::fmt::Display;
+use core::fmt::Formatter;
+
+use identity_core::common::Object;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// A [claim format designation](https://identity.foundation/claim-format-registry/) identifying how a credential or
+/// presentation is encoded and secured.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum ClaimFormat {
+  /// A JSON Web Token.
+  #[serde(rename = "jwt")]
+  Jwt,
+  /// A Verifiable Credential encoded as a JWT.
+  #[serde(rename = "jwt_vc")]
+  JwtVc,
+  /// A Verifiable Credential encoded as a JWT, using the JSON representation of the data model.
+  #[serde(rename = "jwt_vc_json")]
+  JwtVcJson,
+  /// A Verifiable Presentation encoded as a JWT.
+  #[serde(rename = "jwt_vp")]
+  JwtVp,
+  /// A Verifiable Presentation encoded as a JWT, using the JSON representation of the data model.
+  #[serde(rename = "jwt_vp_json")]
+  JwtVpJson,
+  /// A Linked Data document secured with an embedded proof.
+  #[serde(rename = "ldp")]
+  Ldp,
+  /// A Verifiable Credential secured with an embedded proof.
+  #[serde(rename = "ldp_vc")]
+  LdpVc,
+  /// A Verifiable Presentation secured with an embedded proof.
+  #[serde(rename = "ldp_vp")]
+  LdpVp,
+  /// A Verifiable Credential issued as an SD-JWT.
+  #[serde(rename = "vc+sd-jwt")]
+  SdJwtVc,
+  /// Any other claim format designation.
+  #[serde(untagged)]
+  Other(String),
+}
+
+impl ClaimFormat {
+  /// Returns the string representation of the claim format designation.
+  pub fn as_str(&self) -> &str {
+    match self {
+      Self::Jwt => "jwt",
+      Self::JwtVc => "jwt_vc",
+      Self::JwtVcJson => "jwt_vc_json",
+      Self::JwtVp => "jwt_vp",
+      Self::JwtVpJson => "jwt_vp_json",
+      Self::Ldp => "ldp",
+      Self::LdpVc => "ldp_vc",
+      Self::LdpVp => "ldp_vp",
+      Self::SdJwtVc => "vc+sd-jwt",
+      Self::Other(format) => format,
+    }
+  }
+
+  /// Returns whether values of this format are encoded as a JWT.
+  pub fn is_jwt(&self) -> bool {
+    matches!(
+      self,
+      Self::Jwt | Self::JwtVc | Self::JwtVcJson | Self::JwtVp | Self::JwtVpJson
+    )
+  }
+
+  /// Returns whether this format is one of the keys of the `format` property of a presentation definition or input
+  /// descriptor, treating the JWT designations with and without the `_json` suffix as equivalent.
+  pub(crate) fn is_accepted_by(&self, formats: &Object) -> bool {
+    let equivalent: Option<ClaimFormat> = match self {
+      Self::JwtVc => Some(Self::JwtVcJson),
+      Self::JwtVcJson => Some(Self::JwtVc),
+      Self::JwtVp => Some(Self::JwtVpJson),
+      Self::JwtVpJson => Some(Self::JwtVp),
+      _ => None,
+    };
+
+    formats.contains_key(self.as_str())
+      || equivalent
+        .map(|format| formats.contains_key(format.as_str()))
+        .unwrap_or(false)
+  }
+}
+
+impl Display for ClaimFormat {
+  fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+    f.write_str(self.as_str())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn serde_claim_format() {
+    for (format, json) in [
+      (ClaimFormat::JwtVc, "\"jwt_vc\""),
+      (ClaimFormat::SdJwtVc, "\"vc+sd-jwt\""),
+      (ClaimFormat::Other("mso_mdoc".to_owned()), "\"mso_mdoc\""),
+    ] {
+      assert_eq!(serde_json::to_string(&format).unwrap(), json);
+      assert_eq!(serde_json::from_str::<ClaimFormat>(json).unwrap(), format);
+      assert_eq!(format.to_string(), json.trim_matches('"'));
+    }
+  }
+}
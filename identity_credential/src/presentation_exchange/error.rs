@@ -0,0 +1,105 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::validator::CompoundCredentialValidationError;
+use crate::validator::CompoundJwtPresentationValidationError;
+
+/// Errors that can occur when evaluating a presentation definition or a presentation submission.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum PresentationExchangeError {
+  /// Caused by a presentation definition that is not structurally valid.
+  #[error("invalid presentation definition: {0}")]
+  InvalidDefinition(String),
+  /// Caused by an input descriptor whose constraints use a feature that is not supported.
+  #[error("input descriptor `{id}` uses the unsupported constraint `{constraint}`")]
+  #[non_exhaustive]
+  UnsupportedConstraint {
+    /// The id of the input descriptor.
+    id: String,
+    /// The name of the unsupported constraint.
+    constraint: String,
+  },
+  /// Caused by a JSONPath expression that cannot be parsed.
+  #[error("invalid JSONPath expression `{path}`")]
+  #[non_exhaustive]
+  InvalidJsonPath {
+    /// The JSONPath expression.
+    path: String,
+    /// The parsing error.
+    source: serde_json_path::ParseError,
+  },
+  /// Caused by a `filter` that is not a valid JSON Schema.
+  #[error("invalid filter: {0}")]
+  InvalidFilter(String),
+  /// Caused by a failure to decode a credential into its JSON claims.
+  #[error("could not decode the credential")]
+  CredentialDecoding(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
+  /// Caused by a failure to validate the presentation.
+  #[error("presentation validation failed")]
+  PresentationValidation(#[source] CompoundJwtPresentationValidationError),
+  /// Caused by a presentation submission referring to a different presentation definition.
+  #[error("the submission refers to presentation definition `{0}`")]
+  DefinitionMismatch(String),
+  /// Caused by a descriptor mapping referring to an input descriptor not present in the presentation definition.
+  #[error("unknown input descriptor `{0}`")]
+  UnknownInputDescriptor(String),
+  /// Caused by the path of a descriptor mapping not resolving to exactly one value.
+  #[error("the path `{path}` of input descriptor `{id}` does not resolve to exactly one value")]
+  #[non_exhaustive]
+  InvalidDescriptorPath {
+    /// The id of the input descriptor.
+    id: String,
+    /// The JSONPath expression of the descriptor mapping.
+    path: String,
+  },
+  /// Caused by a submitted credential whose claim format is not accepted by its input descriptor.
+  #[error("claim format `{format}` is not accepted by input descriptor `{id}`")]
+  #[non_exhaustive]
+  UnsupportedFormat {
+    /// The id of the input descriptor.
+    id: String,
+    /// The claim format of the submitted credential.
+    format: String,
+  },
+  /// Caused by a submitted credential not satisfying the constraints of its input descriptor.
+  #[error("input descriptor `{0}` is not satisfied")]
+  InputDescriptorNotSatisfied(String),
+  /// Caused by an input descriptor requiring limited disclosure from a credential whose claim format does not
+  /// support selective disclosure.
+  #[error("input descriptor `{id}` requires limited disclosure, which claim format `{format}` does not support")]
+  #[non_exhaustive]
+  LimitDisclosureUnsupported {
+    /// The id of the input descriptor.
+    id: String,
+    /// The claim format of the submitted credential.
+    format: String,
+  },
+  /// Caused by a submitted credential disclosing claims not requested by its input descriptor, which requires
+  /// limited disclosure.
+  #[error("the credential submitted for input descriptor `{0}` discloses claims that were not requested")]
+  UnrequestedDisclosure(String),
+  /// Caused by a failure to validate a submitted credential.
+  #[error("the credential submitted for input descriptor `{id}` is invalid")]
+  #[non_exhaustive]
+  CredentialValidation {
+    /// The id of the input descriptor.
+    id: String,
+    /// The validation error.
+    source: CompoundCredentialValidationError,
+  },
+  /// Caused by a submitted credential whose claim format cannot be validated.
+  #[error(
+    "the credential submitted for input descriptor `{id}` cannot be validated: unsupported claim format `{format}`"
+  )]
+  #[non_exhaustive]
+  UnsupportedCredentialValidation {
+    /// The id of the input descriptor.
+    id: String,
+    /// The claim format of the submitted credential.
+    format: String,
+  },
+  /// Caused by a presentation submission not satisfying the requirements of the presentation definition.
+  #[error("the submission does not satisfy the requirements of the presentation definition")]
+  RequirementsNotSatisfied,
+}
@@ -0,0 +1,25 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Implementation of [Presentation Exchange v2](https://identity.foundation/presentation-exchange/spec/v2.0.0/).
+//!
+//! Verifiers express which credentials they accept with a [`PresentationDefinition`]. Holders select matching
+//! credentials with [`PresentationDefinition::match_candidates`] and describe where they placed them in the
+//! presentation with a [`PresentationSubmission`], which verifiers check using the
+//! [`PresentationExchangeValidator`].
+
+mod claim_format;
+mod error;
+mod presentation_candidate;
+mod presentation_definition;
+mod presentation_exchange_validation_options;
+mod presentation_exchange_validator;
+mod presentation_submission;
+
+pub use self::claim_format::*;
+pub use self::error::*;
+pub use self::presentation_candidate::*;
+pub use self::presentation_definition::*;
+pub use self::presentation_exchange_validation_options::*;
+pub use self::presentation_exchange_validator::*;
+pub use self::presentation_submission::*;
@@ -0,0 +1,304 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Value;
+use identity_verification::jws::Decoder;
+use identity_verification::jws::JwsValidationItem;
+#[cfg(feature = "sd-jwt")]
+use sd_jwt_payload::SdJwt;
+#[cfg(feature = "sd-jwt")]
+use sd_jwt_payload::SdObjectDecoder;
+use serde::Serialize;
+
+use crate::credential::Credential;
+use crate::credential::Jwt;
+
+use super::ClaimFormat;
+use super::PresentationDefinition;
+use super::PresentationExchangeError;
+
+/// A credential held by a holder, which can be matched against the input descriptors of a
+/// [`PresentationDefinition`].
+pub trait PresentationCandidate {
+  /// The claim format of the credential, if known.
+  ///
+  /// Credentials with an unknown format only match input descriptors for which the verifier does not restrict the
+  /// `format`.
+  fn claim_format(&self) -> Option<ClaimFormat>;
+
+  /// The JSON representation of the credential, which the constraints of input descriptors are evaluated against.
+  fn claims(&self) -> Result<Value, PresentationExchangeError>;
+}
+
+/// A [`Credential`] is in the `ldp_vc` format if it contains a `proof`, otherwise its format is unknown.
+impl<T: Serialize> PresentationCandidate for Credential<T> {
+  fn claim_format(&self) -> Option<ClaimFormat> {
+    self.proof.as_ref().map(|_| ClaimFormat::LdpVc)
+  }
+
+  fn claims(&self) -> Result<Value, PresentationExchangeError> {
+    serde_json::to_value(self).map_err(|err| PresentationExchangeError::CredentialDecoding(err.into()))
+  }
+}
+
+/// A credential issued as a [`Jwt`] is evaluated against its JWT claims set, without verifying its signature.
+impl PresentationCandidate for Jwt {
+  fn claim_format(&self) -> Option<ClaimFormat> {
+    Some(ClaimFormat::JwtVc)
+  }
+
+  fn claims(&self) -> Result<Value, PresentationExchangeError> {
+    decode_jwt_claims(self.as_str())
+  }
+}
+
+/// A credential issued as an [`SdJwt`] is evaluated against its JWT claims set with all of its disclosures
+/// applied, without verifying its signature.
+#[cfg(feature = "sd-jwt")]
+impl PresentationCandidate for SdJwt {
+  fn claim_format(&self) -> Option<ClaimFormat> {
+    Some(ClaimFormat::SdJwtVc)
+  }
+
+  fn claims(&self) -> Result<Value, PresentationExchangeError> {
+    decode_sd_jwt_claims(self)
+  }
+}
+
+impl<C: PresentationCandidate + ?Sized> PresentationCandidate for &C {
+  fn claim_format(&self) -> Option<ClaimFormat> {
+    (**self).claim_format()
+  }
+
+  fn claims(&self) -> Result<Value, PresentationExchangeError> {
+    (**self).claims()
+  }
+}
+
+/// The candidates satisfying an input descriptor of a [`PresentationDefinition`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct InputDescriptorMatch {
+  /// The id of the input descriptor.
+  pub descriptor_id: String,
+  /// The indices of the candidates satisfying the input descriptor.
+  pub candidates: Vec<usize>,
+}
+
+impl PresentationDefinition {
+  /// Matches the holder's `candidates` against the input descriptors of the presentation definition.
+  ///
+  /// Returns one [`InputDescriptorMatch`] per input descriptor, in the order of
+  /// [`input_descriptors`](PresentationDefinition::input_descriptors), listing the candidates with an accepted claim
+  /// format that satisfy the descriptor's constraints. Which of the matching candidates to present is up to the
+  /// holder, [`PresentationDefinition::is_fulfilled_by`] can be used to check whether a selection fulfills the
+  /// definition.
+  ///
+  /// # Errors
+  /// Fails if a candidate cannot be decoded, if the definition uses an unsupported constraint or if it contains an
+  /// invalid JSONPath expression or `filter`.
+  pub fn match_candidates<C>(&self, candidates: &[C]) -> Result<Vec<InputDescriptorMatch>, PresentationExchangeError>
+  where
+    C: PresentationCandidate,
+  {
+    let candidates: Vec<(Option<ClaimFormat>, Value)> = candidates
+      .iter()
+      .map(|candidate| Ok((candidate.claim_format(), candidate.claims()?)))
+      .collect::<Result<_, PresentationExchangeError>>()?;
+
+    self
+      .input_descriptors
+      .iter()
+      .map(|descriptor| {
+        descriptor.check_constraints_supported()?;
+        let mut matching: Vec<usize> = Vec::new();
+        for (index, (format, claims)) in candidates.iter().enumerate() {
+          let format_accepted: bool = match format {
+            Some(format) => self.accepts_format(descriptor, format),
+            None => descriptor.format.is_none() && self.format.is_none(),
+          };
+          if format_accepted && descriptor.evaluate(claims)? {
+            matching.push(index);
+          }
+        }
+
+        Ok(InputDescriptorMatch {
+          descriptor_id: descriptor.id.clone(),
+          candidates: matching,
+        })
+      })
+      .collect()
+  }
+}
+
+/// Decodes the claims set of a JWT without verifying its signature.
+pub(crate) fn decode_jwt_claims(jwt: &str) -> Result<Value, PresentationExchangeError> {
+  let validation_item: JwsValidationItem<'_> = Decoder::new()
+    .decode_compact_serialization(jwt.as_bytes(), None)
+    .map_err(|err| PresentationExchangeError::CredentialDecoding(err.into()))?;
+
+  serde_json::from_slice(validation_item.claims())
+    .map_err(|err| PresentationExchangeError::CredentialDecoding(err.into()))
+}
+
+/// Decodes the claims set of an SD-JWT with all of its disclosures applied, without verifying its signature.
+#[cfg(feature = "sd-jwt")]
+pub(crate) fn decode_sd_jwt_claims(sd_jwt: &SdJwt) -> Result<Value, PresentationExchangeError> {
+  let claims: Value = decode_jwt_claims(&sd_jwt.jwt)?;
+  let object = claims
+    .as_object()
+    .ok_or_else(|| PresentationExchangeError::CredentialDecoding("the SD-JWT claims are not a JSON object".into()))?;
+
+  SdObjectDecoder::new_with_sha256()
+    .decode(object, &sd_jwt.disclosures)
+    .map(Value::Object)
+    .map_err(|err| PresentationExchangeError::CredentialDecoding(err.into()))
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_core::common::Object;
+  use identity_core::convert::FromJson;
+  use identity_verification::jwu;
+  use serde_json::json;
+
+  use super::*;
+
+  fn definition() -> PresentationDefinition {
+    PresentationDefinition::from_json_value(json!({
+      "id": "definition",
+      "input_descriptors": [
+        {
+          "id": "university_degree",
+          "format": { "ldp_vc": {}, "jwt_vc_json": {}, "vc+sd-jwt": {} },
+          "constraints": {
+            "fields": [
+              {
+                "path": ["$.credentialSubject.degree.type", "$.vc.credentialSubject.degree.type"],
+                "filter": { "type": "string", "pattern": "^Bachelor" }
+              }
+            ]
+          }
+        },
+        {
+          "id": "address",
+          "format": { "vc+sd-jwt": {} },
+          "constraints": {
+            "fields": [{ "path": ["$.vc.credentialSubject.address.country"] }]
+          }
+        }
+      ]
+    }))
+    .unwrap()
+  }
+
+  fn jwt(claims: &Value) -> String {
+    format!(
+      "{}.{}.{}",
+      jwu::encode_b64(r#"{"alg":"EdDSA"}"#),
+      jwu::encode_b64(claims.to_string()),
+      jwu::encode_b64("signature")
+    )
+  }
+
+  fn credential(degree: &str, proof: bool) -> Credential {
+    let mut credential: Value = json!({
+      "@context": "https://www.w3.org/2018/credentials/v1",
+      "type": ["VerifiableCredential", "UniversityDegreeCredential"],
+      "issuer": "did:example:issuer",
+      "issuanceDate": "2010-01-01T19:23:24Z",
+      "credentialSubject": {
+        "id": "did:example:holder",
+        "degree": { "type": degree }
+      }
+    });
+    if proof {
+      credential["proof"] = json!({ "type": "DataIntegrityProof" });
+    }
+    Credential::from_json_value(credential).unwrap()
+  }
+
+  #[test]
+  fn match_credentials() {
+    let candidates: [Credential; 3] = [
+      credential("BachelorDegree", true),
+      credential("MasterDegree", true),
+      // Credentials of unknown format are not accepted where the format is restricted.
+      credential("BachelorOfScience", false),
+    ];
+
+    let matches: Vec<InputDescriptorMatch> = definition().match_candidates(&candidates).unwrap();
+    assert_eq!(matches.len(), 2);
+    assert_eq!(matches[0].descriptor_id, "university_degree");
+    assert_eq!(matches[0].candidates, [0]);
+    assert!(matches[1].candidates.is_empty());
+
+    // The format of the definition applies to input descriptors without their own.
+    let mut definition: PresentationDefinition = definition();
+    definition.input_descriptors[0].format = None;
+    assert_eq!(definition.match_candidates(&candidates).unwrap()[0].candidates, [0, 2]);
+    definition.format = Some(Object::from_iter([("ldp_vc".to_owned(), json!({}))]));
+    assert_eq!(definition.match_candidates(&candidates).unwrap()[0].candidates, [0]);
+  }
+
+  #[test]
+  fn match_jwt_credentials() {
+    let claims: Value = json!({
+      "iss": "did:example:issuer",
+      "vc": {
+        "@context": "https://www.w3.org/2018/credentials/v1",
+        "type": ["VerifiableCredential"],
+        "credentialSubject": {
+          "degree": { "type": "BachelorDegree" },
+          "address": { "country": "CH" }
+        }
+      }
+    });
+    let jwt: Jwt = Jwt::new(jwt(&claims));
+
+    // The `jwt_vc` format is accepted as `jwt_vc_json`, but a JWT is not accepted as an SD-JWT.
+    let matches: Vec<InputDescriptorMatch> = definition().match_candidates(&[jwt]).unwrap();
+    assert_eq!(matches[0].candidates, [0]);
+    assert!(matches[1].candidates.is_empty());
+
+    let invalid: Jwt = Jwt::new("invalid".to_owned());
+    assert!(matches!(
+      definition().match_candidates(&[invalid]).unwrap_err(),
+      PresentationExchangeError::CredentialDecoding(_)
+    ));
+  }
+
+  #[cfg(feature = "sd-jwt")]
+  #[test]
+  fn match_sd_jwt_credentials() {
+    use sd_jwt_payload::SdObjectEncoder;
+
+    let claims: Value = json!({
+      "iss": "did:example:issuer",
+      "vc": {
+        "@context": "https://www.w3.org/2018/credentials/v1",
+        "type": ["VerifiableCredential"],
+        "credentialSubject": {
+          "degree": { "type": "BachelorDegree" },
+          "address": { "country": "CH" }
+        }
+      }
+    });
+    let mut encoder: SdObjectEncoder = SdObjectEncoder::new(&claims.to_string()).unwrap();
+    let disclosures: Vec<String> = ["/vc/credentialSubject/degree", "/vc/credentialSubject/address/country"]
+      .iter()
+      .map(|path| encoder.conceal(path, None).unwrap().into_string())
+      .collect();
+    encoder.add_sd_alg_property();
+    let claims: Value = Value::Object(encoder.object().unwrap().clone());
+
+    // Only disclosed claims can be matched.
+    let sd_jwt: SdJwt = SdJwt::new(jwt(&claims), disclosures.clone(), None);
+    let undisclosed: SdJwt = SdJwt::new(jwt(&claims), disclosures[..1].to_vec(), None);
+    let matches: Vec<InputDescriptorMatch> = definition()
+      .match_candidates(&[&sd_jwt as &dyn PresentationCandidate, &undisclosed])
+      .unwrap();
+    assert_eq!(matches[0].candidates, [0, 1]);
+    assert_eq!(matches[1].candidates, [0]);
+  }
+}
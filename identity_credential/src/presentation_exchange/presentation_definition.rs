@@ -0,0 +1,689 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use identity_core::common::Object;
+use identity_core::common::Value;
+use jsonschema::Draft;
+use jsonschema::JSONSchema;
+use once_cell::sync::OnceCell;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json_path::JsonPath;
+
+use super::ClaimFormat;
+use super::PresentationExchangeError;
+
+/// Describes the proofs a verifier requires, as defined in
+/// [Presentation Exchange v2](https://identity.foundation/presentation-exchange/spec/v2.0.0/#presentation-definition).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PresentationDefinition {
+  /// The unique identifier of the presentation definition.
+  pub id: String,
+  /// A human-friendly name of the presentation definition.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub name: Option<String>,
+  /// The purpose for which the presentation definition's inputs are used.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub purpose: Option<String>,
+  /// The claim formats accepted by the verifier, keyed by their designation.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub format: Option<Object>,
+  /// Which combinations of input descriptors must be satisfied. If empty, all input descriptors must be satisfied.
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub submission_requirements: Vec<SubmissionRequirement>,
+  /// The inputs required by the verifier.
+  pub input_descriptors: Vec<InputDescriptor>,
+  /// Miscellaneous properties.
+  #[serde(flatten)]
+  pub properties: Object,
+}
+
+impl PresentationDefinition {
+  /// Creates a new [`PresentationDefinition`] requiring all of the given `input_descriptors`.
+  pub fn new(id: impl Into<String>, input_descriptors: Vec<InputDescriptor>) -> Self {
+    Self {
+      id: id.into(),
+      name: None,
+      purpose: None,
+      format: None,
+      submission_requirements: Vec::new(),
+      input_descriptors,
+      properties: Object::new(),
+    }
+  }
+
+  /// Returns the [`InputDescriptor`] with the given `id`, if any.
+  pub fn input_descriptor(&self, id: &str) -> Option<&InputDescriptor> {
+    self.input_descriptors.iter().find(|descriptor| descriptor.id == id)
+  }
+
+  /// Validates the semantic structure of the presentation definition.
+  ///
+  /// Checks that input descriptor ids are unique, that their constraints only use supported features, that each
+  /// field has at least one valid JSONPath expression and a valid JSON Schema `filter`, and that each submission
+  /// requirement refers to either a group of input descriptors or to nested requirements.
+  pub fn check_structure(&self) -> Result<(), PresentationExchangeError> {
+    let mut ids: BTreeSet<&str> = BTreeSet::new();
+    for descriptor in self.input_descriptors.iter() {
+      if !ids.insert(descriptor.id.as_str()) {
+        return Err(PresentationExchangeError::InvalidDefinition(format!(
+          "duplicate input descriptor `{}`",
+          descriptor.id
+        )));
+      }
+      descriptor.check_constraints_supported()?;
+
+      for field in descriptor.constraints.fields.iter() {
+        if field.path.is_empty() {
+          return Err(PresentationExchangeError::InvalidDefinition(format!(
+            "field without path in input descriptor `{}`",
+            descriptor.id
+          )));
+        }
+        field.compiled()?;
+      }
+    }
+
+    fn check_requirement(
+      definition: &PresentationDefinition,
+      requirement: &SubmissionRequirement,
+    ) -> Result<(), PresentationExchangeError> {
+      match (&requirement.from, requirement.from_nested.is_empty()) {
+        (Some(group), true) => {
+          if definition
+            .input_descriptors
+            .iter()
+            .any(|descriptor| descriptor.group.contains(group))
+          {
+            Ok(())
+          } else {
+            Err(PresentationExchangeError::InvalidDefinition(format!(
+              "no input descriptor belongs to group `{group}`"
+            )))
+          }
+        }
+        (None, false) => requirement
+          .from_nested
+          .iter()
+          .try_for_each(|nested| check_requirement(definition, nested)),
+        _ => Err(PresentationExchangeError::InvalidDefinition(
+          "a submission requirement must contain either `from` or `from_nested`".to_owned(),
+        )),
+      }
+    }
+
+    self
+      .submission_requirements
+      .iter()
+      .try_for_each(|requirement| check_requirement(self, requirement))
+  }
+
+  /// Returns whether submitting credentials satisfying the input descriptors with the given ids fulfills the
+  /// presentation definition.
+  ///
+  /// If the definition has no submission requirements, all of its input descriptors must be satisfied.
+  pub fn is_fulfilled_by<'a, I>(&self, descriptor_ids: I) -> bool
+  where
+    I: IntoIterator<Item = &'a str>,
+  {
+    let fulfilled: BTreeSet<&str> = descriptor_ids.into_iter().collect();
+    if self.submission_requirements.is_empty() {
+      self
+        .input_descriptors
+        .iter()
+        .all(|descriptor| fulfilled.contains(descriptor.id.as_str()))
+    } else {
+      self
+        .submission_requirements
+        .iter()
+        .all(|requirement| requirement.is_satisfied(self, &fulfilled))
+    }
+  }
+
+  /// Returns whether a credential with the given claim format is accepted by `descriptor`.
+  ///
+  /// The `format` of the input descriptor takes precedence over the one of the definition. If neither is set, any
+  /// format is accepted.
+  pub fn accepts_format(&self, descriptor: &InputDescriptor, format: &ClaimFormat) -> bool {
+    descriptor
+      .format
+      .as_ref()
+      .or(self.format.as_ref())
+      .map(|formats| format.is_accepted_by(formats))
+      .unwrap_or(true)
+  }
+}
+
+/// Describes a single input required by a [`PresentationDefinition`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InputDescriptor {
+  /// The identifier of the input descriptor, unique within its presentation definition.
+  pub id: String,
+  /// A human-friendly name of the input descriptor.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub name: Option<String>,
+  /// The purpose for which the input is requested.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub purpose: Option<String>,
+  /// The groups the input descriptor belongs to, as referenced by [`SubmissionRequirement::from`].
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub group: Vec<String>,
+  /// The claim formats accepted for this input, overriding [`PresentationDefinition::format`].
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub format: Option<Object>,
+  /// The constraints a credential must satisfy.
+  pub constraints: Constraints,
+  /// Miscellaneous properties.
+  #[serde(flatten)]
+  pub properties: Object,
+}
+
+impl InputDescriptor {
+  /// Creates a new [`InputDescriptor`] with the given `constraints`.
+  pub fn new(id: impl Into<String>, constraints: Constraints) -> Self {
+    Self {
+      id: id.into(),
+      name: None,
+      purpose: None,
+      group: Vec::new(),
+      format: None,
+      constraints,
+      properties: Object::new(),
+    }
+  }
+
+  /// Evaluates the constraints of the input descriptor against the JSON representation of a credential.
+  ///
+  /// Returns `true` if every field that is not optional is satisfied.
+  ///
+  /// # Errors
+  /// Fails if the constraints use a feature that is not supported, such as `subject_is_issuer` or `statuses`, or if
+  /// a field contains an invalid JSONPath expression or `filter`.
+  pub fn evaluate(&self, credential: &Value) -> Result<bool, PresentationExchangeError> {
+    self.check_constraints_supported()?;
+    for field in self.constraints.fields.iter().filter(|field| !field.optional) {
+      if field.evaluate(credential)?.is_none() {
+        return Ok(false);
+      }
+    }
+    Ok(true)
+  }
+
+  /// Checks that the constraints only use features that can be evaluated.
+  ///
+  /// Constraint features that are not modelled, e.g. `subject_is_issuer`, `is_holder`, `same_subject` or
+  /// `statuses`, are kept in [`Constraints::properties`] and [`Field::properties`]. Ignoring them would accept
+  /// credentials the verifier did not allow.
+  pub(crate) fn check_constraints_supported(&self) -> Result<(), PresentationExchangeError> {
+    let unsupported: Option<&String> = self.constraints.properties.keys().next().or_else(|| {
+      self
+        .constraints
+        .fields
+        .iter()
+        .find_map(|field| field.properties.keys().next())
+    });
+
+    match unsupported {
+      Some(constraint) => Err(PresentationExchangeError::UnsupportedConstraint {
+        id: self.id.clone(),
+        constraint: constraint.clone(),
+      }),
+      None => Ok(()),
+    }
+  }
+}
+
+/// The constraints of an [`InputDescriptor`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Constraints {
+  /// The claims a credential must contain.
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub fields: Vec<Field>,
+  /// Whether the holder must only disclose the claims requested in `fields`.
+  ///
+  /// If required, [`PresentationDefinition::evaluate_submission`] only accepts credentials issued as SD-JWTs
+  /// whose disclosures are all needed to select the requested claims.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub limit_disclosure: Option<Optionality>,
+  /// Miscellaneous properties, including constraint features that are not supported.
+  #[serde(flatten)]
+  pub properties: Object,
+}
+
+impl Constraints {
+  /// Creates new [`Constraints`] with the given `fields`.
+  pub fn new(fields: Vec<Field>) -> Self {
+    Self {
+      fields,
+      limit_disclosure: None,
+      properties: Object::new(),
+    }
+  }
+}
+
+/// Whether a feature is required or only preferred by the verifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Optionality {
+  /// The feature is required.
+  Required,
+  /// The feature is preferred.
+  Preferred,
+}
+
+/// A claim constraint within [`Constraints`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Field {
+  /// The identifier of the field.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub id: Option<String>,
+  /// JSONPath expressions selecting the claim, evaluated in order until one selects a value satisfying `filter`.
+  pub path: Vec<String>,
+  /// A human-friendly name of the field.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub name: Option<String>,
+  /// The purpose for which the claim is requested.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub purpose: Option<String>,
+  /// A JSON Schema the selected claim must be valid against.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub filter: Option<Value>,
+  /// Whether the field may be omitted.
+  #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+  pub optional: bool,
+  /// Whether the verifier intends to retain the claim.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub intent_to_retain: Option<bool>,
+  /// Miscellaneous properties, including field features that are not supported, such as `predicate`.
+  #[serde(flatten)]
+  pub properties: Object,
+  #[serde(skip)]
+  compiled: CompiledFieldCell,
+}
+
+impl Field {
+  /// Creates a new [`Field`] selecting the claim at one of the given JSONPath expressions.
+  pub fn new(path: Vec<String>) -> Self {
+    Self {
+      id: None,
+      path,
+      name: None,
+      purpose: None,
+      filter: None,
+      optional: false,
+      intent_to_retain: None,
+      properties: Object::new(),
+      compiled: CompiledFieldCell::default(),
+    }
+  }
+
+  /// Sets the JSON Schema the selected claim must be valid against.
+  #[must_use]
+  pub fn filter(mut self, filter: Value) -> Self {
+    self.filter = Some(filter);
+    self
+  }
+
+  /// Sets whether the field may be omitted.
+  #[must_use]
+  pub fn optional(mut self, optional: bool) -> Self {
+    self.optional = optional;
+    self
+  }
+
+  /// Evaluates the field against the JSON representation of a credential.
+  ///
+  /// Returns the first value selected by one of the paths that is valid against the `filter`, if any.
+  ///
+  /// # Errors
+  /// Fails if a path is not a valid JSONPath expression or if the filter is not a valid JSON Schema.
+  pub fn evaluate<'v>(&self, credential: &'v Value) -> Result<Option<&'v Value>, PresentationExchangeError> {
+    let compiled: Arc<CompiledField> = self.compiled()?;
+    for path in compiled.paths.iter() {
+      let selected: Option<&Value> = path.query(credential).all().into_iter().find(|value| {
+        compiled
+          .schema
+          .as_ref()
+          .map(|schema| schema.is_valid(value))
+          .unwrap_or(true)
+      });
+      if selected.is_some() {
+        return Ok(selected);
+      }
+    }
+    Ok(None)
+  }
+
+  /// Returns the compiled paths and filter of the field, compiling them on first use or after they changed.
+  fn compiled(&self) -> Result<Arc<CompiledField>, PresentationExchangeError> {
+    if let Some(compiled) = self
+      .compiled
+      .0
+      .get()
+      .filter(|compiled| compiled.path == self.path && compiled.filter == self.filter)
+    {
+      return Ok(Arc::clone(compiled));
+    }
+
+    let compiled: Arc<CompiledField> = Arc::new(CompiledField {
+      paths: self
+        .path
+        .iter()
+        .map(|path| parse_path(path))
+        .collect::<Result<_, _>>()?,
+      schema: self.filter.as_ref().map(compile_filter).transpose()?,
+      path: self.path.clone(),
+      filter: self.filter.clone(),
+    });
+    // Fields are usually not modified once evaluated, so only the first compilation is cached.
+    let _ = self.compiled.0.set(Arc::clone(&compiled));
+    Ok(compiled)
+  }
+}
+
+/// The JSONPath expressions and `filter` of a [`Field`] together with the sources they were compiled from.
+#[derive(Debug)]
+struct CompiledField {
+  path: Vec<String>,
+  filter: Option<Value>,
+  paths: Vec<JsonPath>,
+  schema: Option<JSONSchema>,
+}
+
+/// Caches the [`CompiledField`] of a [`Field`], without taking part in its comparison.
+#[derive(Debug, Clone, Default)]
+struct CompiledFieldCell(OnceCell<Arc<CompiledField>>);
+
+impl PartialEq for CompiledFieldCell {
+  fn eq(&self, _other: &Self) -> bool {
+    true
+  }
+}
+
+impl Eq for CompiledFieldCell {}
+
+/// Describes which combinations of input descriptors satisfy a [`PresentationDefinition`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubmissionRequirement {
+  /// A human-friendly name of the requirement.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub name: Option<String>,
+  /// The purpose of the requirement.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub purpose: Option<String>,
+  /// How many of the inputs must be satisfied.
+  pub rule: SubmissionRequirementRule,
+  /// The exact number of inputs to satisfy when `rule` is [`SubmissionRequirementRule::Pick`].
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub count: Option<usize>,
+  /// The minimum number of inputs to satisfy when `rule` is [`SubmissionRequirementRule::Pick`].
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub min: Option<usize>,
+  /// The maximum number of inputs to satisfy when `rule` is [`SubmissionRequirementRule::Pick`].
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub max: Option<usize>,
+  /// The group of input descriptors the requirement applies to.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub from: Option<String>,
+  /// The nested requirements the requirement applies to, if `from` is not set.
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub from_nested: Vec<SubmissionRequirement>,
+}
+
+impl SubmissionRequirement {
+  fn is_satisfied(&self, definition: &PresentationDefinition, fulfilled: &BTreeSet<&str>) -> bool {
+    let (satisfied, total): (usize, usize) = match &self.from {
+      Some(group) => definition
+        .input_descriptors
+        .iter()
+        .filter(|descriptor| descriptor.group.contains(group))
+        .fold((0, 0), |(satisfied, total), descriptor| {
+          (
+            satisfied + usize::from(fulfilled.contains(descriptor.id.as_str())),
+            total + 1,
+          )
+        }),
+      None => (
+        self
+          .from_nested
+          .iter()
+          .filter(|nested| nested.is_satisfied(definition, fulfilled))
+          .count(),
+        self.from_nested.len(),
+      ),
+    };
+
+    match self.rule {
+      SubmissionRequirementRule::All => satisfied == total,
+      SubmissionRequirementRule::Pick => {
+        self.count.map(|count| satisfied == count).unwrap_or(true)
+          && self.min.map(|min| satisfied >= min).unwrap_or(true)
+          && self.max.map(|max| satisfied <= max).unwrap_or(true)
+      }
+    }
+  }
+}
+
+/// The rule of a [`SubmissionRequirement`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SubmissionRequirementRule {
+  /// All inputs must be satisfied.
+  All,
+  /// A number of inputs bounded by `count`, `min` and `max` must be satisfied.
+  Pick,
+}
+
+pub(crate) fn parse_path(path: &str) -> Result<JsonPath, PresentationExchangeError> {
+  JsonPath::parse(path).map_err(|source| PresentationExchangeError::InvalidJsonPath {
+    path: path.to_owned(),
+    source,
+  })
+}
+
+/// Compiles a `filter` as a JSON Schema Draft 2020-12, as required by Presentation Exchange v2.
+fn compile_filter(filter: &Value) -> Result<JSONSchema, PresentationExchangeError> {
+  JSONSchema::options()
+    .with_draft(Draft::Draft202012)
+    .compile(filter)
+    .map_err(|err| PresentationExchangeError::InvalidFilter(err.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_core::convert::FromJson;
+  use serde_json::json;
+
+  use super::*;
+
+  fn definition() -> PresentationDefinition {
+    PresentationDefinition::from_json_value(json!({
+      "id": "32f54163-7166-48f1-93d8-ff217bdb0653",
+      "submission_requirements": [
+        {
+          "name": "Citizenship",
+          "rule": "all",
+          "from": "A"
+        },
+        {
+          "name": "Proof of address",
+          "rule": "pick",
+          "count": 1,
+          "from": "B"
+        }
+      ],
+      "input_descriptors": [
+        {
+          "id": "citizenship_input",
+          "group": ["A"],
+          "constraints": {
+            "fields": [
+              {
+                "path": ["$.credentialSubject.nationality", "$.vc.credentialSubject.nationality"],
+                "filter": { "type": "string", "const": "CH" }
+              },
+              {
+                "path": ["$.credentialSubject.name"],
+                "optional": true
+              }
+            ]
+          }
+        },
+        {
+          "id": "utility_bill_input",
+          "group": ["B"],
+          "constraints": { "fields": [{ "path": ["$.credentialSubject.address"] }] }
+        },
+        {
+          "id": "bank_statement_input",
+          "group": ["B"],
+          "constraints": { "fields": [{ "path": ["$.credentialSubject.address"] }] }
+        }
+      ]
+    }))
+    .unwrap()
+  }
+
+  #[test]
+  fn serde_roundtrip() {
+    let definition: PresentationDefinition = definition();
+    assert!(definition.check_structure().is_ok());
+    assert_eq!(definition.submission_requirements[1].count, Some(1));
+
+    let json: Value = serde_json::to_value(&definition).unwrap();
+    assert_eq!(PresentationDefinition::from_json_value(json).unwrap(), definition);
+  }
+
+  #[test]
+  fn evaluate_field() {
+    let field: &Field = &definition().input_descriptors[0].constraints.fields[0];
+    let credential: Value = json!({ "credentialSubject": { "nationality": "CH" } });
+    assert_eq!(field.evaluate(&credential).unwrap(), Some(&json!("CH")));
+
+    // Later paths are evaluated if earlier ones select nothing.
+    let credential: Value = json!({ "vc": { "credentialSubject": { "nationality": "CH" } } });
+    assert_eq!(field.evaluate(&credential).unwrap(), Some(&json!("CH")));
+
+    // The selected value must satisfy the filter.
+    let credential: Value = json!({ "credentialSubject": { "nationality": "DE" } });
+    assert_eq!(field.evaluate(&credential).unwrap(), None);
+
+    let field: Field = Field::new(vec!["$.credentialSubject[".to_owned()]);
+    assert!(matches!(
+      field.evaluate(&credential).unwrap_err(),
+      PresentationExchangeError::InvalidJsonPath { .. }
+    ));
+  }
+
+  #[test]
+  fn evaluate_field_filter_draft_2020_12() {
+    // `prefixItems` only constrains arrays as of Draft 2020-12.
+    let mut field: Field = Field::new(vec!["$.type".to_owned()]).filter(json!({
+      "type": "array",
+      "prefixItems": [{ "const": "VerifiableCredential" }]
+    }));
+    let credential: Value = json!({ "type": ["UniversityDegreeCredential"] });
+    assert_eq!(field.evaluate(&credential).unwrap(), None);
+
+    // Changing the filter of an evaluated field takes effect.
+    field.filter = Some(json!({ "type": "array" }));
+    assert_eq!(field.evaluate(&credential).unwrap(), Some(&credential["type"]));
+  }
+
+  #[test]
+  fn evaluate_input_descriptor() {
+    let descriptor: &InputDescriptor = &definition().input_descriptors[0];
+    // Optional fields need not be present.
+    assert!(descriptor
+      .evaluate(&json!({ "credentialSubject": { "nationality": "CH" } }))
+      .unwrap());
+    assert!(!descriptor
+      .evaluate(&json!({ "credentialSubject": { "name": "Alice" } }))
+      .unwrap());
+  }
+
+  #[test]
+  fn submission_requirements() {
+    let definition: PresentationDefinition = definition();
+    assert!(definition.is_fulfilled_by(["citizenship_input", "utility_bill_input"]));
+    assert!(definition.is_fulfilled_by(["citizenship_input", "bank_statement_input"]));
+    assert!(!definition.is_fulfilled_by(["citizenship_input"]));
+    assert!(!definition.is_fulfilled_by(["utility_bill_input"]));
+    assert!(!definition.is_fulfilled_by(["citizenship_input", "utility_bill_input", "bank_statement_input"]));
+
+    // Without submission requirements all input descriptors must be satisfied.
+    let definition: PresentationDefinition =
+      PresentationDefinition::new("definition", definition.input_descriptors.clone());
+    assert!(!definition.is_fulfilled_by(["citizenship_input", "utility_bill_input"]));
+    assert!(definition.is_fulfilled_by(["citizenship_input", "utility_bill_input", "bank_statement_input"]));
+  }
+
+  #[test]
+  fn check_structure() {
+    let mut invalid: PresentationDefinition = definition();
+    invalid.submission_requirements[0].from = Some("C".to_owned());
+    assert!(matches!(
+      invalid.check_structure().unwrap_err(),
+      PresentationExchangeError::InvalidDefinition(_)
+    ));
+
+    let mut invalid: PresentationDefinition = definition();
+    invalid.input_descriptors[2].id = "utility_bill_input".to_owned();
+    assert!(matches!(
+      invalid.check_structure().unwrap_err(),
+      PresentationExchangeError::InvalidDefinition(_)
+    ));
+
+    let mut invalid: PresentationDefinition = definition();
+    invalid.input_descriptors[0].constraints.fields[0].filter = Some(json!({ "type": 1 }));
+    assert!(matches!(
+      invalid.check_structure().unwrap_err(),
+      PresentationExchangeError::InvalidFilter(_)
+    ));
+  }
+
+  #[test]
+  fn unsupported_constraints() {
+    for (constraints, constraint) in [
+      (json!({ "subject_is_issuer": "required" }), "subject_is_issuer"),
+      (
+        json!({ "is_holder": [{ "field_id": ["a"], "directive": "required" }] }),
+        "is_holder",
+      ),
+      (
+        json!({ "same_subject": [{ "field_id": ["a", "b"], "directive": "required" }] }),
+        "same_subject",
+      ),
+      (
+        json!({ "statuses": { "active": { "directive": "required" } } }),
+        "statuses",
+      ),
+      (
+        json!({ "fields": [{ "path": ["$.credentialSubject.age"], "predicate": "required" }] }),
+        "predicate",
+      ),
+    ] {
+      let descriptor: InputDescriptor =
+        InputDescriptor::from_json_value(json!({ "id": "input", "constraints": constraints })).unwrap();
+
+      // Unsupported features are kept.
+      let json: Value = serde_json::to_value(&descriptor).unwrap();
+      assert_eq!(json["constraints"], constraints);
+
+      let definition: PresentationDefinition = PresentationDefinition::new("definition", vec![descriptor.clone()]);
+      for err in [
+        definition.check_structure().unwrap_err(),
+        descriptor.evaluate(&json!({})).unwrap_err(),
+      ] {
+        assert!(matches!(
+          err,
+          PresentationExchangeError::UnsupportedConstraint { ref id, constraint: ref unsupported }
+            if id == "input" && unsupported == constraint
+        ));
+      }
+    }
+  }
+}
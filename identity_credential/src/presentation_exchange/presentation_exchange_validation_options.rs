@@ -0,0 +1,42 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::validator::JwtCredentialValidationOptions;
+use crate::validator::JwtPresentationValidationOptions;
+
+/// Criteria for validating a presentation against a [`PresentationDefinition`](super::PresentationDefinition) with
+/// the [`PresentationExchangeValidator`](super::PresentationExchangeValidator).
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
+#[serde(rename_all = "camelCase")]
+pub struct PresentationExchangeValidationOptions {
+  /// Options for validating the presentation.
+  #[serde(default)]
+  pub presentation_options: JwtPresentationValidationOptions,
+
+  /// Options for validating the credentials submitted in the presentation.
+  #[serde(default)]
+  pub credential_options: JwtCredentialValidationOptions,
+}
+
+impl PresentationExchangeValidationOptions {
+  /// Constructor that sets all options to their defaults.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Set options for validating the presentation.
+  pub fn presentation_options(mut self, options: JwtPresentationValidationOptions) -> Self {
+    self.presentation_options = options;
+    self
+  }
+
+  /// Set options for validating the credentials submitted in the presentation.
+  pub fn credential_options(mut self, options: JwtCredentialValidationOptions) -> Self {
+    self.credential_options = options;
+    self
+  }
+}
@@ -0,0 +1,567 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeSet;
+
+use identity_core::common::Object;
+use identity_core::common::Value;
+use identity_document::document::CoreDocument;
+use identity_verification::jwk::Jwk;
+use identity_verification::jws::JwsVerifier;
+use identity_verification::jws::SignatureVerificationError;
+use identity_verification::jws::VerificationInput;
+#[cfg(feature = "sd-jwt")]
+use sd_jwt_payload::SdJwt;
+#[cfg(feature = "sd-jwt")]
+use sd_jwt_payload::SdObjectDecoder;
+
+use crate::credential::Jwt;
+use crate::validator::CompoundCredentialValidationError;
+use crate::validator::DecodedJwtCredential;
+use crate::validator::DecodedJwtPresentation;
+use crate::validator::FailFast;
+use crate::validator::JwtCredentialValidationOptions;
+use crate::validator::JwtCredentialValidator;
+use crate::validator::JwtPresentationValidator;
+#[cfg(feature = "sd-jwt")]
+use crate::validator::SdJwtCredentialValidator;
+
+use super::presentation_candidate::decode_jwt_claims;
+#[cfg(feature = "sd-jwt")]
+use super::presentation_candidate::decode_sd_jwt_claims;
+use super::presentation_definition::parse_path;
+use super::ClaimFormat;
+use super::InputDescriptor;
+use super::InputDescriptorMapping;
+use super::Optionality;
+use super::PresentationDefinition;
+use super::PresentationExchangeError;
+use super::PresentationExchangeValidationOptions;
+use super::PresentationSubmission;
+
+/// A type for validating that a [`Presentation`](crate::presentation::Presentation) issued as a JWT satisfies a
+/// [`PresentationDefinition`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct PresentationExchangeValidator<V: JwsVerifier>(V);
+
+impl<V> PresentationExchangeValidator<V>
+where
+  V: JwsVerifier,
+{
+  /// Creates a new [`PresentationExchangeValidator`] using a specific [`JwsVerifier`] for both the presentation and
+  /// the submitted credentials.
+  pub fn with_signature_verifier(signature_verifier: V) -> Self {
+    Self(signature_verifier)
+  }
+
+  /// Validates a presentation JWT and checks that the credentials located by `submission` satisfy `definition`.
+  ///
+  /// The presentation is validated using the [`JwtPresentationValidator`] according to
+  /// [`presentation_options`](PresentationExchangeValidationOptions::presentation_options), after which
+  /// [`PresentationDefinition::evaluate_submission`] is evaluated against the presentation's JWT claims set. Each
+  /// located credential must additionally be issued by one of the trusted `issuers` and is validated according to
+  /// [`credential_options`](PresentationExchangeValidationOptions::credential_options) before its input descriptor
+  /// is considered satisfied. Only credentials issued as JWTs or SD-JWTs can be validated.
+  ///
+  /// # Warning
+  /// The caller must ensure that the DID Documents in `holder` and `issuers` are up-to-date. The key binding JWT of
+  /// SD-JWT credentials is not validated.
+  ///
+  /// # Errors
+  /// An error is returned if the presentation or one of the submitted credentials cannot be validated, or if the
+  /// submission does not satisfy the definition.
+  pub fn validate<HDOC, IDOC, CRED, T>(
+    &self,
+    presentation: &Jwt,
+    holder: &HDOC,
+    issuers: &[IDOC],
+    definition: &PresentationDefinition,
+    submission: &PresentationSubmission,
+    options: &PresentationExchangeValidationOptions,
+  ) -> Result<DecodedJwtPresentation<CRED, T>, PresentationExchangeError>
+  where
+    HDOC: AsRef<CoreDocument> + ?Sized,
+    IDOC: AsRef<CoreDocument>,
+    T: ToOwned<Owned = T> + serde::Serialize + serde::de::DeserializeOwned,
+    CRED: ToOwned<Owned = CRED> + serde::Serialize + serde::de::DeserializeOwned + Clone,
+  {
+    let decoded_presentation: DecodedJwtPresentation<CRED, T> =
+      JwtPresentationValidator::with_signature_verifier(BorrowedVerifier(&self.0))
+        .validate(presentation, holder, &options.presentation_options)
+        .map_err(PresentationExchangeError::PresentationValidation)?;
+
+    let claims: Value = decode_jwt_claims(presentation.as_str())?;
+    definition.evaluate_submission_with(submission, &claims, |descriptor, credential| {
+      self.validate_credential(descriptor, credential, issuers, &options.credential_options)
+    })?;
+
+    Ok(decoded_presentation)
+  }
+
+  /// Validates a credential located for `descriptor`, which must be issued as a JWT or SD-JWT.
+  fn validate_credential<IDOC>(
+    &self,
+    descriptor: &InputDescriptor,
+    credential: &LocatedCredential<'_>,
+    issuers: &[IDOC],
+    options: &JwtCredentialValidationOptions,
+  ) -> Result<(), PresentationExchangeError>
+  where
+    IDOC: AsRef<CoreDocument>,
+  {
+    let verified: Result<DecodedJwtCredential<Object>, CompoundCredentialValidationError> =
+      match (credential.format, &credential.encoded) {
+        #[cfg(feature = "sd-jwt")]
+        (ClaimFormat::SdJwtVc, Value::String(encoded)) => {
+          let sd_jwt: SdJwt =
+            SdJwt::parse(encoded).map_err(|err| PresentationExchangeError::CredentialDecoding(err.into()))?;
+          SdJwtCredentialValidator::with_signature_verifier(
+            BorrowedVerifier(&self.0),
+            SdObjectDecoder::new_with_sha256(),
+          )
+          .verify_signature(&sd_jwt, issuers, &options.verification_options)
+          .map_err(|err| CompoundCredentialValidationError {
+            validation_errors: [err].into(),
+          })
+        }
+        (format, Value::String(encoded)) if format.is_jwt() => {
+          JwtCredentialValidator::with_signature_verifier(BorrowedVerifier(&self.0))
+            .verify_signature(&Jwt::new(encoded.clone()), issuers, &options.verification_options)
+            .map_err(|err| CompoundCredentialValidationError {
+              validation_errors: [err].into(),
+            })
+        }
+        (format, _) => {
+          return Err(PresentationExchangeError::UnsupportedCredentialValidation {
+            id: descriptor.id.clone(),
+            format: format.to_string(),
+          })
+        }
+      };
+
+    verified
+      .and_then(|credential| {
+        JwtCredentialValidator::<V>::validate_decoded_credential(credential, issuers, options, FailFast::FirstError)
+      })
+      .map(|_| ())
+      .map_err(|source| PresentationExchangeError::CredentialValidation {
+        id: descriptor.id.clone(),
+        source,
+      })
+  }
+}
+
+/// Lends the [`JwsVerifier`] of a [`PresentationExchangeValidator`] to the presentation and credential validators.
+struct BorrowedVerifier<'v, V>(&'v V);
+
+impl<V: JwsVerifier> JwsVerifier for BorrowedVerifier<'_, V> {
+  fn verify(&self, input: VerificationInput, public_key: &Jwk) -> Result<(), SignatureVerificationError> {
+    self.0.verify(input, public_key)
+  }
+}
+
+impl PresentationDefinition {
+  /// Checks that the credentials located in `presentation` by the descriptor map of `submission` satisfy the
+  /// presentation definition.
+  ///
+  /// `presentation` is the JSON representation the descriptor map paths are evaluated against, i.e. the JWT claims
+  /// set for presentations in the `jwt_vp` format. Values located by a mapping whose format is encoded as a JWT or
+  /// SD-JWT are decoded, without verifying their signatures, before evaluating nested paths or constraints against
+  /// them.
+  ///
+  /// # Errors
+  /// Fails if the submission refers to another definition, if an input descriptor uses an unsupported constraint,
+  /// if a mapping cannot be resolved, if a located credential is not in an accepted format, does not satisfy its
+  /// input descriptor or discloses more than requested, or if the satisfied input descriptors do not fulfill the
+  /// definition.
+  pub fn evaluate_submission(
+    &self,
+    submission: &PresentationSubmission,
+    presentation: &Value,
+  ) -> Result<(), PresentationExchangeError> {
+    self.evaluate_submission_with(submission, presentation, |_, _| Ok(()))
+  }
+
+  /// Evaluates the submission like [`PresentationDefinition::evaluate_submission`], additionally checking each
+  /// located credential with `validate_credential` before considering its input descriptor satisfied.
+  fn evaluate_submission_with<F>(
+    &self,
+    submission: &PresentationSubmission,
+    presentation: &Value,
+    mut validate_credential: F,
+  ) -> Result<(), PresentationExchangeError>
+  where
+    F: FnMut(&InputDescriptor, &LocatedCredential<'_>) -> Result<(), PresentationExchangeError>,
+  {
+    if submission.definition_id != self.id {
+      return Err(PresentationExchangeError::DefinitionMismatch(
+        submission.definition_id.clone(),
+      ));
+    }
+
+    self
+      .input_descriptors
+      .iter()
+      .try_for_each(InputDescriptor::check_constraints_supported)?;
+
+    let mut fulfilled: BTreeSet<&str> = BTreeSet::new();
+    for mapping in submission.descriptor_map.iter() {
+      let descriptor: &InputDescriptor = self
+        .input_descriptor(&mapping.id)
+        .ok_or_else(|| PresentationExchangeError::UnknownInputDescriptor(mapping.id.clone()))?;
+
+      let credential: LocatedCredential<'_> = resolve_mapping(mapping, presentation)?;
+      if !self.accepts_format(descriptor, credential.format) {
+        return Err(PresentationExchangeError::UnsupportedFormat {
+          id: descriptor.id.clone(),
+          format: credential.format.to_string(),
+        });
+      }
+      if !descriptor.evaluate(&credential.claims)? {
+        return Err(PresentationExchangeError::InputDescriptorNotSatisfied(
+          descriptor.id.clone(),
+        ));
+      }
+      if descriptor.constraints.limit_disclosure == Some(Optionality::Required) {
+        check_limited_disclosure(descriptor, &credential)?;
+      }
+      validate_credential(descriptor, &credential)?;
+
+      fulfilled.insert(descriptor.id.as_str());
+    }
+
+    self
+      .is_fulfilled_by(fulfilled)
+      .then_some(())
+      .ok_or(PresentationExchangeError::RequirementsNotSatisfied)
+  }
+}
+
+/// A credential located in a presentation by an [`InputDescriptorMapping`].
+struct LocatedCredential<'m> {
+  /// The claim format of the credential.
+  format: &'m ClaimFormat,
+  /// The credential as it appears in the presentation, e.g. an encoded JWT.
+  encoded: Value,
+  /// The JSON representation of the credential, which the constraints of input descriptors are evaluated against.
+  claims: Value,
+}
+
+/// Returns the credential located by `mapping` in `value`.
+fn resolve_mapping<'m>(
+  mapping: &'m InputDescriptorMapping,
+  value: &Value,
+) -> Result<LocatedCredential<'m>, PresentationExchangeError> {
+  let selected: &Value = parse_path(&mapping.path)?.query(value).exactly_one().map_err(|_| {
+    PresentationExchangeError::InvalidDescriptorPath {
+      id: mapping.id.clone(),
+      path: mapping.path.clone(),
+    }
+  })?;
+
+  let decoded: Value = match selected {
+    #[cfg(feature = "sd-jwt")]
+    Value::String(encoded) if mapping.format == ClaimFormat::SdJwtVc => decode_sd_jwt_claims(
+      &SdJwt::parse(encoded).map_err(|err| PresentationExchangeError::CredentialDecoding(err.into()))?,
+    )?,
+    Value::String(encoded) if mapping.format.is_jwt() => decode_jwt_claims(encoded)?,
+    _ => selected.clone(),
+  };
+
+  match mapping.path_nested.as_deref() {
+    Some(nested) => resolve_mapping(nested, &decoded),
+    None => Ok(LocatedCredential {
+      format: &mapping.format,
+      encoded: selected.clone(),
+      claims: decoded,
+    }),
+  }
+}
+
+/// Checks that `credential` only discloses the claims selected by the fields of `descriptor`.
+///
+/// Only SD-JWTs can limit disclosure. Each of their disclosures must be needed to select the requested claims,
+/// where a disclosure containing nested disclosures is needed as long as the SD-JWT cannot be decoded without it.
+fn check_limited_disclosure(
+  descriptor: &InputDescriptor,
+  credential: &LocatedCredential<'_>,
+) -> Result<(), PresentationExchangeError> {
+  match (credential.format, &credential.encoded) {
+    #[cfg(feature = "sd-jwt")]
+    (ClaimFormat::SdJwtVc, Value::String(encoded)) => {
+      let sd_jwt: SdJwt =
+        SdJwt::parse(encoded).map_err(|err| PresentationExchangeError::CredentialDecoding(err.into()))?;
+      let selected_claims = |disclosures: Vec<String>| -> Result<Vec<Option<Value>>, PresentationExchangeError> {
+        let claims: Value = decode_sd_jwt_claims(&SdJwt::new(sd_jwt.jwt.clone(), disclosures, None))?;
+        descriptor
+          .constraints
+          .fields
+          .iter()
+          .map(|field| field.evaluate(&claims).map(Option::<&Value>::cloned))
+          .collect()
+      };
+
+      let requested: Vec<Option<Value>> = selected_claims(sd_jwt.disclosures.clone())?;
+      for index in 0..sd_jwt.disclosures.len() {
+        let mut disclosures: Vec<String> = sd_jwt.disclosures.clone();
+        disclosures.remove(index);
+        if selected_claims(disclosures).ok().as_ref() == Some(&requested) {
+          return Err(PresentationExchangeError::UnrequestedDisclosure(descriptor.id.clone()));
+        }
+      }
+      Ok(())
+    }
+    (format, _) => Err(PresentationExchangeError::LimitDisclosureUnsupported {
+      id: descriptor.id.clone(),
+      format: format.to_string(),
+    }),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_core::convert::FromJson;
+  use identity_verification::jwu;
+  use serde_json::json;
+
+  use super::*;
+
+  fn definition() -> PresentationDefinition {
+    PresentationDefinition::from_json_value(json!({
+      "id": "definition",
+      "format": { "jwt_vc": {}, "ldp_vc": {} },
+      "input_descriptors": [
+        {
+          "id": "university_degree",
+          "constraints": {
+            "fields": [
+              {
+                "path": ["$.vc.credentialSubject.degree.type", "$.credentialSubject.degree.type"],
+                "filter": { "type": "string", "const": "BachelorDegree" }
+              }
+            ]
+          }
+        }
+      ]
+    }))
+    .unwrap()
+  }
+
+  fn jwt(claims: &Value) -> String {
+    format!(
+      "{}.{}.{}",
+      jwu::encode_b64(r#"{"alg":"EdDSA"}"#),
+      jwu::encode_b64(claims.to_string()),
+      jwu::encode_b64("signature")
+    )
+  }
+
+  fn presentation(degree: &str) -> Value {
+    let credential: Value = json!({
+      "iss": "did:example:issuer",
+      "vc": {
+        "@context": "https://www.w3.org/2018/credentials/v1",
+        "type": ["VerifiableCredential"],
+        "credentialSubject": { "degree": { "type": degree } }
+      }
+    });
+    let jwt: String = jwt(&credential);
+
+    json!({
+      "iss": "did:example:holder",
+      "vp": {
+        "@context": "https://www.w3.org/2018/credentials/v1",
+        "type": "VerifiablePresentation",
+        "verifiableCredential": [jwt, credential["vc"]]
+      }
+    })
+  }
+
+  fn submission(mapping: InputDescriptorMapping) -> PresentationSubmission {
+    PresentationSubmission::new("submission", "definition", vec![mapping])
+  }
+
+  #[test]
+  fn evaluate_submission() {
+    let definition: PresentationDefinition = definition();
+    let jwt_mapping: InputDescriptorMapping =
+      InputDescriptorMapping::new("university_degree", ClaimFormat::JwtVp, "$").path_nested(
+        InputDescriptorMapping::new("university_degree", ClaimFormat::JwtVc, "$.vp.verifiableCredential[0]"),
+      );
+    let ldp_mapping: InputDescriptorMapping =
+      InputDescriptorMapping::new("university_degree", ClaimFormat::LdpVc, "$.vp.verifiableCredential[1]");
+
+    for mapping in [jwt_mapping.clone(), ldp_mapping] {
+      assert!(definition
+        .evaluate_submission(&submission(mapping.clone()), &presentation("BachelorDegree"))
+        .is_ok());
+      assert!(matches!(
+        definition
+          .evaluate_submission(&submission(mapping), &presentation("MasterDegree"))
+          .unwrap_err(),
+        PresentationExchangeError::InputDescriptorNotSatisfied(_)
+      ));
+    }
+
+    // Credentials must be in an accepted format.
+    let mut vp_mapping: InputDescriptorMapping = jwt_mapping.clone();
+    vp_mapping.path_nested.as_mut().unwrap().format = ClaimFormat::JwtVp;
+    assert!(matches!(
+      definition
+        .evaluate_submission(&submission(vp_mapping), &presentation("BachelorDegree"))
+        .unwrap_err(),
+      PresentationExchangeError::UnsupportedFormat { .. }
+    ));
+  }
+
+  #[test]
+  fn evaluate_submission_unsupported_constraint() {
+    let mapping: InputDescriptorMapping =
+      InputDescriptorMapping::new("university_degree", ClaimFormat::LdpVc, "$.vp.verifiableCredential[1]");
+
+    // A credential satisfying the supported constraints is not accepted if others cannot be checked.
+    let mut definition: PresentationDefinition = definition();
+    definition.input_descriptors[0]
+      .constraints
+      .properties
+      .insert("subject_is_issuer".to_owned(), json!("required"));
+    assert!(matches!(
+      definition
+        .evaluate_submission(&submission(mapping), &presentation("BachelorDegree"))
+        .unwrap_err(),
+      PresentationExchangeError::UnsupportedConstraint { .. }
+    ));
+  }
+
+  #[test]
+  fn evaluate_invalid_submission() {
+    let definition: PresentationDefinition = definition();
+    let presentation: Value = presentation("BachelorDegree");
+
+    let mut other_definition: PresentationSubmission = submission(InputDescriptorMapping::new(
+      "university_degree",
+      ClaimFormat::LdpVc,
+      "$.vp.verifiableCredential[1]",
+    ));
+    other_definition.definition_id = "other".to_owned();
+    assert!(matches!(
+      definition
+        .evaluate_submission(&other_definition, &presentation)
+        .unwrap_err(),
+      PresentationExchangeError::DefinitionMismatch(_)
+    ));
+
+    let unknown_descriptor: PresentationSubmission = submission(InputDescriptorMapping::new(
+      "drivers_license",
+      ClaimFormat::LdpVc,
+      "$.vp.verifiableCredential[1]",
+    ));
+    assert!(matches!(
+      definition
+        .evaluate_submission(&unknown_descriptor, &presentation)
+        .unwrap_err(),
+      PresentationExchangeError::UnknownInputDescriptor(_)
+    ));
+
+    // The path must select exactly one value.
+    for path in ["$.vp.verifiableCredential[2]", "$.vp.verifiableCredential[*]"] {
+      let invalid_path: PresentationSubmission = submission(InputDescriptorMapping::new(
+        "university_degree",
+        ClaimFormat::LdpVc,
+        path,
+      ));
+      assert!(matches!(
+        definition
+          .evaluate_submission(&invalid_path, &presentation)
+          .unwrap_err(),
+        PresentationExchangeError::InvalidDescriptorPath { .. }
+      ));
+    }
+
+    // All input descriptors must be satisfied.
+    let empty: PresentationSubmission = PresentationSubmission::new("submission", "definition", Vec::new());
+    assert!(matches!(
+      definition.evaluate_submission(&empty, &presentation).unwrap_err(),
+      PresentationExchangeError::RequirementsNotSatisfied
+    ));
+  }
+
+  #[test]
+  fn evaluate_limited_disclosure() {
+    let mut definition: PresentationDefinition = definition();
+    definition.input_descriptors[0].constraints.limit_disclosure = Some(Optionality::Required);
+
+    // Credentials that cannot selectively disclose claims are rejected.
+    let mapping: InputDescriptorMapping =
+      InputDescriptorMapping::new("university_degree", ClaimFormat::LdpVc, "$.vp.verifiableCredential[1]");
+    assert!(matches!(
+      definition
+        .evaluate_submission(&submission(mapping.clone()), &presentation("BachelorDegree"))
+        .unwrap_err(),
+      PresentationExchangeError::LimitDisclosureUnsupported { .. }
+    ));
+
+    // Preferring limited disclosure does not reject them.
+    definition.input_descriptors[0].constraints.limit_disclosure = Some(Optionality::Preferred);
+    assert!(definition
+      .evaluate_submission(&submission(mapping), &presentation("BachelorDegree"))
+      .is_ok());
+  }
+
+  #[cfg(feature = "sd-jwt")]
+  #[test]
+  fn evaluate_limited_disclosure_sd_jwt() {
+    use identity_core::common::Object;
+    use sd_jwt_payload::SdObjectEncoder;
+
+    let mut definition: PresentationDefinition = definition();
+    definition.format = Some(Object::from_iter([("vc+sd-jwt".to_owned(), json!({}))]));
+    definition.input_descriptors[0].constraints.limit_disclosure = Some(Optionality::Required);
+
+    let claims: Value = json!({
+      "iss": "did:example:issuer",
+      "vc": {
+        "@context": "https://www.w3.org/2018/credentials/v1",
+        "type": ["VerifiableCredential"],
+        "credentialSubject": {
+          "degree": { "type": "BachelorDegree" },
+          "address": { "country": "CH" }
+        }
+      }
+    });
+    let mut encoder: SdObjectEncoder = SdObjectEncoder::new(&claims.to_string()).unwrap();
+    let disclosures: Vec<String> = [
+      "/vc/credentialSubject/degree/type",
+      "/vc/credentialSubject/degree",
+      "/vc/credentialSubject/address",
+    ]
+    .iter()
+    .map(|path| encoder.conceal(path, None).unwrap().into_string())
+    .collect();
+    encoder.add_sd_alg_property();
+    let claims: Value = Value::Object(encoder.object().unwrap().clone());
+    let presentation = |disclosures: &[String]| -> Value {
+      let sd_jwt: SdJwt = SdJwt::new(jwt(&claims), disclosures.to_vec(), None);
+      json!({ "vp": { "verifiableCredential": [sd_jwt.presentation()] } })
+    };
+    let mapping: InputDescriptorMapping = InputDescriptorMapping::new(
+      "university_degree",
+      ClaimFormat::SdJwtVc,
+      "$.vp.verifiableCredential[0]",
+    );
+
+    // The nested disclosures of the degree are needed to select its type.
+    assert!(definition
+      .evaluate_submission(&submission(mapping.clone()), &presentation(&disclosures[..2]))
+      .is_ok());
+
+    // Disclosing the address is not requested.
+    assert!(matches!(
+      definition
+        .evaluate_submission(&submission(mapping), &presentation(&disclosures))
+        .unwrap_err(),
+      PresentationExchangeError::UnrequestedDisclosure(_)
+    ));
+  }
+}
@@ -0,0 +1,69 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::ClaimFormat;
+
+/// Describes how the credentials in a presentation satisfy a [`PresentationDefinition`](super::PresentationDefinition),
+/// as defined in
+/// [Presentation Exchange v2](https://identity.foundation/presentation-exchange/spec/v2.0.0/#presentation-submission).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PresentationSubmission {
+  /// The unique identifier of the presentation submission.
+  pub id: String,
+  /// The id of the presentation definition the submission satisfies.
+  pub definition_id: String,
+  /// Where the credentials satisfying each input descriptor are located in the presentation.
+  pub descriptor_map: Vec<InputDescriptorMapping>,
+}
+
+impl PresentationSubmission {
+  /// Creates a new [`PresentationSubmission`].
+  pub fn new(
+    id: impl Into<String>,
+    definition_id: impl Into<String>,
+    descriptor_map: Vec<InputDescriptorMapping>,
+  ) -> Self {
+    Self {
+      id: id.into(),
+      definition_id: definition_id.into(),
+      descriptor_map,
+    }
+  }
+}
+
+/// Locates the credential satisfying an input descriptor within a presentation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InputDescriptorMapping {
+  /// The id of the satisfied input descriptor.
+  pub id: String,
+  /// The claim format of the value located by `path`.
+  pub format: ClaimFormat,
+  /// A JSONPath expression selecting the value, relative to the presentation or, when nested, to the value
+  /// selected by the parent mapping.
+  pub path: String,
+  /// Locates the credential within the value selected by `path`, such as a credential within a presentation.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub path_nested: Option<Box<InputDescriptorMapping>>,
+}
+
+impl InputDescriptorMapping {
+  /// Creates a new [`InputDescriptorMapping`].
+  pub fn new(id: impl Into<String>, format: ClaimFormat, path: impl Into<String>) -> Self {
+    Self {
+      id: id.into(),
+      format,
+      path: path.into(),
+      path_nested: None,
+    }
+  }
+
+  /// Sets the mapping locating the credential within the value selected by this mapping.
+  #[must_use]
+  pub fn path_nested(mut self, path_nested: InputDescriptorMapping) -> Self {
+    self.path_nested = Some(Box::new(path_nested));
+    self
+  }
+}
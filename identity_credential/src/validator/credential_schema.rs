@@ -0,0 +1,154 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+use identity_core::common::Url;
+use identity_core::common::Value;
+use jsonschema::Draft;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// The [`credentialSchema`](https://www.w3.org/TR/vc-json-schema/) type of schemas that can be validated.
+pub const JSON_SCHEMA_TYPE: &str = "JsonSchema";
+
+/// The JSON Schema dialects supported by the [VC JSON Schema](https://www.w3.org/TR/vc-json-schema/#jsonschema)
+/// specification, identified by their `$schema` URI.
+const JSON_SCHEMA_DRAFTS: [(&str, Draft); 3] = [
+  ("https://json-schema.org/draft/2020-12/schema", Draft::Draft202012),
+  ("https://json-schema.org/draft/2019-09/schema", Draft::Draft201909),
+  ("http://json-schema.org/draft-07/schema", Draft::Draft7),
+];
+
+/// A source of the JSON Schemas referenced by the
+/// [`credentialSchema`](https://www.w3.org/TR/vc-data-model/#data-schemas) property of credentials.
+///
+/// Implement this trait to look up schemas from a custom location such as a cache. A preloaded set of schemas
+/// can be supplied with a [`CredentialSchemaRegistry`].
+pub trait CredentialSchemaFetcher {
+  /// Returns the JSON Schema identified by `schema_id`.
+  fn fetch_schema(&self, schema_id: &Url)
+    -> Result<Cow<'_, Value>, Box<dyn std::error::Error + Send + Sync + 'static>>;
+}
+
+/// A preloaded set of JSON Schemas, indexed by the URL credentials use to reference them in their
+/// [`credentialSchema`](https://www.w3.org/TR/vc-data-model/#data-schemas) property.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct CredentialSchemaRegistry(BTreeMap<Url, Value>);
+
+impl CredentialSchemaRegistry {
+  /// Creates an empty [`CredentialSchemaRegistry`].
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Registers the JSON Schema `schema` under `schema_id`, returning the previously registered schema, if any.
+  pub fn insert(&mut self, schema_id: Url, schema: Value) -> Option<Value> {
+    self.0.insert(schema_id, schema)
+  }
+
+  /// Registers the JSON Schema `schema` under `schema_id`.
+  pub fn with_schema(mut self, schema_id: Url, schema: Value) -> Self {
+    self.insert(schema_id, schema);
+    self
+  }
+
+  /// Returns the JSON Schema registered under `schema_id`, if any.
+  pub fn get(&self, schema_id: &Url) -> Option<&Value> {
+    self.0.get(schema_id)
+  }
+
+  /// Removes the JSON Schema registered under `schema_id` and returns it, if any.
+  pub fn remove(&mut self, schema_id: &Url) -> Option<Value> {
+    self.0.remove(schema_id)
+  }
+}
+
+impl std::fmt::Debug for dyn CredentialSchemaFetcher + Send + Sync {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.write_str("CredentialSchemaFetcher")
+  }
+}
+
+impl CredentialSchemaFetcher for CredentialSchemaRegistry {
+  fn fetch_schema(
+    &self,
+    schema_id: &Url,
+  ) -> Result<Cow<'_, Value>, Box<dyn std::error::Error + Send + Sync + 'static>> {
+    self
+      .get(schema_id)
+      .map(Cow::Borrowed)
+      .ok_or_else(|| format!("schema `{schema_id}` is not registered").into())
+  }
+}
+
+/// Returns the dialect declared by the `$schema` keyword of `schema`, defaulting to Draft 2020-12.
+///
+/// Fails if `$schema` refers to a dialect not supported by the VC JSON Schema specification.
+pub(crate) fn json_schema_draft(schema: &Value) -> Result<Draft, String> {
+  let Some(dialect) = schema.get("$schema") else {
+    return Ok(Draft::Draft202012);
+  };
+
+  dialect
+    .as_str()
+    .map(|uri| uri.trim_end_matches('#'))
+    .and_then(|uri| {
+      JSON_SCHEMA_DRAFTS
+        .iter()
+        .find(|(draft_uri, _)| *draft_uri == uri)
+        .map(|(_, draft)| *draft)
+    })
+    .ok_or_else(|| format!("unsupported `$schema` {dialect}"))
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_core::convert::FromJson;
+  use identity_core::convert::ToJson;
+
+  use super::*;
+
+  #[test]
+  fn test_registry_serde() {
+    let schema_id: Url = Url::parse("https://example.com/schemas/email.json").unwrap();
+    let registry: CredentialSchemaRegistry =
+      CredentialSchemaRegistry::new().with_schema(schema_id.clone(), serde_json::json!({ "type": "object" }));
+
+    let json: String = registry.to_json().unwrap();
+    assert_eq!(json, r#"{"https://example.com/schemas/email.json":{"type":"object"}}"#);
+    let deserialized: CredentialSchemaRegistry = CredentialSchemaRegistry::from_json(&json).unwrap();
+    assert_eq!(deserialized, registry);
+
+    assert!(registry.fetch_schema(&schema_id).is_ok());
+    assert!(registry
+      .fetch_schema(&Url::parse("https://example.com/schemas/other.json").unwrap())
+      .is_err());
+  }
+
+  #[test]
+  fn test_json_schema_draft() {
+    assert_eq!(
+      json_schema_draft(&serde_json::json!({ "type": "object" })).unwrap(),
+      Draft::Draft202012
+    );
+    for (uri, draft) in [
+      ("https://json-schema.org/draft/2020-12/schema", Draft::Draft202012),
+      ("https://json-schema.org/draft/2019-09/schema#", Draft::Draft201909),
+      ("http://json-schema.org/draft-07/schema#", Draft::Draft7),
+    ] {
+      assert_eq!(
+        json_schema_draft(&serde_json::json!({ "$schema": uri })).unwrap(),
+        draft
+      );
+    }
+    for uri in [
+      serde_json::json!("http://json-schema.org/draft-04/schema#"),
+      serde_json::json!(7),
+    ] {
+      assert!(json_schema_draft(&serde_json::json!({ "$schema": uri })).is_err());
+    }
+  }
+}
@@ -58,6 +58,18 @@ pub enum JwtValidationError {
     signer_ctx: SignerContext,
   },
 
+  /// Indicates that the embedded proof of a credential (resp. presentation) could not be verified using the
+  /// issuer's (resp. holder's) DID Document.
+  #[error("could not verify the {signer_ctx}'s proof")]
+  #[non_exhaustive]
+  ProofVerification {
+    /// Proof verification error.
+    source: Box<dyn std::error::Error + Send + Sync + 'static>,
+    /// Specifies whether the error was from the DID Document of a credential issuer
+    /// or the presentation holder.
+    signer_ctx: SignerContext,
+  },
+
   /// Indicates that the credential's (resp. presentation's) issuer's (resp. holder's) URL could
   /// not be parsed as a valid DID.
   #[error("{signer_ctx} URL is not a valid DID")]
@@ -94,6 +106,9 @@ pub enum JwtValidationError {
   /// Indicates that the credential's status is invalid.
   #[error("invalid credential status")]
   InvalidStatus(#[source] crate::Error),
+  /// Indicates that the credential does not conform to the JSON Schema referenced by its `credentialSchema`.
+  #[error("the credential does not conform to its credential schema")]
+  CredentialSchema(#[source] crate::Error),
   /// Indicates that the the credential's service is invalid.
   #[error("service lookup error")]
   #[non_exhaustive]
@@ -40,6 +40,14 @@ pub struct JwtCredentialValidationOptions {
   /// Options which affect the verification of the signature on the credential.
   #[serde(default)]
   pub verification_options: JwsVerificationOptions,
+
+  /// The source of the JSON Schemas to validate the credential against, as referenced by its
+  /// [`credentialSchema`](https://www.w3.org/TR/vc-json-schema/) property.
+  ///
+  /// The check is skipped if not set. This option is not serialized.
+  #[cfg(feature = "credential-schema")]
+  #[serde(skip)]
+  pub credential_schemas: Option<std::sync::Arc<dyn crate::validator::CredentialSchemaFetcher + Send + Sync>>,
 }
 
 impl JwtCredentialValidationOptions {
@@ -85,4 +93,16 @@ impl JwtCredentialValidationOptions {
     self.verification_options = options;
     self
   }
+
+  /// Declare that the credential must conform to the JSON Schemas referenced by its
+  /// [`credentialSchema`](https://www.w3.org/TR/vc-json-schema/) property, which are looked up using `fetcher`, e.g.
+  /// a [`CredentialSchemaRegistry`](crate::validator::CredentialSchemaRegistry).
+  #[cfg(feature = "credential-schema")]
+  pub fn credential_schemas<F>(mut self, fetcher: F) -> Self
+  where
+    F: crate::validator::CredentialSchemaFetcher + Send + Sync + 'static,
+  {
+    self.credential_schemas = Some(std::sync::Arc::new(fetcher));
+    self
+  }
 }
@@ -40,7 +40,10 @@ impl<V: JwsVerifier> JwtCredentialValidator<V> {
   /// - the issuer's signature on the JWS,
   /// - the expiration date,
   /// - the issuance date,
-  /// - the semantic structure.
+  /// - the semantic structure,
+  /// - the `credentialSchema`, if
+  ///   [`JwtCredentialValidationOptions::credential_schemas`](JwtCredentialValidationOptions::credential_schemas())
+  ///   is set.
   ///
   /// # Warning
   /// The lack of an error returned from this method is in of itself not enough to conclude that the credential can be
@@ -52,7 +55,7 @@ impl<V: JwsVerifier> JwtCredentialValidator<V> {
   ///
   /// ## Properties that are not validated
   ///  There are many properties defined in [The Verifiable Credentials Data Model](https://www.w3.org/TR/vc-data-model/) that are **not** validated, such as:
-  /// `proof`, `credentialStatus`, `type`, `credentialSchema`, `refreshService` **and more**.
+  /// `proof`, `credentialStatus`, `type`, `refreshService` **and more**.
   /// These should be manually checked after validation, according to your requirements.
   ///
   /// # Errors
@@ -128,58 +131,7 @@ impl<V: JwsVerifier> JwtCredentialValidator<V> {
     T: ToOwned<Owned = T> + serde::Serialize + serde::de::DeserializeOwned,
     DOC: AsRef<CoreDocument>,
   {
-    let credential: &Credential<T> = &credential_token.credential;
-    // Run all single concern Credential validations in turn and fail immediately if `fail_fast` is true.
-
-    let expiry_date_validation = std::iter::once_with(|| {
-      JwtCredentialValidatorUtils::check_expires_on_or_after(
-        &credential_token.credential,
-        options.earliest_expiry_date.unwrap_or_default(),
-      )
-    });
-
-    let issuance_date_validation = std::iter::once_with(|| {
-      JwtCredentialValidatorUtils::check_issued_on_or_before(
-        credential,
-        options.latest_issuance_date.unwrap_or_default(),
-      )
-    });
-
-    let structure_validation = std::iter::once_with(|| JwtCredentialValidatorUtils::check_structure(credential));
-
-    let subject_holder_validation = std::iter::once_with(|| {
-      options
-        .subject_holder_relationship
-        .as_ref()
-        .map(|(holder, relationship)| {
-          JwtCredentialValidatorUtils::check_subject_holder_relationship(credential, holder, *relationship)
-        })
-        .unwrap_or(Ok(()))
-    });
-
-    let validation_units_iter = issuance_date_validation
-      .chain(expiry_date_validation)
-      .chain(structure_validation)
-      .chain(subject_holder_validation);
-
-    #[cfg(feature = "revocation-bitmap")]
-    let validation_units_iter = {
-      let revocation_validation =
-        std::iter::once_with(|| JwtCredentialValidatorUtils::check_status(credential, issuers, options.status));
-      validation_units_iter.chain(revocation_validation)
-    };
-
-    let validation_units_error_iter = validation_units_iter.filter_map(|result| result.err());
-    let validation_errors: Vec<JwtValidationError> = match fail_fast {
-      FailFast::FirstError => validation_units_error_iter.take(1).collect(),
-      FailFast::AllErrors => validation_units_error_iter.collect(),
-    };
-
-    if validation_errors.is_empty() {
-      Ok(credential_token)
-    } else {
-      Err(CompoundCredentialValidationError { validation_errors })
-    }
+    validate_credential_properties(&credential_token.credential, issuers, options, fail_fast).map(|_| credential_token)
   }
 
   pub(crate) fn parse_jwk<'a, 'i, DOC>(
@@ -328,6 +280,78 @@ impl<V: JwsVerifier> JwtCredentialValidator<V> {
   }
 }
 
+/// Runs the single concern validations of a [`Credential`] that are independent of how it is secured: the issuance
+/// and expiration dates, the semantic structure, the subject-holder relationship, the `credentialSchema` and the
+/// `credentialStatus`, according to `options`.
+pub(crate) fn validate_credential_properties<DOC, T>(
+  credential: &Credential<T>,
+  issuers: &[DOC],
+  options: &JwtCredentialValidationOptions,
+  fail_fast: FailFast,
+) -> Result<(), CompoundCredentialValidationError>
+where
+  T: serde::Serialize,
+  DOC: AsRef<CoreDocument>,
+{
+  // Run all single concern Credential validations in turn and fail immediately if `fail_fast` is true.
+
+  let expiry_date_validation = std::iter::once_with(|| {
+    JwtCredentialValidatorUtils::check_expires_on_or_after(credential, options.earliest_expiry_date.unwrap_or_default())
+  });
+
+  let issuance_date_validation = std::iter::once_with(|| {
+    JwtCredentialValidatorUtils::check_issued_on_or_before(credential, options.latest_issuance_date.unwrap_or_default())
+  });
+
+  let structure_validation = std::iter::once_with(|| JwtCredentialValidatorUtils::check_structure(credential));
+
+  let subject_holder_validation = std::iter::once_with(|| {
+    options
+      .subject_holder_relationship
+      .as_ref()
+      .map(|(holder, relationship)| {
+        JwtCredentialValidatorUtils::check_subject_holder_relationship(credential, holder, *relationship)
+      })
+      .unwrap_or(Ok(()))
+  });
+
+  let validation_units_iter = issuance_date_validation
+    .chain(expiry_date_validation)
+    .chain(structure_validation)
+    .chain(subject_holder_validation);
+
+  #[cfg(feature = "credential-schema")]
+  let validation_units_iter = {
+    let schema_validation = std::iter::once_with(|| {
+      options
+        .credential_schemas
+        .as_ref()
+        .map(|fetcher| JwtCredentialValidatorUtils::check_credential_schema(credential, fetcher.as_ref()))
+        .unwrap_or(Ok(()))
+    });
+    validation_units_iter.chain(schema_validation)
+  };
+
+  #[cfg(feature = "revocation-bitmap")]
+  let validation_units_iter = {
+    let revocation_validation =
+      std::iter::once_with(|| JwtCredentialValidatorUtils::check_status(credential, issuers, options.status));
+    validation_units_iter.chain(revocation_validation)
+  };
+
+  let validation_units_error_iter = validation_units_iter.filter_map(|result| result.err());
+  let validation_errors: Vec<JwtValidationError> = match fail_fast {
+    FailFast::FirstError => validation_units_error_iter.take(1).collect(),
+    FailFast::AllErrors => validation_units_error_iter.collect(),
+  };
+
+  if validation_errors.is_empty() {
+    Ok(())
+  } else {
+    Err(CompoundCredentialValidationError { validation_errors })
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use crate::credential::Subject;
@@ -373,6 +397,7 @@ mod tests {
       &SIMPLE_CREDENTIAL,
       SIMPLE_CREDENTIAL
         .issuance_date
+        .unwrap()
         .checked_sub(Duration::minutes(1))
         .unwrap()
     )
@@ -383,6 +408,7 @@ mod tests {
       &SIMPLE_CREDENTIAL,
       SIMPLE_CREDENTIAL
         .issuance_date
+        .unwrap()
         .checked_add(Duration::minutes(1))
         .unwrap()
     )
@@ -493,6 +519,190 @@ mod tests {
     .is_ok());
   }
 
+  #[test]
+  fn validity_period_v2() {
+    let mut credential: Credential = Credential::from_json(
+      r#"{
+        "@context": "https://www.w3.org/ns/credentials/v2",
+        "type": "VerifiableCredential",
+        "issuer": "https://example.edu/issuers/14",
+        "validFrom": "2010-01-01T19:23:24Z",
+        "validUntil": "2020-01-01T19:23:24Z",
+        "credentialSubject": {
+          "id": "did:example:ebfeb1f712ebc6f1c276e12ec21"
+        }
+      }"#,
+    )
+    .unwrap();
+    let valid_from: Timestamp = credential.valid_from.unwrap();
+    let valid_until: Timestamp = credential.valid_until.unwrap();
+
+    assert!(JwtCredentialValidatorUtils::check_issued_on_or_before(
+      &credential,
+      valid_from.checked_sub(Duration::minutes(1)).unwrap()
+    )
+    .is_err());
+    assert!(JwtCredentialValidatorUtils::check_issued_on_or_before(&credential, valid_from).is_ok());
+    assert!(JwtCredentialValidatorUtils::check_expires_on_or_after(
+      &credential,
+      valid_until.checked_add(Duration::minutes(1)).unwrap()
+    )
+    .is_err());
+    assert!(JwtCredentialValidatorUtils::check_expires_on_or_after(&credential, valid_until).is_ok());
+
+    // Without a validity period the credential is always valid.
+    credential.valid_from = None;
+    credential.valid_until = None;
+    assert!(
+      JwtCredentialValidatorUtils::check_issued_on_or_before(&credential, Timestamp::from_unix(0).unwrap()).is_ok()
+    );
+    assert!(JwtCredentialValidatorUtils::check_expires_on_or_after(&credential, Timestamp::now_utc()).is_ok());
+  }
+
+  #[cfg(feature = "credential-schema")]
+  #[test]
+  fn check_credential_schema() {
+    use crate::credential::Schema;
+    use crate::validator::CredentialSchemaRegistry;
+
+    let schema_id: Url = Url::parse("https://example.edu/schemas/degree.json").unwrap();
+    let registry: CredentialSchemaRegistry = CredentialSchemaRegistry::new().with_schema(
+      schema_id.clone(),
+      serde_json::json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "type": "object",
+        "properties": {
+          "credentialSubject": {
+            "type": "object",
+            "properties": {
+              "degree": {
+                "type": "object",
+                "properties": {
+                  "type": { "type": "string" },
+                  "name": { "type": "string" }
+                },
+                "required": ["type", "name"]
+              }
+            },
+            "required": ["degree"]
+          }
+        },
+        "required": ["credentialSubject"]
+      }),
+    );
+
+    // Credentials without a schema pass.
+    let mut credential: Credential = SIMPLE_CREDENTIAL.clone();
+    assert!(JwtCredentialValidatorUtils::check_credential_schema(&credential, &registry).is_ok());
+
+    credential.credential_schema = Schema::new(schema_id, "JsonSchema".to_owned()).into();
+    assert!(JwtCredentialValidatorUtils::check_credential_schema(&credential, &registry).is_ok());
+
+    // Subject claims violating the schema fail.
+    let mut properties: Object = credential.credential_subject.as_slice()[0].properties.clone();
+    properties.insert("degree".to_owned(), serde_json::json!({ "type": "BachelorDegree" }));
+    credential.credential_subject = Subject::with_properties(properties).into();
+    assert!(matches!(
+      JwtCredentialValidatorUtils::check_credential_schema(&credential, &registry).unwrap_err(),
+      JwtValidationError::CredentialSchema(crate::Error::InvalidCredentialSchema(_))
+    ));
+
+    // Unregistered schemas fail.
+    credential.credential_schema = Schema::new(
+      Url::parse("https://example.edu/schemas/other.json").unwrap(),
+      "JsonSchema".to_owned(),
+    )
+    .into();
+    assert!(JwtCredentialValidatorUtils::check_credential_schema(&credential, &registry).is_err());
+
+    // Unsupported schema types fail.
+    credential.credential_schema = Schema::new(
+      Url::parse("https://example.edu/schemas/degree.json").unwrap(),
+      "ZkpExampleSchema2018".to_owned(),
+    )
+    .into();
+    assert!(JwtCredentialValidatorUtils::check_credential_schema(&credential, &registry).is_err());
+  }
+
+  #[cfg(feature = "credential-schema")]
+  #[test]
+  fn check_credential_schema_draft_2020_12() {
+    use std::borrow::Cow;
+
+    use identity_core::common::Value;
+
+    use crate::credential::Schema;
+    use crate::validator::CredentialSchemaFetcher;
+
+    // A fetcher serving a single schema whose `unevaluatedProperties` only exists since Draft 2019-09.
+    #[derive(Debug)]
+    struct DegreeSchema(Value);
+
+    impl CredentialSchemaFetcher for DegreeSchema {
+      fn fetch_schema(
+        &self,
+        _schema_id: &Url,
+      ) -> Result<Cow<'_, Value>, Box<dyn std::error::Error + Send + Sync + 'static>> {
+        Ok(Cow::Borrowed(&self.0))
+      }
+    }
+
+    let degree_schema = |dialect: Option<&str>| {
+      let mut schema: Value = serde_json::json!({
+        "type": "object",
+        "properties": {
+          "credentialSubject": {
+            "properties": {
+              "degree": {
+                "allOf": [{ "properties": { "type": { "type": "string" } } }],
+                "unevaluatedProperties": false
+              }
+            }
+          }
+        }
+      });
+      if let Some(dialect) = dialect {
+        schema["$schema"] = Value::from(dialect);
+      }
+      DegreeSchema(schema)
+    };
+
+    let mut credential: Credential = SIMPLE_CREDENTIAL.clone();
+    credential.credential_schema = Schema::new(
+      Url::parse("https://example.edu/schemas/degree.json").unwrap(),
+      "JsonSchema".to_owned(),
+    )
+    .into();
+
+    // The `name` of the degree is not evaluated by any subschema.
+    for dialect in [None, Some("https://json-schema.org/draft/2020-12/schema")] {
+      let options: JwtCredentialValidationOptions = JwtCredentialValidationOptions::default()
+        .earliest_expiry_date(Timestamp::parse("2019-01-01T00:00:00Z").unwrap())
+        .credential_schemas(degree_schema(dialect));
+      let error: CompoundCredentialValidationError =
+        validate_credential_properties::<CoreDocument, Object>(&credential, &[], &options, FailFast::AllErrors)
+          .unwrap_err();
+      assert!(matches!(
+        error.validation_errors.as_slice(),
+        [JwtValidationError::CredentialSchema(_)]
+      ));
+    }
+
+    // Draft 7 does not define `unevaluatedProperties`.
+    assert!(JwtCredentialValidatorUtils::check_credential_schema(
+      &credential,
+      &degree_schema(Some("http://json-schema.org/draft-07/schema#"))
+    )
+    .is_ok());
+
+    // Dialects not supported by the VC JSON Schema specification fail.
+    assert!(JwtCredentialValidatorUtils::check_credential_schema(
+      &credential,
+      &degree_schema(Some("http://json-schema.org/draft-04/schema#"))
+    )
+    .is_err());
+  }
+
   #[test]
   fn simple_expires_on_or_after_with_expiration_date() {
     let later_than_expiration_date = SIMPLE_CREDENTIAL
@@ -533,8 +743,8 @@ mod tests {
     #[test]
     fn property_based_issued_before(seconds in 0 ..1_000_000_000_u32) {
 
-      let earlier_than_issuance_date = SIMPLE_CREDENTIAL.issuance_date.checked_sub(Duration::seconds(seconds)).unwrap();
-      let later_than_issuance_date = SIMPLE_CREDENTIAL.issuance_date.checked_add(Duration::seconds(seconds)).unwrap();
+      let earlier_than_issuance_date = SIMPLE_CREDENTIAL.issuance_date.unwrap().checked_sub(Duration::seconds(seconds)).unwrap();
+      let later_than_issuance_date = SIMPLE_CREDENTIAL.issuance_date.unwrap().checked_add(Duration::seconds(seconds)).unwrap();
       assert!(JwtCredentialValidatorUtils::check_issued_on_or_before(&SIMPLE_CREDENTIAL, earlier_than_issuance_date).is_err());
       assert!(JwtCredentialValidatorUtils::check_issued_on_or_before(&SIMPLE_CREDENTIAL, later_than_issuance_date).is_ok());
     }
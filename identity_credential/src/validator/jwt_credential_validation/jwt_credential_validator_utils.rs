@@ -31,6 +31,7 @@ impl JwtCredentialValidatorUtils {
   ///
   /// # Warning
   /// This does not validate against the credential's schema nor the structure of the subject claims.
+  /// See [`Self::check_credential_schema`] for the former.
   pub fn check_structure<T>(credential: &Credential<T>) -> ValidationUnitResult {
     credential
       .check_structure()
@@ -38,16 +39,22 @@ impl JwtCredentialValidatorUtils {
   }
 
   /// Validate that the [`Credential`] expires on or after the specified [`Timestamp`].
+  ///
+  /// The end of the validity period is given by `validUntil`, or `expirationDate` for VC Data Model v1.1 credentials.
   pub fn check_expires_on_or_after<T>(credential: &Credential<T>, timestamp: Timestamp) -> ValidationUnitResult {
-    let expiration_date: Option<Timestamp> = credential.expiration_date;
+    let expiration_date: Option<Timestamp> = credential.validity_end();
     (expiration_date.is_none() || expiration_date >= Some(timestamp))
       .then_some(())
       .ok_or(JwtValidationError::ExpirationDate)
   }
 
   /// Validate that the [`Credential`] is issued on or before the specified [`Timestamp`].
+  ///
+  /// The start of the validity period is given by `validFrom`, or `issuanceDate` for VC Data Model v1.1 credentials.
+  /// Credentials without either are considered valid from any time.
   pub fn check_issued_on_or_before<T>(credential: &Credential<T>, timestamp: Timestamp) -> ValidationUnitResult {
-    (credential.issuance_date <= timestamp)
+    let issuance_date: Option<Timestamp> = credential.validity_start();
+    (issuance_date.is_none() || issuance_date <= Some(timestamp))
       .then_some(())
       .ok_or(JwtValidationError::IssuanceDate)
   }
@@ -84,6 +91,79 @@ impl JwtCredentialValidatorUtils {
       .ok_or(JwtValidationError::SubjectHolderRelationship)
   }
 
+  /// Validates the [`Credential`] against the JSON Schemas referenced by its
+  /// [`credentialSchema`](https://www.w3.org/TR/vc-json-schema/) property, which are looked up using `fetcher`.
+  ///
+  /// As defined by the VC JSON Schema specification, each schema is applied to the entire credential, so claims
+  /// about the subject are constrained through the `credentialSubject` property of the schema.
+  ///
+  /// Only schemas of type `JsonSchema` are supported, any other type results in an error. Schemas are evaluated
+  /// according to the dialect declared by their `$schema` keyword, which must be one of Draft 2020-12 (the
+  /// default), Draft 2019-09 or Draft 7.
+  #[cfg(feature = "credential-schema")]
+  pub fn check_credential_schema<T, F>(credential: &Credential<T>, fetcher: &F) -> ValidationUnitResult
+  where
+    T: serde::Serialize,
+    F: crate::validator::CredentialSchemaFetcher + ?Sized,
+  {
+    use identity_core::common::Value;
+    use identity_core::convert::ToJson;
+    use jsonschema::Draft;
+    use jsonschema::JSONSchema;
+
+    let schema_error =
+      |message: String| JwtValidationError::CredentialSchema(crate::Error::InvalidCredentialSchema(message));
+
+    if credential.credential_schema.is_empty() {
+      return Ok(());
+    }
+
+    let instance: Value = credential
+      .to_json_value()
+      .map_err(|err| schema_error(format!("could not serialize credential: {err}")))?;
+
+    for schema in credential.credential_schema.iter() {
+      if !schema
+        .types
+        .iter()
+        .any(|type_| type_ == crate::validator::JSON_SCHEMA_TYPE)
+      {
+        return Err(schema_error(format!(
+          "unsupported type '{}' of schema `{}`",
+          schema.types.as_slice().join(", "),
+          schema.id
+        )));
+      }
+
+      let json_schema: Value = fetcher
+        .fetch_schema(&schema.id)
+        .map_err(|err| schema_error(format!("could not fetch schema `{}`: {err}", schema.id)))?
+        .into_owned();
+      let draft: Draft = crate::validator::credential_schema::json_schema_draft(&json_schema)
+        .map_err(|err| schema_error(format!("could not compile schema `{}`: {err}", schema.id)))?;
+      let compiled: JSONSchema = JSONSchema::options()
+        .with_draft(draft)
+        .compile(&json_schema)
+        .map_err(|err| schema_error(format!("could not compile schema `{}`: {err}", schema.id)))?;
+
+      let violations: Vec<String> = match compiled.validate(&instance) {
+        Ok(()) => Vec::new(),
+        Err(errors) => errors
+          .map(|err| format!("{} at `{}`", err, err.instance_path))
+          .collect(),
+      };
+      if !violations.is_empty() {
+        return Err(schema_error(format!(
+          "schema `{}` not satisfied: {}",
+          schema.id,
+          violations.join("; ")
+        )));
+      }
+    }
+
+    Ok(())
+  }
+
   /// Checks whether the status specified in `credentialStatus` has been set by the issuer.
   ///
   /// Only supports `StatusList2021`.
@@ -0,0 +1,301 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Value;
+use identity_did::CoreDID;
+use identity_document::document::CoreDocument;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::ProofSuite;
+use crate::credential::Credential;
+use crate::credential::Issuer;
+use crate::data_integrity::ProofPurpose;
+use crate::validator::jwt_credential_validation::validate_credential_properties;
+use crate::validator::CompoundCredentialValidationError;
+use crate::validator::FailFast;
+use crate::validator::JwtCredentialValidationOptions;
+use crate::validator::JwtValidationError;
+use crate::validator::SignerContext;
+
+/// Options for validating credentials, regardless of whether they are secured as a JWT or with an embedded proof.
+pub type CredentialValidationOptions = JwtCredentialValidationOptions;
+
+/// An error associated with validating credentials and presentations, regardless of how they are secured.
+pub type ValidationError = JwtValidationError;
+
+/// A type for validating [`Credential`]s secured with an embedded proof, such as a
+/// [`DataIntegrityProof`](crate::data_integrity::DataIntegrityProof), rather than issued as a JWT.
+#[non_exhaustive]
+pub struct LinkedDataCredentialValidator<S: ProofSuite>(S);
+
+impl<S: ProofSuite> LinkedDataCredentialValidator<S> {
+  /// Create a new [`LinkedDataCredentialValidator`] that delegates the verification of embedded proofs to the given
+  /// `proof_suite`.
+  pub fn with_proof_suite(proof_suite: S) -> Self {
+    Self(proof_suite)
+  }
+
+  /// Validates a credential secured with an embedded proof, given as the JSON it was received in, and returns the
+  /// parsed [`Credential`] upon success.
+  ///
+  /// The following properties are validated according to `options`:
+  /// - the issuer's proof, which must have the `assertionMethod` purpose,
+  /// - the expiration date,
+  /// - the issuance date,
+  /// - the semantic structure,
+  /// - the relationship between the subject and the holder, if set,
+  /// - the `credentialSchema`, if
+  ///   [`CredentialValidationOptions::credential_schemas`](JwtCredentialValidationOptions::credential_schemas())
+  ///   is set,
+  /// - the `credentialStatus`.
+  ///
+  /// The [`verification_options`](JwtCredentialValidationOptions::verification_options) only apply to JWS signatures
+  /// and are ignored.
+  ///
+  /// # Warning
+  /// The lack of an error returned from this method is in of itself not enough to conclude that the credential can be
+  /// trusted. The caller must ensure that `issuer` represents an up-to-date DID Document, and that any properties not
+  /// listed above, such as `type` or `refreshService`, are checked according to their requirements.
+  ///
+  /// # Errors
+  /// An error is returned whenever a validated condition is not satisfied. If the credential cannot be parsed or its
+  /// proof cannot be verified, this is the only error returned.
+  pub fn validate<DOC, T>(
+    &self,
+    credential: &Value,
+    issuer: &DOC,
+    options: &CredentialValidationOptions,
+    fail_fast: FailFast,
+  ) -> Result<Credential<T>, CompoundCredentialValidationError>
+  where
+    T: Serialize + DeserializeOwned,
+    DOC: AsRef<CoreDocument>,
+  {
+    let issuers: &[CoreDocument] = std::slice::from_ref(issuer.as_ref());
+    let parsed: Credential<T> = Credential::<T>::deserialize(credential)
+      .map_err(|err| ValidationError::CredentialStructure(crate::Error::CredentialDeserializationError(err.into())))
+      .and_then(|parsed| self.verify_proof(credential, issuers).map(|_| parsed))
+      .map_err(|err| CompoundCredentialValidationError {
+        validation_errors: [err].into(),
+      })?;
+
+    validate_credential_properties(&parsed, issuers, options, fail_fast)?;
+    Ok(parsed)
+  }
+
+  /// Verifies the proof embedded in a credential, given as the JSON it was received in, using the DID Document of a
+  /// trusted issuer.
+  ///
+  /// # Errors
+  /// This method immediately returns an error if the credential issuer's url cannot be parsed to a DID belonging to
+  /// one of the trusted issuers. Otherwise an attempt to verify the credential's proof will be made and an error is
+  /// returned upon failure.
+  pub fn verify_proof<DOC>(&self, credential: &Value, trusted_issuers: &[DOC]) -> Result<(), ValidationError>
+  where
+    DOC: AsRef<CoreDocument>,
+  {
+    let issuer_did: CoreDID = extract_issuer(credential)?;
+    let issuer: &CoreDocument = trusted_issuers
+      .iter()
+      .map(AsRef::as_ref)
+      .find(|issuer| <CoreDocument>::id(issuer) == &issuer_did)
+      .ok_or(ValidationError::DocumentMismatch(SignerContext::Issuer))?;
+
+    self
+      .0
+      .verify_proof(credential, issuer, ProofPurpose::AssertionMethod)
+      .map_err(|err| ValidationError::ProofVerification {
+        source: err.into(),
+        signer_ctx: SignerContext::Issuer,
+      })
+  }
+}
+
+/// Extracts the `issuer` of a credential in JSON representation as a DID.
+fn extract_issuer(credential: &Value) -> Result<CoreDID, ValidationError> {
+  let issuer: Issuer = credential
+    .get("issuer")
+    .ok_or(ValidationError::CredentialStructure(crate::Error::MissingIssuer))
+    .and_then(|issuer| {
+      Issuer::deserialize(issuer)
+        .map_err(|err| ValidationError::CredentialStructure(crate::Error::CredentialDeserializationError(err.into())))
+    })?;
+  CoreDID::parse(issuer.url().as_str()).map_err(|err| ValidationError::SignerUrl {
+    signer_ctx: SignerContext::Issuer,
+    source: err.into(),
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_core::common::Duration;
+  use identity_core::common::Object;
+  use identity_core::common::Timestamp;
+  use identity_core::common::Url;
+  use identity_core::convert::FromJson;
+
+  use super::*;
+  use crate::validator::test_utils;
+
+  #[derive(Debug, thiserror::Error)]
+  #[error("invalid mock proof")]
+  struct MockProofError;
+
+  /// Accepts the credential if its proof is of type `MockProof`.
+  struct MockProofSuite;
+
+  impl ProofSuite for MockProofSuite {
+    type Error = MockProofError;
+
+    fn verify_proof(
+      &self,
+      document: &Value,
+      _signer: &CoreDocument,
+      proof_purpose: ProofPurpose,
+    ) -> Result<(), Self::Error> {
+      (proof_purpose == ProofPurpose::AssertionMethod && document["proof"]["type"] == "MockProof")
+        .then_some(())
+        .ok_or(MockProofError)
+    }
+  }
+
+  fn credential(issuer: &CoreDocument, proof_type: &str) -> Value {
+    serde_json::json!({
+      "@context": "https://www.w3.org/2018/credentials/v1",
+      "type": "VerifiableCredential",
+      "issuer": issuer.id().to_string(),
+      "issuanceDate": "2010-01-01T19:23:24Z",
+      "expirationDate": "2020-01-01T19:23:24Z",
+      "credentialSubject": {
+        "id": "did:example:ebfeb1f712ebc6f1c276e12ec21"
+      },
+      "proof": {
+        "type": proof_type
+      }
+    })
+  }
+
+  #[test]
+  fn validate_credential() {
+    let (issuer, _, _) = test_utils::generate_jwk_document_with_keys();
+    let credential: Value = credential(&issuer, "MockProof");
+    let validator = LinkedDataCredentialValidator::with_proof_suite(MockProofSuite);
+    let options = CredentialValidationOptions::new()
+      .earliest_expiry_date(Timestamp::parse("2019-01-01T00:00:00Z").unwrap())
+      .latest_issuance_date(Timestamp::parse("2019-01-01T00:00:00Z").unwrap());
+
+    let parsed: Credential = validator
+      .validate::<_, Object>(&credential, &issuer, &options, FailFast::FirstError)
+      .unwrap();
+    assert_eq!(parsed, Credential::from_json_value(credential.clone()).unwrap());
+
+    // Credentials that cannot be parsed fail.
+    let mut malformed: Value = credential.clone();
+    malformed.as_object_mut().unwrap().remove("credentialSubject");
+    assert!(matches!(
+      validator
+        .validate::<_, Object>(&malformed, &issuer, &options, FailFast::AllErrors)
+        .unwrap_err()
+        .validation_errors
+        .as_slice(),
+      [ValidationError::CredentialStructure(_)]
+    ));
+
+    // Time checks are applied.
+    let options = CredentialValidationOptions::new()
+      .earliest_expiry_date(
+        Timestamp::parse("2020-01-01T19:23:24Z")
+          .unwrap()
+          .checked_add(Duration::seconds(1))
+          .unwrap(),
+      )
+      .latest_issuance_date(
+        Timestamp::parse("2010-01-01T19:23:24Z")
+          .unwrap()
+          .checked_sub(Duration::seconds(1))
+          .unwrap(),
+      );
+    let errors: Vec<ValidationError> = validator
+      .validate::<_, Object>(&credential, &issuer, &options, FailFast::AllErrors)
+      .unwrap_err()
+      .validation_errors;
+    assert_eq!(errors.len(), 2);
+    assert!(errors.iter().any(|err| matches!(err, ValidationError::ExpirationDate)));
+    assert!(errors.iter().any(|err| matches!(err, ValidationError::IssuanceDate)));
+
+    let errors: Vec<ValidationError> = validator
+      .validate::<_, Object>(&credential, &issuer, &options, FailFast::FirstError)
+      .unwrap_err()
+      .validation_errors;
+    assert_eq!(errors.len(), 1);
+  }
+
+  #[test]
+  fn validate_credential_proof() {
+    let (issuer, _, _) = test_utils::generate_jwk_document_with_keys();
+    let (other_issuer, _, _) = test_utils::generate_jwk_document_with_keys();
+    let validator = LinkedDataCredentialValidator::with_proof_suite(MockProofSuite);
+    let options = CredentialValidationOptions::new()
+      .earliest_expiry_date(Timestamp::parse("2019-01-01T00:00:00Z").unwrap())
+      .latest_issuance_date(Timestamp::parse("2019-01-01T00:00:00Z").unwrap());
+
+    // An invalid proof is the only error returned.
+    let errors: Vec<ValidationError> = validator
+      .validate::<_, Object>(
+        &credential(&issuer, "OtherProof"),
+        &issuer,
+        &options,
+        FailFast::AllErrors,
+      )
+      .unwrap_err()
+      .validation_errors;
+    assert!(matches!(
+      errors.as_slice(),
+      [ValidationError::ProofVerification {
+        signer_ctx: SignerContext::Issuer,
+        ..
+      }]
+    ));
+
+    // The proof is verified against the issuer's document.
+    assert!(matches!(
+      validator
+        .verify_proof(&credential(&issuer, "MockProof"), &[other_issuer])
+        .unwrap_err(),
+      ValidationError::DocumentMismatch(SignerContext::Issuer)
+    ));
+
+    // The issuer must be a DID.
+    let mut credential: Value = credential(&issuer, "MockProof");
+    credential["issuer"] = Value::from("https://example.edu/issuers/14");
+    assert!(matches!(
+      validator.verify_proof(&credential, &[issuer]).unwrap_err(),
+      ValidationError::SignerUrl { .. }
+    ));
+  }
+
+  #[test]
+  fn validate_credential_subject_holder_relationship() {
+    let (issuer, _, _) = test_utils::generate_jwk_document_with_keys();
+    let credential: Value = credential(&issuer, "MockProof");
+    let validator = LinkedDataCredentialValidator::with_proof_suite(MockProofSuite);
+    let options = CredentialValidationOptions::new()
+      .earliest_expiry_date(Timestamp::parse("2019-01-01T00:00:00Z").unwrap())
+      .latest_issuance_date(Timestamp::parse("2019-01-01T00:00:00Z").unwrap())
+      .subject_holder_relationship(
+        Url::parse("did:example:other").unwrap(),
+        crate::validator::SubjectHolderRelationship::AlwaysSubject,
+      );
+
+    let errors: Vec<ValidationError> = validator
+      .validate::<_, Object>(&credential, &issuer, &options, FailFast::AllErrors)
+      .unwrap_err()
+      .validation_errors;
+    assert!(matches!(
+      errors.as_slice(),
+      [ValidationError::SubjectHolderRelationship]
+    ));
+  }
+}
@@ -0,0 +1,9 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Contains functionality for validating credentials secured with an embedded proof.
+mod linked_data_credential_validator;
+mod proof_suite;
+
+pub use linked_data_credential_validator::*;
+pub use proof_suite::*;
@@ -0,0 +1,47 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Value;
+use identity_document::document::CoreDocument;
+use identity_verification::jws::JwsVerifier;
+
+use crate::data_integrity::DataIntegrityError;
+use crate::data_integrity::EddsaJcs2022Verifier;
+use crate::data_integrity::ProofPurpose;
+
+/// Trait for verifying the proof embedded in the `proof` property of credentials and presentations, as used by the
+/// [`LinkedDataCredentialValidator`](crate::validator::LinkedDataCredentialValidator).
+///
+/// Implement this trait to support proof types or cryptosuites other than
+/// [`eddsa-jcs-2022`](crate::data_integrity::EddsaJcs2022).
+pub trait ProofSuite {
+  /// The error returned when a proof cannot be verified.
+  type Error: std::error::Error + Send + Sync + 'static;
+
+  /// Verifies the proof embedded in `document` using the DID Document of its `signer`.
+  ///
+  /// `document` is the JSON as received, so that the proof is verified over exactly what was secured rather than
+  /// over a re-serialization of it. The proof must have the given `proof_purpose` and its verification method must
+  /// belong to `signer`.
+  fn verify_proof(
+    &self,
+    document: &Value,
+    signer: &CoreDocument,
+    proof_purpose: ProofPurpose,
+  ) -> Result<(), Self::Error>;
+}
+
+impl<V: JwsVerifier> ProofSuite for EddsaJcs2022Verifier<V> {
+  type Error = DataIntegrityError;
+
+  fn verify_proof(
+    &self,
+    document: &Value,
+    signer: &CoreDocument,
+    proof_purpose: ProofPurpose,
+  ) -> Result<(), Self::Error> {
+    self
+      .verify(document, std::slice::from_ref(signer), proof_purpose)
+      .map(|_| ())
+  }
+}
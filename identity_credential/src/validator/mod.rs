@@ -3,16 +3,24 @@
 
 //! Verifiable Credential and Presentation validators.
 
+#[cfg(feature = "credential-schema")]
+pub use self::credential_schema::*;
 pub use self::jwt_credential_validation::*;
 pub use self::jwt_presentation_validation::*;
+#[cfg(feature = "data-integrity")]
+pub use self::linked_data_credential_validation::*;
 pub use self::options::FailFast;
 pub use self::options::StatusCheck;
 pub use self::options::SubjectHolderRelationship;
 #[cfg(feature = "sd-jwt")]
 pub use self::sd_jwt::*;
 
+#[cfg(feature = "credential-schema")]
+mod credential_schema;
 mod jwt_credential_validation;
 mod jwt_presentation_validation;
+#[cfg(feature = "data-integrity")]
+mod linked_data_credential_validation;
 mod options;
 #[cfg(feature = "sd-jwt")]
 mod sd_jwt;
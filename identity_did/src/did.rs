@@ -100,7 +100,7 @@ pub trait DID:
 
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Deserialize, serde::Serialize)]
 #[repr(transparent)]
-#[serde(into = "BaseDIDUrl", try_from = "BaseDIDUrl")]
+#[serde(into = "BaseDIDUrl", try_from = "String")]
 /// A wrapper around [`BaseDIDUrl`](BaseDIDUrl).
 pub struct CoreDID(BaseDIDUrl);
 
@@ -111,7 +111,7 @@ impl CoreDID {
   ///
   /// Returns `Err` if the input is not a valid [`DID`].
   pub fn parse(input: impl AsRef<str>) -> Result<Self, Error> {
-    let base_did_url: BaseDIDUrl = BaseDIDUrl::parse(input).map_err(Error::from)?;
+    let base_did_url: BaseDIDUrl = parse_base_did_url(input.as_ref()).map_err(Error::from)?;
     Self::try_from_base_did(base_did_url)
   }
 
@@ -144,9 +144,19 @@ impl CoreDID {
   }
 
   /// Validates whether a string is a valid [`DID`] method-id.
+  ///
+  /// Besides the characters allowed by [`is_char_method_id`], percent-encoded octets (e.g. `%3A`) are accepted.
   pub fn valid_method_id(value: &str) -> Result<(), Error> {
-    if !value.chars().all(is_char_method_id) {
-      return Err(Error::InvalidMethodId);
+    let mut chars = value.chars();
+    while let Some(ch) = chars.next() {
+      let valid: bool = if ch == '%' {
+        matches!((chars.next(), chars.next()), (Some(hi), Some(lo)) if hi.is_ascii_hexdigit() && lo.is_ascii_hexdigit())
+      } else {
+        is_char_method_id(ch)
+      };
+      if !valid {
+        return Err(Error::InvalidMethodId);
+      }
     }
     Ok(())
   }
@@ -258,6 +268,25 @@ impl KeyComparable for CoreDID {
   }
 }
 
+/// Parses a [`BaseDIDUrl`], additionally accepting percent-encoded octets in the method-specific id, e.g. the port
+/// of `did:web:localhost%3A8443`, which [`BaseDIDUrl::parse`] rejects.
+pub(crate) fn parse_base_did_url(input: &str) -> Result<BaseDIDUrl, did_url::Error> {
+  let Some((method, rest)) = input.strip_prefix("did:").and_then(|rest| rest.split_once(':')) else {
+    return BaseDIDUrl::parse(input);
+  };
+  let method_id: &str = rest.split(&['/', '?', '#'][..]).next().unwrap_or_default();
+  if !method_id.contains('%') {
+    return BaseDIDUrl::parse(input);
+  }
+  CoreDID::valid_method_id(method_id).map_err(|_| did_url::Error::InvalidMethodId)?;
+
+  // Parse with a placeholder of the same length in place of `%`, then restore the original method id.
+  let placeholder: String = method_id.replace('%', "_");
+  let mut did: BaseDIDUrl = BaseDIDUrl::parse(format!("did:{method}:{placeholder}{}", &rest[method_id.len()..]))?;
+  did.set_method_id(method_id);
+  Ok(did)
+}
+
 /// Checks whether a character satisfies DID method name constraints:
 /// { 0-9 | a-z }
 #[inline(always)]
@@ -301,6 +330,10 @@ mod tests {
       CoreDID::parse("did:iota:main:123456890").unwrap(),
       "did:iota:main:123456890"
     );
+    assert_eq!(
+      CoreDID::parse("did:web:localhost%3A8443").unwrap(),
+      "did:web:localhost%3A8443"
+    );
   }
 
   #[test]
@@ -308,6 +341,9 @@ mod tests {
     assert!(CoreDID::parse("").is_err());
     assert!(CoreDID::parse("did:").is_err());
     assert!(CoreDID::parse("dad:example:123456890").is_err());
+    assert!(CoreDID::parse("did:example:123%").is_err());
+    assert!(CoreDID::parse("did:example:123%3").is_err());
+    assert!(CoreDID::parse("did:example:123%3G").is_err());
   }
 
   proptest::proptest! {
@@ -0,0 +1,227 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::borrow::Cow;
+
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::str::FromStr;
+
+use identity_core::common::KeyComparable;
+use identity_core::convert::FromJson;
+use identity_core::convert::ToJson;
+use identity_jose::jwk::Jwk;
+use identity_jose::jwu;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::BaseDIDUrl;
+use crate::CoreDID;
+use crate::Error;
+use crate::DID;
+
+/// Alias for a `Result` with the error type [`Error`].
+type Result<T> = std::result::Result<T, Error>;
+
+/// A DID conforming to the [did:jwk method specification](https://github.com/quartzjer/did-jwk/blob/main/spec.md).
+///
+/// The method specific identifier of a did:jwk is the base64url encoding of a public [`Jwk`], from which the DID
+/// document can be derived without any network access.
+#[derive(Clone, Hash, Debug, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(into = "CoreDID", try_from = "CoreDID")]
+pub struct DIDJwk(CoreDID);
+
+impl DIDJwk {
+  /// The did:jwk method name (`"jwk"`).
+  pub const METHOD: &'static str = "jwk";
+
+  /// Creates a new [`DIDJwk`] encoding the public part of the given `jwk`.
+  ///
+  /// # Errors
+  ///
+  /// Fails if `jwk` is a symmetric key.
+  pub fn new(jwk: &Jwk) -> Result<Self> {
+    let public_jwk: Cow<'_, Jwk> = if jwk.is_public() {
+      Cow::Borrowed(jwk)
+    } else {
+      Cow::Owned(
+        jwk
+          .to_public()
+          .ok_or(Error::Other("did:jwk requires an asymmetric JWK"))?,
+      )
+    };
+    let json: Vec<u8> = public_jwk
+      .to_json_vec()
+      .map_err(|_| Error::Other("unable to serialize JWK"))?;
+    CoreDID::parse(format!("did:{}:{}", Self::METHOD, jwu::encode_b64(json))).map(Self)
+  }
+
+  /// Parses a [`DIDJwk`] from the given `input`.
+  ///
+  /// # Errors
+  ///
+  /// Returns `Err` if the input is not a valid did:jwk.
+  pub fn parse(input: impl AsRef<str>) -> Result<Self> {
+    CoreDID::parse(input).and_then(Self::try_from_core)
+  }
+
+  /// Converts a [`CoreDID`] to a [`DIDJwk`].
+  ///
+  /// # Errors
+  ///
+  /// Returns `Err` if the input is not a valid did:jwk.
+  pub fn try_from_core(did: CoreDID) -> Result<Self> {
+    if did.method() != Self::METHOD {
+      return Err(Error::InvalidMethodName);
+    }
+    decode_method_id(did.method_id())?;
+    Ok(Self(did))
+  }
+
+  /// Returns the public [`Jwk`] encoded in the DID.
+  pub fn jwk(&self) -> Jwk {
+    decode_method_id(self.0.method_id()).expect("the method id was validated when constructing the did:jwk")
+  }
+}
+
+/// Decodes the method specific identifier of a did:jwk into the public JWK.
+fn decode_method_id(method_id: &str) -> Result<Jwk> {
+  let json: Vec<u8> = jwu::decode_b64(method_id).map_err(|_| Error::InvalidMethodId)?;
+  let jwk: Jwk = Jwk::from_json_slice(&json).map_err(|_| Error::InvalidMethodId)?;
+  if !jwk.is_public() {
+    return Err(Error::Other("did:jwk requires a public JWK"));
+  }
+  Ok(jwk)
+}
+
+impl FromStr for DIDJwk {
+  type Err = Error;
+
+  fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+    Self::parse(s)
+  }
+}
+
+impl TryFrom<&str> for DIDJwk {
+  type Error = Error;
+
+  fn try_from(other: &str) -> std::result::Result<Self, Self::Error> {
+    Self::parse(other)
+  }
+}
+
+impl TryFrom<String> for DIDJwk {
+  type Error = Error;
+
+  fn try_from(other: String) -> std::result::Result<Self, Self::Error> {
+    Self::parse(other)
+  }
+}
+
+impl Display for DIDJwk {
+  fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+    write!(f, "{}", self.0)
+  }
+}
+
+impl From<DIDJwk> for CoreDID {
+  fn from(did: DIDJwk) -> Self {
+    did.0
+  }
+}
+
+impl From<DIDJwk> for String {
+  fn from(did: DIDJwk) -> Self {
+    did.0.into()
+  }
+}
+
+impl TryFrom<CoreDID> for DIDJwk {
+  type Error = Error;
+
+  fn try_from(value: CoreDID) -> std::result::Result<Self, Self::Error> {
+    Self::try_from_core(value)
+  }
+}
+
+impl TryFrom<BaseDIDUrl> for DIDJwk {
+  type Error = Error;
+
+  fn try_from(other: BaseDIDUrl) -> Result<Self> {
+    CoreDID::try_from(other).and_then(Self::try_from_core)
+  }
+}
+
+impl AsRef<CoreDID> for DIDJwk {
+  fn as_ref(&self) -> &CoreDID {
+    &self.0
+  }
+}
+
+impl KeyComparable for DIDJwk {
+  type Key = CoreDID;
+
+  #[inline]
+  fn key(&self) -> &Self::Key {
+    self.as_ref()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_jose::jwk::JwkParamsOkp;
+
+  use super::*;
+
+  // The P-256 example of the did:jwk specification.
+  const P256_DID: &str = "did:jwk:eyJjcnYiOiJQLTI1NiIsImt0eSI6IkVDIiwieCI6ImFjYklRaXVNczNpOF91c3pFakoydHBUdFJNNEVVM3l6OTFQSDZDZEgyVjAiLCJ5IjoiX0tjeUxqOXZXTXB0bm1LdG00NkdxRHo4d2Y3NEk1TEtncmwyR3pIM25TRSJ9";
+
+  #[test]
+  fn parse_spec_did() {
+    let did: DIDJwk = DIDJwk::parse(P256_DID).unwrap();
+    let jwk: Jwk = did.jwk();
+    let params = jwk.try_ec_params().unwrap();
+    assert_eq!(params.crv, "P-256");
+    assert_eq!(params.x, "acbIQiuMs3i8_uszEjJ2tpTtRM4EU3yz91PH6CdH2V0");
+    assert_eq!(params.y, "_KcyLj9vWMptnmKtm46GqDz8wf74I5LKgrl2GzH3nSE");
+  }
+
+  #[test]
+  fn roundtrip() {
+    let mut params: JwkParamsOkp = JwkParamsOkp::new();
+    params.crv = "Ed25519".to_owned();
+    params.x = "11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo".to_owned();
+    let jwk: Jwk = Jwk::from_params(params);
+
+    let did: DIDJwk = DIDJwk::new(&jwk).unwrap();
+    assert_eq!(did.jwk(), jwk);
+    assert_eq!(DIDJwk::parse(did.as_str()).unwrap(), did);
+  }
+
+  #[test]
+  fn private_jwk() {
+    let mut params: JwkParamsOkp = JwkParamsOkp::new();
+    params.crv = "Ed25519".to_owned();
+    params.x = "11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo".to_owned();
+    params.d = Some("nWGxne_9WmC6hEr0kuwsxERJxWl7MmkZcDusAxyuf2A".to_owned());
+    let jwk: Jwk = Jwk::from_params(params);
+
+    // Only the public part of a private JWK is encoded.
+    let did: DIDJwk = DIDJwk::new(&jwk).unwrap();
+    assert!(did.jwk().is_public());
+    assert_eq!(&did.jwk(), &jwk.to_public().unwrap());
+
+    let json: String = jwk.to_json().unwrap();
+    assert!(DIDJwk::parse(format!("did:jwk:{}", jwu::encode_b64(json))).is_err());
+  }
+
+  #[test]
+  fn parse_invalid() {
+    assert!(matches!(
+      DIDJwk::parse(format!("did:foo:{}", P256_DID.trim_start_matches("did:jwk:"))),
+      Err(Error::InvalidMethodName)
+    ));
+    assert!(DIDJwk::parse("did:jwk:not-a-jwk").is_err());
+    assert!(DIDJwk::parse(format!("did:jwk:{}", jwu::encode_b64("{}"))).is_err());
+  }
+}
@@ -0,0 +1,284 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::str::FromStr;
+
+use identity_core::common::KeyComparable;
+use identity_core::convert::Base;
+use identity_core::convert::BaseEncoding;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::BaseDIDUrl;
+use crate::CoreDID;
+use crate::Error;
+use crate::DID;
+
+/// Alias for a `Result` with the error type [`Error`].
+type Result<T> = std::result::Result<T, Error>;
+
+/// The type of the public key encoded in a [`DIDKey`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum DIDKeyType {
+  /// An Ed25519 public key (multicodec `ed25519-pub`).
+  Ed25519,
+  /// An X25519 public key (multicodec `x25519-pub`).
+  X25519,
+  /// A compressed NIST P-256 public key (multicodec `p256-pub`).
+  P256,
+  /// A compressed secp256k1 public key (multicodec `secp256k1-pub`).
+  Secp256k1,
+}
+
+impl DIDKeyType {
+  const ALL: [Self; 4] = [Self::Ed25519, Self::X25519, Self::P256, Self::Secp256k1];
+
+  /// Returns the unsigned varint encoding of the multicodec code of the key type.
+  const fn multicodec_prefix(self) -> &'static [u8] {
+    match self {
+      Self::Ed25519 => &[0xed, 0x01],
+      Self::X25519 => &[0xec, 0x01],
+      Self::P256 => &[0x80, 0x24],
+      Self::Secp256k1 => &[0xe7, 0x01],
+    }
+  }
+
+  /// Returns the length in bytes of public keys of this type.
+  pub const fn public_key_len(self) -> usize {
+    match self {
+      Self::Ed25519 | Self::X25519 => 32,
+      Self::P256 | Self::Secp256k1 => 33,
+    }
+  }
+
+  /// Encodes a public key of this type as a multikey, i.e. the multibase (base58-btc) encoding of the multicodec
+  /// prefixed public key.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the length of `public_key` does not match the key type.
+  pub fn encode_multikey(self, public_key: &[u8]) -> Result<String> {
+    if public_key.len() != self.public_key_len() {
+      return Err(Error::Other("invalid public key length"));
+    }
+    let mut bytes: Vec<u8> = self.multicodec_prefix().to_vec();
+    bytes.extend_from_slice(public_key);
+    Ok(BaseEncoding::encode_multibase(&bytes, Some(Base::Base58Btc)))
+  }
+
+  /// Decodes a multikey into the type of the key and the public key.
+  ///
+  /// # Errors
+  ///
+  /// Fails if `multikey` is not base58-btc encoded, or does not encode a public key of a supported type.
+  pub fn decode_multikey(multikey: &str) -> Result<(Self, Vec<u8>)> {
+    // Only the base58-btc multibase encoding is allowed.
+    if !multikey.starts_with('z') {
+      return Err(Error::Other("multikey is not base58-btc encoded"));
+    }
+    let mut bytes: Vec<u8> =
+      BaseEncoding::decode_multibase(multikey).map_err(|_| Error::Other("invalid multibase encoding"))?;
+
+    let key_type: Self = Self::ALL
+      .into_iter()
+      .find(|key_type| bytes.starts_with(key_type.multicodec_prefix()))
+      .ok_or(Error::Other("unsupported multicodec"))?;
+    let public_key: Vec<u8> = bytes.split_off(key_type.multicodec_prefix().len());
+    if public_key.len() != key_type.public_key_len() {
+      return Err(Error::Other("invalid public key length"));
+    }
+
+    Ok((key_type, public_key))
+  }
+}
+
+/// A DID conforming to the [did:key method specification](https://w3c-ccg.github.io/did-method-key/).
+///
+/// The method specific identifier of a did:key is the multibase (base58-btc) encoding of a multicodec prefixed public
+/// key, from which the DID document can be derived without any network access.
+#[derive(Clone, Hash, Debug, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(into = "CoreDID", try_from = "CoreDID")]
+pub struct DIDKey(CoreDID);
+
+impl DIDKey {
+  /// The did:key method name (`"key"`).
+  pub const METHOD: &'static str = "key";
+
+  /// Creates a new [`DIDKey`] encoding the given public key of type `key_type`.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the length of `public_key` does not match `key_type`.
+  pub fn new(key_type: DIDKeyType, public_key: &[u8]) -> Result<Self> {
+    let method_id: String = key_type.encode_multikey(public_key)?;
+    CoreDID::parse(format!("did:{}:{method_id}", Self::METHOD)).map(Self)
+  }
+
+  /// Parses a [`DIDKey`] from the given `input`.
+  ///
+  /// # Errors
+  ///
+  /// Returns `Err` if the input is not a valid did:key.
+  pub fn parse(input: impl AsRef<str>) -> Result<Self> {
+    CoreDID::parse(input).and_then(Self::try_from_core)
+  }
+
+  /// Converts a [`CoreDID`] to a [`DIDKey`].
+  ///
+  /// # Errors
+  ///
+  /// Returns `Err` if the input is not a valid did:key.
+  pub fn try_from_core(did: CoreDID) -> Result<Self> {
+    if did.method() != Self::METHOD {
+      return Err(Error::InvalidMethodName);
+    }
+    decode_method_id(did.method_id())?;
+    Ok(Self(did))
+  }
+
+  /// Returns the type of the public key encoded in the DID.
+  pub fn key_type(&self) -> DIDKeyType {
+    self.decode().0
+  }
+
+  /// Returns the public key encoded in the DID.
+  pub fn public_key(&self) -> Vec<u8> {
+    self.decode().1
+  }
+
+  fn decode(&self) -> (DIDKeyType, Vec<u8>) {
+    decode_method_id(self.0.method_id()).expect("the method id was validated when constructing the did:key")
+  }
+}
+
+/// Decodes the method specific identifier of a did:key into the type of the key and the public key.
+fn decode_method_id(method_id: &str) -> Result<(DIDKeyType, Vec<u8>)> {
+  DIDKeyType::decode_multikey(method_id).map_err(|_| Error::InvalidMethodId)
+}
+
+impl FromStr for DIDKey {
+  type Err = Error;
+
+  fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+    Self::parse(s)
+  }
+}
+
+impl TryFrom<&str> for DIDKey {
+  type Error = Error;
+
+  fn try_from(other: &str) -> std::result::Result<Self, Self::Error> {
+    Self::parse(other)
+  }
+}
+
+impl TryFrom<String> for DIDKey {
+  type Error = Error;
+
+  fn try_from(other: String) -> std::result::Result<Self, Self::Error> {
+    Self::parse(other)
+  }
+}
+
+impl Display for DIDKey {
+  fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+    write!(f, "{}", self.0)
+  }
+}
+
+impl From<DIDKey> for CoreDID {
+  fn from(did: DIDKey) -> Self {
+    did.0
+  }
+}
+
+impl From<DIDKey> for String {
+  fn from(did: DIDKey) -> Self {
+    did.0.into()
+  }
+}
+
+impl TryFrom<CoreDID> for DIDKey {
+  type Error = Error;
+
+  fn try_from(value: CoreDID) -> std::result::Result<Self, Self::Error> {
+    Self::try_from_core(value)
+  }
+}
+
+impl TryFrom<BaseDIDUrl> for DIDKey {
+  type Error = Error;
+
+  fn try_from(other: BaseDIDUrl) -> Result<Self> {
+    CoreDID::try_from(other).and_then(Self::try_from_core)
+  }
+}
+
+impl AsRef<CoreDID> for DIDKey {
+  fn as_ref(&self) -> &CoreDID {
+    &self.0
+  }
+}
+
+impl KeyComparable for DIDKey {
+  type Key = CoreDID;
+
+  #[inline]
+  fn key(&self) -> &Self::Key {
+    self.as_ref()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parse_spec_dids() {
+    for (did, key_type) in [
+      (
+        "did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK",
+        DIDKeyType::Ed25519,
+      ),
+      (
+        "did:key:z6LSeu9HkTHSfLLeUs2nnzUSNedgDUevfNQgQjQC23ZCit6F",
+        DIDKeyType::X25519,
+      ),
+      (
+        "did:key:zDnaerDaTF5BXEavCrfRZEk316dpbLsfPDZ3WJ5hRTPFU2169",
+        DIDKeyType::P256,
+      ),
+      (
+        "did:key:zQ3shokFTS3brHcDQrn82RUDfCZESWL1ZdCEJwekUDPQiYBme",
+        DIDKeyType::Secp256k1,
+      ),
+    ] {
+      let did_key: DIDKey = DIDKey::parse(did).unwrap();
+      assert_eq!(did_key.key_type(), key_type);
+      assert_eq!(did_key.public_key().len(), key_type.public_key_len());
+      assert_eq!(DIDKey::new(key_type, &did_key.public_key()).unwrap(), did_key);
+      assert_eq!(did_key.as_str(), did);
+    }
+  }
+
+  #[test]
+  fn parse_invalid() {
+    // Wrong method.
+    assert!(matches!(
+      DIDKey::parse("did:foo:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK"),
+      Err(Error::InvalidMethodName)
+    ));
+    // Not base58-btc.
+    assert!(DIDKey::parse("did:key:f6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK").is_err());
+    // Truncated key.
+    assert!(DIDKey::parse("did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2do").is_err());
+    // Unsupported multicodec.
+    let method_id: String = BaseEncoding::encode_multibase(&[0x00; 34], Some(Base::Base58Btc));
+    assert!(DIDKey::parse(format!("did:key:{method_id}")).is_err());
+    // Invalid key length.
+    assert!(DIDKey::new(DIDKeyType::Ed25519, &[0; 33]).is_err());
+  }
+}
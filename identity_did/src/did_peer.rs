@@ -0,0 +1,455 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::str::FromStr;
+
+use identity_core::common::KeyComparable;
+use identity_core::common::Object;
+use identity_core::common::Value;
+use identity_core::convert::FromJson;
+use identity_core::convert::ToJson;
+use identity_jose::jwu;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::BaseDIDUrl;
+use crate::CoreDID;
+use crate::DIDKeyType;
+use crate::Error;
+use crate::DID;
+
+/// Alias for a `Result` with the error type [`Error`].
+type Result<T> = std::result::Result<T, Error>;
+
+/// Abbreviations of service properties and values in numalgo 2 did:peer DIDs.
+const SERVICE_ABBREVIATIONS: [(&str, &str); 4] = [
+  ("type", "t"),
+  ("serviceEndpoint", "s"),
+  ("routingKeys", "r"),
+  ("accept", "a"),
+];
+const DIDCOMM_MESSAGING: (&str, &str) = ("DIDCommMessaging", "dm");
+
+/// The purpose of a key encoded in a numalgo 2 [`DIDPeer`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum DIDPeerPurpose {
+  /// An assertion method key (prefix `A`).
+  Assertion,
+  /// A key agreement key (prefix `E`).
+  Encryption,
+  /// An authentication key (prefix `V`).
+  Verification,
+  /// A capability invocation key (prefix `I`).
+  CapabilityInvocation,
+  /// A capability delegation key (prefix `D`).
+  CapabilityDelegation,
+}
+
+impl DIDPeerPurpose {
+  const ALL: [Self; 5] = [
+    Self::Assertion,
+    Self::Encryption,
+    Self::Verification,
+    Self::CapabilityInvocation,
+    Self::CapabilityDelegation,
+  ];
+  const SERVICE_PREFIX: char = 'S';
+
+  /// Returns the prefix of elements with this purpose.
+  pub const fn prefix(self) -> char {
+    match self {
+      Self::Assertion => 'A',
+      Self::Encryption => 'E',
+      Self::Verification => 'V',
+      Self::CapabilityInvocation => 'I',
+      Self::CapabilityDelegation => 'D',
+    }
+  }
+}
+
+/// An element of the method specific identifier of a numalgo 2 [`DIDPeer`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DIDPeerElement {
+  /// A public key with its purpose.
+  Key {
+    /// The purpose of the key.
+    purpose: DIDPeerPurpose,
+    /// The type of the key.
+    key_type: DIDKeyType,
+    /// The public key.
+    public_key: Vec<u8>,
+  },
+  /// A service, with the abbreviations of the did:peer specification expanded.
+  Service(Object),
+}
+
+/// A DID conforming to the [did:peer method specification](https://identity.foundation/peer-did-method-spec/).
+///
+/// Only the statically resolvable numalgos are supported:
+/// - numalgo 0, encoding a single inception key, e.g. `did:peer:0z6Mk...`.
+/// - numalgo 2, encoding multiple keys and services, e.g. `did:peer:2.Ez6LS....Vz6Mk....SeyJ0Ij...`.
+#[derive(Clone, Hash, Debug, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(into = "CoreDID", try_from = "CoreDID")]
+pub struct DIDPeer(CoreDID);
+
+impl DIDPeer {
+  /// The did:peer method name (`"peer"`).
+  pub const METHOD: &'static str = "peer";
+
+  /// Creates a new numalgo 0 [`DIDPeer`] encoding the given public key of type `key_type`.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the length of `public_key` does not match `key_type`.
+  pub fn new_numalgo_0(key_type: DIDKeyType, public_key: &[u8]) -> Result<Self> {
+    let multikey: String = key_type.encode_multikey(public_key)?;
+    CoreDID::parse(format!("did:{}:0{multikey}", Self::METHOD)).map(Self)
+  }
+
+  /// Creates a new numalgo 2 [`DIDPeer`] encoding the given `elements`, in order.
+  ///
+  /// Services are encoded using the abbreviations of the did:peer specification.
+  ///
+  /// # Errors
+  ///
+  /// Fails if `elements` is empty or if a public key does not match its key type.
+  pub fn new_numalgo_2(elements: &[DIDPeerElement]) -> Result<Self> {
+    if elements.is_empty() {
+      return Err(Error::Other("numalgo 2 did:peer requires at least one element"));
+    }
+    let mut method_id: String = "2".to_owned();
+    for element in elements {
+      method_id.push('.');
+      match element {
+        DIDPeerElement::Key {
+          purpose,
+          key_type,
+          public_key,
+        } => {
+          method_id.push(purpose.prefix());
+          method_id.push_str(&key_type.encode_multikey(public_key)?);
+        }
+        DIDPeerElement::Service(service) => {
+          let json: Vec<u8> = abbreviate_service(service)
+            .to_json_vec()
+            .map_err(|_| Error::Other("unable to serialize did:peer service"))?;
+          method_id.push(DIDPeerPurpose::SERVICE_PREFIX);
+          method_id.push_str(&jwu::encode_b64(json));
+        }
+      }
+    }
+    CoreDID::parse(format!("did:{}:{method_id}", Self::METHOD)).map(Self)
+  }
+
+  /// Parses a [`DIDPeer`] from the given `input`.
+  ///
+  /// # Errors
+  ///
+  /// Returns `Err` if the input is not a valid numalgo 0 or numalgo 2 did:peer.
+  pub fn parse(input: impl AsRef<str>) -> Result<Self> {
+    CoreDID::parse(input).and_then(Self::try_from_core)
+  }
+
+  /// Converts a [`CoreDID`] to a [`DIDPeer`].
+  ///
+  /// # Errors
+  ///
+  /// Returns `Err` if the input is not a valid numalgo 0 or numalgo 2 did:peer.
+  pub fn try_from_core(did: CoreDID) -> Result<Self> {
+    if did.method() != Self::METHOD {
+      return Err(Error::InvalidMethodName);
+    }
+    decode_method_id(did.method_id())?;
+    Ok(Self(did))
+  }
+
+  /// Returns the numalgo of the DID, either `0` or `2`.
+  pub fn numalgo(&self) -> u8 {
+    match self.0.method_id().as_bytes().first() {
+      Some(b'0') => 0,
+      _ => 2,
+    }
+  }
+
+  /// Returns the type and public key of the inception key of a numalgo 0 DID, or `None` for numalgo 2.
+  pub fn inception_key(&self) -> Option<(DIDKeyType, Vec<u8>)> {
+    match self.decode() {
+      DecodedMethodId::Numalgo0(key_type, public_key) => Some((key_type, public_key)),
+      DecodedMethodId::Numalgo2(_) => None,
+    }
+  }
+
+  /// Returns the elements encoded in a numalgo 2 DID, in order. For numalgo 0 the result is empty.
+  pub fn elements(&self) -> Vec<DIDPeerElement> {
+    match self.decode() {
+      DecodedMethodId::Numalgo0(..) => Vec::new(),
+      DecodedMethodId::Numalgo2(elements) => elements,
+    }
+  }
+
+  fn decode(&self) -> DecodedMethodId {
+    decode_method_id(self.0.method_id()).expect("the method id was validated when constructing the did:peer")
+  }
+}
+
+enum DecodedMethodId {
+  Numalgo0(DIDKeyType, Vec<u8>),
+  Numalgo2(Vec<DIDPeerElement>),
+}
+
+/// Decodes the method specific identifier of a numalgo 0 or numalgo 2 did:peer.
+fn decode_method_id(method_id: &str) -> Result<DecodedMethodId> {
+  if let Some(multikey) = method_id.strip_prefix('0') {
+    let (key_type, public_key) = DIDKeyType::decode_multikey(multikey).map_err(|_| Error::InvalidMethodId)?;
+    return Ok(DecodedMethodId::Numalgo0(key_type, public_key));
+  }
+  let encoded_elements: &str = method_id
+    .strip_prefix("2.")
+    .ok_or(Error::Other("unsupported did:peer numalgo"))?;
+
+  let mut elements: Vec<DIDPeerElement> = Vec::new();
+  for element in encoded_elements.split('.') {
+    let mut chars = element.chars();
+    let prefix: char = chars.next().ok_or(Error::InvalidMethodId)?;
+    let value: &str = chars.as_str();
+    if prefix == DIDPeerPurpose::SERVICE_PREFIX {
+      let json: Vec<u8> = jwu::decode_b64(value).map_err(|_| Error::InvalidMethodId)?;
+      let service: Object = Object::from_json_slice(&json).map_err(|_| Error::InvalidMethodId)?;
+      elements.push(DIDPeerElement::Service(expand_service(service)));
+    } else {
+      let purpose: DIDPeerPurpose = DIDPeerPurpose::ALL
+        .into_iter()
+        .find(|purpose| purpose.prefix() == prefix)
+        .ok_or(Error::InvalidMethodId)?;
+      let (key_type, public_key) = DIDKeyType::decode_multikey(value).map_err(|_| Error::InvalidMethodId)?;
+      elements.push(DIDPeerElement::Key {
+        purpose,
+        key_type,
+        public_key,
+      });
+    }
+  }
+  Ok(DecodedMethodId::Numalgo2(elements))
+}
+
+/// Replaces the properties and values of a service with their abbreviations.
+fn abbreviate_service(service: &Object) -> Value {
+  let service: Value = Value::Object(service.clone().into_iter().collect());
+  rename_service_value(service, true)
+}
+
+/// Replaces the abbreviated properties and values of a service with their full form.
+fn expand_service(service: Object) -> Object {
+  match rename_service_value(Value::Object(service.into_iter().collect()), false) {
+    Value::Object(service) => service.into_iter().collect(),
+    _ => unreachable!("renaming preserves the type of the value"),
+  }
+}
+
+/// Abbreviates, or expands, the properties of all objects nested in `value`.
+fn rename_service_value(value: Value, abbreviate: bool) -> Value {
+  match value {
+    Value::Object(object) => Value::Object(
+      object
+        .into_iter()
+        .map(|(key, value)| {
+          let key: String = rename_service_property(key, abbreviate);
+          let value: Value = rename_type(&key, rename_service_value(value, abbreviate), abbreviate);
+          (key, value)
+        })
+        .collect(),
+    ),
+    Value::Array(values) => Value::Array(
+      values
+        .into_iter()
+        .map(|value| rename_service_value(value, abbreviate))
+        .collect(),
+    ),
+    value => value,
+  }
+}
+
+fn rename_service_property(key: String, abbreviate: bool) -> String {
+  SERVICE_ABBREVIATIONS
+    .iter()
+    .map(|&(property, abbreviation)| {
+      if abbreviate {
+        (property, abbreviation)
+      } else {
+        (abbreviation, property)
+      }
+    })
+    .find(|(from, _)| *from == key)
+    .map(|(_, to)| to.to_owned())
+    .unwrap_or(key)
+}
+
+/// Abbreviates, or expands, the `DIDCommMessaging` service type.
+fn rename_type(key: &str, value: Value, abbreviate: bool) -> Value {
+  let (type_key, from, to) = if abbreviate {
+    ("t", DIDCOMM_MESSAGING.0, DIDCOMM_MESSAGING.1)
+  } else {
+    ("type", DIDCOMM_MESSAGING.1, DIDCOMM_MESSAGING.0)
+  };
+  match value {
+    Value::String(type_) if key == type_key && type_ == from => Value::from(to),
+    value => value,
+  }
+}
+
+impl FromStr for DIDPeer {
+  type Err = Error;
+
+  fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+    Self::parse(s)
+  }
+}
+
+impl TryFrom<&str> for DIDPeer {
+  type Error = Error;
+
+  fn try_from(other: &str) -> std::result::Result<Self, Self::Error> {
+    Self::parse(other)
+  }
+}
+
+impl TryFrom<String> for DIDPeer {
+  type Error = Error;
+
+  fn try_from(other: String) -> std::result::Result<Self, Self::Error> {
+    Self::parse(other)
+  }
+}
+
+impl Display for DIDPeer {
+  fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+    write!(f, "{}", self.0)
+  }
+}
+
+impl From<DIDPeer> for CoreDID {
+  fn from(did: DIDPeer) -> Self {
+    did.0
+  }
+}
+
+impl From<DIDPeer> for String {
+  fn from(did: DIDPeer) -> Self {
+    did.0.into()
+  }
+}
+
+impl TryFrom<CoreDID> for DIDPeer {
+  type Error = Error;
+
+  fn try_from(value: CoreDID) -> std::result::Result<Self, Self::Error> {
+    Self::try_from_core(value)
+  }
+}
+
+impl TryFrom<BaseDIDUrl> for DIDPeer {
+  type Error = Error;
+
+  fn try_from(other: BaseDIDUrl) -> Result<Self> {
+    CoreDID::try_from(other).and_then(Self::try_from_core)
+  }
+}
+
+impl AsRef<CoreDID> for DIDPeer {
+  fn as_ref(&self) -> &CoreDID {
+    &self.0
+  }
+}
+
+impl KeyComparable for DIDPeer {
+  type Key = CoreDID;
+
+  #[inline]
+  fn key(&self) -> &Self::Key {
+    self.as_ref()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_core::json;
+
+  use super::*;
+
+  // The numalgo 2 example of the did:peer specification.
+  const NUMALGO_2_DID: &str = "did:peer:2.Vz6Mkj3PUd1WjvaDhNZhhhXQdz5UnZXmS7ehtx8bsPpD47kKc.Ez6LSg8zQom395jKLrGiBNruB9MM6V8PWuf2FpEy4uRFiqQBR.SeyJ0IjoiZG0iLCJzIjp7InVyaSI6Imh0dHA6Ly9leGFtcGxlLmNvbS9kaWRjb21tIiwiYSI6WyJkaWRjb21tL3YyIl0sInIiOlsiZGlkOmV4YW1wbGU6MTIzNDU2Nzg5YWJjZGVmZ2hpI2tleS0xIl19fQ.SeyJ0IjoiZG0iLCJzIjp7InVyaSI6Imh0dHA6Ly9leGFtcGxlLmNvbS9hbm90aGVyIiwiYSI6WyJkaWRjb21tL3YyIl0sInIiOlsiZGlkOmV4YW1wbGU6MTIzNDU2Nzg5YWJjZGVmZ2hpI2tleS0yIl19fQ";
+
+  #[test]
+  fn parse_numalgo_0() {
+    let did: DIDPeer = DIDPeer::parse("did:peer:0z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH").unwrap();
+    assert_eq!(did.numalgo(), 0);
+    let (key_type, public_key) = did.inception_key().unwrap();
+    assert_eq!(key_type, DIDKeyType::Ed25519);
+    assert_eq!(DIDPeer::new_numalgo_0(key_type, &public_key).unwrap(), did);
+    assert!(did.elements().is_empty());
+  }
+
+  #[test]
+  fn parse_numalgo_2() {
+    let did: DIDPeer = DIDPeer::parse(NUMALGO_2_DID).unwrap();
+    assert_eq!(did.numalgo(), 2);
+    assert!(did.inception_key().is_none());
+
+    let elements: Vec<DIDPeerElement> = did.elements();
+    assert_eq!(elements.len(), 4);
+    assert!(matches!(
+      elements[0],
+      DIDPeerElement::Key {
+        purpose: DIDPeerPurpose::Verification,
+        key_type: DIDKeyType::Ed25519,
+        ..
+      }
+    ));
+    assert!(matches!(
+      elements[1],
+      DIDPeerElement::Key {
+        purpose: DIDPeerPurpose::Encryption,
+        key_type: DIDKeyType::X25519,
+        ..
+      }
+    ));
+    let expected = json!({
+      "type": "DIDCommMessaging",
+      "serviceEndpoint": {
+        "uri": "http://example.com/didcomm",
+        "accept": ["didcomm/v2"],
+        "routingKeys": ["did:example:123456789abcdefghi#key-1"]
+      }
+    });
+    assert_eq!(
+      elements[2],
+      DIDPeerElement::Service(Object::from_json_value(expected).unwrap())
+    );
+
+    // The properties of the encoded services may be ordered differently, which results in a different DID.
+    let encoded: DIDPeer = DIDPeer::new_numalgo_2(&elements).unwrap();
+    assert_eq!(encoded.elements(), elements);
+  }
+
+  #[test]
+  fn parse_invalid() {
+    assert!(matches!(
+      DIDPeer::parse("did:foo:0z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH"),
+      Err(Error::InvalidMethodName)
+    ));
+    // Unsupported numalgos.
+    assert!(DIDPeer::parse("did:peer:1zQmZMygzYqNwU6Uhmewx5Xepf2VLp5S4HLSwwgf2aiKZuwa").is_err());
+    assert!(DIDPeer::parse("did:peer:3zQmS19jtYDvGtKVrJhQnRFpBQAx3pJ9omx2HpNrcXFuRCz9").is_err());
+    // Unknown purpose.
+    assert!(DIDPeer::parse("did:peer:2.Xz6Mkj3PUd1WjvaDhNZhhhXQdz5UnZXmS7ehtx8bsPpD47kKc").is_err());
+    // Invalid key and service.
+    assert!(DIDPeer::parse("did:peer:2.Vz6Mkj3PUd1WjvaDhNZhhhXQdz5UnZXmS7ehtx8bsPpD47k").is_err());
+    assert!(DIDPeer::parse("did:peer:2.SeyJ0IjoiZG0i").is_err());
+    assert!(DIDPeer::parse("did:peer:2").is_err());
+    assert!(DIDPeer::new_numalgo_2(&[]).is_err());
+  }
+}
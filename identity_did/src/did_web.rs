@@ -0,0 +1,247 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::str::FromStr;
+
+use identity_core::common::KeyComparable;
+use identity_core::common::Url;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::BaseDIDUrl;
+use crate::CoreDID;
+use crate::Error;
+use crate::DID;
+
+/// Alias for a `Result` with the error type [`Error`].
+type Result<T> = std::result::Result<T, Error>;
+
+/// A DID conforming to the [did:web method specification](https://w3c-ccg.github.io/did-method-web/).
+///
+/// The method specific identifier of a did:web is a domain name, optionally followed by a percent-encoded port
+/// (e.g. `did:web:localhost%3A8443`), and an optional `:` separated path. The DID document is hosted at the HTTPS URL
+/// derived from it, see [`DIDWeb::url`].
+#[derive(Clone, Hash, Debug, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(into = "CoreDID", try_from = "CoreDID")]
+pub struct DIDWeb(CoreDID);
+
+impl DIDWeb {
+  /// The did:web method name (`"web"`).
+  pub const METHOD: &'static str = "web";
+
+  /// Parses a [`DIDWeb`] from the given `input`.
+  ///
+  /// # Errors
+  ///
+  /// Returns `Err` if the input is not a valid did:web.
+  pub fn parse(input: impl AsRef<str>) -> Result<Self> {
+    CoreDID::parse(input).and_then(Self::try_from_core)
+  }
+
+  /// Converts a [`CoreDID`] to a [`DIDWeb`].
+  ///
+  /// # Errors
+  ///
+  /// Returns `Err` if the input is not a valid did:web.
+  pub fn try_from_core(did: CoreDID) -> Result<Self> {
+    if did.method() != Self::METHOD {
+      return Err(Error::InvalidMethodName);
+    }
+    document_url(did.method_id())?;
+    Ok(Self(did))
+  }
+
+  /// Returns the domain name of the DID, including the percent-encoded port, if any.
+  pub fn domain(&self) -> &str {
+    self.0.method_id().split(':').next().unwrap_or_default()
+  }
+
+  /// Returns the HTTPS URL at which the DID document is hosted.
+  ///
+  /// This is `https://<domain>/.well-known/did.json` if the DID has no path, and `https://<domain>/<path>/did.json`
+  /// otherwise. A percent-encoded port is decoded, e.g. `did:web:localhost%3A8443` is hosted at
+  /// `https://localhost:8443/.well-known/did.json`.
+  pub fn url(&self) -> Url {
+    document_url(self.0.method_id()).expect("the method id was validated when constructing the did:web")
+  }
+}
+
+/// Transforms the method specific identifier of a did:web into the URL of its DID document.
+fn document_url(method_id: &str) -> Result<Url> {
+  let mut segments = method_id.split(':');
+  let domain: String = segments
+    .next()
+    .unwrap_or_default()
+    .replace("%3A", ":")
+    .replace("%3a", ":");
+  let path: Vec<&str> = segments.collect();
+  if domain.is_empty()
+    || domain.contains('%')
+    || path
+      .iter()
+      .any(|segment| segment.is_empty() || *segment == "." || *segment == "..")
+  {
+    return Err(Error::InvalidMethodId);
+  }
+
+  let path: String = if path.is_empty() {
+    ".well-known".to_owned()
+  } else {
+    path.join("/")
+  };
+  let url: Url = Url::parse(format!("https://{domain}/{path}/did.json")).map_err(|_| Error::InvalidMethodId)?;
+  let valid_port: bool = domain
+    .split_once(':')
+    .map(|(_, port)| !port.is_empty() && port.bytes().all(|byte| byte.is_ascii_digit()))
+    .unwrap_or(true);
+  if url.host_str().map(str::is_empty).unwrap_or(true) || !valid_port {
+    return Err(Error::InvalidMethodId);
+  }
+  Ok(url)
+}
+
+impl FromStr for DIDWeb {
+  type Err = Error;
+
+  fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+    Self::parse(s)
+  }
+}
+
+impl TryFrom<&str> for DIDWeb {
+  type Error = Error;
+
+  fn try_from(other: &str) -> std::result::Result<Self, Self::Error> {
+    Self::parse(other)
+  }
+}
+
+impl TryFrom<String> for DIDWeb {
+  type Error = Error;
+
+  fn try_from(other: String) -> std::result::Result<Self, Self::Error> {
+    Self::parse(other)
+  }
+}
+
+impl Display for DIDWeb {
+  fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+    write!(f, "{}", self.0)
+  }
+}
+
+impl From<DIDWeb> for CoreDID {
+  fn from(did: DIDWeb) -> Self {
+    did.0
+  }
+}
+
+impl From<DIDWeb> for String {
+  fn from(did: DIDWeb) -> Self {
+    did.0.into()
+  }
+}
+
+impl TryFrom<CoreDID> for DIDWeb {
+  type Error = Error;
+
+  fn try_from(value: CoreDID) -> std::result::Result<Self, Self::Error> {
+    Self::try_from_core(value)
+  }
+}
+
+impl TryFrom<BaseDIDUrl> for DIDWeb {
+  type Error = Error;
+
+  fn try_from(other: BaseDIDUrl) -> Result<Self> {
+    CoreDID::try_from(other).and_then(Self::try_from_core)
+  }
+}
+
+impl AsRef<CoreDID> for DIDWeb {
+  fn as_ref(&self) -> &CoreDID {
+    &self.0
+  }
+}
+
+impl KeyComparable for DIDWeb {
+  type Key = CoreDID;
+
+  #[inline]
+  fn key(&self) -> &Self::Key {
+    self.as_ref()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parse_spec_dids() {
+    for (did, url) in [
+      (
+        "did:web:w3c-ccg.github.io",
+        "https://w3c-ccg.github.io/.well-known/did.json",
+      ),
+      (
+        "did:web:w3c-ccg.github.io:user:alice",
+        "https://w3c-ccg.github.io/user/alice/did.json",
+      ),
+    ] {
+      let did_web: DIDWeb = DIDWeb::parse(did).unwrap();
+      assert_eq!(did_web.url().as_str(), url);
+      assert_eq!(did_web.domain(), "w3c-ccg.github.io");
+      assert_eq!(did_web.as_str(), did);
+    }
+  }
+
+  #[test]
+  fn parse_port() {
+    for (did, url) in [
+      (
+        "did:web:localhost%3A8443",
+        "https://localhost:8443/.well-known/did.json",
+      ),
+      (
+        "did:web:localhost%3a8443:user:alice",
+        "https://localhost:8443/user/alice/did.json",
+      ),
+    ] {
+      let did_web: DIDWeb = DIDWeb::parse(did).unwrap();
+      assert_eq!(did_web.url().as_str(), url);
+      assert_eq!(did_web.as_str(), did);
+      assert_eq!(DIDWeb::parse(did_web.to_string()).unwrap(), did_web);
+
+      // DID URLs relative to the DID keep the encoded port.
+      let did_url: crate::DIDUrl = did_web.clone().into_url().join("#key-1").unwrap();
+      assert_eq!(did_url.to_string(), format!("{did}#key-1"));
+      assert_eq!(crate::DIDUrl::parse(did_url.to_string()).unwrap(), did_url);
+    }
+    assert_eq!(
+      DIDWeb::parse("did:web:localhost%3A8443").unwrap().domain(),
+      "localhost%3A8443"
+    );
+
+    // The default HTTPS port is omitted from the URL.
+    assert_eq!(
+      DIDWeb::parse("did:web:example.com%3A443").unwrap().url().as_str(),
+      "https://example.com/.well-known/did.json"
+    );
+  }
+
+  #[test]
+  fn parse_invalid() {
+    assert!(matches!(
+      DIDWeb::parse("did:foo:w3c-ccg.github.io"),
+      Err(Error::InvalidMethodName)
+    ));
+    assert!(DIDWeb::parse("did:web:example.com::alice").is_err());
+    assert!(DIDWeb::parse("did:web:example.com:..:alice").is_err());
+    assert!(DIDWeb::parse("did:web:localhost%3Aport").is_err());
+    assert!(DIDWeb::parse("did:web:localhost%3A").is_err());
+    assert!(DIDWeb::parse("did:web:localhost%2F8443").is_err());
+  }
+}
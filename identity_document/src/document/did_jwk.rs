@@ -0,0 +1,114 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Object;
+use identity_did::DIDJwk;
+use identity_did::DIDUrl;
+use identity_verification::jose::jwk::Jwk;
+use identity_verification::jose::jwk::JwkUse;
+use identity_verification::VerificationMethod;
+
+use crate::document::CoreDocument;
+use crate::document::DocumentBuilder;
+use crate::error::Error;
+use crate::error::Result;
+
+impl CoreDocument {
+  /// The fragment of the verification method of a document expanded from a [`DIDJwk`].
+  pub const DID_JWK_FRAGMENT: &'static str = "0";
+
+  /// Expands a [`DIDJwk`] into the DID document it represents, following the
+  /// [did:jwk specification](https://github.com/quartzjer/did-jwk/blob/main/spec.md#to-create-the-did-url).
+  ///
+  /// The document contains a single verification method with the fragment [`Self::DID_JWK_FRAGMENT`], which is
+  /// referenced by the verification relationships allowed by the `use` parameter of the JWK:
+  /// - `sig`: `authentication`, `assertionMethod`, `capabilityInvocation` and `capabilityDelegation`.
+  /// - `enc`: `keyAgreement`.
+  /// - unset: all of the above.
+  pub fn expand_did_jwk(did: DIDJwk) -> Result<Self> {
+    let jwk: Jwk = did.jwk();
+    let key_use: Option<JwkUse> = jwk.use_();
+    let method: VerificationMethod =
+      VerificationMethod::new_from_jwk(did.clone(), jwk, Some(Self::DID_JWK_FRAGMENT))
+        .map_err(|_| Error::InvalidDocument("invalid did:jwk verification method", None))?;
+    let method_id: DIDUrl = method.id().clone();
+
+    let mut builder: DocumentBuilder = CoreDocument::builder(Object::new())
+      .id(did.into())
+      .verification_method(method);
+    if key_use != Some(JwkUse::Encryption) {
+      builder = builder
+        .authentication(method_id.clone())
+        .assertion_method(method_id.clone())
+        .capability_invocation(method_id.clone())
+        .capability_delegation(method_id.clone());
+    }
+    if key_use != Some(JwkUse::Signature) {
+      builder = builder.key_agreement(method_id);
+    }
+
+    builder.build()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_core::convert::FromJson;
+  use identity_core::convert::ToJson;
+  use identity_core::json;
+
+  use super::*;
+
+  #[test]
+  fn expand_spec_example() {
+    // The P-256 example of the did:jwk specification.
+    let did: DIDJwk = DIDJwk::parse("did:jwk:eyJjcnYiOiJQLTI1NiIsImt0eSI6IkVDIiwieCI6ImFjYklRaXVNczNpOF91c3pFakoydHBUdFJNNEVVM3l6OTFQSDZDZEgyVjAiLCJ5IjoiX0tjeUxqOXZXTXB0bm1LdG00NkdxRHo4d2Y3NEk1TEtncmwyR3pIM25TRSJ9").unwrap();
+    let document: CoreDocument = CoreDocument::expand_did_jwk(did.clone()).unwrap();
+
+    let method: String = format!("{did}#0");
+    let expected = json!({
+      "id": did,
+      "verificationMethod": [{
+        "id": method,
+        "controller": did,
+        "type": "JsonWebKey",
+        "publicKeyJwk": {
+          "crv": "P-256",
+          "kty": "EC",
+          "x": "acbIQiuMs3i8_uszEjJ2tpTtRM4EU3yz91PH6CdH2V0",
+          "y": "_KcyLj9vWMptnmKtm46GqDz8wf74I5LKgrl2GzH3nSE"
+        }
+      }],
+      "authentication": [method],
+      "assertionMethod": [method],
+      "keyAgreement": [method],
+      "capabilityDelegation": [method],
+      "capabilityInvocation": [method]
+    });
+    assert_eq!(document.to_json_value().unwrap(), expected);
+    assert_eq!(CoreDocument::from_json_value(expected).unwrap(), document);
+  }
+
+  #[test]
+  fn expand_with_key_use() {
+    // The X25519 example of the did:jwk specification, whose key is restricted to encryption.
+    let did: DIDJwk = DIDJwk::parse("did:jwk:eyJrdHkiOiJPS1AiLCJjcnYiOiJYMjU1MTkiLCJ1c2UiOiJlbmMiLCJ4IjoiM3A3YmZYdDl3YlRUVzJIQzdPUTFOei1EUThoYmVHZE5yZngtRkctSUswOCJ9").unwrap();
+    let document: CoreDocument = CoreDocument::expand_did_jwk(did).unwrap();
+    assert_eq!(document.key_agreement().len(), 1);
+    assert!(document.authentication().is_empty());
+    assert!(document.assertion_method().is_empty());
+    assert!(document.capability_invocation().is_empty());
+    assert!(document.capability_delegation().is_empty());
+
+    let mut jwk: Jwk = document.verification_method()[0]
+      .data()
+      .public_key_jwk()
+      .unwrap()
+      .clone();
+    jwk.set_use(JwkUse::Signature);
+    let document: CoreDocument = CoreDocument::expand_did_jwk(DIDJwk::new(&jwk).unwrap()).unwrap();
+    assert!(document.key_agreement().is_empty());
+    assert_eq!(document.authentication().len(), 1);
+    assert_eq!(document.assertion_method().len(), 1);
+  }
+}
@@ -0,0 +1,231 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Object;
+use identity_did::DIDKey;
+use identity_did::DIDKeyType;
+use identity_did::DIDUrl;
+use identity_did::DID;
+use identity_verification::jose::jwk::EcCurve;
+use identity_verification::jose::jwk::EcxCurve;
+use identity_verification::jose::jwk::EdCurve;
+use identity_verification::jose::jwk::Jwk;
+use identity_verification::jose::jwk::JwkParamsEc;
+use identity_verification::jose::jwk::JwkParamsOkp;
+use identity_verification::jwu;
+use identity_verification::VerificationMethod;
+
+use crate::document::CoreDocument;
+use crate::document::DocumentBuilder;
+use crate::error::Error;
+use crate::error::Result;
+
+impl CoreDocument {
+  /// Expands a [`DIDKey`] into the DID document it represents, following the
+  /// [did:key specification](https://w3c-ccg.github.io/did-method-key/#document-creation-algorithm).
+  ///
+  /// The public key is expressed as a [`Jwk`] in a verification method whose fragment is the method specific
+  /// identifier of the DID.
+  /// - Ed25519, P-256 and secp256k1 keys are referenced by the `authentication`, `assertionMethod`,
+  ///   `capabilityInvocation` and `capabilityDelegation` relationships.
+  /// - An X25519 key derived from an Ed25519 key is embedded in the `keyAgreement` relationship.
+  /// - X25519, P-256 and secp256k1 keys are referenced by the `keyAgreement` relationship.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the encoded public key is not a valid point on its curve.
+  pub fn expand_did_key(did: DIDKey) -> Result<Self> {
+    let key_type: DIDKeyType = did.key_type();
+    let public_key: Vec<u8> = did.public_key();
+
+    let jwk: Jwk = match key_type {
+      DIDKeyType::Ed25519 => okp_jwk(EdCurve::Ed25519.name(), &public_key),
+      DIDKeyType::X25519 => okp_jwk(EcxCurve::X25519.name(), &public_key),
+      DIDKeyType::P256 => p256_jwk(&public_key)?,
+      DIDKeyType::Secp256k1 => secp256k1_jwk(&public_key)?,
+      _ => return Err(Error::InvalidDocument("unsupported did:key key type", None)),
+    };
+    let method: VerificationMethod = new_method(&did, jwk, did.method_id())?;
+    let method_id: DIDUrl = method.id().clone();
+
+    let mut builder: DocumentBuilder = CoreDocument::builder(Object::new())
+      .id(did.clone().into())
+      .verification_method(method);
+    if key_type != DIDKeyType::X25519 {
+      builder = builder
+        .authentication(method_id.clone())
+        .assertion_method(method_id.clone())
+        .capability_invocation(method_id.clone())
+        .capability_delegation(method_id.clone());
+    }
+    builder = match key_type {
+      DIDKeyType::Ed25519 => {
+        let x25519_public_key: [u8; 32] = ed25519_to_x25519(&public_key)?;
+        let x25519_did: DIDKey = DIDKey::new(DIDKeyType::X25519, &x25519_public_key).map_err(invalid_public_key)?;
+        let jwk: Jwk = okp_jwk(EcxCurve::X25519.name(), &x25519_public_key);
+        builder.key_agreement(new_method(&did, jwk, x25519_did.method_id())?)
+      }
+      _ => builder.key_agreement(method_id),
+    };
+
+    builder.build()
+  }
+}
+
+fn new_method(did: &DIDKey, jwk: Jwk, fragment: &str) -> Result<VerificationMethod> {
+  VerificationMethod::new_from_jwk(did.clone(), jwk, Some(fragment))
+    .map_err(|_| Error::InvalidDocument("invalid did:key verification method", None))
+}
+
+fn invalid_public_key<E>(_: E) -> Error {
+  Error::InvalidDocument("invalid did:key public key", None)
+}
+
+fn okp_jwk(curve: &str, public_key: &[u8]) -> Jwk {
+  let mut params: JwkParamsOkp = JwkParamsOkp::new();
+  params.crv = curve.to_owned();
+  params.x = jwu::encode_b64(public_key);
+  Jwk::from_params(params)
+}
+
+fn ec_jwk(curve: &str, x: &[u8], y: &[u8]) -> Jwk {
+  let mut params: JwkParamsEc = JwkParamsEc::new();
+  params.crv = curve.to_owned();
+  params.x = jwu::encode_b64(x);
+  params.y = jwu::encode_b64(y);
+  Jwk::from_params(params)
+}
+
+/// Decompresses a SEC1 encoded P-256 public key into a [`Jwk`].
+fn p256_jwk(public_key: &[u8]) -> Result<Jwk> {
+  use p256::elliptic_curve::sec1::ToEncodedPoint;
+
+  let point = p256::PublicKey::from_sec1_bytes(public_key)
+    .map_err(invalid_public_key)?
+    .to_encoded_point(false);
+  let (x, y) = point.x().zip(point.y()).ok_or_else(|| invalid_public_key(()))?;
+  Ok(ec_jwk(EcCurve::P256.name(), x, y))
+}
+
+/// Decompresses a SEC1 encoded secp256k1 public key into a [`Jwk`].
+fn secp256k1_jwk(public_key: &[u8]) -> Result<Jwk> {
+  use k256::elliptic_curve::sec1::ToEncodedPoint;
+
+  let point = k256::PublicKey::from_sec1_bytes(public_key)
+    .map_err(invalid_public_key)?
+    .to_encoded_point(false);
+  let (x, y) = point.x().zip(point.y()).ok_or_else(|| invalid_public_key(()))?;
+  Ok(ec_jwk(EcCurve::Secp256K1.name(), x, y))
+}
+
+/// Converts an Ed25519 public key to the X25519 public key of the birationally equivalent Montgomery curve.
+fn ed25519_to_x25519(public_key: &[u8]) -> Result<[u8; 32]> {
+  let bytes: [u8; 32] = public_key.try_into().map_err(invalid_public_key)?;
+  let ed25519_public_key = crypto::signatures::ed25519::PublicKey::try_from_bytes(bytes).map_err(invalid_public_key)?;
+  let x25519_public_key = crypto::keys::x25519::PublicKey::try_from(&ed25519_public_key).map_err(invalid_public_key)?;
+  Ok(x25519_public_key.to_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_core::convert::FromJson;
+  use identity_core::convert::ToJson;
+  use identity_core::json;
+
+  use super::*;
+
+  #[test]
+  fn expand_ed25519() {
+    let did: DIDKey = DIDKey::parse("did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK").unwrap();
+    let document: CoreDocument = CoreDocument::expand_did_key(did).unwrap();
+
+    // The key agreement key derived from the Ed25519 key, as given in the did:key specification.
+    let method =
+      "did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK#z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK";
+    let key_agreement =
+      "did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK#z6LSj72tK8brWgZja8NLRwPigth2T9QRiG1uH9oKZuKjdh9p";
+    let expected = json!({
+      "id": "did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK",
+      "verificationMethod": [{
+        "id": method,
+        "controller": "did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK",
+        "type": "JsonWebKey",
+        "publicKeyJwk": {
+          "kty": "OKP",
+          "crv": "Ed25519",
+          "x": "Lm_M42cB3HkUiODQsXRcweM6TByfzEHGO9ND274JcOY"
+        }
+      }],
+      "authentication": [method],
+      "assertionMethod": [method],
+      "keyAgreement": [{
+        "id": key_agreement,
+        "controller": "did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK",
+        "type": "JsonWebKey",
+        "publicKeyJwk": {
+          "kty": "OKP",
+          "crv": "X25519",
+          "x": "bl_3kgKpz9jgsg350CNuHa_kQL3B60Gi-98WmdQW2h8"
+        }
+      }],
+      "capabilityDelegation": [method],
+      "capabilityInvocation": [method]
+    });
+    assert_eq!(document.to_json_value().unwrap(), expected);
+    assert_eq!(CoreDocument::from_json_value(expected).unwrap(), document);
+  }
+
+  #[test]
+  fn expand_x25519() {
+    let did: DIDKey = DIDKey::parse("did:key:z6LSeu9HkTHSfLLeUs2nnzUSNedgDUevfNQgQjQC23ZCit6F").unwrap();
+    let document: CoreDocument = CoreDocument::expand_did_key(did.clone()).unwrap();
+
+    assert_eq!(document.verification_method().len(), 1);
+    assert_eq!(document.key_agreement().len(), 1);
+    assert!(document.authentication().is_empty());
+    assert!(document.assertion_method().is_empty());
+    let jwk: &Jwk = document.verification_method()[0].data().public_key_jwk().unwrap();
+    assert_eq!(jwk.try_okp_params().unwrap().crv, "X25519");
+    assert_eq!(
+      jwu::decode_b64(&jwk.try_okp_params().unwrap().x).unwrap(),
+      did.public_key()
+    );
+  }
+
+  #[test]
+  fn expand_p256() {
+    let did: DIDKey = DIDKey::parse("did:key:zDnaerDaTF5BXEavCrfRZEk316dpbLsfPDZ3WJ5hRTPFU2169").unwrap();
+    let document: CoreDocument = CoreDocument::expand_did_key(did).unwrap();
+
+    let jwk: &Jwk = document.verification_method()[0].data().public_key_jwk().unwrap();
+    let params: &JwkParamsEc = jwk.try_ec_params().unwrap();
+    assert_eq!(params.crv, "P-256");
+    assert_eq!(params.x, "fyNYMN0976ci7xqiSdag3buk-ZCwgXU4kz9XNkBlNUI");
+    assert_eq!(params.y, "hW2ojTNfH7Jbi8--CJUo3OCbH3y5n91g-IMA9MLMbTU");
+    assert_eq!(document.authentication().len(), 1);
+    assert_eq!(document.key_agreement().len(), 1);
+  }
+
+  #[test]
+  fn expand_secp256k1() {
+    let did: DIDKey = DIDKey::parse("did:key:zQ3shokFTS3brHcDQrn82RUDfCZESWL1ZdCEJwekUDPQiYBme").unwrap();
+    let document: CoreDocument = CoreDocument::expand_did_key(did).unwrap();
+
+    let jwk: &Jwk = document.verification_method()[0].data().public_key_jwk().unwrap();
+    let params: &JwkParamsEc = jwk.try_ec_params().unwrap();
+    assert_eq!(params.crv, "secp256k1");
+    assert_eq!(params.x, "h0wVx_2iDlOcblulc8E5iEw1EYh5n1RYtLQfeSTyNc0");
+    assert_eq!(params.y, "O2EATIGbu6DezKFptj5scAIRntgfecanVNXxat1rnwE");
+    assert_eq!(document.assertion_method().len(), 1);
+    assert_eq!(document.key_agreement().len(), 1);
+  }
+
+  #[test]
+  fn expand_invalid_point() {
+    // A compressed point whose x-coordinate is not on the P-256 curve.
+    let mut public_key: [u8; 33] = [0xff; 33];
+    public_key[0] = 0x02;
+    let did: DIDKey = DIDKey::new(DIDKeyType::P256, &public_key).unwrap();
+    assert!(CoreDocument::expand_did_key(did).is_err());
+  }
+}
@@ -0,0 +1,432 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Object;
+use identity_core::common::Value;
+use identity_core::convert::FromJson;
+use identity_core::convert::ToJson;
+use identity_did::CoreDID;
+use identity_did::DIDKeyType;
+use identity_did::DIDPeer;
+use identity_did::DIDPeerElement;
+use identity_did::DIDPeerPurpose;
+use identity_did::DIDUrl;
+use identity_did::DID;
+use identity_verification::jose::jwk::EcCurve;
+use identity_verification::jose::jwk::EcxCurve;
+use identity_verification::jose::jwk::EdCurve;
+use identity_verification::jose::jwk::Jwk;
+use identity_verification::jose::jwk::JwkParams;
+use identity_verification::jwu;
+use identity_verification::MethodData;
+use identity_verification::MethodRelationship;
+use identity_verification::MethodScope;
+use identity_verification::MethodType;
+use identity_verification::VerificationMethod;
+
+use crate::document::CoreDocument;
+use crate::document::DocumentBuilder;
+use crate::error::Error;
+use crate::error::Result;
+use crate::service::Service;
+
+impl CoreDocument {
+  /// Creates a numalgo 0 [`DIDPeer`] from the public key of the given `method`.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the public key of `method` is not an Ed25519, X25519, P-256 or secp256k1 key expressed as a
+  /// `publicKeyJwk`, a `Multikey`, or an `Ed25519VerificationKey2018` or `X25519KeyAgreementKey2019`.
+  pub fn create_did_peer_0(method: &VerificationMethod) -> Result<DIDPeer> {
+    let (key_type, public_key) = method_public_key(method)?;
+    DIDPeer::new_numalgo_0(key_type, &public_key).map_err(|_| invalid_did_peer("invalid public key"))
+  }
+
+  /// Creates a numalgo 2 [`DIDPeer`] encoding the public keys of the given `methods` with the purposes
+  /// corresponding to their verification relationships, followed by the given `services`.
+  ///
+  /// The ids of the methods are not encoded; the expanded document assigns the ids `#key-1`, `#key-2`, ... in order.
+  /// The fragments of the service ids are preserved.
+  ///
+  /// # Errors
+  ///
+  /// Fails if a method has the scope [`MethodScope::VerificationMethod`], if its public key is not supported (see
+  /// [`CoreDocument::create_did_peer_0`]), or if no methods and services are given.
+  pub fn create_did_peer_2(methods: &[(VerificationMethod, MethodScope)], services: &[Service]) -> Result<DIDPeer> {
+    let mut elements: Vec<DIDPeerElement> = Vec::with_capacity(methods.len() + services.len());
+    for (method, scope) in methods {
+      let purpose: DIDPeerPurpose = match scope {
+        MethodScope::VerificationRelationship(MethodRelationship::Authentication) => DIDPeerPurpose::Verification,
+        MethodScope::VerificationRelationship(MethodRelationship::AssertionMethod) => DIDPeerPurpose::Assertion,
+        MethodScope::VerificationRelationship(MethodRelationship::KeyAgreement) => DIDPeerPurpose::Encryption,
+        MethodScope::VerificationRelationship(MethodRelationship::CapabilityInvocation) => {
+          DIDPeerPurpose::CapabilityInvocation
+        }
+        MethodScope::VerificationRelationship(MethodRelationship::CapabilityDelegation) => {
+          DIDPeerPurpose::CapabilityDelegation
+        }
+        MethodScope::VerificationMethod => {
+          return Err(invalid_did_peer("methods must have a verification relationship"));
+        }
+      };
+      let (key_type, public_key) = method_public_key(method)?;
+      elements.push(DIDPeerElement::Key {
+        purpose,
+        key_type,
+        public_key,
+      });
+    }
+    for service in services {
+      let mut service_json: Object = service
+        .to_json_value()
+        .ok()
+        .and_then(|json| Object::from_json_value(json).ok())
+        .ok_or_else(|| invalid_did_peer("invalid service"))?;
+      // The DID is unknown when encoding the service, so only a relative id is included.
+      let fragment: &str = service.id().fragment().unwrap_or_default();
+      service_json.insert("id".to_owned(), Value::from(format!("#{fragment}")));
+      elements.push(DIDPeerElement::Service(service_json));
+    }
+
+    DIDPeer::new_numalgo_2(&elements).map_err(|_| invalid_did_peer("unable to encode did:peer"))
+  }
+
+  /// Expands a numalgo 0 or numalgo 2 [`DIDPeer`] into the DID document it represents, following the
+  /// [did:peer specification](https://identity.foundation/peer-did-method-spec/).
+  ///
+  /// Public keys are expressed as `Multikey` verification methods.
+  /// - numalgo 0: the fragment of the method is the multikey. X25519 keys are referenced by the `keyAgreement`
+  ///   relationship, other keys by the `authentication`, `assertionMethod`, `capabilityInvocation` and
+  ///   `capabilityDelegation` relationships.
+  /// - numalgo 2: the fragments of the methods are `key-1`, `key-2`, ... in order, and each method is referenced by the
+  ///   relationship corresponding to its purpose. Services without an id are assigned the fragments `service`,
+  ///   `service-1`, ... in order. A DIDComm v2 `serviceEndpoint` object is kept as encoded, see
+  ///   [`ServiceEndpoint::Object`](crate::service::ServiceEndpoint::Object).
+  ///
+  /// # Errors
+  ///
+  /// Fails if an encoded service cannot be represented as a [`Service`].
+  pub fn expand_did_peer(did: DIDPeer) -> Result<Self> {
+    let did_core: CoreDID = did.clone().into();
+    let mut builder: DocumentBuilder = CoreDocument::builder(Object::new()).id(did_core.clone());
+
+    if let Some((key_type, public_key)) = did.inception_key() {
+      let multikey: String = encode_multikey(key_type, &public_key)?;
+      let method: VerificationMethod = multikey_method(&did_core, &multikey, multikey.clone())?;
+      let method_id: DIDUrl = method.id().clone();
+      builder = builder.verification_method(method);
+      builder = match key_type {
+        DIDKeyType::X25519 => builder.key_agreement(method_id),
+        _ => builder
+          .authentication(method_id.clone())
+          .assertion_method(method_id.clone())
+          .capability_invocation(method_id.clone())
+          .capability_delegation(method_id),
+      };
+      return builder.build();
+    }
+
+    let mut key_index: usize = 0;
+    let mut service_index: usize = 0;
+    for element in did.elements() {
+      match element {
+        DIDPeerElement::Key {
+          purpose,
+          key_type,
+          public_key,
+        } => {
+          key_index += 1;
+          let multikey: String = encode_multikey(key_type, &public_key)?;
+          let method: VerificationMethod = multikey_method(&did_core, &format!("key-{key_index}"), multikey)?;
+          let method_id: DIDUrl = method.id().clone();
+          builder = builder.verification_method(method);
+          builder = match purpose {
+            DIDPeerPurpose::Assertion => builder.assertion_method(method_id),
+            DIDPeerPurpose::Encryption => builder.key_agreement(method_id),
+            DIDPeerPurpose::Verification => builder.authentication(method_id),
+            DIDPeerPurpose::CapabilityInvocation => builder.capability_invocation(method_id),
+            DIDPeerPurpose::CapabilityDelegation => builder.capability_delegation(method_id),
+            _ => return Err(invalid_did_peer("unsupported key purpose")),
+          };
+        }
+        DIDPeerElement::Service(mut service_json) => {
+          let fragment: String = match service_json.get("id").and_then(Value::as_str) {
+            Some(id) => id.trim_start_matches('#').to_owned(),
+            None => {
+              let fragment: String = match service_index {
+                0 => "service".to_owned(),
+                index => format!("service-{index}"),
+              };
+              service_index += 1;
+              fragment
+            }
+          };
+          let service_id: DIDUrl = did_core
+            .clone()
+            .join(format!("#{fragment}"))
+            .map_err(|_| invalid_did_peer("invalid service id"))?;
+          service_json.insert("id".to_owned(), Value::from(service_id.to_string()));
+          let service: Service = Service::from_json_value(Value::Object(service_json.into_iter().collect()))
+            .map_err(|_| invalid_did_peer("invalid service"))?;
+          builder = builder.service(service);
+        }
+        _ => return Err(invalid_did_peer("unsupported element")),
+      }
+    }
+
+    builder.build()
+  }
+}
+
+fn invalid_did_peer(message: &'static str) -> Error {
+  Error::InvalidDocument(message, None)
+}
+
+fn encode_multikey(key_type: DIDKeyType, public_key: &[u8]) -> Result<String> {
+  key_type
+    .encode_multikey(public_key)
+    .map_err(|_| invalid_did_peer("invalid public key"))
+}
+
+fn multikey_method(did: &CoreDID, fragment: &str, multikey: String) -> Result<VerificationMethod> {
+  let id: DIDUrl = did
+    .clone()
+    .join(format!("#{fragment}"))
+    .map_err(|_| invalid_did_peer("invalid method id"))?;
+  VerificationMethod::builder(Object::new())
+    .id(id)
+    .controller(did.clone())
+    .type_(MethodType::MULTIKEY)
+    .data(MethodData::PublicKeyMultibase(multikey))
+    .build()
+    .map_err(|_| invalid_did_peer("invalid verification method"))
+}
+
+/// Extracts the type and public key of the given `method`, with elliptic curve keys in compressed form.
+fn method_public_key(method: &VerificationMethod) -> Result<(DIDKeyType, Vec<u8>)> {
+  let unsupported = || invalid_did_peer("unsupported verification method");
+  match method.data() {
+    MethodData::PublicKeyJwk(jwk) => jwk_public_key(jwk).ok_or_else(unsupported),
+    MethodData::PublicKeyMultibase(multikey) if method.type_() == &MethodType::MULTIKEY => {
+      DIDKeyType::decode_multikey(multikey).map_err(|_| unsupported())
+    }
+    data @ (MethodData::PublicKeyMultibase(_) | MethodData::PublicKeyBase58(_)) => {
+      let key_type: DIDKeyType = if method.type_() == &MethodType::ED25519_VERIFICATION_KEY_2018 {
+        DIDKeyType::Ed25519
+      } else if method.type_() == &MethodType::X25519_KEY_AGREEMENT_KEY_2019 {
+        DIDKeyType::X25519
+      } else {
+        return Err(unsupported());
+      };
+      let public_key: Vec<u8> = data.try_decode().map_err(|_| unsupported())?;
+      Ok((key_type, public_key))
+    }
+    _ => Err(unsupported()),
+  }
+}
+
+fn jwk_public_key(jwk: &Jwk) -> Option<(DIDKeyType, Vec<u8>)> {
+  match jwk.params() {
+    JwkParams::Okp(params) => {
+      let key_type: DIDKeyType = if params.crv == EdCurve::Ed25519.name() {
+        DIDKeyType::Ed25519
+      } else if params.crv == EcxCurve::X25519.name() {
+        DIDKeyType::X25519
+      } else {
+        return None;
+      };
+      Some((key_type, jwu::decode_b64(&params.x).ok()?))
+    }
+    JwkParams::Ec(params) => {
+      let key_type: DIDKeyType = if params.crv == EcCurve::P256.name() {
+        DIDKeyType::P256
+      } else if params.crv == EcCurve::Secp256K1.name() {
+        DIDKeyType::Secp256k1
+      } else {
+        return None;
+      };
+      // SEC1 compressed point: the parity of the y-coordinate followed by the x-coordinate.
+      let x: Vec<u8> = jwu::decode_b64(&params.x).ok()?;
+      let y: Vec<u8> = jwu::decode_b64(&params.y).ok()?;
+      let mut public_key: Vec<u8> = vec![0x02 | (y.last()? & 1)];
+      public_key.extend(x);
+      Some((key_type, public_key))
+    }
+    _ => None,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_core::json;
+  use identity_did::DIDKey;
+
+  use super::*;
+
+  // A numalgo 2 example of the did:peer specification, with a service endpoint expressed as a URL.
+  const NUMALGO_2_DID: &str = "did:peer:2.Ez6LSbysY2xFMRpGMhb7tFTLMpeuPRaqaWM1yECx2AtzE3KCc.Vz6MkqRYqQiSgvZQdnBytw86Qbs2ZWUkGv22od935YF4s8M7V.Vz6MkgoLTnTypo3tDRwCkZXSccTPHRLhF4ZnjhueYAFpEX6vg.SeyJ0IjoiZG0iLCJzIjoiaHR0cHM6Ly9leGFtcGxlLmNvbS9lbmRwb2ludCIsInIiOlsiZGlkOmV4YW1wbGU6c29tZW1lZGlhdG9yI3NvbWVrZXkiXSwiYSI6WyJkaWRjb21tL3YyIiwiZGlkY29tbS9haXAyO2Vudj1yZmM1ODciXX0";
+
+  #[test]
+  fn expand_numalgo_2_spec_example() {
+    let did: DIDPeer = DIDPeer::parse(NUMALGO_2_DID).unwrap();
+    let document: CoreDocument = CoreDocument::expand_did_peer(did).unwrap();
+
+    let method = |index: usize, multikey: &str| {
+      json!({
+        "id": format!("{NUMALGO_2_DID}#key-{index}"),
+        "controller": NUMALGO_2_DID,
+        "type": "Multikey",
+        "publicKeyMultibase": multikey
+      })
+    };
+    let expected = json!({
+      "id": NUMALGO_2_DID,
+      "verificationMethod": [
+        method(1, "z6LSbysY2xFMRpGMhb7tFTLMpeuPRaqaWM1yECx2AtzE3KCc"),
+        method(2, "z6MkqRYqQiSgvZQdnBytw86Qbs2ZWUkGv22od935YF4s8M7V"),
+        method(3, "z6MkgoLTnTypo3tDRwCkZXSccTPHRLhF4ZnjhueYAFpEX6vg")
+      ],
+      "authentication": [format!("{NUMALGO_2_DID}#key-2"), format!("{NUMALGO_2_DID}#key-3")],
+      "keyAgreement": [format!("{NUMALGO_2_DID}#key-1")],
+      "service": [{
+        "id": format!("{NUMALGO_2_DID}#service"),
+        "type": "DIDCommMessaging",
+        "serviceEndpoint": "https://example.com/endpoint",
+        "routingKeys": ["did:example:somemediator#somekey"],
+        "accept": ["didcomm/v2", "didcomm/aip2;env=rfc587"]
+      }]
+    });
+    assert_eq!(document, CoreDocument::from_json_value(expected).unwrap());
+  }
+
+  #[test]
+  fn expand_numalgo_2_didcomm_endpoint() {
+    // The numalgo 2 example of the did:peer specification, with service endpoints expressed as DIDComm v2 objects.
+    const DID: &str = "did:peer:2.Vz6Mkj3PUd1WjvaDhNZhhhXQdz5UnZXmS7ehtx8bsPpD47kKc.Ez6LSg8zQom395jKLrGiBNruB9MM6V8PWuf2FpEy4uRFiqQBR.SeyJ0IjoiZG0iLCJzIjp7InVyaSI6Imh0dHA6Ly9leGFtcGxlLmNvbS9kaWRjb21tIiwiYSI6WyJkaWRjb21tL3YyIl0sInIiOlsiZGlkOmV4YW1wbGU6MTIzNDU2Nzg5YWJjZGVmZ2hpI2tleS0xIl19fQ.SeyJ0IjoiZG0iLCJzIjp7InVyaSI6Imh0dHA6Ly9leGFtcGxlLmNvbS9hbm90aGVyIiwiYSI6WyJkaWRjb21tL3YyIl0sInIiOlsiZGlkOmV4YW1wbGU6MTIzNDU2Nzg5YWJjZGVmZ2hpI2tleS0yIl19fQ";
+    let document: CoreDocument = CoreDocument::expand_did_peer(DIDPeer::parse(DID).unwrap()).unwrap();
+
+    let service = |fragment: &str, endpoint: &str, routing_key: &str| {
+      Service::from_json_value(json!({
+        "id": format!("{DID}#{fragment}"),
+        "type": "DIDCommMessaging",
+        "serviceEndpoint": {
+          "uri": endpoint,
+          "accept": ["didcomm/v2"],
+          "routingKeys": [routing_key]
+        }
+      }))
+      .unwrap()
+    };
+    assert_eq!(
+      document.service().as_slice(),
+      [
+        service(
+          "service",
+          "http://example.com/didcomm",
+          "did:example:123456789abcdefghi#key-1"
+        ),
+        service(
+          "service-1",
+          "http://example.com/another",
+          "did:example:123456789abcdefghi#key-2"
+        ),
+      ]
+    );
+    assert_eq!(document.verification_method().len(), 2);
+  }
+
+  #[test]
+  fn create_and_expand_numalgo_0() {
+    let did_key: DIDKey = DIDKey::parse("did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK").unwrap();
+    let method: VerificationMethod = multikey_method(did_key.as_ref(), "key", did_key.method_id().to_owned()).unwrap();
+
+    let did: DIDPeer = CoreDocument::create_did_peer_0(&method).unwrap();
+    assert_eq!(
+      did.as_str(),
+      "did:peer:0z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK"
+    );
+
+    let document: CoreDocument = CoreDocument::expand_did_peer(did.clone()).unwrap();
+    let expanded_method: &VerificationMethod = &document.verification_method()[0];
+    assert_eq!(
+      expanded_method.id().fragment(),
+      Some("z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK")
+    );
+    assert_eq!(expanded_method.data(), method.data());
+    assert_eq!(document.authentication().len(), 1);
+    assert_eq!(document.capability_delegation().len(), 1);
+    assert!(document.key_agreement().is_empty());
+  }
+
+  #[test]
+  fn create_and_expand_numalgo_2() {
+    let placeholder: CoreDID = CoreDID::parse("did:example:123").unwrap();
+    let mut ed25519_jwk = identity_verification::jose::jwk::JwkParamsOkp::new();
+    ed25519_jwk.crv = EdCurve::Ed25519.name().to_owned();
+    ed25519_jwk.x = "Lm_M42cB3HkUiODQsXRcweM6TByfzEHGO9ND274JcOY".to_owned();
+    let ed25519_method: VerificationMethod =
+      VerificationMethod::new_from_jwk(placeholder.clone(), Jwk::from_params(ed25519_jwk), Some("sig")).unwrap();
+    let mut p256_jwk = identity_verification::jose::jwk::JwkParamsEc::new();
+    p256_jwk.crv = EcCurve::P256.name().to_owned();
+    p256_jwk.x = "fyNYMN0976ci7xqiSdag3buk-ZCwgXU4kz9XNkBlNUI".to_owned();
+    p256_jwk.y = "hW2ojTNfH7Jbi8--CJUo3OCbH3y5n91g-IMA9MLMbTU".to_owned();
+    let p256_method: VerificationMethod =
+      VerificationMethod::new_from_jwk(placeholder.clone(), Jwk::from_params(p256_jwk), Some("enc")).unwrap();
+    let service: Service = Service::from_json_value(json!({
+      "id": "did:example:123#didcomm",
+      "type": "DIDCommMessaging",
+      "serviceEndpoint": "https://example.com/didcomm"
+    }))
+    .unwrap();
+
+    let did: DIDPeer = CoreDocument::create_did_peer_2(
+      &[
+        (ed25519_method, MethodScope::authentication()),
+        (ed25519_method_copy(), MethodScope::assertion_method()),
+        (p256_method, MethodScope::key_agreement()),
+      ],
+      &[service],
+    )
+    .unwrap();
+    assert!(did
+      .as_str()
+      .starts_with("did:peer:2.Vz6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK.Az6Mkha"));
+
+    let document: CoreDocument = CoreDocument::expand_did_peer(did.clone()).unwrap();
+    assert_eq!(document.verification_method().len(), 3);
+    assert_eq!(document.authentication().len(), 1);
+    assert_eq!(document.assertion_method().len(), 1);
+    assert_eq!(document.key_agreement().len(), 1);
+    // The P-256 key is encoded in compressed form, as in the corresponding did:key.
+    assert_eq!(
+      document.verification_method()[2].data(),
+      &MethodData::PublicKeyMultibase("zDnaerDaTF5BXEavCrfRZEk316dpbLsfPDZ3WJ5hRTPFU2169".to_owned())
+    );
+    let service: &Service = &document.service()[0];
+    assert_eq!(service.id().to_string(), format!("{did}#didcomm"));
+    assert_eq!(service.type_().as_slice(), ["DIDCommMessaging"]);
+
+    fn ed25519_method_copy() -> VerificationMethod {
+      let did_key: DIDKey = DIDKey::parse("did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK").unwrap();
+      VerificationMethod::builder(Object::new())
+        .id(did_key.clone().join("#key").unwrap())
+        .controller(did_key.clone().into())
+        .type_(MethodType::ED25519_VERIFICATION_KEY_2018)
+        .data(MethodData::new_multibase(did_key.public_key()))
+        .build()
+        .unwrap()
+    }
+  }
+
+  #[test]
+  fn create_invalid() {
+    let did_key: DIDKey = DIDKey::parse("did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK").unwrap();
+    let method: VerificationMethod = multikey_method(did_key.as_ref(), "key", did_key.method_id().to_owned()).unwrap();
+    assert!(CoreDocument::create_did_peer_2(&[(method.clone(), MethodScope::VerificationMethod)], &[]).is_err());
+    assert!(CoreDocument::create_did_peer_2(&[], &[]).is_err());
+
+    let mut unsupported: VerificationMethod = method;
+    *unsupported.type_mut() = MethodType::custom("EcdsaSecp256k1VerificationKey2019");
+    assert!(CoreDocument::create_did_peer_0(&unsupported).is_err());
+  }
+}
@@ -10,3 +10,7 @@ pub use self::core_document::CoreDocument;
 
 mod builder;
 mod core_document;
+mod did_jwk;
+#[cfg(feature = "did-key")]
+mod did_key;
+mod did_peer;
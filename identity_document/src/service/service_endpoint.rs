@@ -5,21 +5,48 @@ use core::fmt::Display;
 use core::fmt::Formatter;
 
 use indexmap::map::IndexMap;
+use serde::de::Error as _;
+use serde::Deserialize;
+use serde::Deserializer;
 use serde::Serialize;
 
+use identity_core::common::Object;
 use identity_core::common::OrderedSet;
 use identity_core::common::Url;
+use identity_core::common::Value;
 use identity_core::convert::FmtJson;
+use identity_core::convert::FromJson;
 
 /// A single URL, set, or map of endpoints specified in a [`Service`](crate::service::Service).
 ///
+/// Maps whose values are not all sets, such as DIDComm v2 endpoints with `uri`, `accept` and `routingKeys` entries, are
+/// kept as an [`Object`].
+///
 /// [Specification](https://www.w3.org/TR/did-core/#dfn-serviceendpoint)
-#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
 #[serde(untagged)]
 pub enum ServiceEndpoint {
   One(Url),
   Set(OrderedSet<Url>),
   Map(IndexMap<String, OrderedSet<Url>>),
+  Object(Object),
+}
+
+impl<'de> Deserialize<'de> for ServiceEndpoint {
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    let value: Value = Value::deserialize(deserializer)?;
+    let endpoint: Result<Self, identity_core::Error> = match value {
+      Value::String(_) => Url::from_json_value(value).map(Self::One),
+      Value::Array(_) => OrderedSet::from_json_value(value).map(Self::Set),
+      Value::Object(ref map) if map.values().all(Value::is_array) => IndexMap::from_json_value(value).map(Self::Map),
+      Value::Object(map) => Ok(Self::Object(map.into_iter().collect())),
+      _ => return Err(D::Error::custom("expected a URL, a set of URLs or a map")),
+    };
+    endpoint.map_err(D::Error::custom)
+  }
 }
 
 impl From<Url> for ServiceEndpoint {
@@ -40,6 +67,12 @@ impl From<IndexMap<String, OrderedSet<Url>>> for ServiceEndpoint {
   }
 }
 
+impl From<Object> for ServiceEndpoint {
+  fn from(object: Object) -> Self {
+    ServiceEndpoint::Object(object)
+  }
+}
+
 impl Display for ServiceEndpoint {
   fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
     self.fmt_json(f)
@@ -189,6 +222,20 @@ mod tests {
     assert_eq!(endpoint_map, ServiceEndpoint::from_json(&ser_endpoint_map).unwrap());
   }
 
+  #[test]
+  fn test_service_endpoint_object() {
+    // VALID: Object, e.g. a DIDComm v2 service endpoint.
+    let ser_endpoint_object: &str =
+      r#"{"accept":["didcomm/v2"],"routingKeys":["did:example:mediator#key-1"],"uri":"https://example.com/didcomm"}"#;
+    let endpoint_object: ServiceEndpoint = ServiceEndpoint::from_json(ser_endpoint_object).unwrap();
+    assert!(matches!(endpoint_object, ServiceEndpoint::Object(_)));
+    assert_eq!(endpoint_object.to_json().unwrap(), ser_endpoint_object);
+
+    // Maps of URL sets are still deserialized as such.
+    let endpoint_map: ServiceEndpoint = ServiceEndpoint::from_json(r#"{"origins":["https://iota.org/"]}"#).unwrap();
+    assert!(matches!(endpoint_map, ServiceEndpoint::Map(_)));
+  }
+
   #[test]
   fn test_service_endpoint_serde_fails() {
     // INVALID: empty
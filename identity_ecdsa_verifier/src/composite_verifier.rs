@@ -0,0 +1,45 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_jose::jwk::Jwk;
+use identity_jose::jws::JwsVerifier;
+use identity_jose::jws::SignatureVerificationError;
+use identity_jose::jws::SignatureVerificationErrorKind;
+use identity_jose::jws::VerificationInput;
+
+/// An implementor of [`JwsVerifier`] that dispatches on the `alg` of the [`VerificationInput`] and can handle
+/// every algorithm supported by the enabled features:
+///
+/// - [`JwsAlgorithm::EdDSA`](identity_jose::jws::JwsAlgorithm::EdDSA) with feature `ed25519`.
+/// - [`JwsAlgorithm::ES256`](identity_jose::jws::JwsAlgorithm::ES256) with feature `es256`.
+/// - [`JwsAlgorithm::ES384`](identity_jose::jws::JwsAlgorithm::ES384) with feature `es384`.
+/// - [`JwsAlgorithm::ES256K`](identity_jose::jws::JwsAlgorithm::ES256K) with feature `es256k`.
+///
+/// This is useful when verifying credentials or presentations whose issuers or holders use different key types.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct CompositeJwsVerifier;
+
+impl Default for CompositeJwsVerifier {
+  /// Constructs a [`CompositeJwsVerifier`]. This is the only way to obtain a [`CompositeJwsVerifier`].
+  fn default() -> Self {
+    Self
+  }
+}
+
+impl JwsVerifier for CompositeJwsVerifier {
+  /// Verifies a JWS signature with the verifier matching its `alg`, or fails with
+  /// [`SignatureVerificationErrorKind::UnsupportedAlg`] if none of the enabled verifiers supports it.
+  // Allow unused variables in case of no-default-features.
+  #[allow(unused_variables)]
+  fn verify(&self, input: VerificationInput, public_key: &Jwk) -> std::result::Result<(), SignatureVerificationError> {
+    match input.alg {
+      #[cfg(feature = "ed25519")]
+      identity_jose::jws::JwsAlgorithm::EdDSA => identity_eddsa_verifier::Ed25519Verifier::verify(input, public_key),
+      identity_jose::jws::JwsAlgorithm::ES256
+      | identity_jose::jws::JwsAlgorithm::ES384
+      | identity_jose::jws::JwsAlgorithm::ES256K => crate::EcDSAJwsVerifier::default().verify(input, public_key),
+      _ => Err(SignatureVerificationErrorKind::UnsupportedAlg.into()),
+    }
+  }
+}
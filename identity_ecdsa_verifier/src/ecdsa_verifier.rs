@@ -0,0 +1,45 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_jose::jwk::Jwk;
+use identity_jose::jws::JwsVerifier;
+use identity_jose::jws::SignatureVerificationError;
+use identity_jose::jws::SignatureVerificationErrorKind;
+use identity_jose::jws::VerificationInput;
+
+/// An implementor of [`JwsVerifier`] that can handle the
+/// [`JwsAlgorithm::ES256`](identity_jose::jws::JwsAlgorithm::ES256),
+/// [`JwsAlgorithm::ES384`](identity_jose::jws::JwsAlgorithm::ES384) and
+/// [`JwsAlgorithm::ES256K`](identity_jose::jws::JwsAlgorithm::ES256K) algorithms.
+///
+/// Each algorithm is only supported if the corresponding feature (`es256`, `es384` or `es256k`) is enabled.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct EcDSAJwsVerifier;
+
+impl Default for EcDSAJwsVerifier {
+  /// Constructs an [`EcDSAJwsVerifier`]. This is the only way to obtain an [`EcDSAJwsVerifier`].
+  fn default() -> Self {
+    Self
+  }
+}
+
+impl JwsVerifier for EcDSAJwsVerifier {
+  /// This implements verification of JWS signatures signed with the
+  /// [`JwsAlgorithm::ES256`](identity_jose::jws::JwsAlgorithm::ES256),
+  /// [`JwsAlgorithm::ES384`](identity_jose::jws::JwsAlgorithm::ES384) and
+  /// [`JwsAlgorithm::ES256K`](identity_jose::jws::JwsAlgorithm::ES256K) algorithms.
+  // Allow unused variables in case of no-default-features.
+  #[allow(unused_variables)]
+  fn verify(&self, input: VerificationInput, public_key: &Jwk) -> std::result::Result<(), SignatureVerificationError> {
+    match input.alg {
+      #[cfg(feature = "es256")]
+      identity_jose::jws::JwsAlgorithm::ES256 => crate::Es256Verifier::verify(input, public_key),
+      #[cfg(feature = "es384")]
+      identity_jose::jws::JwsAlgorithm::ES384 => crate::Es384Verifier::verify(input, public_key),
+      #[cfg(feature = "es256k")]
+      identity_jose::jws::JwsAlgorithm::ES256K => crate::Es256KVerifier::verify(input, public_key),
+      _ => Err(SignatureVerificationErrorKind::UnsupportedAlg.into()),
+    }
+  }
+}
@@ -0,0 +1,48 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::ops::Deref;
+
+use identity_jose::jwk::EcCurve;
+use identity_jose::jwk::Jwk;
+use identity_jose::jws::SignatureVerificationError;
+use identity_jose::jws::SignatureVerificationErrorKind;
+use identity_jose::jws::VerificationInput;
+use p256::ecdsa::signature::Verifier;
+use p256::ecdsa::Signature;
+use p256::ecdsa::VerifyingKey;
+
+/// A verifier that can handle the [`JwsAlgorithm::ES256`](identity_jose::jws::JwsAlgorithm::ES256) algorithm with
+/// curve [`EcCurve::P256`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct Es256Verifier;
+
+impl Es256Verifier {
+  /// Verify a JWS signature secured with the [`JwsAlgorithm::ES256`](identity_jose::jws::JwsAlgorithm::ES256)
+  /// algorithm and curve [`EcCurve::P256`]. This associated method is only available when the
+  /// `es256` feature is enabled.
+  ///
+  /// The signature is expected to be the concatenation of the big-endian encoded `r` and `s` values
+  /// as described in [RFC 7518 section 3.4](https://www.rfc-editor.org/rfc/rfc7518#section-3.4). The
+  /// `signing_input` is hashed with SHA-256.
+  ///
+  /// # Warning
+  ///
+  /// This function does not check whether `alg = ES256` in the protected header. Callers are expected to assert this
+  /// prior to calling the function.
+  pub fn verify(input: VerificationInput, public_key: &Jwk) -> Result<(), SignatureVerificationError> {
+    let encoded_point: Vec<u8> = crate::sec1::encoded_point(public_key, EcCurve::P256, 32)?;
+
+    let verifying_key: VerifyingKey = VerifyingKey::from_sec1_bytes(&encoded_point).map_err(|err| {
+      SignatureVerificationError::new(SignatureVerificationErrorKind::KeyDecodingFailure).with_source(err)
+    })?;
+
+    let signature: Signature = Signature::from_slice(input.decoded_signature.deref())
+      .map_err(|_| SignatureVerificationErrorKind::InvalidSignature)?;
+
+    verifying_key
+      .verify(&input.signing_input, &signature)
+      .map_err(|err| SignatureVerificationError::new(SignatureVerificationErrorKind::InvalidSignature).with_source(err))
+  }
+}
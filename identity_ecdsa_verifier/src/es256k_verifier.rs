@@ -0,0 +1,52 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::ops::Deref;
+
+use identity_jose::jwk::EcCurve;
+use identity_jose::jwk::Jwk;
+use identity_jose::jws::SignatureVerificationError;
+use identity_jose::jws::SignatureVerificationErrorKind;
+use identity_jose::jws::VerificationInput;
+use k256::ecdsa::signature::Verifier;
+use k256::ecdsa::Signature;
+use k256::ecdsa::VerifyingKey;
+
+/// A verifier that can handle the [`JwsAlgorithm::ES256K`](identity_jose::jws::JwsAlgorithm::ES256K) algorithm with
+/// curve [`EcCurve::Secp256K1`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct Es256KVerifier;
+
+impl Es256KVerifier {
+  /// Verify a JWS signature secured with the [`JwsAlgorithm::ES256K`](identity_jose::jws::JwsAlgorithm::ES256K)
+  /// algorithm and curve [`EcCurve::Secp256K1`]. This associated method is only available when the
+  /// `es256k` feature is enabled.
+  ///
+  /// The signature is expected to be the concatenation of the big-endian encoded `r` and `s` values
+  /// as described in [RFC 7518 section 3.4](https://www.rfc-editor.org/rfc/rfc7518#section-3.4). The
+  /// `signing_input` is hashed with SHA-256.
+  ///
+  /// Signatures with a high `s` value are normalized before verification, since
+  /// [RFC 8812](https://www.rfc-editor.org/rfc/rfc8812#section-3.2) does not require signers to produce low `s` values.
+  ///
+  /// # Warning
+  ///
+  /// This function does not check whether `alg = ES256K` in the protected header. Callers are expected to assert this
+  /// prior to calling the function.
+  pub fn verify(input: VerificationInput, public_key: &Jwk) -> Result<(), SignatureVerificationError> {
+    let encoded_point: Vec<u8> = crate::sec1::encoded_point(public_key, EcCurve::Secp256K1, 32)?;
+
+    let verifying_key: VerifyingKey = VerifyingKey::from_sec1_bytes(&encoded_point).map_err(|err| {
+      SignatureVerificationError::new(SignatureVerificationErrorKind::KeyDecodingFailure).with_source(err)
+    })?;
+
+    let signature: Signature = Signature::from_slice(input.decoded_signature.deref())
+      .map_err(|_| SignatureVerificationErrorKind::InvalidSignature)?;
+    let signature: Signature = signature.normalize_s().unwrap_or(signature);
+
+    verifying_key
+      .verify(&input.signing_input, &signature)
+      .map_err(|err| SignatureVerificationError::new(SignatureVerificationErrorKind::InvalidSignature).with_source(err))
+  }
+}
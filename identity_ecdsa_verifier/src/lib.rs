@@ -0,0 +1,25 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+mod composite_verifier;
+mod ecdsa_verifier;
+#[cfg(feature = "es256")]
+mod es256_verifier;
+#[cfg(feature = "es256k")]
+mod es256k_verifier;
+#[cfg(feature = "es384")]
+mod es384_verifier;
+#[cfg(any(feature = "es256", feature = "es384", feature = "es256k"))]
+mod sec1;
+
+pub use composite_verifier::*;
+pub use ecdsa_verifier::*;
+#[cfg(feature = "es256")]
+pub use es256_verifier::*;
+#[cfg(feature = "es256k")]
+pub use es256k_verifier::*;
+#[cfg(feature = "es384")]
+pub use es384_verifier::*;
+
+#[cfg(all(test, any(feature = "es256", feature = "es384", feature = "es256k")))]
+mod tests;
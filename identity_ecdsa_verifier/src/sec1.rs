@@ -0,0 +1,48 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_jose::jwk::EcCurve;
+use identity_jose::jwk::Jwk;
+use identity_jose::jwk::JwkParamsEc;
+use identity_jose::jws::SignatureVerificationError;
+use identity_jose::jws::SignatureVerificationErrorKind;
+use identity_jose::jwu;
+
+/// Extracts the public key from an `EC` [`Jwk`] on the given `curve` and returns it as an uncompressed
+/// SEC1 encoded point.
+///
+/// Both coordinates are expected to be exactly `coordinate_len` bytes long as required by
+/// [RFC 7518 section 6.2.1](https://www.rfc-editor.org/rfc/rfc7518#section-6.2.1).
+pub(crate) fn encoded_point(
+  public_key: &Jwk,
+  curve: EcCurve,
+  coordinate_len: usize,
+) -> Result<Vec<u8>, SignatureVerificationError> {
+  let params: &JwkParamsEc = public_key
+    .try_ec_params()
+    .map_err(|_| SignatureVerificationErrorKind::UnsupportedKeyType)?;
+
+  if params
+    .try_ec_curve()
+    .ok()
+    .filter(|curve_param| *curve_param == curve)
+    .is_none()
+  {
+    return Err(SignatureVerificationErrorKind::UnsupportedKeyParams.into());
+  }
+
+  let decode_coordinate = |coordinate: &str, name: &'static str| -> Result<Vec<u8>, SignatureVerificationError> {
+    jwu::decode_b64(coordinate)
+      .ok()
+      .filter(|value| value.len() == coordinate_len)
+      .ok_or_else(|| {
+        SignatureVerificationError::new(SignatureVerificationErrorKind::KeyDecodingFailure).with_custom_message(name)
+      })
+  };
+
+  let x: Vec<u8> = decode_coordinate(params.x.as_str(), "could not decode x parameter from jwk")?;
+  let y: Vec<u8> = decode_coordinate(params.y.as_str(), "could not decode y parameter from jwk")?;
+
+  // Transformation according to section 2.3.3 from http://www.secg.org/sec1-v2.pdf.
+  Ok([0x04].into_iter().chain(x).chain(y).collect())
+}
@@ -0,0 +1,185 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_jose::jwk::EcCurve;
+use identity_jose::jwk::Jwk;
+use identity_jose::jwk::JwkParamsEc;
+use identity_jose::jws::CompactJwsEncoder;
+use identity_jose::jws::Decoder;
+use identity_jose::jws::JwsAlgorithm;
+use identity_jose::jws::JwsHeader;
+use identity_jose::jws::JwsVerifier;
+use identity_jose::jws::SignatureVerificationError;
+#[cfg(feature = "es256")]
+use identity_jose::jws::SignatureVerificationErrorKind;
+#[cfg(feature = "es256")]
+use identity_jose::jws::VerificationInput;
+use identity_jose::jwu;
+
+use crate::CompositeJwsVerifier;
+use crate::EcDSAJwsVerifier;
+
+const CLAIMS: &[u8] = br#"{"iss":"did:example:issuer","sub":"did:example:holder"}"#;
+
+/// Builds a public `EC` JWK from an uncompressed SEC1 encoded point.
+fn ec_jwk(curve: EcCurve, encoded_point: &[u8]) -> Jwk {
+  let coordinate_len: usize = (encoded_point.len() - 1) / 2;
+  let mut params: JwkParamsEc = JwkParamsEc::new();
+  params.crv = curve.name().to_owned();
+  params.x = jwu::encode_b64(&encoded_point[1..=coordinate_len]);
+  params.y = jwu::encode_b64(&encoded_point[1 + coordinate_len..]);
+  Jwk::from_params(params)
+}
+
+/// Creates a compact JWS over [`CLAIMS`] using the given signing function.
+fn create_jws(alg: JwsAlgorithm, sign: impl FnOnce(&[u8]) -> Vec<u8>) -> String {
+  let mut header: JwsHeader = JwsHeader::new();
+  header.set_alg(alg);
+  let encoder: CompactJwsEncoder<'_> = CompactJwsEncoder::new(CLAIMS, &header).unwrap();
+  let signature: Vec<u8> = sign(encoder.signing_input());
+  encoder.into_jws(&signature)
+}
+
+fn verify(jws: &str, verifier: &impl JwsVerifier, public_key: &Jwk) -> Result<(), SignatureVerificationError> {
+  Decoder::new()
+    .decode_compact_serialization(jws.as_bytes(), None)
+    .unwrap()
+    .verify(verifier, public_key)
+    .map(|decoded| assert_eq!(decoded.claims.as_ref(), CLAIMS))
+    .map_err(|err| match err {
+      identity_jose::error::Error::SignatureVerificationError(err) => err,
+      other => panic!("unexpected error: {other}"),
+    })
+}
+
+#[cfg(feature = "es256")]
+fn es256() -> (String, Jwk) {
+  use p256::ecdsa::signature::Signer;
+  use p256::ecdsa::Signature;
+  use p256::ecdsa::SigningKey;
+
+  let signing_key: SigningKey = SigningKey::from_slice(&[7; 32]).unwrap();
+  let jws: String = create_jws(JwsAlgorithm::ES256, |input| {
+    let signature: Signature = signing_key.sign(input);
+    signature.to_vec()
+  });
+  let encoded_point = signing_key.verifying_key().to_encoded_point(false);
+  (jws, ec_jwk(EcCurve::P256, encoded_point.as_bytes()))
+}
+
+#[cfg(feature = "es384")]
+fn es384() -> (String, Jwk) {
+  use p384::ecdsa::signature::Signer;
+  use p384::ecdsa::Signature;
+  use p384::ecdsa::SigningKey;
+
+  let signing_key: SigningKey = SigningKey::from_slice(&[7; 48]).unwrap();
+  let jws: String = create_jws(JwsAlgorithm::ES384, |input| {
+    let signature: Signature = signing_key.sign(input);
+    signature.to_vec()
+  });
+  let encoded_point = signing_key.verifying_key().to_encoded_point(false);
+  (jws, ec_jwk(EcCurve::P384, encoded_point.as_bytes()))
+}
+
+#[cfg(feature = "es256k")]
+fn es256k() -> (String, Jwk) {
+  use k256::ecdsa::signature::Signer;
+  use k256::ecdsa::Signature;
+  use k256::ecdsa::SigningKey;
+
+  let signing_key: SigningKey = SigningKey::from_slice(&[7; 32]).unwrap();
+  let jws: String = create_jws(JwsAlgorithm::ES256K, |input| {
+    let signature: Signature = signing_key.sign(input);
+    signature.to_vec()
+  });
+  let encoded_point = signing_key.verifying_key().to_encoded_point(false);
+  (jws, ec_jwk(EcCurve::Secp256K1, encoded_point.as_bytes()))
+}
+
+#[test]
+fn test_ecdsa_verifiers() {
+  let mut cases: Vec<(String, Jwk)> = Vec::new();
+  #[cfg(feature = "es256")]
+  cases.push(es256());
+  #[cfg(feature = "es384")]
+  cases.push(es384());
+  #[cfg(feature = "es256k")]
+  cases.push(es256k());
+
+  for (jws, public_key) in cases {
+    assert!(verify(&jws, &EcDSAJwsVerifier::default(), &public_key).is_ok());
+    assert!(verify(&jws, &CompositeJwsVerifier::default(), &public_key).is_ok());
+  }
+}
+
+#[cfg(feature = "es256")]
+#[test]
+fn test_invalid_signature() {
+  let (jws, public_key) = es256();
+  let (head, signature) = jws.rsplit_once('.').unwrap();
+  let mut signature: Vec<u8> = jwu::decode_b64(signature).unwrap();
+  signature[0] ^= 1;
+  let tampered: String = format!("{head}.{}", jwu::encode_b64(signature));
+
+  assert!(matches!(
+    verify(&tampered, &CompositeJwsVerifier::default(), &public_key)
+      .unwrap_err()
+      .kind(),
+    SignatureVerificationErrorKind::InvalidSignature
+  ));
+}
+
+#[cfg(feature = "es256k")]
+#[test]
+fn test_high_s_es256k_signature() {
+  use k256::ecdsa::signature::Signer;
+  use k256::ecdsa::Signature;
+  use k256::ecdsa::SigningKey;
+  use k256::elliptic_curve::ops::Neg;
+
+  // Signers are not required to produce low `s` values for ES256K.
+  let signing_key: SigningKey = SigningKey::from_slice(&[7; 32]).unwrap();
+  let jws: String = create_jws(JwsAlgorithm::ES256K, |input| {
+    let signature: Signature = signing_key.sign(input);
+    let (r, s) = signature.split_scalars();
+    Signature::from_scalars(r, s.as_ref().neg()).unwrap().to_vec()
+  });
+  let encoded_point = signing_key.verifying_key().to_encoded_point(false);
+  let public_key: Jwk = ec_jwk(EcCurve::Secp256K1, encoded_point.as_bytes());
+
+  assert!(verify(&jws, &EcDSAJwsVerifier::default(), &public_key).is_ok());
+}
+
+#[cfg(all(feature = "es256", feature = "es384"))]
+#[test]
+fn test_mismatched_curve() {
+  let (jws, _) = es256();
+  let (_, p384_key) = es384();
+
+  assert!(matches!(
+    verify(&jws, &EcDSAJwsVerifier::default(), &p384_key)
+      .unwrap_err()
+      .kind(),
+    SignatureVerificationErrorKind::UnsupportedKeyParams
+  ));
+}
+
+#[cfg(feature = "es256")]
+#[test]
+fn test_unsupported_alg() {
+  let (_, public_key) = es256();
+  let input: VerificationInput = VerificationInput {
+    alg: JwsAlgorithm::HS256,
+    signing_input: CLAIMS.into(),
+    decoded_signature: vec![0; 32].into_boxed_slice(),
+  };
+
+  assert!(matches!(
+    EcDSAJwsVerifier::default()
+      .verify(input, &public_key)
+      .unwrap_err()
+      .kind(),
+    SignatureVerificationErrorKind::UnsupportedAlg
+  ));
+}
@@ -34,10 +34,14 @@ pub mod credential {
   //! [Specification](https://www.w3.org/TR/vc-data-model/)
 
   pub use identity_credential::credential::*;
+  #[cfg(feature = "data-integrity")]
+  pub use identity_credential::data_integrity::*;
   #[cfg(feature = "domain-linkage")]
   pub use identity_credential::domain_linkage::*;
   pub use identity_credential::error::*;
   pub use identity_credential::presentation::*;
+  #[cfg(feature = "presentation-exchange")]
+  pub use identity_credential::presentation_exchange::*;
   #[cfg(feature = "revocation-bitmap")]
   pub use identity_credential::revocation::*;
   pub use identity_credential::validator::*;
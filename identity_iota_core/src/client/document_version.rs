@@ -0,0 +1,23 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Timestamp;
+
+use crate::block::output::OutputId;
+use crate::IotaDocument;
+
+/// A historical version of an IOTA DID document, as returned by
+/// [`IotaIdentityClientExt::resolve_did_history`](crate::IotaIdentityClientExt::resolve_did_history()).
+///
+/// A version corresponds to a state of the Alias Output containing the DID document, identified by its state index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IotaDocumentVersion {
+  /// The state index of the Alias Output at this version.
+  pub state_index: u32,
+  /// The identifier of the Alias Output that first contained this version.
+  pub output_id: OutputId,
+  /// The time at which the Alias Output that first contained this version was booked in the ledger.
+  pub timestamp: Timestamp,
+  /// The DID document at this version. Empty and deactivated if the state metadata was empty.
+  pub document: IotaDocument,
+}
@@ -1,6 +1,7 @@
 // Copyright 2020-2023 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use identity_core::common::Timestamp;
 #[cfg(feature = "test")]
 use iota_sdk::client::Client;
 
@@ -19,8 +20,10 @@ use crate::block::protocol::ProtocolParameters;
 use crate::Error;
 use crate::IotaDID;
 use crate::IotaDocument;
+use crate::IotaDocumentVersion;
 use crate::NetworkName;
 use crate::Result;
+use crate::StorageDepositChange;
 
 /// Helper functions necessary for the [`IotaIdentityClientExt`] trait.
 #[cfg_attr(feature = "send-sync-client-ext", async_trait::async_trait)]
@@ -30,6 +33,33 @@ pub trait IotaIdentityClient {
   async fn get_alias_output(&self, alias_id: AliasId) -> Result<(OutputId, AliasOutput)>;
   /// Get the protocol parameters of the node we are trying to connect to.
   async fn get_protocol_parameters(&self) -> Result<ProtocolParameters>;
+
+  /// Fetches the previous state of the Alias Output with the given `alias_id`, i.e. the [`AliasOutput`] consumed by
+  /// the transaction that created the output with the given `output_id`. Returns `None` if that transaction created
+  /// the Alias Output.
+  ///
+  /// The default implementation fails with [`Error::AliasOutputHistoryError`], as not every client has access to
+  /// spent outputs.
+  async fn get_previous_alias_output(
+    &self,
+    alias_id: AliasId,
+    output_id: OutputId,
+  ) -> Result<Option<(OutputId, AliasOutput)>> {
+    let _ = (alias_id, output_id);
+    Err(Error::AliasOutputHistoryError(
+      "the client does not support fetching spent outputs",
+    ))
+  }
+
+  /// Returns the time at which the output with the given `output_id` was booked in the ledger.
+  ///
+  /// The default implementation fails with [`Error::AliasOutputHistoryError`].
+  async fn get_output_timestamp(&self, output_id: OutputId) -> Result<Timestamp> {
+    let _ = output_id;
+    Err(Error::AliasOutputHistoryError(
+      "the client does not support fetching output timestamps",
+    ))
+  }
 }
 
 /// An extension trait that provides helper functions for publication
@@ -82,7 +112,8 @@ pub trait IotaIdentityClientExt: IotaIdentityClient {
 
   /// Fetches the associated Alias Output and updates it with `document` in its state metadata.
   /// The storage deposit on the output is left unchanged. If the size of the document increased,
-  /// the amount should be increased manually.
+  /// the amount should be increased manually, or use
+  /// [`Self::update_did_output_with_storage_deposit`](IotaIdentityClientExt::update_did_output_with_storage_deposit()).
   ///
   /// NOTE: This does *not* publish the updated Alias Output.
   ///
@@ -104,6 +135,45 @@ pub trait IotaIdentityClientExt: IotaIdentityClient {
     alias_output_builder.finish().map_err(Error::AliasOutputBuildError)
   }
 
+  /// Fetches the associated Alias Output and updates it with `document` in its state metadata, like
+  /// [`Self::update_did_output`](IotaIdentityClientExt::update_did_output()), and sets its amount to the minimum
+  /// storage deposit required by the `rent_structure`, which will be fetched from the node if not provided.
+  ///
+  /// The returned [`StorageDepositChange`] reports how many tokens must be added to the output, or are freed from
+  /// it, such that they can be provided or reclaimed when publishing the update.
+  ///
+  /// NOTE: This does *not* publish the updated Alias Output.
+  ///
+  /// # Errors
+  ///
+  /// - Returns `Err` when failing to resolve the DID contained in `document`.
+  /// - [`Error::DIDUpdateError`] when retrieving the `RentStructure` fails.
+  /// - [`Error::AliasOutputBuildError`] when building the Alias Output fails.
+  async fn update_did_output_with_storage_deposit(
+    &self,
+    document: IotaDocument,
+    rent_structure: Option<RentStructure>,
+  ) -> Result<(AliasOutput, StorageDepositChange)> {
+    let alias_output: AliasOutput = self.update_did_output(document).await?;
+    let rent_structure: RentStructure = if let Some(rent) = rent_structure {
+      rent
+    } else {
+      self.get_rent_structure().await?
+    };
+
+    let previous_amount: u64 = alias_output.amount();
+    let alias_output: AliasOutput = AliasOutputBuilder::from(&alias_output)
+      .with_minimum_storage_deposit(rent_structure)
+      .finish()
+      .map_err(Error::AliasOutputBuildError)?;
+    let change: StorageDepositChange = StorageDepositChange {
+      previous_amount,
+      amount: alias_output.amount(),
+    };
+
+    Ok((alias_output, change))
+  }
+
   /// Removes the DID document from the state metadata of its Alias Output,
   /// effectively deactivating it. The storage deposit on the output is left unchanged,
   /// and should be reallocated manually.
@@ -146,6 +216,107 @@ pub trait IotaIdentityClientExt: IotaIdentityClient {
     IotaDocument::unpack_from_output(did, &alias_output, true)
   }
 
+  /// Resolves all versions of the DID document of `did`, ordered from the oldest to the latest version.
+  ///
+  /// The versions are obtained by walking the history of the Alias Output from its latest state back to its
+  /// creation, such that the client needs access to spent outputs, e.g. through a permanode. Governance transitions
+  /// do not change the DID document, hence every state index is listed once, with the output that first contained
+  /// it.
+  ///
+  /// # Errors
+  ///
+  /// - [`NetworkMismatch`](Error::NetworkMismatch) if the network of the DID and client differ.
+  /// - [`NotFound`](iota_sdk::client::Error::NoOutput) if the associated Alias Output was not found, which includes
+  ///   destroyed Alias Outputs.
+  /// - [`AliasOutputHistoryError`](Error::AliasOutputHistoryError) if the history cannot be fetched by the client.
+  async fn resolve_did_history(&self, did: &IotaDID) -> Result<Vec<IotaDocumentVersion>> {
+    validate_network(self, did).await?;
+
+    let alias_id: AliasId = AliasId::from(did);
+    let (mut output_id, mut alias_output) = self.get_alias_output(alias_id).await?;
+    let mut versions: Vec<IotaDocumentVersion> = Vec::new();
+    loop {
+      let timestamp: Timestamp = self.get_output_timestamp(output_id).await?;
+      if versions.last().map(|version| version.state_index) == Some(alias_output.state_index()) {
+        // The state was published by an older output.
+        versions.pop();
+      }
+      versions.push(IotaDocumentVersion {
+        state_index: alias_output.state_index(),
+        output_id,
+        timestamp,
+        document: IotaDocument::unpack_from_output(did, &alias_output, true)?,
+      });
+
+      match self.get_previous_alias_output(alias_id, output_id).await? {
+        Some((previous_id, previous_output)) => (output_id, alias_output) = (previous_id, previous_output),
+        None => break,
+      }
+    }
+
+    versions.reverse();
+    Ok(versions)
+  }
+
+  /// Resolves the [`IotaDocument`] of `did` as it was at the given `state_index` of its Alias Output.
+  ///
+  /// See [`Self::resolve_did_history`](IotaIdentityClientExt::resolve_did_history()) for the requirements on the
+  /// client.
+  ///
+  /// # Errors
+  ///
+  /// Besides the errors of [`Self::resolve_did_history`](IotaIdentityClientExt::resolve_did_history()), fails with
+  /// [`DIDVersionNotFound`](Error::DIDVersionNotFound) if the Alias Output never had the given `state_index`.
+  async fn resolve_did_at_state_index(&self, did: &IotaDID, state_index: u32) -> Result<IotaDocument> {
+    validate_network(self, did).await?;
+
+    let alias_id: AliasId = AliasId::from(did);
+    let (mut output_id, mut alias_output) = self.get_alias_output(alias_id).await?;
+    loop {
+      if alias_output.state_index() == state_index {
+        return IotaDocument::unpack_from_output(did, &alias_output, true);
+      }
+      if alias_output.state_index() < state_index {
+        break;
+      }
+      match self.get_previous_alias_output(alias_id, output_id).await? {
+        Some((previous_id, previous_output)) => (output_id, alias_output) = (previous_id, previous_output),
+        None => break,
+      }
+    }
+
+    Err(Error::DIDVersionNotFound(format!("state index {state_index}")))
+  }
+
+  /// Resolves the [`IotaDocument`] of `did` as it was at the given point in time, according to the time at which
+  /// the states of its Alias Output were booked in the ledger.
+  ///
+  /// This allows, e.g., verifying a credential against the verification methods of its issuer at issuance time.
+  /// See [`Self::resolve_did_history`](IotaIdentityClientExt::resolve_did_history()) for the requirements on the
+  /// client.
+  ///
+  /// # Errors
+  ///
+  /// Besides the errors of [`Self::resolve_did_history`](IotaIdentityClientExt::resolve_did_history()), fails with
+  /// [`DIDVersionNotFound`](Error::DIDVersionNotFound) if the Alias Output was created after `timestamp`.
+  async fn resolve_did_at_time(&self, did: &IotaDID, timestamp: Timestamp) -> Result<IotaDocument> {
+    validate_network(self, did).await?;
+
+    let alias_id: AliasId = AliasId::from(did);
+    let (mut output_id, mut alias_output) = self.get_alias_output(alias_id).await?;
+    loop {
+      if self.get_output_timestamp(output_id).await? <= timestamp {
+        return IotaDocument::unpack_from_output(did, &alias_output, true);
+      }
+      match self.get_previous_alias_output(alias_id, output_id).await? {
+        Some((previous_id, previous_output)) => (output_id, alias_output) = (previous_id, previous_output),
+        None => break,
+      }
+    }
+
+    Err(Error::DIDVersionNotFound(timestamp.to_rfc3339()))
+  }
+
   /// Fetches the [`AliasOutput`] associated with the given DID.
   ///
   /// # Errors
@@ -198,6 +369,8 @@ pub trait IotaIdentityClientExt: IotaIdentityClient {
 impl<T> IotaIdentityClientExt for T where T: IotaIdentityClient {}
 #[cfg(feature = "test")]
 impl IotaIdentityClientExt for Client {}
+#[cfg(all(feature = "test", feature = "memory-client"))]
+impl IotaIdentityClientExt for crate::MemIdentityClient {}
 
 pub(super) async fn validate_network<T>(client: &T, did: &IotaDID) -> Result<()>
 where
@@ -215,3 +388,239 @@ where
   };
   Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+  use identity_verification::MethodScope;
+
+  use crate::block::address::Ed25519Address;
+  use crate::block::output::Output;
+  use crate::block::output::Rent;
+  use crate::block::payload::transaction::TransactionId;
+  use crate::document::test_utils::generate_method;
+
+  use super::*;
+
+  /// A client with the history of a single Alias Output, ordered from its creation to its latest state.
+  struct MockClient {
+    history: Vec<(OutputId, AliasOutput, Timestamp)>,
+  }
+
+  #[cfg_attr(feature = "send-sync-client-ext", async_trait::async_trait)]
+  #[cfg_attr(not(feature = "send-sync-client-ext"), async_trait::async_trait(?Send))]
+  impl IotaIdentityClient for MockClient {
+    async fn get_alias_output(&self, _alias_id: AliasId) -> Result<(OutputId, AliasOutput)> {
+      let (output_id, alias_output, _) = self.history.last().unwrap();
+      Ok((*output_id, alias_output.clone()))
+    }
+
+    async fn get_protocol_parameters(&self) -> Result<ProtocolParameters> {
+      Ok(ProtocolParameters::default())
+    }
+
+    async fn get_previous_alias_output(
+      &self,
+      _alias_id: AliasId,
+      output_id: OutputId,
+    ) -> Result<Option<(OutputId, AliasOutput)>> {
+      let index: usize = self.index(output_id);
+      Ok(
+        index
+          .checked_sub(1)
+          .map(|index| (self.history[index].0, self.history[index].1.clone())),
+      )
+    }
+
+    async fn get_output_timestamp(&self, output_id: OutputId) -> Result<Timestamp> {
+      Ok(self.history[self.index(output_id)].2)
+    }
+  }
+
+  #[cfg(feature = "test")]
+  impl IotaIdentityClientExt for MockClient {}
+
+  impl MockClient {
+    fn index(&self, output_id: OutputId) -> usize {
+      self.history.iter().position(|(id, _, _)| *id == output_id).unwrap()
+    }
+  }
+
+  fn alias_output(alias_id: AliasId, state_index: u32, document: Option<IotaDocument>) -> AliasOutput {
+    let address: Address = Address::Ed25519(Ed25519Address::new([3; 32]));
+    AliasOutputBuilder::new_with_amount(1_000_000, alias_id)
+      .with_state_index(state_index)
+      .with_state_metadata(document.map(|document| document.pack().unwrap()).unwrap_or_default())
+      .add_unlock_condition(UnlockCondition::StateControllerAddress(
+        StateControllerAddressUnlockCondition::new(address),
+      ))
+      .add_unlock_condition(UnlockCondition::GovernorAddress(GovernorAddressUnlockCondition::new(
+        address,
+      )))
+      .finish()
+      .unwrap()
+  }
+
+  fn output_id(index: u8) -> OutputId {
+    OutputId::new(TransactionId::new([index; 32]), 0).unwrap()
+  }
+
+  fn time(seconds: i64) -> Timestamp {
+    Timestamp::from_unix(seconds).unwrap()
+  }
+
+  /// Returns a DID and a client whose Alias Output was created at 100 with state index 0, updated at 200 to state
+  /// index 1, transitioned by its governor at 250 and deactivated at 300 with state index 2.
+  fn setup() -> (IotaDID, MockClient) {
+    let alias_id: AliasId = AliasId::new([1; 32]);
+    let did: IotaDID = IotaDID::new(&[1; 32], &NetworkName::try_from("smr").unwrap());
+
+    let document: IotaDocument = IotaDocument::new_with_id(did.clone());
+    let mut updated_document: IotaDocument = document.clone();
+    updated_document
+      .insert_method(generate_method(&did, "key-1"), MethodScope::VerificationMethod)
+      .unwrap();
+
+    let history = vec![
+      (
+        output_id(0),
+        alias_output(AliasId::null(), 0, Some(document)),
+        time(100),
+      ),
+      (
+        output_id(1),
+        alias_output(alias_id, 1, Some(updated_document.clone())),
+        time(200),
+      ),
+      (
+        output_id(2),
+        alias_output(alias_id, 1, Some(updated_document)),
+        time(250),
+      ),
+      (output_id(3), alias_output(alias_id, 2, None), time(300)),
+    ];
+    (did, MockClient { history })
+  }
+
+  fn has_key(document: &IotaDocument) -> bool {
+    document.resolve_method("key-1", None).is_some()
+  }
+
+  #[tokio::test]
+  async fn resolve_did_history() {
+    let (did, client) = setup();
+    let versions: Vec<IotaDocumentVersion> = client.resolve_did_history(&did).await.unwrap();
+
+    assert_eq!(versions.len(), 3);
+    assert_eq!(
+      versions.iter().map(|version| version.state_index).collect::<Vec<_>>(),
+      [0, 1, 2]
+    );
+    assert_eq!(
+      versions.iter().map(|version| version.output_id).collect::<Vec<_>>(),
+      [output_id(0), output_id(1), output_id(3)]
+    );
+    assert_eq!(
+      versions.iter().map(|version| version.timestamp).collect::<Vec<_>>(),
+      [time(100), time(200), time(300)]
+    );
+    assert!(versions.iter().all(|version| version.document.id() == &did));
+    assert!(!has_key(&versions[0].document));
+    assert!(has_key(&versions[1].document));
+    assert_eq!(versions[2].document.metadata.deactivated, Some(true));
+  }
+
+  #[tokio::test]
+  async fn resolve_did_at_state_index() {
+    let (did, client) = setup();
+    assert!(!has_key(&client.resolve_did_at_state_index(&did, 0).await.unwrap()));
+    assert!(has_key(&client.resolve_did_at_state_index(&did, 1).await.unwrap()));
+    let document: IotaDocument = client.resolve_did_at_state_index(&did, 2).await.unwrap();
+    assert_eq!(document.metadata.deactivated, Some(true));
+    assert!(matches!(
+      client.resolve_did_at_state_index(&did, 3).await.unwrap_err(),
+      Error::DIDVersionNotFound(_)
+    ));
+  }
+
+  #[tokio::test]
+  async fn resolve_did_at_time() {
+    let (did, client) = setup();
+    assert!(!has_key(&client.resolve_did_at_time(&did, time(100)).await.unwrap()));
+    assert!(!has_key(&client.resolve_did_at_time(&did, time(199)).await.unwrap()));
+    assert!(has_key(&client.resolve_did_at_time(&did, time(200)).await.unwrap()));
+    assert!(has_key(&client.resolve_did_at_time(&did, time(299)).await.unwrap()));
+    let document: IotaDocument = client.resolve_did_at_time(&did, time(1000)).await.unwrap();
+    assert_eq!(document.metadata.deactivated, Some(true));
+    assert!(matches!(
+      client.resolve_did_at_time(&did, time(99)).await.unwrap_err(),
+      Error::DIDVersionNotFound(_)
+    ));
+  }
+
+  #[tokio::test]
+  async fn history_requires_client_support() {
+    struct LatestOnlyClient(MockClient);
+
+    #[cfg_attr(feature = "send-sync-client-ext", async_trait::async_trait)]
+    #[cfg_attr(not(feature = "send-sync-client-ext"), async_trait::async_trait(?Send))]
+    impl IotaIdentityClient for LatestOnlyClient {
+      async fn get_alias_output(&self, alias_id: AliasId) -> Result<(OutputId, AliasOutput)> {
+        self.0.get_alias_output(alias_id).await
+      }
+
+      async fn get_protocol_parameters(&self) -> Result<ProtocolParameters> {
+        self.0.get_protocol_parameters().await
+      }
+    }
+
+    #[cfg(feature = "test")]
+    impl IotaIdentityClientExt for LatestOnlyClient {}
+
+    let (did, client) = setup();
+    let client: LatestOnlyClient = LatestOnlyClient(client);
+    assert!(client.resolve_did(&did).await.is_ok());
+    assert!(matches!(
+      client.resolve_did_history(&did).await.unwrap_err(),
+      Error::AliasOutputHistoryError(_)
+    ));
+    // The latest state is available without history.
+    assert!(client.resolve_did_at_state_index(&did, 2).await.is_ok());
+  }
+
+  #[tokio::test]
+  async fn update_did_output_with_storage_deposit() {
+    let (did, client) = setup();
+    let mut document: IotaDocument = client.resolve_did_at_state_index(&did, 1).await.unwrap();
+    document
+      .insert_method(generate_method(&did, "key-2"), MethodScope::VerificationMethod)
+      .unwrap();
+
+    // The default rent structure requires less than the current amount.
+    let (alias_output, change) = client
+      .update_did_output_with_storage_deposit(document.clone(), None)
+      .await
+      .unwrap();
+    assert_eq!(change.previous_amount, 1_000_000);
+    assert_eq!(change.amount, alias_output.amount());
+    assert_eq!(
+      alias_output.amount(),
+      Output::Alias(alias_output.clone()).rent_cost(ProtocolParameters::default().rent_structure())
+    );
+    assert_eq!(change.required(), 0);
+    assert_eq!(change.freed(), 1_000_000 - alias_output.amount());
+    assert!(IotaDocument::unpack_from_output(&did, &alias_output, true).is_ok());
+
+    // A more expensive rent structure requires additional funds.
+    let rent_structure: RentStructure = RentStructure::default().with_byte_cost(10_000);
+    let (alias_output, change) = client
+      .update_did_output_with_storage_deposit(document, Some(rent_structure))
+      .await
+      .unwrap();
+    assert_eq!(
+      alias_output.amount(),
+      Output::Alias(alias_output.clone()).rent_cost(&rent_structure)
+    );
+    assert_eq!(change.required(), alias_output.amount() - 1_000_000);
+    assert_eq!(change.freed(), 0);
+  }
+}
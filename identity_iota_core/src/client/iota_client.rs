@@ -3,12 +3,14 @@
 
 use std::ops::Deref;
 
+use identity_core::common::Timestamp;
 use iota_sdk::client::api::input_selection::Burn;
 use iota_sdk::client::secret::SecretManager;
 use iota_sdk::client::Client;
 use iota_sdk::types::block::protocol::ProtocolParameters;
 
 use crate::block::address::Address;
+use crate::block::input::Input;
 use crate::block::output::unlock_condition::AddressUnlockCondition;
 use crate::block::output::AliasId;
 use crate::block::output::AliasOutput;
@@ -16,6 +18,8 @@ use crate::block::output::BasicOutputBuilder;
 use crate::block::output::Output;
 use crate::block::output::OutputId;
 use crate::block::output::UnlockCondition;
+use crate::block::payload::transaction::TransactionEssence;
+use crate::block::payload::Payload;
 use crate::block::Block;
 use crate::client::identity_client::validate_network;
 use crate::error::Result;
@@ -137,6 +141,51 @@ impl IotaIdentityClient for Client {
       Err(Error::NotAnAliasOutput(output_id))
     }
   }
+
+  async fn get_previous_alias_output(
+    &self,
+    alias_id: AliasId,
+    output_id: OutputId,
+  ) -> Result<Option<(OutputId, AliasOutput)>> {
+    let block: Block = self
+      .get_included_block(output_id.transaction_id())
+      .await
+      .map_err(Error::DIDResolutionError)?;
+    let Some(Payload::Transaction(transaction_payload)) = block.payload() else {
+      return Err(Error::AliasOutputHistoryError(
+        "expected the block of the output to contain a transaction",
+      ));
+    };
+    let TransactionEssence::Regular(essence) = transaction_payload.essence();
+
+    for input in essence.inputs() {
+      let Input::Utxo(utxo_input) = input else {
+        continue;
+      };
+      let input_id: OutputId = *utxo_input.output_id();
+      let output: Output = self
+        .get_output(&input_id)
+        .await
+        .map_err(Error::DIDResolutionError)?
+        .into_output();
+      if let Output::Alias(alias_output) = output {
+        if alias_output.alias_id_non_null(&input_id) == alias_id {
+          return Ok(Some((input_id, alias_output)));
+        }
+      }
+    }
+    Ok(None)
+  }
+
+  async fn get_output_timestamp(&self, output_id: OutputId) -> Result<Timestamp> {
+    let timestamp: u32 = self
+      .get_output_metadata(&output_id)
+      .await
+      .map_err(Error::DIDResolutionError)?
+      .milestone_timestamp_booked();
+    Timestamp::from_unix(timestamp.into())
+      .map_err(|err| Error::SerializationError("invalid output timestamp", Some(err)))
+  }
 }
 
 /// Publishes an `alias_output`.
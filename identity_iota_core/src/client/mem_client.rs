@@ -0,0 +1,348 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use identity_core::common::Timestamp;
+use tokio::sync::RwLock;
+use tokio::sync::RwLockReadGuard;
+use tokio::sync::RwLockWriteGuard;
+
+use crate::block::output::AliasId;
+use crate::block::output::AliasOutput;
+use crate::block::output::Output;
+use crate::block::output::OutputId;
+use crate::block::output::RentStructure;
+use crate::block::payload::transaction::TransactionId;
+use crate::block::protocol::ProtocolParameters;
+use crate::Error;
+use crate::IotaDID;
+use crate::IotaDocument;
+use crate::IotaIdentityClient;
+use crate::NetworkName;
+use crate::Result;
+
+/// The states of an Alias Output, ordered from its creation to its latest state.
+type AliasOutputHistory = Vec<(OutputId, AliasOutput, Timestamp)>;
+
+/// An insecure, in-memory [`IotaIdentityClient`] implementation that may be used in tests.
+///
+/// The client simulates a ledger that stores the history of Alias Outputs and enforces state index increments and
+/// storage deposits according to its [`ProtocolParameters`]. DID documents can be published, updated and deactivated
+/// using the [`IotaIdentityClientExt`](crate::IotaIdentityClientExt) methods together with
+/// [`MemIdentityClient::publish_did_output`], and resolved by the same methods or by a `Resolver`.
+///
+/// Clones of a [`MemIdentityClient`] share the same ledger.
+#[derive(Debug, Clone)]
+pub struct MemIdentityClient {
+  ledger: Arc<RwLock<MemLedger>>,
+}
+
+#[derive(Debug)]
+struct MemLedger {
+  protocol_parameters: ProtocolParameters,
+  aliases: HashMap<AliasId, AliasOutputHistory>,
+  destroyed: Vec<AliasId>,
+  transaction_count: u64,
+}
+
+impl MemIdentityClient {
+  /// Creates a new client with an empty ledger and the default [`ProtocolParameters`] of the `smr` network.
+  pub fn new() -> Self {
+    Self::new_with_protocol_parameters(ProtocolParameters::default())
+  }
+
+  /// Creates a new client with an empty ledger and the given `protocol_parameters`.
+  pub fn new_with_protocol_parameters(protocol_parameters: ProtocolParameters) -> Self {
+    Self {
+      ledger: Arc::new(RwLock::new(MemLedger {
+        protocol_parameters,
+        aliases: HashMap::new(),
+        destroyed: Vec::new(),
+        transaction_count: 0,
+      })),
+    }
+  }
+
+  /// Replaces the [`ProtocolParameters`] of the ledger, e.g. to simulate a change of the `RentStructure`.
+  ///
+  /// Outputs that are already stored are not affected.
+  pub async fn set_protocol_parameters(&self, protocol_parameters: ProtocolParameters) {
+    self.ledger.write().await.protocol_parameters = protocol_parameters;
+  }
+
+  /// Publishes the given `alias_output` to the ledger and returns the DID document it contains.
+  ///
+  /// An Alias Output with a null [`AliasId`] creates a new DID, whose identifier is derived from the id of the
+  /// published output. Otherwise, the output replaces the latest state of the existing Alias Output, and either
+  /// increments its state index by one, or keeps the state index and state metadata unchanged.
+  ///
+  /// # Errors
+  ///
+  /// - [`Error::InvalidAliasOutputTransition`] if the state index is invalid or the storage deposit is insufficient.
+  /// - [`Error::AliasOutputNotFound`] if the Alias Output to update does not exist.
+  pub async fn publish_did_output(&self, alias_output: AliasOutput) -> Result<IotaDocument> {
+    let mut ledger: RwLockWriteGuard<'_, MemLedger> = self.ledger.write().await;
+    let network_name: NetworkName = NetworkName::try_from(ledger.protocol_parameters.bech32_hrp().to_string())?;
+
+    let rent_structure: RentStructure = *ledger.protocol_parameters.rent_structure();
+    let token_supply: u64 = ledger.protocol_parameters.token_supply();
+    Output::Alias(alias_output.clone())
+      .verify_storage_deposit(rent_structure, token_supply)
+      .map_err(|_| Error::InvalidAliasOutputTransition("insufficient storage deposit"))?;
+
+    let alias_id: AliasId = if alias_output.alias_id().is_null() {
+      if alias_output.state_index() != 0 {
+        return Err(Error::InvalidAliasOutputTransition(
+          "a new alias output must have state index 0",
+        ));
+      }
+      let output_id: OutputId = ledger.next_output_id();
+      let alias_id: AliasId = AliasId::from(&output_id);
+      ledger
+        .aliases
+        .insert(alias_id, vec![(output_id, alias_output.clone(), Timestamp::now_utc())]);
+      alias_id
+    } else {
+      let alias_id: AliasId = *alias_output.alias_id();
+      let (_, latest_output, _) = ledger.latest(alias_id).ok_or(Error::AliasOutputNotFound(alias_id))?;
+      let is_state_transition: bool = alias_output.state_index() == latest_output.state_index() + 1;
+      let is_governance_transition: bool = alias_output.state_index() == latest_output.state_index()
+        && alias_output.state_metadata() == latest_output.state_metadata();
+      if !is_state_transition && !is_governance_transition {
+        return Err(Error::InvalidAliasOutputTransition(
+          "the state index must be incremented by one, or the state must remain unchanged",
+        ));
+      }
+
+      let output_id: OutputId = ledger.next_output_id();
+      ledger
+        .aliases
+        .entry(alias_id)
+        .or_default()
+        .push((output_id, alias_output.clone(), Timestamp::now_utc()));
+      alias_id
+    };
+
+    let did: IotaDID = IotaDID::new(&alias_id, &network_name);
+    IotaDocument::unpack_from_output(&did, &alias_output, true)
+  }
+
+  /// Destroys the Alias Output containing the given `did`, such that it can no longer be resolved.
+  ///
+  /// # Errors
+  ///
+  /// [`Error::AliasOutputNotFound`] if the Alias Output does not exist.
+  pub async fn delete_did_output(&self, did: &IotaDID) -> Result<()> {
+    let alias_id: AliasId = AliasId::from(did);
+    let mut ledger: RwLockWriteGuard<'_, MemLedger> = self.ledger.write().await;
+    ledger.latest(alias_id).ok_or(Error::AliasOutputNotFound(alias_id))?;
+    ledger.destroyed.push(alias_id);
+    Ok(())
+  }
+
+  /// Returns the state index of the latest Alias Output containing the given `did`, or `None` if it does not exist.
+  pub async fn state_index(&self, did: &IotaDID) -> Option<u32> {
+    let ledger: RwLockReadGuard<'_, MemLedger> = self.ledger.read().await;
+    ledger
+      .latest(AliasId::from(did))
+      .map(|(_, alias_output, _)| alias_output.state_index())
+  }
+}
+
+impl Default for MemIdentityClient {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl MemLedger {
+  fn next_output_id(&mut self) -> OutputId {
+    self.transaction_count += 1;
+    let mut transaction_id: [u8; TransactionId::LENGTH] = [0; TransactionId::LENGTH];
+    transaction_id[TransactionId::LENGTH - 8..].copy_from_slice(&self.transaction_count.to_be_bytes());
+    // PANIC: the output index 0 is always valid.
+    OutputId::new(TransactionId::new(transaction_id), 0).expect("valid output index")
+  }
+
+  /// Returns the history of the Alias Output with the given `alias_id`, unless it does not exist or was destroyed.
+  fn history(&self, alias_id: AliasId) -> Option<&AliasOutputHistory> {
+    self
+      .aliases
+      .get(&alias_id)
+      .filter(|_| !self.destroyed.contains(&alias_id))
+  }
+
+  fn latest(&self, alias_id: AliasId) -> Option<&(OutputId, AliasOutput, Timestamp)> {
+    self.history(alias_id).and_then(|history| history.last())
+  }
+}
+
+#[cfg_attr(feature = "send-sync-client-ext", async_trait::async_trait)]
+#[cfg_attr(not(feature = "send-sync-client-ext"), async_trait::async_trait(?Send))]
+impl IotaIdentityClient for MemIdentityClient {
+  async fn get_alias_output(&self, alias_id: AliasId) -> Result<(OutputId, AliasOutput)> {
+    let ledger: RwLockReadGuard<'_, MemLedger> = self.ledger.read().await;
+    let (output_id, alias_output, _) = ledger.latest(alias_id).ok_or(Error::AliasOutputNotFound(alias_id))?;
+    Ok((*output_id, alias_output.clone()))
+  }
+
+  async fn get_protocol_parameters(&self) -> Result<ProtocolParameters> {
+    Ok(self.ledger.read().await.protocol_parameters.clone())
+  }
+
+  async fn get_previous_alias_output(
+    &self,
+    alias_id: AliasId,
+    output_id: OutputId,
+  ) -> Result<Option<(OutputId, AliasOutput)>> {
+    let ledger: RwLockReadGuard<'_, MemLedger> = self.ledger.read().await;
+    let history: &AliasOutputHistory = ledger.history(alias_id).ok_or(Error::AliasOutputNotFound(alias_id))?;
+    let index: usize = history
+      .iter()
+      .position(|(id, _, _)| *id == output_id)
+      .ok_or(Error::AliasOutputHistoryError(
+        "output not found in the history of the alias output",
+      ))?;
+    Ok(
+      index
+        .checked_sub(1)
+        .map(|index| (history[index].0, history[index].1.clone())),
+    )
+  }
+
+  async fn get_output_timestamp(&self, output_id: OutputId) -> Result<Timestamp> {
+    let ledger: RwLockReadGuard<'_, MemLedger> = self.ledger.read().await;
+    ledger
+      .aliases
+      .values()
+      .flatten()
+      .find(|(id, _, _)| *id == output_id)
+      .map(|(_, _, timestamp)| *timestamp)
+      .ok_or(Error::AliasOutputHistoryError("output not found in the ledger"))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_verification::MethodScope;
+
+  use crate::block::address::Address;
+  use crate::block::address::Ed25519Address;
+  use crate::block::output::AliasOutput;
+  use crate::document::test_utils::generate_method;
+  use crate::IotaDocumentVersion;
+  use crate::IotaIdentityClientExt;
+
+  use super::*;
+
+  async fn publish_new_did(client: &MemIdentityClient) -> IotaDocument {
+    let address: Address = Address::Ed25519(Ed25519Address::new([3; 32]));
+    let document: IotaDocument = IotaDocument::new(&client.network_name().await.unwrap());
+    let alias_output: AliasOutput = client.new_did_output(address, document, None).await.unwrap();
+    client.publish_did_output(alias_output).await.unwrap()
+  }
+
+  #[tokio::test]
+  async fn publish_update_deactivate_resolve() {
+    let client: MemIdentityClient = MemIdentityClient::new();
+    let document: IotaDocument = publish_new_did(&client).await;
+    let did: IotaDID = document.id().clone();
+    assert!(!did.is_placeholder());
+    assert_eq!(client.resolve_did(&did).await.unwrap(), document);
+    assert_eq!(client.state_index(&did).await, Some(0));
+
+    let mut updated_document: IotaDocument = document.clone();
+    updated_document
+      .insert_method(generate_method(&did, "key-1"), MethodScope::VerificationMethod)
+      .unwrap();
+    let (alias_output, _) = client
+      .update_did_output_with_storage_deposit(updated_document.clone(), None)
+      .await
+      .unwrap();
+    assert_eq!(client.publish_did_output(alias_output).await.unwrap(), updated_document);
+    assert_eq!(client.resolve_did(&did).await.unwrap(), updated_document);
+    assert_eq!(client.state_index(&did).await, Some(1));
+
+    let alias_output: AliasOutput = client.deactivate_did_output(&did).await.unwrap();
+    client.publish_did_output(alias_output).await.unwrap();
+    let deactivated_document: IotaDocument = client.resolve_did(&did).await.unwrap();
+    assert_eq!(deactivated_document.metadata.deactivated, Some(true));
+    assert_eq!(client.state_index(&did).await, Some(2));
+
+    let versions: Vec<IotaDocumentVersion> = client.resolve_did_history(&did).await.unwrap();
+    assert_eq!(versions.len(), 3);
+    assert_eq!(versions[1].document, updated_document);
+
+    client.delete_did_output(&did).await.unwrap();
+    assert!(matches!(
+      client.resolve_did(&did).await.unwrap_err(),
+      Error::AliasOutputNotFound(_)
+    ));
+    assert_eq!(client.state_index(&did).await, None);
+  }
+
+  #[tokio::test]
+  async fn clones_share_the_ledger() {
+    let client: MemIdentityClient = MemIdentityClient::new();
+    let document: IotaDocument = publish_new_did(&client.clone()).await;
+    assert_eq!(client.resolve_did(document.id()).await.unwrap(), document);
+    assert_ne!(publish_new_did(&client).await.id(), document.id());
+  }
+
+  #[tokio::test]
+  async fn invalid_transitions_are_rejected() {
+    let client: MemIdentityClient = MemIdentityClient::new();
+    let document: IotaDocument = publish_new_did(&client).await;
+
+    // Publishing a state based on an outdated state.
+    let mut updated_document: IotaDocument = document.clone();
+    updated_document
+      .insert_method(
+        generate_method(updated_document.id(), "key-1"),
+        MethodScope::VerificationMethod,
+      )
+      .unwrap();
+    let alias_output: AliasOutput = client.update_did_output(document.clone()).await.unwrap();
+    let outdated_alias_output: AliasOutput = client.update_did_output(updated_document).await.unwrap();
+    client.publish_did_output(alias_output).await.unwrap();
+    assert!(matches!(
+      client.publish_did_output(outdated_alias_output).await.unwrap_err(),
+      Error::InvalidAliasOutputTransition(_)
+    ));
+
+    // Publishing an output with an insufficient storage deposit.
+    let protocol_parameters: ProtocolParameters = ProtocolParameters::new(
+      2,
+      "shimmer".to_owned(),
+      "smr",
+      1500,
+      15,
+      RentStructure::default().with_byte_cost(10_000),
+      1_813_620_509_061_365,
+    )
+    .unwrap();
+    client.set_protocol_parameters(protocol_parameters).await;
+    let alias_output: AliasOutput = client.update_did_output(document.clone()).await.unwrap();
+    assert!(matches!(
+      client.publish_did_output(alias_output).await.unwrap_err(),
+      Error::InvalidAliasOutputTransition(_)
+    ));
+    let (alias_output, _) = client
+      .update_did_output_with_storage_deposit(document, None)
+      .await
+      .unwrap();
+    assert!(client.publish_did_output(alias_output).await.is_ok());
+
+    // Updating a DID that does not exist.
+    let unknown_did: IotaDID = IotaDID::new(&[1; 32], &client.network_name().await.unwrap());
+    assert!(matches!(
+      client
+        .update_did_output(IotaDocument::new_with_id(unknown_did))
+        .await
+        .unwrap_err(),
+      Error::AliasOutputNotFound(_)
+    ));
+  }
+}
@@ -1,12 +1,20 @@
 // Copyright 2020-2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+pub use document_version::IotaDocumentVersion;
 pub use identity_client::IotaIdentityClient;
 pub use identity_client::IotaIdentityClientExt;
+#[cfg(feature = "memory-client")]
+pub use mem_client::MemIdentityClient;
+pub use storage_deposit::StorageDepositChange;
 
 #[cfg(feature = "iota-client")]
 pub use self::iota_client::IotaClientExt;
 
+mod document_version;
 mod identity_client;
 #[cfg(feature = "iota-client")]
 mod iota_client;
+#[cfg(feature = "memory-client")]
+mod mem_client;
+mod storage_deposit;
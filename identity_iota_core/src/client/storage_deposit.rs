@@ -0,0 +1,25 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+/// The change of the storage deposit of an Alias Output whose amount was set to the minimum storage deposit, as
+/// returned by
+/// [`IotaIdentityClientExt::update_did_output_with_storage_deposit`](crate::IotaIdentityClientExt::update_did_output_with_storage_deposit()).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StorageDepositChange {
+  /// The amount of the Alias Output before the update.
+  pub previous_amount: u64,
+  /// The amount of the updated Alias Output, which is the minimum storage deposit.
+  pub amount: u64,
+}
+
+impl StorageDepositChange {
+  /// Returns the amount of tokens that must be added to the Alias Output to cover its storage deposit.
+  pub fn required(&self) -> u64 {
+    self.amount.saturating_sub(self.previous_amount)
+  }
+
+  /// Returns the amount of tokens that are freed from the Alias Output, and can be reclaimed.
+  pub fn freed(&self) -> u64 {
+    self.previous_amount.saturating_sub(self.amount)
+  }
+}
@@ -63,6 +63,20 @@ pub enum Error {
   /// Caused by retrieving an output that is expected to be an alias output but is not.
   #[error("output with id `{0}` is not an alias output")]
   NotAnAliasOutput(iota_sdk::types::block::output::OutputId),
+  #[cfg(feature = "client")]
+  /// Caused by looking up an Alias Output that does not exist in the ledger of an in-memory client.
+  #[error("alias output `{0}` not found")]
+  AliasOutputNotFound(crate::block::output::AliasId),
+  #[cfg(feature = "client")]
+  /// Caused by publishing an Alias Output that is rejected by the ledger of an in-memory client.
+  #[error("invalid alias output transition: {0}")]
+  InvalidAliasOutputTransition(&'static str),
+  /// Caused by a failure to walk the history of an Alias Output.
+  #[error("alias output history: {0}")]
+  AliasOutputHistoryError(&'static str),
+  /// Caused by requesting a version of a DID document that does not exist.
+  #[error("no version of the DID document exists at {0}")]
+  DIDVersionNotFound(String),
   /// Caused by an error when constructing an output id.
   #[error("conversion to an OutputId failed: {0}")]
   OutputIdConversionError(String),
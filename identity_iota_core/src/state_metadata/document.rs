@@ -1,6 +1,13 @@
 // Copyright 2020-2023 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use std::borrow::Cow;
+use std::io::Read;
+use std::io::Write;
+
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
 use identity_core::convert::FromJson;
 use identity_core::convert::ToJson;
 use identity_did::CoreDID;
@@ -23,6 +30,14 @@ pub(crate) static PLACEHOLDER_DID: Lazy<CoreDID> = Lazy::new(|| CoreDID::parse("
 /// Magic bytes used to mark DID documents.
 const DID_MARKER: &[u8] = b"DID";
 
+/// The maximum size of a decompressed document, guarding against decompression bombs.
+const MAX_DECOMPRESSED_SIZE: u64 = 1_048_576;
+
+/// The Brotli compression level, from 0 to 11.
+const BROTLI_QUALITY: u32 = 11;
+/// The base 2 logarithm of the Brotli window size.
+const BROTLI_WINDOW_SIZE: u32 = 22;
+
 /// Intermediate representation of the DID document as it is contained in the state metadata of
 /// an Alias Output.
 ///
@@ -81,9 +96,24 @@ impl StateMetadataDocument {
     self.metadata.state_controller_address = None;
 
     let encoded_message_data: Vec<u8> = match encoding {
-      StateMetadataEncoding::Json => self
-        .to_json_vec()
-        .map_err(|err| Error::SerializationError("failed to serialize document to JSON", Some(err)))?,
+      StateMetadataEncoding::Cbor => {
+        let mut encoded: Vec<u8> = Vec::new();
+        ciborium::into_writer(&self, &mut encoded)
+          .map_err(|_| Error::SerializationError("failed to serialize document to CBOR", None))?;
+        encoded
+      }
+      _ => {
+        let json: Vec<u8> = self
+          .to_json_vec()
+          .map_err(|err| Error::SerializationError("failed to serialize document to JSON", Some(err)))?;
+        match encoding {
+          StateMetadataEncoding::JsonDeflate => compress_deflate(&json)
+            .map_err(|_| Error::SerializationError("failed to compress document with DEFLATE", None))?,
+          StateMetadataEncoding::JsonBrotli => compress_brotli(&json)
+            .map_err(|_| Error::SerializationError("failed to compress document with Brotli", None))?,
+          _ => json,
+        }
+      }
     };
 
     // Prepend flags and length.
@@ -153,17 +183,58 @@ impl StateMetadataDocument {
       ))
       .map_err(Error::InvalidDoc)?;
 
-    match encoding {
-      StateMetadataEncoding::Json => StateMetadataDocument::from_json_slice(data).map_err(|err| {
-        Error::SerializationError(
-          "state metadata decoding: failed to deserialize JSON document",
-          Some(err),
-        )
-      }),
-    }
+    let json: Cow<'_, [u8]> = match encoding {
+      StateMetadataEncoding::Json => Cow::Borrowed(data),
+      StateMetadataEncoding::JsonDeflate => Cow::Owned(
+        decompress(DeflateDecoder::new(data))
+          .map_err(|_| Error::InvalidStateMetadata("failed to decompress DEFLATE document within the maximum size"))?,
+      ),
+      StateMetadataEncoding::JsonBrotli => Cow::Owned(
+        decompress(brotli::Decompressor::new(data, 4096))
+          .map_err(|_| Error::InvalidStateMetadata("failed to decompress Brotli document within the maximum size"))?,
+      ),
+      StateMetadataEncoding::Cbor => {
+        return ciborium::from_reader(data)
+          .map_err(|_| Error::SerializationError("state metadata decoding: failed to deserialize CBOR document", None))
+      }
+    };
+
+    StateMetadataDocument::from_json_slice(&json).map_err(|err| {
+      Error::SerializationError(
+        "state metadata decoding: failed to deserialize JSON document",
+        Some(err),
+      )
+    })
   }
 }
 
+fn compress_deflate(data: &[u8]) -> std::io::Result<Vec<u8>> {
+  let mut encoder: DeflateEncoder<Vec<u8>> = DeflateEncoder::new(Vec::new(), Compression::best());
+  encoder.write_all(data)?;
+  encoder.finish()
+}
+
+fn compress_brotli(data: &[u8]) -> std::io::Result<Vec<u8>> {
+  let mut encoder: brotli::CompressorWriter<Vec<u8>> =
+    brotli::CompressorWriter::new(Vec::new(), 4096, BROTLI_QUALITY, BROTLI_WINDOW_SIZE);
+  encoder.write_all(data)?;
+  // Finishes the stream.
+  Ok(encoder.into_inner())
+}
+
+/// Reads the decompressed data from the given `decoder`, failing if it exceeds [`MAX_DECOMPRESSED_SIZE`].
+fn decompress(decoder: impl Read) -> std::io::Result<Vec<u8>> {
+  let mut data: Vec<u8> = Vec::new();
+  decoder.take(MAX_DECOMPRESSED_SIZE + 1).read_to_end(&mut data)?;
+  if data.len() as u64 > MAX_DECOMPRESSED_SIZE {
+    return Err(std::io::Error::new(
+      std::io::ErrorKind::InvalidData,
+      "decompressed document exceeds the maximum size",
+    ));
+  }
+  Ok(data)
+}
+
 /// Prepends the message flags and marker magic bytes to the data in the following order:
 /// `[marker, version, encoding, data length, data]`.
 fn add_flags_to_message(
@@ -217,6 +288,7 @@ mod tests {
   use identity_did::DID;
   use identity_verification::MethodScope;
 
+  use crate::state_metadata::document::add_flags_to_message;
   use crate::state_metadata::document::DID_MARKER;
   use crate::state_metadata::PLACEHOLDER_DID;
   use crate::test_utils::generate_method;
@@ -534,4 +606,94 @@ mod tests {
       unpacked_doc.document.properties()
     );
   }
+
+  const ENCODINGS: [StateMetadataEncoding; 4] = [
+    StateMetadataEncoding::Json,
+    StateMetadataEncoding::JsonDeflate,
+    StateMetadataEncoding::JsonBrotli,
+    StateMetadataEncoding::Cbor,
+  ];
+
+  #[test]
+  fn test_encodings_roundtrip() {
+    let TestSetup { document, did_self, .. } = test_document();
+
+    for encoding in ENCODINGS {
+      let packed: Vec<u8> = StateMetadataDocument::from(document.clone()).pack(encoding).unwrap();
+      assert_eq!(&packed[0..3], DID_MARKER);
+      assert_eq!(packed[3], StateMetadataVersion::V1 as u8);
+      assert_eq!(packed[4], encoding as u8);
+      assert_eq!(&packed[5..=6], ((packed.len() - 7) as u16).to_le_bytes().as_ref());
+
+      let unpacked: IotaDocument = StateMetadataDocument::unpack(&packed)
+        .unwrap()
+        .into_iota_document(&did_self)
+        .unwrap();
+      assert_eq!(unpacked, document, "{encoding:?}");
+    }
+  }
+
+  #[test]
+  fn test_compact_encodings_are_smaller() {
+    let TestSetup {
+      mut document, did_self, ..
+    } = test_document();
+    for index in 0..20 {
+      document
+        .insert_method(
+          generate_method(&did_self, &format!("key-{index}")),
+          MethodScope::VerificationMethod,
+        )
+        .unwrap();
+    }
+
+    let json_len: usize = document
+      .clone()
+      .pack_with_encoding(StateMetadataEncoding::Json)
+      .unwrap()
+      .len();
+    for encoding in [
+      StateMetadataEncoding::JsonDeflate,
+      StateMetadataEncoding::JsonBrotli,
+      StateMetadataEncoding::Cbor,
+    ] {
+      let packed_len: usize = document.clone().pack_with_encoding(encoding).unwrap().len();
+      assert!(packed_len < json_len, "{encoding:?}: {packed_len} >= {json_len}");
+    }
+  }
+
+  #[test]
+  fn test_unpack_invalid_encoding() {
+    let TestSetup { document, .. } = test_document();
+    let mut packed: Vec<u8> = StateMetadataDocument::from(document)
+      .pack(StateMetadataEncoding::JsonBrotli)
+      .unwrap();
+
+    // INVALID: the data is not DEFLATE compressed.
+    packed[4] = StateMetadataEncoding::JsonDeflate as u8;
+    assert!(StateMetadataDocument::unpack(&packed).is_err());
+    // INVALID: the data is not CBOR.
+    packed[4] = StateMetadataEncoding::Cbor as u8;
+    assert!(StateMetadataDocument::unpack(&packed).is_err());
+    // INVALID: unknown encoding.
+    packed[4] = u8::MAX;
+    assert!(StateMetadataDocument::unpack(&packed).is_err());
+  }
+
+  #[test]
+  fn test_unpack_decompression_limit() {
+    use std::io::Write;
+
+    // A small payload that decompresses to more than the maximum size.
+    let mut encoder = flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::best());
+    encoder.write_all(&vec![b' '; 2 * 1_048_576]).unwrap();
+    let compressed: Vec<u8> = encoder.finish().unwrap();
+    let packed: Vec<u8> =
+      add_flags_to_message(compressed, StateMetadataVersion::V1, StateMetadataEncoding::JsonDeflate).unwrap();
+
+    assert!(matches!(
+      StateMetadataDocument::unpack(&packed),
+      Err(crate::Error::InvalidStateMetadata(_))
+    ));
+  }
 }
@@ -6,12 +6,22 @@ use num_traits::FromPrimitive;
 use crate::Error;
 
 /// Indicates the encoding of a DID document in state metadata.
+///
+/// Since the storage deposit of an Alias Output grows with the size of its state metadata, the compact encodings can
+/// considerably lower the cost of publishing larger documents. Note that documents using an encoding other than
+/// [`StateMetadataEncoding::Json`] cannot be resolved by versions of this library prior to their introduction.
 #[derive(Copy, Clone, Debug, Default, Ord, PartialOrd, Eq, PartialEq, num_derive::FromPrimitive)]
 #[non_exhaustive]
 pub enum StateMetadataEncoding {
   /// State Metadata encoded as JSON.
   #[default]
   Json = 0,
+  /// State Metadata encoded as JSON and compressed with DEFLATE.
+  JsonDeflate = 1,
+  /// State Metadata encoded as JSON and compressed with Brotli.
+  JsonBrotli = 2,
+  /// State Metadata encoded as CBOR.
+  Cbor = 3,
 }
 
 impl TryFrom<u8> for StateMetadataEncoding {
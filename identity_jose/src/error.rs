@@ -40,6 +40,18 @@ pub enum Error {
   /// Caused by a string that does not correspond to a supported [`JwsAlgorithm`](crate::jws::JwsAlgorithm).
   #[error("attempt to parse an unregistered jws algorithm")]
   JwsAlgorithmParsingError,
+  /// Caused by a string that does not correspond to a supported JWE algorithm.
+  #[error("attempt to parse an unregistered jwe algorithm")]
+  JweAlgorithmParsingError,
+  /// Caused by a string that does not correspond to a supported JWE content encryption algorithm.
+  #[error("attempt to parse an unregistered jwe content encryption algorithm")]
+  JweEncryptionParsingError,
+  /// Caused by a failure to encrypt the content or key of a JSON Web Encryption.
+  #[error("encryption error: {0}")]
+  EncryptionError(&'static str),
+  /// Caused by a failure to decrypt the content or key of a JSON Web Encryption.
+  #[error("decryption error: {0}")]
+  DecryptionError(&'static str),
   /// Caused by an error during signature verification.
   #[error("signature verification error")]
   SignatureVerificationError(#[source] crate::jws::SignatureVerificationError),
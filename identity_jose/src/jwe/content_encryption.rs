@@ -0,0 +1,98 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use chacha20poly1305::aead::AeadInPlace;
+use chacha20poly1305::aead::KeyInit;
+use chacha20poly1305::ChaCha20Poly1305;
+use crypto::ciphers::aes_gcm::Aes256Gcm;
+use crypto::ciphers::traits::Aead;
+
+use crate::error::Error;
+use crate::error::Result;
+use crate::jwe::JweEncryption;
+
+/// The output of authenticated content encryption.
+pub(crate) struct EncryptedContent {
+  pub(crate) ciphertext: Vec<u8>,
+  pub(crate) tag: Vec<u8>,
+}
+
+/// Encrypts `plaintext` with the content encryption algorithm `enc`.
+pub(crate) fn encrypt(
+  enc: JweEncryption,
+  cek: &[u8],
+  iv: &[u8],
+  aad: &[u8],
+  plaintext: &[u8],
+) -> Result<EncryptedContent> {
+  check_lengths(enc, cek, iv)?;
+
+  match enc {
+    JweEncryption::A256GCM => {
+      let mut ciphertext: Vec<u8> = vec![0; plaintext.len()];
+      let mut tag: Vec<u8> = vec![0; enc.tag_len()];
+      Aes256Gcm::try_encrypt(cek, iv, aad, plaintext, &mut ciphertext, &mut tag)
+        .map_err(|_| Error::EncryptionError("content encryption failed"))?;
+      Ok(EncryptedContent { ciphertext, tag })
+    }
+    JweEncryption::C20P => {
+      let cipher: ChaCha20Poly1305 =
+        ChaCha20Poly1305::new_from_slice(cek).map_err(|_| Error::EncryptionError("invalid content encryption key"))?;
+      let mut ciphertext: Vec<u8> = plaintext.to_vec();
+      let tag = cipher
+        .encrypt_in_place_detached(iv.into(), aad, &mut ciphertext)
+        .map_err(|_| Error::EncryptionError("content encryption failed"))?;
+      Ok(EncryptedContent {
+        ciphertext,
+        tag: tag.to_vec(),
+      })
+    }
+  }
+}
+
+/// Decrypts `ciphertext` with the content encryption algorithm `enc` and verifies the
+/// authentication `tag`.
+pub(crate) fn decrypt(
+  enc: JweEncryption,
+  cek: &[u8],
+  iv: &[u8],
+  aad: &[u8],
+  ciphertext: &[u8],
+  tag: &[u8],
+) -> Result<Vec<u8>> {
+  check_lengths(enc, cek, iv).map_err(|_| Error::DecryptionError("invalid key or iv length"))?;
+
+  if tag.len() != enc.tag_len() {
+    return Err(Error::DecryptionError("invalid authentication tag length"));
+  }
+
+  match enc {
+    JweEncryption::A256GCM => {
+      let mut plaintext: Vec<u8> = vec![0; ciphertext.len()];
+      Aes256Gcm::try_decrypt(cek, iv, aad, &mut plaintext, ciphertext, tag)
+        .map_err(|_| Error::DecryptionError("content decryption failed"))?;
+      Ok(plaintext)
+    }
+    JweEncryption::C20P => {
+      let cipher: ChaCha20Poly1305 =
+        ChaCha20Poly1305::new_from_slice(cek).map_err(|_| Error::DecryptionError("invalid content encryption key"))?;
+      let mut plaintext: Vec<u8> = ciphertext.to_vec();
+      cipher
+        .decrypt_in_place_detached(iv.into(), aad, &mut plaintext, tag.into())
+        .map_err(|_| Error::DecryptionError("content decryption failed"))?;
+      Ok(plaintext)
+    }
+  }
+}
+
+fn check_lengths(enc: JweEncryption, cek: &[u8], iv: &[u8]) -> Result<()> {
+  if cek.len() != enc.key_len() {
+    return Err(Error::EncryptionError("invalid content encryption key length"));
+  }
+
+  if iv.len() != enc.iv_len() {
+    return Err(Error::EncryptionError("invalid initialization vector length"));
+  }
+
+  Ok(())
+}
@@ -0,0 +1,239 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use zeroize::Zeroizing;
+
+use crate::error::Error;
+use crate::error::Result;
+use crate::jwe::content_encryption;
+use crate::jwe::key_agreement;
+use crate::jwe::JweAlgorithm;
+use crate::jwe::JweEncryption;
+use crate::jwe::JweHeader;
+use crate::jwk::Jwk;
+use crate::jwu::decode_b64;
+use crate::jwu::decode_b64_json;
+use crate::jwu::parse_utf8;
+
+use super::utils;
+use super::utils::JweHeaders;
+
+/// A decrypted JWE.
+///
+/// Contains the decoded headers that applied to the recipient, the additional authenticated data and the plaintext.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct DecodedJwe {
+  /// The decoded protected header.
+  pub protected: Option<JweHeader>,
+  /// The decoded shared unprotected header.
+  pub unprotected: Option<JweHeader>,
+  /// The decoded per-recipient unprotected header.
+  pub recipient: Option<JweHeader>,
+  /// The decoded additional authenticated data.
+  pub aad: Option<Vec<u8>>,
+  /// The decrypted plaintext.
+  pub plaintext: Vec<u8>,
+}
+
+// =============================================================================================
+// Format dependent deserializable helper structs used by the decoder
+// =============================================================================================
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JweRecipient<'a> {
+  header: Option<JweHeader>,
+  encrypted_key: Option<&'a str>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct General<'a> {
+  protected: Option<&'a str>,
+  unprotected: Option<JweHeader>,
+  #[serde(borrow)]
+  recipients: Vec<JweRecipient<'a>>,
+  aad: Option<&'a str>,
+  iv: &'a str,
+  ciphertext: &'a str,
+  tag: &'a str,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Flatten<'a> {
+  protected: Option<&'a str>,
+  unprotected: Option<JweHeader>,
+  header: Option<JweHeader>,
+  encrypted_key: Option<&'a str>,
+  aad: Option<&'a str>,
+  iv: &'a str,
+  ciphertext: &'a str,
+  tag: &'a str,
+}
+
+/// The encoded parts of a JWE that are shared by all recipients.
+struct EncodedShared<'a> {
+  protected: Option<&'a str>,
+  unprotected: Option<JweHeader>,
+  aad: Option<&'a str>,
+  iv: &'a str,
+  ciphertext: &'a str,
+  tag: &'a str,
+}
+
+// =============================================================================
+// Decoder
+// =============================================================================
+
+/// The [`Decoder`] is responsible for decrypting a JWE with the private key of one of its recipients.
+#[derive(Debug, Clone)]
+pub struct Decoder;
+
+impl Decoder {
+  /// Constructs a new [`Decoder`].
+  pub fn new() -> Decoder {
+    Self
+  }
+
+  /// Decrypt a JWE encoded with the [JWE compact serialization format](https://www.rfc-editor.org/rfc/rfc7516#section-3.1)
+  /// using the recipient's `private_key`.
+  pub fn decode_compact_serialization(&self, jwe_bytes: &[u8], private_key: &Jwk) -> Result<DecodedJwe> {
+    let mut segments = jwe_bytes.split(|byte| *byte == b'.');
+
+    let (Some(protected), Some(encrypted_key), Some(iv), Some(ciphertext), Some(tag), None) = (
+      segments.next(),
+      segments.next(),
+      segments.next(),
+      segments.next(),
+      segments.next(),
+      segments.next(),
+    ) else {
+      return Err(Error::InvalidContent("invalid segments count"));
+    };
+
+    let shared: EncodedShared<'_> = EncodedShared {
+      protected: Some(parse_utf8(protected)?),
+      unprotected: None,
+      aad: None,
+      iv: parse_utf8(iv)?,
+      ciphertext: parse_utf8(ciphertext)?,
+      tag: parse_utf8(tag)?,
+    };
+
+    self.decrypt(&shared, None, Some(parse_utf8(encrypted_key)?), private_key)
+  }
+
+  /// Decrypt a JWE encoded with the [flattened JWE JSON serialization format](https://www.rfc-editor.org/rfc/rfc7516#section-7.2.2)
+  /// using the recipient's `private_key`.
+  pub fn decode_flattened_serialization(&self, jwe_bytes: &[u8], private_key: &Jwk) -> Result<DecodedJwe> {
+    let data: Flatten<'_> = serde_json::from_slice(jwe_bytes).map_err(Error::InvalidJson)?;
+
+    let shared: EncodedShared<'_> = EncodedShared {
+      protected: data.protected,
+      unprotected: data.unprotected,
+      aad: data.aad,
+      iv: data.iv,
+      ciphertext: data.ciphertext,
+      tag: data.tag,
+    };
+
+    self.decrypt(&shared, data.header, data.encrypted_key, private_key)
+  }
+
+  /// Decrypt a JWE encoded with the [general JWE JSON serialization format](https://www.rfc-editor.org/rfc/rfc7516#section-7.2.1)
+  /// using the recipient's `private_key`.
+  ///
+  /// If the `private_key` has a `kid` only recipients with a matching `kid` in their headers are considered, otherwise
+  /// decryption is attempted for each recipient in turn.
+  pub fn decode_general_serialization(&self, jwe_bytes: &[u8], private_key: &Jwk) -> Result<DecodedJwe> {
+    let data: General<'_> = serde_json::from_slice(jwe_bytes).map_err(Error::InvalidJson)?;
+
+    let shared: EncodedShared<'_> = EncodedShared {
+      protected: data.protected,
+      unprotected: data.unprotected,
+      aad: data.aad,
+      iv: data.iv,
+      ciphertext: data.ciphertext,
+      tag: data.tag,
+    };
+
+    let protected: Option<JweHeader> = shared.protected.map(decode_b64_json).transpose()?;
+    let kid: Option<&str> = private_key.kid();
+    let shared_kid: Option<&str> = protected
+      .as_ref()
+      .and_then(|header| header.kid())
+      .or_else(|| shared.unprotected.as_ref().and_then(|header| header.kid()));
+
+    let mut error: Error = Error::InvalidContent("no matching recipient");
+    for recipient in data.recipients {
+      let recipient_kid: Option<&str> = recipient.header.as_ref().and_then(|header| header.kid()).or(shared_kid);
+      if kid.is_some() && recipient_kid.is_some() && kid != recipient_kid {
+        continue;
+      }
+
+      match self.decrypt(&shared, recipient.header, recipient.encrypted_key, private_key) {
+        Ok(decoded) => return Ok(decoded),
+        Err(err) => error = err,
+      }
+    }
+
+    Err(error)
+  }
+
+  fn decrypt(
+    &self,
+    shared: &EncodedShared<'_>,
+    recipient: Option<JweHeader>,
+    encrypted_key: Option<&str>,
+    private_key: &Jwk,
+  ) -> Result<DecodedJwe> {
+    let protected: Option<JweHeader> = shared.protected.map(decode_b64_json).transpose()?;
+
+    let headers: JweHeaders<'_> = JweHeaders {
+      protected: protected.as_ref(),
+      unprotected: shared.unprotected.as_ref(),
+      recipient: recipient.as_ref(),
+    };
+    headers.validate()?;
+
+    let alg: JweAlgorithm = headers.alg()?;
+    let enc: JweEncryption = headers.enc()?;
+    let epk: &Jwk = headers.epk().ok_or(Error::MissingParam("epk"))?;
+
+    let shared_secret: Zeroizing<Vec<u8>> = key_agreement::ecdh_static(private_key, epk)?;
+    let derived_key: Zeroizing<Vec<u8>> =
+      key_agreement::derive_key(alg, enc, &shared_secret, headers.apu(), headers.apv())?;
+    let encrypted_key: Vec<u8> = encrypted_key.map(decode_b64).transpose()?.unwrap_or_default();
+
+    let cek: Zeroizing<Vec<u8>> = match alg {
+      JweAlgorithm::ECDH_ES if encrypted_key.is_empty() => derived_key,
+      JweAlgorithm::ECDH_ES => return Err(Error::InvalidContent("encrypted key must be empty")),
+      _ => key_agreement::unwrap_key(alg, &derived_key, &encrypted_key)?,
+    };
+
+    let aad: Vec<u8> = utils::create_aad(shared.protected, shared.aad);
+    let plaintext: Vec<u8> = content_encryption::decrypt(
+      enc,
+      &cek,
+      &decode_b64(shared.iv)?,
+      &aad,
+      &decode_b64(shared.ciphertext)?,
+      &decode_b64(shared.tag)?,
+    )?;
+
+    Ok(DecodedJwe {
+      protected,
+      unprotected: shared.unprotected.clone(),
+      recipient,
+      aad: shared.aad.map(decode_b64).transpose()?,
+      plaintext,
+    })
+  }
+}
+
+impl Default for Decoder {
+  fn default() -> Self {
+    Self::new()
+  }
+}
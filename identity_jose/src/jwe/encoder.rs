@@ -0,0 +1,355 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::Serialize;
+use zeroize::Zeroizing;
+
+use crate::error::Error;
+use crate::error::Result;
+use crate::jwe::content_encryption;
+use crate::jwe::content_encryption::EncryptedContent;
+use crate::jwe::key_agreement;
+use crate::jwe::JweAlgorithm;
+use crate::jwe::JweEncryption;
+use crate::jwe::JweHeader;
+use crate::jwe::Recipient;
+use crate::jwk::Jwk;
+use crate::jwu;
+
+use super::utils;
+use super::utils::JweHeaders;
+
+/// An encoder for JSON Web Encryption (JWE) tokens.
+///
+/// The content encryption key is made available to each [`Recipient`] through ECDH-ES key agreement,
+/// either directly (`ECDH-ES`) or by wrapping a random key with the agreed key (`ECDH-ES+A*KW`).
+///
+/// Supports the [compact](Self::encode_compact_serialization), [flattened JSON](Self::encode_flattened_serialization)
+/// and [general JSON](Self::encode_general_serialization) serialization formats.
+#[derive(Clone, Default)]
+pub struct Encoder<'a> {
+  protected: Option<&'a JweHeader>,
+  unprotected: Option<&'a JweHeader>,
+  aad: Option<&'a [u8]>,
+  recipients: Vec<Recipient<'a>>,
+}
+
+impl<'a> Encoder<'a> {
+  /// Constructs a new [`Encoder`] without any headers or recipients.
+  pub fn new() -> Self {
+    Self {
+      protected: None,
+      unprotected: None,
+      aad: None,
+      recipients: Vec::new(),
+    }
+  }
+
+  /// Set the integrity-protected JOSE header shared by all recipients.
+  pub fn protected(mut self, value: &'a JweHeader) -> Self {
+    self.protected = Some(value);
+    self
+  }
+
+  /// Set the non integrity-protected JOSE header shared by all recipients.
+  ///
+  /// Not supported by the compact serialization format.
+  pub fn unprotected(mut self, value: &'a JweHeader) -> Self {
+    self.unprotected = Some(value);
+    self
+  }
+
+  /// Set additional authenticated data that is integrity-protected but not encrypted.
+  ///
+  /// Not supported by the compact serialization format.
+  pub fn aad(mut self, value: &'a [u8]) -> Self {
+    self.aad = Some(value);
+    self
+  }
+
+  /// Add a recipient to the JWE.
+  pub fn recipient(mut self, value: Recipient<'a>) -> Self {
+    self.recipients.push(value);
+    self
+  }
+
+  /// Encrypt `plaintext` and encode the result with the
+  /// [JWE compact serialization format](https://www.rfc-editor.org/rfc/rfc7516#section-3.1).
+  ///
+  /// The compact serialization requires exactly one recipient without a per-recipient header and neither an
+  /// unprotected header nor additional authenticated data. The ephemeral public key is added to the protected header.
+  pub fn encode_compact_serialization(&self, plaintext: &[u8]) -> Result<String> {
+    if self.unprotected.is_some() || self.aad.is_some() {
+      return Err(Error::InvalidContent(
+        "unprotected header and aad are not supported by the compact serialization",
+      ));
+    }
+
+    let recipient: Recipient<'_> = match self.recipients.as_slice() {
+      [recipient] if recipient.header.is_none() => *recipient,
+      [_] => {
+        return Err(Error::InvalidContent(
+          "per-recipient headers are not supported by the compact serialization",
+        ))
+      }
+      _ => {
+        return Err(Error::InvalidContent(
+          "compact serialization requires exactly one recipient",
+        ))
+      }
+    };
+
+    let protected: &JweHeader = self.protected.ok_or(Error::MissingHeader("missing protected header"))?;
+    let headers: JweHeaders<'_> = self.headers(Some(protected), None);
+    headers.validate()?;
+    let (alg, enc) = (headers.alg()?, headers.enc()?);
+
+    let cek: Zeroizing<Vec<u8>> = match alg {
+      JweAlgorithm::ECDH_ES => Zeroizing::new(Vec::new()),
+      _ => key_agreement::random_bytes(enc.key_len())?,
+    };
+    let (epk, cek, encrypted_key) = Self::process_recipient(recipient.public_key, headers, alg, enc, cek)?;
+
+    let mut protected: JweHeader = protected.clone();
+    protected.set_epk(epk);
+    let protected: String = jwu::encode_b64_json(&protected)?;
+
+    let EncodedContent { iv, ciphertext, tag } = Self::encrypt_content(enc, &cek, Some(&protected), None, plaintext)?;
+
+    Ok(format!(
+      "{protected}.{}.{iv}.{ciphertext}.{tag}",
+      jwu::encode_b64(encrypted_key)
+    ))
+  }
+
+  /// Encrypt `plaintext` and encode the result with the
+  /// [flattened JWE JSON serialization format](https://www.rfc-editor.org/rfc/rfc7516#section-7.2.2).
+  ///
+  /// Requires exactly one recipient.
+  pub fn encode_flattened_serialization(&self, plaintext: &[u8]) -> Result<String> {
+    if self.recipients.len() != 1 {
+      return Err(Error::InvalidContent(
+        "flattened serialization requires exactly one recipient",
+      ));
+    }
+
+    let EncodedJson {
+      protected,
+      aad,
+      mut recipients,
+      content: EncodedContent { iv, ciphertext, tag },
+    } = self.encode_json(plaintext)?;
+
+    Flatten {
+      protected,
+      unprotected: self.unprotected,
+      recipient: recipients.remove(0),
+      aad,
+      iv,
+      ciphertext,
+      tag,
+    }
+    .to_json()
+  }
+
+  /// Encrypt `plaintext` and encode the result with the
+  /// [general JWE JSON serialization format](https://www.rfc-editor.org/rfc/rfc7516#section-7.2.1).
+  pub fn encode_general_serialization(&self, plaintext: &[u8]) -> Result<String> {
+    let EncodedJson {
+      protected,
+      aad,
+      recipients,
+      content: EncodedContent { iv, ciphertext, tag },
+    } = self.encode_json(plaintext)?;
+
+    General {
+      protected,
+      unprotected: self.unprotected,
+      recipients,
+      aad,
+      iv,
+      ciphertext,
+      tag,
+    }
+    .to_json()
+  }
+
+  fn headers(&self, protected: Option<&'a JweHeader>, recipient: Option<&'a JweHeader>) -> JweHeaders<'a> {
+    JweHeaders {
+      protected,
+      unprotected: self.unprotected,
+      recipient,
+    }
+  }
+
+  fn encode_json(&self, plaintext: &[u8]) -> Result<EncodedJson> {
+    if self.recipients.is_empty() {
+      return Err(Error::InvalidContent("missing recipient"));
+    }
+
+    if self.protected.is_none() && self.unprotected.is_none() {
+      return Err(Error::MissingHeader("at least one shared header must be set"));
+    }
+
+    let mut cek: Option<Zeroizing<Vec<u8>>> = None;
+    let mut enc: Option<JweEncryption> = None;
+    let mut recipients: Vec<EncodedRecipient> = Vec::with_capacity(self.recipients.len());
+
+    for recipient in self.recipients.iter() {
+      let headers: JweHeaders<'_> = self.headers(self.protected, recipient.header);
+      headers.validate()?;
+      let (alg, recipient_enc) = (headers.alg()?, headers.enc()?);
+      enc = Some(recipient_enc);
+
+      if alg == JweAlgorithm::ECDH_ES && self.recipients.len() != 1 {
+        return Err(Error::InvalidParam(
+          "direct key agreement only supports a single recipient",
+        ));
+      }
+
+      let recipient_cek: Zeroizing<Vec<u8>> = match cek.take() {
+        Some(cek) => cek,
+        None if alg == JweAlgorithm::ECDH_ES => Zeroizing::new(Vec::new()),
+        None => key_agreement::random_bytes(recipient_enc.key_len())?,
+      };
+      let (epk, recipient_cek, encrypted_key) =
+        Self::process_recipient(recipient.public_key, headers, alg, recipient_enc, recipient_cek)?;
+      cek = Some(recipient_cek);
+
+      let mut header: JweHeader = recipient.header.cloned().unwrap_or_default();
+      header.set_epk(epk);
+
+      recipients.push(EncodedRecipient {
+        header,
+        encrypted_key: (!encrypted_key.is_empty()).then(|| jwu::encode_b64(encrypted_key)),
+      });
+    }
+
+    // Both values are set since there is at least one recipient.
+    let (Some(cek), Some(enc)) = (cek, enc) else {
+      return Err(Error::InvalidContent("missing recipient"));
+    };
+
+    let protected: Option<String> = self.protected.map(jwu::encode_b64_json).transpose()?;
+    let aad: Option<String> = self.aad.map(jwu::encode_b64);
+    let content: EncodedContent = Self::encrypt_content(enc, &cek, protected.as_deref(), aad.as_deref(), plaintext)?;
+
+    Ok(EncodedJson {
+      protected,
+      aad,
+      recipients,
+      content,
+    })
+  }
+
+  /// Performs the key management for a single recipient.
+  ///
+  /// Returns the ephemeral public key, the content encryption key and the encrypted key. An empty `cek` is replaced
+  /// by the agreed key in case of direct key agreement.
+  fn process_recipient(
+    public_key: &Jwk,
+    headers: JweHeaders<'_>,
+    alg: JweAlgorithm,
+    enc: JweEncryption,
+    cek: Zeroizing<Vec<u8>>,
+  ) -> Result<(Jwk, Zeroizing<Vec<u8>>, Vec<u8>)> {
+    if headers.epk().is_some() {
+      return Err(Error::InvalidParam("epk must not be set by the caller"));
+    }
+
+    let (epk, shared_secret) = key_agreement::ecdh_ephemeral(public_key)?;
+    let derived_key: Zeroizing<Vec<u8>> =
+      key_agreement::derive_key(alg, enc, &shared_secret, headers.apu(), headers.apv())?;
+
+    match alg {
+      JweAlgorithm::ECDH_ES => Ok((epk, derived_key, Vec::new())),
+      _ => {
+        let encrypted_key: Vec<u8> = key_agreement::wrap_key(alg, &derived_key, &cek)?;
+        Ok((epk, cek, encrypted_key))
+      }
+    }
+  }
+
+  fn encrypt_content(
+    enc: JweEncryption,
+    cek: &[u8],
+    protected: Option<&str>,
+    aad: Option<&str>,
+    plaintext: &[u8],
+  ) -> Result<EncodedContent> {
+    let iv: Zeroizing<Vec<u8>> = key_agreement::random_bytes(enc.iv_len())?;
+    let aad: Vec<u8> = utils::create_aad(protected, aad);
+    let EncryptedContent { ciphertext, tag } = content_encryption::encrypt(enc, cek, &iv, &aad, plaintext)?;
+
+    Ok(EncodedContent {
+      iv: jwu::encode_b64(iv),
+      ciphertext: jwu::encode_b64(ciphertext),
+      tag: jwu::encode_b64(tag),
+    })
+  }
+}
+
+// ===================================================================================
+//  JWE JSON Serialization
+// ===================================================================================
+
+struct EncodedContent {
+  iv: String,
+  ciphertext: String,
+  tag: String,
+}
+
+struct EncodedJson {
+  protected: Option<String>,
+  aad: Option<String>,
+  recipients: Vec<EncodedRecipient>,
+  content: EncodedContent,
+}
+
+#[derive(Serialize)]
+struct EncodedRecipient {
+  header: JweHeader,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  encrypted_key: Option<String>,
+}
+
+#[derive(Serialize)]
+struct Flatten<'a> {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  protected: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  unprotected: Option<&'a JweHeader>,
+  #[serde(flatten)]
+  recipient: EncodedRecipient,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  aad: Option<String>,
+  iv: String,
+  ciphertext: String,
+  tag: String,
+}
+
+impl<'a> Flatten<'a> {
+  fn to_json(&self) -> Result<String> {
+    serde_json::to_string(&self).map_err(Error::InvalidJson)
+  }
+}
+
+#[derive(Serialize)]
+struct General<'a> {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  protected: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  unprotected: Option<&'a JweHeader>,
+  recipients: Vec<EncodedRecipient>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  aad: Option<String>,
+  iv: String,
+  ciphertext: String,
+  tag: String,
+}
+
+impl<'a> General<'a> {
+  fn to_json(&self) -> Result<String> {
+    serde_json::to_string(&self).map_err(Error::InvalidJson)
+  }
+}
@@ -0,0 +1,77 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::fmt::Result;
+use std::str::FromStr;
+
+use crate::error::Error;
+
+/// Supported algorithms for the JSON Web Encryption `enc` claim.
+///
+/// [More Info](https://www.iana.org/assignments/jose/jose.xhtml#web-signature-encryption-algorithms)
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, serde::Deserialize, serde::Serialize)]
+pub enum JweEncryption {
+  /// AES GCM using 256-bit key
+  A256GCM,
+  /// ChaCha20-Poly1305
+  ///
+  /// [More Info](https://datatracker.ietf.org/doc/html/draft-amringer-jose-chacha-02)
+  C20P,
+}
+
+impl JweEncryption {
+  /// A slice of all supported [`JweEncryption`]s.
+  pub const ALL: &'static [Self] = &[Self::A256GCM, Self::C20P];
+
+  /// Returns the JWE encryption algorithm as a `str` slice.
+  pub const fn name(self) -> &'static str {
+    match self {
+      Self::A256GCM => "A256GCM",
+      Self::C20P => "C20P",
+    }
+  }
+
+  /// Returns the length in bytes of the content encryption key.
+  pub const fn key_len(self) -> usize {
+    match self {
+      Self::A256GCM => 32,
+      Self::C20P => 32,
+    }
+  }
+
+  /// Returns the length in bytes of the initialization vector.
+  pub const fn iv_len(self) -> usize {
+    match self {
+      Self::A256GCM => 12,
+      Self::C20P => 12,
+    }
+  }
+
+  /// Returns the length in bytes of the authentication tag.
+  pub const fn tag_len(self) -> usize {
+    match self {
+      Self::A256GCM => 16,
+      Self::C20P => 16,
+    }
+  }
+}
+
+impl FromStr for JweEncryption {
+  type Err = crate::error::Error;
+
+  fn from_str(string: &str) -> std::result::Result<Self, Self::Err> {
+    match string {
+      "A256GCM" => Ok(Self::A256GCM),
+      "C20P" => Ok(Self::C20P),
+      _ => Err(Error::JweEncryptionParsingError),
+    }
+  }
+}
+
+impl Display for JweEncryption {
+  fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+    f.write_str(self.name())
+  }
+}
@@ -0,0 +1,244 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::ops::Deref;
+use core::ops::DerefMut;
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
+use crate::jose::JoseHeader;
+use crate::jwe::JweAlgorithm;
+use crate::jwe::JweEncryption;
+use crate::jwk::Jwk;
+use crate::jwt::JwtHeader;
+
+/// JSON Web Encryption JOSE Header.
+///
+/// [More Info](https://tools.ietf.org/html/rfc7516#section-4)
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct JweHeader {
+  /// Common JOSE Header Parameters.
+  #[serde(flatten)]
+  common: JwtHeader,
+  /// Algorithm.
+  ///
+  /// Identifies the cryptographic algorithm used to encrypt or determine the
+  /// value of the CEK.
+  ///
+  /// [More Info](https://tools.ietf.org/html/rfc7516#section-4.1.1)
+  #[serde(skip_serializing_if = "Option::is_none")]
+  alg: Option<JweAlgorithm>,
+  /// Encryption Algorithm.
+  ///
+  /// Identifies the content encryption algorithm used to perform authenticated
+  /// encryption on the plaintext to produce the ciphertext and the
+  /// Authentication Tag.
+  ///
+  /// [More Info](https://tools.ietf.org/html/rfc7516#section-4.1.2)
+  #[serde(skip_serializing_if = "Option::is_none")]
+  enc: Option<JweEncryption>,
+  /// Ephemeral Public Key.
+  ///
+  /// Public key created by the originator for use in key agreement algorithms.
+  ///
+  /// [More Info](https://tools.ietf.org/html/rfc7518#section-4.6.1.1)
+  #[serde(skip_serializing_if = "Option::is_none")]
+  epk: Option<Jwk>,
+  /// Agreement PartyUInfo.
+  ///
+  /// Value used for key derivation via Concat KDF.
+  ///
+  /// [More Info](https://tools.ietf.org/html/rfc7518#section-4.6.1.2)
+  #[serde(skip_serializing_if = "Option::is_none")]
+  apu: Option<String>,
+  /// Agreement PartyVInfo.
+  ///
+  /// Value used for key derivation via Concat KDF.
+  ///
+  /// [More Info](https://tools.ietf.org/html/rfc7518#section-4.6.1.3)
+  #[serde(skip_serializing_if = "Option::is_none")]
+  apv: Option<String>,
+
+  /// Additional header parameters.
+  #[serde(flatten, skip_serializing_if = "Option::is_none")]
+  custom: Option<BTreeMap<String, Value>>,
+}
+
+impl JweHeader {
+  /// Create a new empty `JweHeader`.
+  pub const fn new() -> Self {
+    Self {
+      common: JwtHeader::new(),
+      alg: None,
+      enc: None,
+      epk: None,
+      apu: None,
+      apv: None,
+      custom: None,
+    }
+  }
+
+  /// Returns the value for the algorithm claim (alg).
+  pub fn alg(&self) -> Option<JweAlgorithm> {
+    self.alg
+  }
+
+  /// Sets a value for the algorithm claim (alg).
+  pub fn set_alg(&mut self, value: impl Into<JweAlgorithm>) {
+    self.alg = Some(value.into());
+  }
+
+  /// Returns the value of the encryption claim (enc).
+  pub fn enc(&self) -> Option<JweEncryption> {
+    self.enc
+  }
+
+  /// Sets a value for the encryption claim (enc).
+  pub fn set_enc(&mut self, value: impl Into<JweEncryption>) {
+    self.enc = Some(value.into());
+  }
+
+  /// Returns the value of the ephemeral public key claim (epk).
+  pub fn epk(&self) -> Option<&Jwk> {
+    self.epk.as_ref()
+  }
+
+  /// Sets a value for the ephemeral public key claim (epk).
+  pub fn set_epk(&mut self, value: impl Into<Jwk>) {
+    self.epk = Some(value.into());
+  }
+
+  /// Returns the value of the partyuinfo claim (apu).
+  pub fn apu(&self) -> Option<&str> {
+    self.apu.as_deref()
+  }
+
+  /// Sets a value for the partyuinfo claim (apu).
+  ///
+  /// The value is expected to be base64url-encoded.
+  pub fn set_apu(&mut self, value: impl Into<String>) {
+    self.apu = Some(value.into());
+  }
+
+  /// Returns the value of the partyvinfo claim (apv).
+  pub fn apv(&self) -> Option<&str> {
+    self.apv.as_deref()
+  }
+
+  /// Sets a value for the partyvinfo claim (apv).
+  ///
+  /// The value is expected to be base64url-encoded.
+  pub fn set_apv(&mut self, value: impl Into<String>) {
+    self.apv = Some(value.into());
+  }
+
+  /// Returns the additional parameters in the header.
+  pub fn custom(&self) -> Option<&BTreeMap<String, Value>> {
+    self.custom.as_ref()
+  }
+
+  /// Sets additional parameters in the header.
+  pub fn set_custom(&mut self, value: BTreeMap<String, Value>) {
+    self.custom = Some(value)
+  }
+
+  /// Returns `true` if the header contains the given `claim`, `false` otherwise.
+  pub fn has(&self, claim: &str) -> bool {
+    match claim {
+      "alg" => self.alg().is_some(),
+      "enc" => self.enc().is_some(),
+      "epk" => self.epk().is_some(),
+      "apu" => self.apu().is_some(),
+      "apv" => self.apv().is_some(),
+      _ => {
+        self.common.has(claim)
+          || self
+            .custom
+            .as_ref()
+            .map(|custom| custom.get(claim).is_some())
+            .unwrap_or(false)
+      }
+    }
+  }
+
+  /// Returns `true` if none of the fields are set in both `self` and `other`.
+  pub fn is_disjoint(&self, other: &JweHeader) -> bool {
+    let has_duplicate: bool = self.alg.is_some() && other.alg.is_some()
+      || self.enc.is_some() && other.enc.is_some()
+      || self.epk.is_some() && other.epk.is_some()
+      || self.apu.is_some() && other.apu.is_some()
+      || self.apv.is_some() && other.apv.is_some();
+
+    !has_duplicate && self.common.is_disjoint(other.common()) && self.is_custom_disjoint(other)
+  }
+
+  /// Returns `true` if none of the fields are set in both `self.custom` and `other.custom`.
+  fn is_custom_disjoint(&self, other: &JweHeader) -> bool {
+    match (&self.custom, &other.custom) {
+      (Some(self_custom), Some(other_custom)) => self_custom.keys().all(|key| !other_custom.contains_key(key)),
+      _ => true,
+    }
+  }
+}
+
+impl Deref for JweHeader {
+  type Target = JwtHeader;
+
+  fn deref(&self) -> &Self::Target {
+    &self.common
+  }
+}
+
+impl DerefMut for JweHeader {
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    &mut self.common
+  }
+}
+
+impl JoseHeader for JweHeader {
+  fn common(&self) -> &JwtHeader {
+    self
+  }
+
+  fn has_claim(&self, claim: &str) -> bool {
+    self.has(claim)
+  }
+}
+
+impl Default for JweHeader {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_header_disjoint() {
+    let header1: JweHeader = serde_json::from_value(serde_json::json!({
+      "alg": "ECDH-ES+A256KW",
+      "enc": "A256GCM",
+    }))
+    .unwrap();
+    let header2: JweHeader = serde_json::from_value(serde_json::json!({
+      "alg": "ECDH-ES",
+      "kid": "key-1",
+    }))
+    .unwrap();
+    let header3: JweHeader = serde_json::from_value(serde_json::json!({
+      "kid": "key-2",
+      "custom": "test value",
+    }))
+    .unwrap();
+
+    assert_eq!(header1.alg(), Some(JweAlgorithm::ECDH_ES_A256KW));
+    assert_eq!(header1.enc(), Some(JweEncryption::A256GCM));
+    assert!(!header1.is_disjoint(&header2));
+    assert!(header1.is_disjoint(&header3));
+    assert!(!header2.is_disjoint(&header3));
+    assert!(header3.has("custom"));
+  }
+}
@@ -0,0 +1,253 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crypto::ciphers::aes_kw::Aes128Kw;
+use crypto::ciphers::aes_kw::Aes192Kw;
+use crypto::ciphers::aes_kw::Aes256Kw;
+use crypto::hashes::sha::SHA256;
+use crypto::hashes::sha::SHA256_LEN;
+use crypto::keys::x25519;
+use p256::elliptic_curve::sec1::ToEncodedPoint;
+use zeroize::Zeroizing;
+
+use crate::error::Error;
+use crate::error::Result;
+use crate::jwe::JweAlgorithm;
+use crate::jwe::JweEncryption;
+use crate::jwk::EcCurve;
+use crate::jwk::EcxCurve;
+use crate::jwk::Jwk;
+use crate::jwk::JwkParams;
+use crate::jwk::JwkParamsEc;
+use crate::jwk::JwkParamsOkp;
+use crate::jwu;
+
+/// The size of the integrity check value prepended by AES Key Wrap.
+const KEY_WRAP_BLOCK: usize = 8;
+
+/// The curves supported for ECDH-ES key agreement.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum AgreementCurve {
+  X25519,
+  P256,
+}
+
+impl AgreementCurve {
+  fn from_jwk(jwk: &Jwk) -> Result<Self> {
+    match jwk.params() {
+      JwkParams::Okp(params) => match params.try_ecx_curve()? {
+        EcxCurve::X25519 => Ok(Self::X25519),
+        EcxCurve::X448 => Err(Error::KeyError("X25519")),
+      },
+      JwkParams::Ec(params) => match params.try_ec_curve()? {
+        EcCurve::P256 => Ok(Self::P256),
+        _ => Err(Error::KeyError("P-256")),
+      },
+      _ => Err(Error::KeyError("Okp or Ec")),
+    }
+  }
+}
+
+/// Fills a buffer of `len` bytes with cryptographically secure random data.
+pub(crate) fn random_bytes(len: usize) -> Result<Zeroizing<Vec<u8>>> {
+  let mut bytes: Zeroizing<Vec<u8>> = Zeroizing::new(vec![0; len]);
+  crypto::utils::rand::fill(&mut bytes).map_err(|_| Error::EncryptionError("failed to generate random bytes"))?;
+  Ok(bytes)
+}
+
+/// Generates an ephemeral key pair on the curve of `public_key` and computes the shared secret `Z`
+/// with it.
+///
+/// Returns the public part of the ephemeral key, to be transmitted in the `epk` header parameter,
+/// together with `Z`.
+pub(crate) fn ecdh_ephemeral(public_key: &Jwk) -> Result<(Jwk, Zeroizing<Vec<u8>>)> {
+  match AgreementCurve::from_jwk(public_key)? {
+    AgreementCurve::X25519 => {
+      let public: x25519::PublicKey = x25519_public_key(public_key)?;
+      let secret: x25519::SecretKey =
+        x25519::SecretKey::generate().map_err(|_| Error::EncryptionError("failed to generate ephemeral key"))?;
+      let shared_secret: Zeroizing<Vec<u8>> = x25519_diffie_hellman(&secret, &public)?;
+
+      let mut params: JwkParamsOkp = JwkParamsOkp::new();
+      params.crv = EcxCurve::X25519.name().to_owned();
+      params.x = jwu::encode_b64(secret.public_key().to_bytes());
+
+      Ok((Jwk::from_params(params), shared_secret))
+    }
+    AgreementCurve::P256 => {
+      let public: p256::PublicKey = p256_public_key(public_key)?;
+      let secret: p256::SecretKey = loop {
+        // The probability of a random scalar being out of range is negligible.
+        let bytes: Zeroizing<Vec<u8>> = random_bytes(32)?;
+        if let Ok(secret) = p256::SecretKey::from_be_bytes(&bytes) {
+          break secret;
+        }
+      };
+      let shared_secret: Zeroizing<Vec<u8>> = p256_diffie_hellman(&secret, &public);
+
+      let point = secret.public_key().to_encoded_point(false);
+      let mut params: JwkParamsEc = JwkParamsEc::new();
+      params.crv = EcCurve::P256.name().to_owned();
+      params.x = point.x().map(jwu::encode_b64).unwrap_or_default();
+      params.y = point.y().map(jwu::encode_b64).unwrap_or_default();
+
+      Ok((Jwk::from_params(params), shared_secret))
+    }
+  }
+}
+
+/// Computes the shared secret `Z` between the recipient's `private_key` and the sender's ephemeral
+/// public key `epk`.
+pub(crate) fn ecdh_static(private_key: &Jwk, epk: &Jwk) -> Result<Zeroizing<Vec<u8>>> {
+  let curve: AgreementCurve = AgreementCurve::from_jwk(private_key)?;
+
+  if AgreementCurve::from_jwk(epk)? != curve {
+    return Err(Error::InvalidParam("epk curve does not match the recipient key"));
+  }
+
+  match curve {
+    AgreementCurve::X25519 => {
+      let params: &JwkParamsOkp = private_key.try_okp_params()?;
+      let d: Zeroizing<Vec<u8>> = Zeroizing::new(jwu::decode_b64(params.d.as_deref().ok_or(Error::KeyError("Okp"))?)?);
+      let secret: x25519::SecretKey = x25519::SecretKey::try_from_slice(&d).map_err(|_| Error::KeyError("Okp"))?;
+      x25519_diffie_hellman(&secret, &x25519_public_key(epk)?)
+    }
+    AgreementCurve::P256 => {
+      let params: &JwkParamsEc = private_key.try_ec_params()?;
+      let d: Zeroizing<Vec<u8>> = Zeroizing::new(jwu::decode_b64(params.d.as_deref().ok_or(Error::KeyError("Ec"))?)?);
+      let secret: p256::SecretKey = p256::SecretKey::from_be_bytes(&d).map_err(|_| Error::KeyError("Ec"))?;
+      Ok(p256_diffie_hellman(&secret, &p256_public_key(epk)?))
+    }
+  }
+}
+
+fn x25519_public_key(jwk: &Jwk) -> Result<x25519::PublicKey> {
+  let params: &JwkParamsOkp = jwk.try_okp_params()?;
+  x25519::PublicKey::try_from_slice(&jwu::decode_b64(&params.x)?).map_err(|_| Error::KeyError("Okp"))
+}
+
+fn x25519_diffie_hellman(secret: &x25519::SecretKey, public: &x25519::PublicKey) -> Result<Zeroizing<Vec<u8>>> {
+  let shared_secret = secret.diffie_hellman(public);
+
+  // Reject low-order points which would result in an all-zero shared secret.
+  // See https://www.rfc-editor.org/rfc/rfc8037#section-3.2.
+  if !shared_secret.was_contributory() {
+    return Err(Error::InvalidParam("non-contributory X25519 shared secret"));
+  }
+
+  Ok(Zeroizing::new(shared_secret.as_bytes().to_vec()))
+}
+
+fn p256_public_key(jwk: &Jwk) -> Result<p256::PublicKey> {
+  let params: &JwkParamsEc = jwk.try_ec_params()?;
+
+  // Transformation according to section 2.3.3 from http://www.secg.org/sec1-v2.pdf.
+  let sec1_bytes: Vec<u8> = [0x04]
+    .into_iter()
+    .chain(jwu::decode_b64(&params.x)?)
+    .chain(jwu::decode_b64(&params.y)?)
+    .collect();
+
+  p256::PublicKey::from_sec1_bytes(&sec1_bytes).map_err(|_| Error::KeyError("Ec"))
+}
+
+fn p256_diffie_hellman(secret: &p256::SecretKey, public: &p256::PublicKey) -> Zeroizing<Vec<u8>> {
+  let shared_secret = p256::ecdh::diffie_hellman(secret.to_nonzero_scalar(), public.as_affine());
+  Zeroizing::new(shared_secret.raw_secret_bytes().to_vec())
+}
+
+/// The Concat KDF as defined in Section 5.8.1 of NIST.800-56A, using SHA-256 and the `OtherInfo`
+/// layout profiled by JWA.
+///
+/// See (<https://www.rfc-editor.org/rfc/rfc7518#section-4.6.2>).
+pub(crate) fn concat_kdf(
+  algorithm_id: &str,
+  key_len: usize,
+  shared_secret: &[u8],
+  apu: &[u8],
+  apv: &[u8],
+) -> Result<Zeroizing<Vec<u8>>> {
+  let mut other_info: Vec<u8> = Vec::new();
+  for value in [algorithm_id.as_bytes(), apu, apv] {
+    let len: u32 = u32::try_from(value.len()).map_err(|_| Error::InvalidParam("concat kdf input too long"))?;
+    other_info.extend_from_slice(&len.to_be_bytes());
+    other_info.extend_from_slice(value);
+  }
+  // SuppPubInfo: the key data length in bits.
+  let key_len_bits: u32 = u32::try_from(key_len * 8).map_err(|_| Error::InvalidParam("concat kdf key length"))?;
+  other_info.extend_from_slice(&key_len_bits.to_be_bytes());
+
+  let mut output: Zeroizing<Vec<u8>> = Zeroizing::new(Vec::with_capacity(key_len + SHA256_LEN));
+  let mut round: u32 = 1;
+  while output.len() < key_len {
+    let mut input: Zeroizing<Vec<u8>> = Zeroizing::new(Vec::with_capacity(4 + shared_secret.len() + other_info.len()));
+    input.extend_from_slice(&round.to_be_bytes());
+    input.extend_from_slice(shared_secret);
+    input.extend_from_slice(&other_info);
+
+    let mut digest: [u8; SHA256_LEN] = [0; SHA256_LEN];
+    SHA256(&input, &mut digest);
+    output.extend_from_slice(&digest);
+    round += 1;
+  }
+  output.truncate(key_len);
+
+  Ok(output)
+}
+
+/// Derives the key resulting from ECDH-ES key agreement for the given `alg`.
+///
+/// For direct key agreement this is the content encryption key, otherwise it is the key used to
+/// wrap the content encryption key.
+pub(crate) fn derive_key(
+  alg: JweAlgorithm,
+  enc: JweEncryption,
+  shared_secret: &[u8],
+  apu: Option<&str>,
+  apv: Option<&str>,
+) -> Result<Zeroizing<Vec<u8>>> {
+  let apu: Vec<u8> = apu.map(jwu::decode_b64).transpose()?.unwrap_or_default();
+  let apv: Vec<u8> = apv.map(jwu::decode_b64).transpose()?.unwrap_or_default();
+
+  match alg.key_wrap_len() {
+    // In the direct key agreement mode the "enc" value is used as the AlgorithmID.
+    None => concat_kdf(enc.name(), enc.key_len(), shared_secret, &apu, &apv),
+    Some(len) => concat_kdf(alg.name(), len, shared_secret, &apu, &apv),
+  }
+}
+
+/// Wraps the content encryption key `cek` with the AES Key Wrap variant of `alg`.
+pub(crate) fn wrap_key(alg: JweAlgorithm, kek: &[u8], cek: &[u8]) -> Result<Vec<u8>> {
+  let mut wrapped: Vec<u8> = vec![0; cek.len() + KEY_WRAP_BLOCK];
+
+  let result = match alg {
+    JweAlgorithm::ECDH_ES_A128KW => Aes128Kw::new(kek).wrap_key(cek, &mut wrapped),
+    JweAlgorithm::ECDH_ES_A192KW => Aes192Kw::new(kek).wrap_key(cek, &mut wrapped),
+    JweAlgorithm::ECDH_ES_A256KW => Aes256Kw::new(kek).wrap_key(cek, &mut wrapped),
+    JweAlgorithm::ECDH_ES => return Err(Error::InvalidParam("alg does not use key wrapping")),
+  };
+
+  result
+    .map(|_| wrapped)
+    .map_err(|_| Error::EncryptionError("key wrapping failed"))
+}
+
+/// Unwraps the content encryption key from `wrapped` with the AES Key Wrap variant of `alg`.
+pub(crate) fn unwrap_key(alg: JweAlgorithm, kek: &[u8], wrapped: &[u8]) -> Result<Zeroizing<Vec<u8>>> {
+  if wrapped.len() <= KEY_WRAP_BLOCK || wrapped.len() % KEY_WRAP_BLOCK != 0 {
+    return Err(Error::DecryptionError("invalid encrypted key length"));
+  }
+
+  let mut cek: Zeroizing<Vec<u8>> = Zeroizing::new(vec![0; wrapped.len() - KEY_WRAP_BLOCK]);
+
+  let result = match alg {
+    JweAlgorithm::ECDH_ES_A128KW => Aes128Kw::new(kek).unwrap_key(wrapped, &mut cek),
+    JweAlgorithm::ECDH_ES_A192KW => Aes192Kw::new(kek).unwrap_key(wrapped, &mut cek),
+    JweAlgorithm::ECDH_ES_A256KW => Aes256Kw::new(kek).unwrap_key(wrapped, &mut cek),
+    JweAlgorithm::ECDH_ES => return Err(Error::InvalidParam("alg does not use key wrapping")),
+  };
+
+  result
+    .map(|_| cek)
+    .map_err(|_| Error::DecryptionError("key unwrapping failed"))
+}
@@ -0,0 +1,25 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! JSON Web Encryption ([JWE](https://tools.ietf.org/html/rfc7516))
+//!
+//! Supports ECDH-ES key agreement ([RFC 7518 section 4.6](https://www.rfc-editor.org/rfc/rfc7518#section-4.6)),
+//! either direct or in combination with AES Key Wrap, over the X25519 and P-256 curves. The content is encrypted with
+//! either AES-256-GCM or ChaCha20-Poly1305. This module is only available when the `jwe` feature is enabled.
+
+mod algorithm;
+mod content_encryption;
+mod decoder;
+mod encoder;
+mod encryption;
+mod header;
+pub(crate) mod key_agreement;
+mod recipient;
+mod utils;
+
+pub use self::algorithm::*;
+pub use self::decoder::*;
+pub use self::encoder::*;
+pub use self::encryption::*;
+pub use self::header::*;
+pub use self::recipient::*;
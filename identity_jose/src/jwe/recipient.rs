@@ -0,0 +1,34 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::jwe::JweHeader;
+use crate::jwk::Jwk;
+
+/// The recipient of a JWE.
+///
+/// The content encryption key is made available to the recipient through key agreement with the
+/// given `public_key`. The optional per-recipient header determines the specifics of the key management
+/// for that recipient, such as what algorithm (`alg`) or key (`kid`) will be used.
+#[derive(Clone, Copy)]
+pub struct Recipient<'a> {
+  /// The public key of the recipient.
+  pub public_key: &'a Jwk,
+  /// The non integrity-protected per-recipient JOSE header.
+  pub header: Option<&'a JweHeader>,
+}
+
+impl<'a> Recipient<'a> {
+  /// Creates a new recipient for the given `public_key` with no header set.
+  pub fn new(public_key: &'a Jwk) -> Self {
+    Self {
+      public_key,
+      header: None,
+    }
+  }
+
+  /// Set the non integrity-protected per-recipient JOSE header.
+  pub fn header(mut self, value: &'a JweHeader) -> Self {
+    self.header = Some(value);
+    self
+  }
+}
@@ -0,0 +1,93 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::error::Error;
+use crate::error::Result;
+use crate::jwe::JweAlgorithm;
+use crate::jwe::JweEncryption;
+use crate::jwe::JweHeader;
+use crate::jwk::Jwk;
+use crate::jwu;
+
+/// The JOSE headers that apply to a single recipient of a JWE.
+#[derive(Clone, Copy)]
+pub(super) struct JweHeaders<'a> {
+  pub(super) protected: Option<&'a JweHeader>,
+  pub(super) unprotected: Option<&'a JweHeader>,
+  pub(super) recipient: Option<&'a JweHeader>,
+}
+
+impl<'a> JweHeaders<'a> {
+  /// Validates that the headers are pairwise disjoint and that the "crit" parameter is only used as permitted.
+  ///
+  /// See (<https://www.rfc-editor.org/rfc/rfc7516#section-5.2>).
+  pub(super) fn validate(&self) -> Result<()> {
+    let headers: [Option<&JweHeader>; 3] = [self.protected, self.unprotected, self.recipient];
+    for (index, header) in headers.iter().enumerate() {
+      for other in headers.iter().skip(index + 1) {
+        if let (Some(header), Some(other)) = (header, other) {
+          if !header.is_disjoint(other) {
+            return Err(Error::InvalidContent("jwe headers are not disjoint"));
+          }
+        }
+      }
+    }
+
+    jwu::validate_crit(self.protected, self.unprotected)?;
+    jwu::validate_crit(self.protected, self.recipient)?;
+
+    if headers.iter().flatten().any(|header| header.has("zip")) {
+      return Err(Error::InvalidParam("compression is not supported"));
+    }
+
+    Ok(())
+  }
+
+  fn find<T>(&self, f: impl Fn(&'a JweHeader) -> Option<T>) -> Option<T> {
+    [self.protected, self.unprotected, self.recipient]
+      .into_iter()
+      .flatten()
+      .find_map(f)
+  }
+
+  /// Returns the `alg` claim from the first header containing it.
+  pub(super) fn alg(&self) -> Result<JweAlgorithm> {
+    self.find(JweHeader::alg).ok_or(Error::MissingParam("alg"))
+  }
+
+  /// Returns the `enc` claim, which must be shared by all recipients.
+  pub(super) fn enc(&self) -> Result<JweEncryption> {
+    if self.recipient.and_then(JweHeader::enc).is_some() {
+      return Err(Error::InvalidParam("enc must not be set in a per-recipient header"));
+    }
+
+    self.find(JweHeader::enc).ok_or(Error::MissingParam("enc"))
+  }
+
+  /// Returns the `epk` claim from the first header containing it.
+  pub(super) fn epk(&self) -> Option<&'a Jwk> {
+    self.find(JweHeader::epk)
+  }
+
+  /// Returns the `apu` claim from the first header containing it.
+  pub(super) fn apu(&self) -> Option<&'a str> {
+    self.find(JweHeader::apu)
+  }
+
+  /// Returns the `apv` claim from the first header containing it.
+  pub(super) fn apv(&self) -> Option<&'a str> {
+    self.find(JweHeader::apv)
+  }
+}
+
+/// Computes the Additional Authenticated Data for content encryption from the
+/// encoded protected header and the optional encoded JWE AAD.
+///
+/// See (<https://www.rfc-editor.org/rfc/rfc7516#section-5.1> step 14).
+pub(super) fn create_aad(protected: Option<&str>, aad: Option<&str>) -> Vec<u8> {
+  let protected: &[u8] = protected.map(str::as_bytes).unwrap_or_default();
+  match aad {
+    Some(aad) => jwu::create_message(protected, aad.as_bytes()),
+    None => protected.to_vec(),
+  }
+}
@@ -17,6 +17,8 @@
 
 pub mod error;
 pub mod jose;
+#[cfg(feature = "jwe")]
+pub mod jwe;
 pub mod jwk;
 pub mod jws;
 pub mod jwt;
@@ -0,0 +1,219 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crypto::keys::x25519;
+
+use crate::error::Error;
+use crate::jwe::Decoder;
+use crate::jwe::Encoder;
+use crate::jwe::JweAlgorithm;
+use crate::jwe::JweEncryption;
+use crate::jwe::JweHeader;
+use crate::jwe::Recipient;
+use crate::jwk::EcxCurve;
+use crate::jwk::Jwk;
+use crate::jwk::JwkParamsOkp;
+use crate::jwu;
+
+const PLAINTEXT: &[u8] = b"The true sign of intelligence is not knowledge but imagination.";
+
+// P-256 key of Bob from https://tools.ietf.org/html/rfc7518#appendix-C.
+const P256_JWK: &str = r#"
+  {
+    "kty": "EC",
+    "crv": "P-256",
+    "x": "weNJy2HscCSM6AEDTDg04biOvhFhyyWvOHQfeF_PxMQ",
+    "y": "e8lnCO-AlStT-NJVX-crhB7QRYhiix03illJOVAOyck",
+    "d": "VEmDZpDXXK8p8N0Cndsxs924q6nS1RXFASRl6BfUqdw"
+  }
+"#;
+
+fn x25519_jwk(kid: &str) -> Jwk {
+  let secret_key: x25519::SecretKey = x25519::SecretKey::generate().unwrap();
+  let mut params: JwkParamsOkp = JwkParamsOkp::new();
+  params.crv = EcxCurve::X25519.name().to_owned();
+  params.x = jwu::encode_b64(secret_key.public_key().to_bytes());
+  params.d = Some(jwu::encode_b64(secret_key.to_bytes()));
+
+  let mut jwk: Jwk = Jwk::from_params(params);
+  jwk.set_kid(kid);
+  jwk
+}
+
+fn p256_jwk() -> Jwk {
+  serde_json::from_str(P256_JWK).unwrap()
+}
+
+fn header(alg: JweAlgorithm, enc: JweEncryption) -> JweHeader {
+  let mut header: JweHeader = JweHeader::new();
+  header.set_alg(alg);
+  header.set_enc(enc);
+  header
+}
+
+#[test]
+fn test_compact_roundtrip() {
+  for private_key in [x25519_jwk("key-1"), p256_jwk()] {
+    let public_key: Jwk = private_key.to_public().unwrap();
+
+    for alg in JweAlgorithm::ALL.iter().copied() {
+      for enc in JweEncryption::ALL.iter().copied() {
+        let mut header: JweHeader = header(alg, enc);
+        header.set_apu(jwu::encode_b64("Alice"));
+        header.set_apv(jwu::encode_b64("Bob"));
+
+        let jwe: String = Encoder::new()
+          .protected(&header)
+          .recipient(Recipient::new(&public_key))
+          .encode_compact_serialization(PLAINTEXT)
+          .unwrap();
+
+        let encrypted_key: &str = jwe.split('.').nth(1).unwrap();
+        assert_eq!(encrypted_key.is_empty(), alg == JweAlgorithm::ECDH_ES);
+
+        let decoded = Decoder::new()
+          .decode_compact_serialization(jwe.as_bytes(), &private_key)
+          .unwrap();
+        let protected: JweHeader = decoded.protected.unwrap();
+
+        assert_eq!(decoded.plaintext, PLAINTEXT);
+        assert_eq!(protected.alg(), Some(alg));
+        assert_eq!(protected.enc(), Some(enc));
+        assert!(protected.epk().unwrap().is_public());
+      }
+    }
+  }
+}
+
+#[test]
+fn test_flattened_roundtrip() {
+  let private_key: Jwk = x25519_jwk("key-1");
+  let public_key: Jwk = private_key.to_public().unwrap();
+
+  let protected: JweHeader = header(JweAlgorithm::ECDH_ES, JweEncryption::C20P);
+  let mut unprotected: JweHeader = JweHeader::new();
+  unprotected.set_kid("key-1");
+
+  let jwe: String = Encoder::new()
+    .protected(&protected)
+    .unprotected(&unprotected)
+    .aad(b"additional data")
+    .recipient(Recipient::new(&public_key))
+    .encode_flattened_serialization(PLAINTEXT)
+    .unwrap();
+
+  let decoded = Decoder::new()
+    .decode_flattened_serialization(jwe.as_bytes(), &private_key)
+    .unwrap();
+
+  assert_eq!(decoded.plaintext, PLAINTEXT);
+  assert_eq!(decoded.aad.as_deref(), Some(b"additional data".as_slice()));
+  assert_eq!(decoded.unprotected.unwrap().kid(), Some("key-1"));
+  assert!(decoded.recipient.unwrap().epk().is_some());
+}
+
+#[test]
+fn test_general_roundtrip_multiple_recipients() {
+  let alice: Jwk = x25519_jwk("alice");
+  let bob: Jwk = x25519_jwk("bob");
+  let carol: Jwk = p256_jwk();
+
+  let alice_public: Jwk = alice.to_public().unwrap();
+  let bob_public: Jwk = bob.to_public().unwrap();
+  let carol_public: Jwk = carol.to_public().unwrap();
+
+  let mut protected: JweHeader = JweHeader::new();
+  protected.set_enc(JweEncryption::A256GCM);
+
+  let mut alice_header: JweHeader = JweHeader::new();
+  alice_header.set_alg(JweAlgorithm::ECDH_ES_A256KW);
+  alice_header.set_kid("alice");
+  let mut bob_header: JweHeader = JweHeader::new();
+  bob_header.set_alg(JweAlgorithm::ECDH_ES_A128KW);
+  bob_header.set_kid("bob");
+  let mut carol_header: JweHeader = JweHeader::new();
+  carol_header.set_alg(JweAlgorithm::ECDH_ES_A192KW);
+
+  let jwe: String = Encoder::new()
+    .protected(&protected)
+    .recipient(Recipient::new(&alice_public).header(&alice_header))
+    .recipient(Recipient::new(&bob_public).header(&bob_header))
+    .recipient(Recipient::new(&carol_public).header(&carol_header))
+    .encode_general_serialization(PLAINTEXT)
+    .unwrap();
+
+  let decoder: Decoder = Decoder::new();
+  for (private_key, alg) in [
+    (alice, JweAlgorithm::ECDH_ES_A256KW),
+    (bob, JweAlgorithm::ECDH_ES_A128KW),
+    (carol, JweAlgorithm::ECDH_ES_A192KW),
+  ] {
+    let decoded = decoder
+      .decode_general_serialization(jwe.as_bytes(), &private_key)
+      .unwrap();
+    assert_eq!(decoded.plaintext, PLAINTEXT);
+    assert_eq!(decoded.recipient.unwrap().alg(), Some(alg));
+  }
+
+  // A key that is not among the recipients cannot decrypt the JWE.
+  assert!(decoder
+    .decode_general_serialization(jwe.as_bytes(), &x25519_jwk("mallory"))
+    .is_err());
+}
+
+#[test]
+fn test_direct_key_agreement_requires_single_recipient() {
+  let alice: Jwk = x25519_jwk("alice").to_public().unwrap();
+  let bob: Jwk = x25519_jwk("bob").to_public().unwrap();
+  let protected: JweHeader = header(JweAlgorithm::ECDH_ES, JweEncryption::A256GCM);
+
+  let result = Encoder::new()
+    .protected(&protected)
+    .recipient(Recipient::new(&alice))
+    .recipient(Recipient::new(&bob))
+    .encode_general_serialization(PLAINTEXT);
+
+  assert!(matches!(result.unwrap_err(), Error::InvalidParam(_)));
+}
+
+#[test]
+fn test_tampered_ciphertext_is_rejected() {
+  let private_key: Jwk = x25519_jwk("key-1");
+  let public_key: Jwk = private_key.to_public().unwrap();
+  let protected: JweHeader = header(JweAlgorithm::ECDH_ES_A256KW, JweEncryption::A256GCM);
+
+  let jwe: String = Encoder::new()
+    .protected(&protected)
+    .recipient(Recipient::new(&public_key))
+    .encode_compact_serialization(PLAINTEXT)
+    .unwrap();
+
+  let mut segments: Vec<String> = jwe.split('.').map(ToOwned::to_owned).collect();
+  let mut ciphertext: Vec<u8> = jwu::decode_b64(&segments[3]).unwrap();
+  ciphertext[0] ^= 1;
+  segments[3] = jwu::encode_b64(ciphertext);
+  let tampered: String = segments.join(".");
+
+  assert!(matches!(
+    Decoder::new()
+      .decode_compact_serialization(tampered.as_bytes(), &private_key)
+      .unwrap_err(),
+    Error::DecryptionError(_)
+  ));
+}
+
+#[test]
+fn test_unsupported_key_is_rejected() {
+  let mut params: JwkParamsOkp = JwkParamsOkp::new();
+  params.crv = "Ed25519".to_owned();
+  params.x = jwu::encode_b64([0; 32]);
+  let public_key: Jwk = Jwk::from_params(params);
+  let protected: JweHeader = header(JweAlgorithm::ECDH_ES, JweEncryption::A256GCM);
+
+  let result = Encoder::new()
+    .protected(&protected)
+    .recipient(Recipient::new(&public_key))
+    .encode_compact_serialization(PLAINTEXT);
+
+  assert!(matches!(result.unwrap_err(), Error::KeyError(_)));
+}
@@ -4,8 +4,12 @@
 mod ed25519;
 mod es256;
 mod hs256;
+#[cfg(feature = "jwe")]
+mod jwe;
 mod rfc7515;
 mod rfc7517;
+#[cfg(feature = "jwe")]
+mod rfc7518;
 mod rfc7638;
 mod rfc7797;
 mod rfc8037;
@@ -0,0 +1,43 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::jwe::key_agreement;
+use crate::jwk::Jwk;
+use crate::jwu;
+
+#[test]
+fn test_rfc7518_ecdh_es() {
+  struct TestVector {
+    alice_ephemeral_jwk: &'static str,
+    bob_jwk: &'static str,
+    alg: &'static str,
+    key_len: usize,
+    apu: &'static str,
+    apv: &'static str,
+    shared_secret: &'static [u8],
+    derived_key: &'static str,
+  }
+
+  static TVS: &[TestVector] = &include!("fixtures/rfc7518.rs");
+
+  for tv in TVS {
+    let alice: Jwk = serde_json::from_str(tv.alice_ephemeral_jwk).unwrap();
+    let bob: Jwk = serde_json::from_str(tv.bob_jwk).unwrap();
+
+    // Both parties arrive at the same shared secret.
+    let shared_secret_bob = key_agreement::ecdh_static(&bob, &alice.to_public().unwrap()).unwrap();
+    let shared_secret_alice = key_agreement::ecdh_static(&alice, &bob.to_public().unwrap()).unwrap();
+    assert_eq!(shared_secret_bob.as_slice(), tv.shared_secret);
+    assert_eq!(shared_secret_alice.as_slice(), tv.shared_secret);
+
+    let derived_key = key_agreement::concat_kdf(
+      tv.alg,
+      tv.key_len,
+      tv.shared_secret,
+      &jwu::decode_b64(tv.apu).unwrap(),
+      &jwu::decode_b64(tv.apv).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(jwu::encode_b64(derived_key.as_slice()), tv.derived_key);
+  }
+}
@@ -1,6 +1,8 @@
 // Copyright 2020-2023 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+#[cfg(feature = "jwe")]
+use crate::jwe::key_agreement;
 use crate::jwk::Jwk;
 use crate::jws::CompactJwsEncoder;
 use crate::jws::Decoder;
@@ -68,3 +70,58 @@ fn test_rfc8037_ed25519() {
     assert_eq!(token.claims, tv.payload.as_bytes());
   }
 }
+
+#[cfg(feature = "jwe")]
+#[test]
+fn test_rfc8037_x25519() {
+  use crate::jwk::JwkParamsOkp;
+  use crate::jwu;
+
+  struct TestVector {
+    public_jwk: &'static str,
+    public_key: &'static str,
+    secret_key: &'static str,
+    eph_public_jwk: &'static str,
+    eph_public_key: &'static str,
+    eph_secret_key: &'static str,
+    shared_secret: &'static str,
+  }
+
+  fn decode_hex(data: &str) -> Vec<u8> {
+    (0..data.len())
+      .step_by(2)
+      .map(|index| u8::from_str_radix(&data[index..index + 2], 16).unwrap())
+      .collect()
+  }
+
+  fn private_jwk(public: &Jwk, secret_key: &str) -> Jwk {
+    let mut params: JwkParamsOkp = public.try_okp_params().unwrap().clone();
+    params.d = Some(jwu::encode_b64(decode_hex(secret_key)));
+    Jwk::from_params(params)
+  }
+
+  static TVS: &[TestVector] = &include!("fixtures/rfc8037_x25519.rs");
+
+  for tv in TVS {
+    let public: Jwk = serde_json::from_str(tv.public_jwk).unwrap();
+    let eph_public: Jwk = serde_json::from_str(tv.eph_public_jwk).unwrap();
+
+    assert_eq!(
+      jwu::decode_b64(&public.try_okp_params().unwrap().x).unwrap(),
+      decode_hex(tv.public_key)
+    );
+    assert_eq!(
+      jwu::decode_b64(&eph_public.try_okp_params().unwrap().x).unwrap(),
+      decode_hex(tv.eph_public_key)
+    );
+
+    let secret: Jwk = private_jwk(&public, tv.secret_key);
+    let eph_secret: Jwk = private_jwk(&eph_public, tv.eph_secret_key);
+
+    let shared_secret = key_agreement::ecdh_static(&secret, &eph_public).unwrap();
+    assert_eq!(shared_secret.as_slice(), decode_hex(tv.shared_secret));
+
+    let shared_secret = key_agreement::ecdh_static(&eph_secret, &public).unwrap();
+    assert_eq!(shared_secret.as_slice(), decode_hex(tv.shared_secret));
+  }
+}
@@ -71,4 +71,26 @@ pub enum ErrorCause {
   /// No client attached to the specific network.
   #[error("none of the attached clients support the network {0}")]
   UnsupportedNetwork(String),
+  /// Caused by requesting a specific version of a DID document from a handler that does not support resolving
+  /// historical documents.
+  #[error("did resolution failed: the handler for the DID method \"{method}\" does not support document versions")]
+  #[non_exhaustive]
+  UnsupportedVersion {
+    /// The method of the DID.
+    method: String,
+  },
+  /// Caused by a DID URL that cannot be dereferenced by the [`Resolver`](crate::resolution::Resolver).
+  #[error("did url dereferencing failed: {reason}")]
+  #[non_exhaustive]
+  InvalidDIDUrl {
+    /// The reason why the DID URL is invalid.
+    reason: &'static str,
+  },
+  /// Caused by a DID URL whose resource is not present in the resolved DID document.
+  #[error("did url dereferencing failed: the resource \"{did_url}\" was not found")]
+  #[non_exhaustive]
+  ResourceNotFound {
+    /// The DID URL that was dereferenced.
+    did_url: String,
+  },
 }
@@ -0,0 +1,136 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::sync::PoisonError;
+
+use identity_core::common::Duration;
+use identity_core::common::Timestamp;
+
+use super::commands::Resolved;
+
+/// A cached resolution result.
+struct CacheEntry<DOC> {
+  resolved: Resolved<DOC>,
+  /// The time after which the entry must no longer be used, or `None` if it never expires.
+  expires_at: Option<Timestamp>,
+  /// The value of the access counter of the cache when the entry was last used.
+  last_used: u64,
+}
+
+struct CacheState<DOC> {
+  entries: HashMap<String, CacheEntry<DOC>>,
+  access_counter: u64,
+}
+
+/// Cache of resolution results, keyed by DID.
+///
+/// Entries expire after the configured time to live. Once the cache is full, expired entries are evicted first,
+/// followed by the least recently used entry.
+pub(super) struct ResolutionCache<DOC> {
+  state: Mutex<CacheState<DOC>>,
+  ttl: Duration,
+  max_size: usize,
+  // Captured when the cache is enabled, such that resolving does not require `DOC: Clone`.
+  clone_document: fn(&DOC) -> DOC,
+}
+
+impl<DOC> ResolutionCache<DOC> {
+  pub(super) fn new(ttl: Duration, max_size: usize) -> Self
+  where
+    DOC: Clone,
+  {
+    Self {
+      state: Mutex::new(CacheState {
+        entries: HashMap::new(),
+        access_counter: 0,
+      }),
+      ttl,
+      max_size,
+      clone_document: DOC::clone,
+    }
+  }
+
+  /// Returns a copy of the unexpired entry for `did`.
+  pub(super) fn get(&self, did: &str) -> Option<Resolved<DOC>> {
+    let now: Timestamp = Timestamp::now_utc();
+    let mut state: MutexGuard<'_, CacheState<DOC>> = self.lock();
+    state.access_counter += 1;
+    let access_counter: u64 = state.access_counter;
+
+    let entry: &mut CacheEntry<DOC> = state.entries.get_mut(did)?;
+    if is_expired(entry, now) {
+      state.entries.remove(did);
+      return None;
+    }
+    entry.last_used = access_counter;
+    let (document, metadata) = &entry.resolved;
+    Some(((self.clone_document)(document), metadata.clone()))
+  }
+
+  /// Stores a copy of `resolved` as the entry for `did`.
+  pub(super) fn insert(&self, did: &str, resolved: &Resolved<DOC>) {
+    if self.max_size == 0 {
+      return;
+    }
+
+    let now: Timestamp = Timestamp::now_utc();
+    let mut state: MutexGuard<'_, CacheState<DOC>> = self.lock();
+    if !state.entries.contains_key(did) && state.entries.len() >= self.max_size {
+      state.entries.retain(|_, entry| !is_expired(entry, now));
+    }
+    if !state.entries.contains_key(did) && state.entries.len() >= self.max_size {
+      let least_recently_used: Option<String> = state
+        .entries
+        .iter()
+        .min_by_key(|(_, entry)| entry.last_used)
+        .map(|(did, _)| did.clone());
+      if let Some(did) = least_recently_used {
+        state.entries.remove(&did);
+      }
+    }
+
+    state.access_counter += 1;
+    let entry: CacheEntry<DOC> = CacheEntry {
+      resolved: ((self.clone_document)(&resolved.0), resolved.1.clone()),
+      expires_at: now.checked_add(self.ttl),
+      last_used: state.access_counter,
+    };
+    state.entries.insert(did.to_owned(), entry);
+  }
+
+  /// Removes the entry for `did`.
+  pub(super) fn remove(&self, did: &str) {
+    self.lock().entries.remove(did);
+  }
+
+  /// Removes all entries of DIDs of the given `method`.
+  pub(super) fn remove_method(&self, method: &str) {
+    let prefix: String = format!("did:{method}:");
+    self.lock().entries.retain(|did, _| !did.starts_with(&prefix));
+  }
+
+  /// Removes all entries.
+  pub(super) fn clear(&self) {
+    self.lock().entries.clear();
+  }
+
+  fn lock(&self) -> MutexGuard<'_, CacheState<DOC>> {
+    // The state is consistent after every operation, so a panic while holding the lock cannot corrupt it.
+    self.state.lock().unwrap_or_else(PoisonError::into_inner)
+  }
+}
+
+fn is_expired<DOC>(entry: &CacheEntry<DOC>, now: Timestamp) -> bool {
+  entry.expires_at.map(|expires_at| now >= expires_at).unwrap_or(false)
+}
+
+impl<DOC> std::fmt::Debug for ResolutionCache<DOC> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("ResolutionCache")
+      .field("max_size", &self.max_size)
+      .finish_non_exhaustive()
+  }
+}
@@ -4,11 +4,16 @@
 use core::future::Future;
 use identity_did::DID;
 
+use crate::DocumentMetadata;
+use crate::DocumentVersion;
 use crate::Error;
 use crate::ErrorCause;
 use crate::Result;
 use std::pin::Pin;
 
+/// A resolved DID document together with its metadata.
+pub(super) type Resolved<DOC> = (DOC, DocumentMetadata);
+
 /// Internal trait used by the resolver to apply the command pattern.
 ///
 /// The resolver is generic over the type of command which enables  
@@ -16,7 +21,7 @@ use std::pin::Pin;
 pub trait Command<'a, T>: std::fmt::Debug + private::Sealed {
   type Output: Future<Output = T> + 'a;
 
-  fn apply(&self, input: &'a str) -> Self::Output;
+  fn apply(&self, input: &'a str, version: Option<DocumentVersion>) -> Self::Output;
 }
 
 mod private {
@@ -40,18 +45,21 @@ impl<DOC: 'static> std::fmt::Debug for SingleThreadedCommand<DOC> {
 }
 
 /// Internal representation of a thread safe handler.
-type SendSyncCallback<DOC> =
-  Box<dyn for<'r> Fn(&'r str) -> Pin<Box<dyn Future<Output = Result<DOC>> + 'r + Send>> + Send + Sync>;
+type SendSyncCallback<DOC> = Box<
+  dyn for<'r> Fn(&'r str, Option<DocumentVersion>) -> Pin<Box<dyn Future<Output = Result<Resolved<DOC>>> + 'r + Send>>
+    + Send
+    + Sync,
+>;
 
 /// Wrapper around a thread safe callback.
 pub struct SendSyncCommand<DOC: 'static> {
   fun: SendSyncCallback<DOC>,
 }
 
-impl<'a, DOC: 'static> Command<'a, Result<DOC>> for SendSyncCommand<DOC> {
-  type Output = Pin<Box<dyn Future<Output = Result<DOC>> + 'a + Send>>;
-  fn apply(&self, input: &'a str) -> Self::Output {
-    (self.fun)(input)
+impl<'a, DOC: 'static> Command<'a, Result<Resolved<DOC>>> for SendSyncCommand<DOC> {
+  type Output = Pin<Box<dyn Future<Output = Result<Resolved<DOC>>> + 'a + Send>>;
+  fn apply(&self, input: &'a str, version: Option<DocumentVersion>) -> Self::Output {
+    (self.fun)(input, version)
   }
 }
 
@@ -61,8 +69,12 @@ impl<DOC: 'static> SendSyncCommand<DOC> {
   /// This is achieved by first producing a callback represented as a dynamic asynchronous function pointer
   /// which is invoked by the [Resolver](crate::Resolver) at a later point.
   /// When the callback is invoked the `Resolver` will then pass a DID represented as a string slice which is then
-  /// converted to the DID type required by the handler and then the handler is called.  
-  pub(super) fn new<D, F, Fut, DOCUMENT, E, DIDERR>(handler: F) -> Self
+  /// converted to the DID type required by the handler and then the handler is called. The `metadata` function
+  /// extracts the metadata of the document returned by the handler.
+  ///
+  /// The resulting command fails with [`ErrorCause::UnsupportedVersion`] if a specific version of the document is
+  /// requested, see [`Self::new_versioned`] for handlers that support resolving historical documents.
+  pub(super) fn new<D, F, Fut, DOCUMENT, E, DIDERR, META>(handler: F, metadata: META) -> Self
   where
     D: DID + Send + for<'r> TryFrom<&'r str, Error = DIDERR> + 'static,
     DOCUMENT: 'static + Into<DOC>,
@@ -70,18 +82,49 @@ impl<DOC: 'static> SendSyncCommand<DOC> {
     Fut: Future<Output = std::result::Result<DOCUMENT, E>> + Send,
     E: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
     DIDERR: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    META: Fn(&DOCUMENT) -> DocumentMetadata + 'static + Clone + Send + Sync,
+  {
+    Self::from_handler(move |did: D, _: Option<DocumentVersion>| handler(did), metadata, false)
+  }
+
+  /// Equivalent to [`Self::new`], but the handler additionally receives the version of the document requested by
+  /// the `versionId` or `versionTime` parameter of a DID URL, or `None` if the latest version is requested.
+  pub(super) fn new_versioned<D, F, Fut, DOCUMENT, E, DIDERR, META>(handler: F, metadata: META) -> Self
+  where
+    D: DID + Send + for<'r> TryFrom<&'r str, Error = DIDERR> + 'static,
+    DOCUMENT: 'static + Into<DOC>,
+    F: Fn(D, Option<DocumentVersion>) -> Fut + 'static + Clone + Send + Sync,
+    Fut: Future<Output = std::result::Result<DOCUMENT, E>> + Send,
+    E: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    DIDERR: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    META: Fn(&DOCUMENT) -> DocumentMetadata + 'static + Clone + Send + Sync,
   {
-    let fun: SendSyncCallback<DOC> = Box::new(move |input: &str| {
+    Self::from_handler(handler, metadata, true)
+  }
+
+  fn from_handler<D, F, Fut, DOCUMENT, E, DIDERR, META>(handler: F, metadata: META, versioned: bool) -> Self
+  where
+    D: DID + Send + for<'r> TryFrom<&'r str, Error = DIDERR> + 'static,
+    DOCUMENT: 'static + Into<DOC>,
+    F: Fn(D, Option<DocumentVersion>) -> Fut + 'static + Clone + Send + Sync,
+    Fut: Future<Output = std::result::Result<DOCUMENT, E>> + Send,
+    E: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    DIDERR: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    META: Fn(&DOCUMENT) -> DocumentMetadata + 'static + Clone + Send + Sync,
+  {
+    let fun: SendSyncCallback<DOC> = Box::new(move |input: &str, version: Option<DocumentVersion>| {
       let handler_clone: F = handler.clone();
-      let did_parse_attempt = D::try_from(input)
-        .map_err(|error| ErrorCause::DIDParsingError { source: error.into() })
-        .map_err(Error::new);
+      let metadata_clone: META = metadata.clone();
+      let did_parse_attempt = parse_did::<D, DIDERR>(input, version.as_ref(), versioned);
 
       Box::pin(async move {
         let did: D = did_parse_attempt?;
-        handler_clone(did)
+        handler_clone(did, version)
           .await
-          .map(Into::into)
+          .map(|document| {
+            let document_metadata: DocumentMetadata = metadata_clone(&document);
+            (document.into(), document_metadata)
+          })
           .map_err(|error| ErrorCause::HandlerError { source: error.into() })
           .map_err(Error::new)
       })
@@ -91,28 +134,45 @@ impl<DOC: 'static> SendSyncCommand<DOC> {
   }
 }
 
+/// Parses the DID passed to a command, failing if a `version` is requested from a handler that does not support it.
+fn parse_did<D, DIDERR>(input: &str, version: Option<&DocumentVersion>, versioned: bool) -> Result<D>
+where
+  D: DID + for<'r> TryFrom<&'r str, Error = DIDERR>,
+  DIDERR: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+{
+  let did: D = D::try_from(input)
+    .map_err(|error| ErrorCause::DIDParsingError { source: error.into() })
+    .map_err(Error::new)?;
+  if version.is_some() && !versioned {
+    return Err(Error::new(ErrorCause::UnsupportedVersion {
+      method: did.method().to_owned(),
+    }));
+  }
+  Ok(did)
+}
+
 // ===========================================================================
 // Single threaded commands
 // ===========================================================================
 
 /// Internal representation of a single threaded handler.
 pub(super) type SingleThreadedCallback<DOC> =
-  Box<dyn for<'r> Fn(&'r str) -> Pin<Box<dyn Future<Output = Result<DOC>> + 'r>>>;
+  Box<dyn for<'r> Fn(&'r str, Option<DocumentVersion>) -> Pin<Box<dyn Future<Output = Result<Resolved<DOC>>> + 'r>>>;
 
 /// Wrapper around a single threaded callback.
 pub struct SingleThreadedCommand<DOC> {
   fun: SingleThreadedCallback<DOC>,
 }
-impl<'a, DOC: 'static> Command<'a, Result<DOC>> for SingleThreadedCommand<DOC> {
-  type Output = Pin<Box<dyn Future<Output = Result<DOC>> + 'a>>;
-  fn apply(&self, input: &'a str) -> Self::Output {
-    (self.fun)(input)
+impl<'a, DOC: 'static> Command<'a, Result<Resolved<DOC>>> for SingleThreadedCommand<DOC> {
+  type Output = Pin<Box<dyn Future<Output = Result<Resolved<DOC>>> + 'a>>;
+  fn apply(&self, input: &'a str, version: Option<DocumentVersion>) -> Self::Output {
+    (self.fun)(input, version)
   }
 }
 
 impl<DOC: 'static> SingleThreadedCommand<DOC> {
   /// Equivalent to [`SendSyncCommand::new`](SendSyncCommand::new()), but with less `Send` + `Sync` bounds.
-  pub(super) fn new<D, F, Fut, DOCUMENT, E, DIDERR>(handler: F) -> Self
+  pub(super) fn new<D, F, Fut, DOCUMENT, E, DIDERR, META>(handler: F, metadata: META) -> Self
   where
     D: DID + for<'r> TryFrom<&'r str, Error = DIDERR> + 'static,
     DOCUMENT: 'static + Into<DOC>,
@@ -120,18 +180,49 @@ impl<DOC: 'static> SingleThreadedCommand<DOC> {
     Fut: Future<Output = std::result::Result<DOCUMENT, E>>,
     E: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
     DIDERR: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    META: Fn(&DOCUMENT) -> DocumentMetadata + 'static + Clone,
+  {
+    Self::from_handler(move |did: D, _: Option<DocumentVersion>| handler(did), metadata, false)
+  }
+
+  /// Equivalent to [`SendSyncCommand::new_versioned`](SendSyncCommand::new_versioned()), but with less `Send` +
+  /// `Sync` bounds.
+  pub(super) fn new_versioned<D, F, Fut, DOCUMENT, E, DIDERR, META>(handler: F, metadata: META) -> Self
+  where
+    D: DID + for<'r> TryFrom<&'r str, Error = DIDERR> + 'static,
+    DOCUMENT: 'static + Into<DOC>,
+    F: Fn(D, Option<DocumentVersion>) -> Fut + 'static + Clone,
+    Fut: Future<Output = std::result::Result<DOCUMENT, E>>,
+    E: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    DIDERR: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    META: Fn(&DOCUMENT) -> DocumentMetadata + 'static + Clone,
+  {
+    Self::from_handler(handler, metadata, true)
+  }
+
+  fn from_handler<D, F, Fut, DOCUMENT, E, DIDERR, META>(handler: F, metadata: META, versioned: bool) -> Self
+  where
+    D: DID + for<'r> TryFrom<&'r str, Error = DIDERR> + 'static,
+    DOCUMENT: 'static + Into<DOC>,
+    F: Fn(D, Option<DocumentVersion>) -> Fut + 'static + Clone,
+    Fut: Future<Output = std::result::Result<DOCUMENT, E>>,
+    E: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    DIDERR: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    META: Fn(&DOCUMENT) -> DocumentMetadata + 'static + Clone,
   {
-    let fun: SingleThreadedCallback<DOC> = Box::new(move |input: &str| {
+    let fun: SingleThreadedCallback<DOC> = Box::new(move |input: &str, version: Option<DocumentVersion>| {
       let handler_clone: F = handler.clone();
-      let did_parse_attempt = D::try_from(input)
-        .map_err(|error| ErrorCause::DIDParsingError { source: error.into() })
-        .map_err(Error::new);
+      let metadata_clone: META = metadata.clone();
+      let did_parse_attempt = parse_did::<D, DIDERR>(input, version.as_ref(), versioned);
 
       Box::pin(async move {
         let did: D = did_parse_attempt?;
-        handler_clone(did)
+        handler_clone(did, version)
           .await
-          .map(Into::into)
+          .map(|document| {
+            let document_metadata: DocumentMetadata = metadata_clone(&document);
+            (document.into(), document_metadata)
+          })
           .map_err(|error| ErrorCause::HandlerError { source: error.into() })
           .map_err(Error::new)
       })
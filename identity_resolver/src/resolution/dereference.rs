@@ -0,0 +1,181 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Timestamp;
+use identity_core::common::Url;
+use identity_did::DIDUrl;
+use identity_document::document::CoreDocument;
+use identity_document::service::Service;
+use identity_document::service::ServiceEndpoint;
+use identity_verification::VerificationMethod;
+
+use crate::Error;
+use crate::ErrorCause;
+use crate::Result;
+
+/// A specific version of a DID document, as requested by the `versionId` or `versionTime` parameters of a DID URL.
+///
+/// Handlers attached with [`Resolver::attach_versioned_handler`](crate::Resolver::attach_versioned_handler()) receive
+/// the requested version and are expected to resolve the document as it was at that version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DocumentVersion {
+  /// The version with the given method specific identifier (`versionId`).
+  Id(String),
+  /// The version that was current at the given time (`versionTime`).
+  Time(Timestamp),
+}
+
+/// The resource a DID URL dereferences to, see [`Resolver::dereference`](crate::Resolver::dereference()).
+#[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
+#[non_exhaustive]
+pub enum DereferencedResource<DOC> {
+  /// The DID document, for DID URLs without a fragment or `service` parameter.
+  Document(DOC),
+  /// The verification method identified by the fragment of the DID URL.
+  VerificationMethod(VerificationMethod),
+  /// The service identified by the fragment of the DID URL.
+  Service(Service),
+  /// The service endpoint URL constructed from the `service` and `relativeRef` parameters of the DID URL.
+  ServiceEndpoint(Url),
+}
+
+/// The parameters of a DID URL relevant to dereferencing.
+#[derive(Debug, Default)]
+pub(super) struct DereferencingParams {
+  pub(super) version: Option<DocumentVersion>,
+  pub(super) service: Option<String>,
+  pub(super) relative_ref: Option<String>,
+}
+
+impl DereferencingParams {
+  /// Extracts the dereferencing parameters from the query of `did_url`.
+  pub(super) fn parse(did_url: &DIDUrl) -> Result<Self> {
+    if matches!(did_url.path(), Some(path) if !path.is_empty()) {
+      return Err(invalid_did_url("dereferencing DID URL paths is not supported"));
+    }
+
+    let mut params: Self = Self::default();
+    for (key, value) in did_url.query_pairs() {
+      match key.as_ref() {
+        "versionId" | "versionTime" if params.version.is_some() => {
+          return Err(invalid_did_url("more than one version parameter"));
+        }
+        "versionId" => params.version = Some(DocumentVersion::Id(value.into_owned())),
+        "versionTime" => {
+          let time: Timestamp = Timestamp::parse(&value).map_err(|_| invalid_did_url("invalid versionTime"))?;
+          params.version = Some(DocumentVersion::Time(time));
+        }
+        "service" if params.service.is_some() => return Err(invalid_did_url("more than one service parameter")),
+        "service" => params.service = Some(value.into_owned()),
+        "relativeRef" if params.relative_ref.is_some() => {
+          return Err(invalid_did_url("more than one relativeRef parameter"));
+        }
+        "relativeRef" => params.relative_ref = Some(value.into_owned()),
+        _ => {}
+      }
+    }
+
+    if params.relative_ref.is_some() && params.service.is_none() {
+      return Err(invalid_did_url(
+        "the relativeRef parameter requires a service parameter",
+      ));
+    }
+    Ok(params)
+  }
+}
+
+/// Dereferences the secondary resource of `did_url` in the resolved `document`.
+pub(super) fn dereference_resource<DOC>(
+  document: DOC,
+  did_url: &DIDUrl,
+  params: DereferencingParams,
+) -> Result<DereferencedResource<DOC>>
+where
+  DOC: AsRef<CoreDocument>,
+{
+  let core_document: &CoreDocument = document.as_ref();
+
+  if let Some(service_name) = params.service {
+    let service: &Service = core_document
+      .service()
+      .iter()
+      .find(|service| service.id().fragment() == Some(service_name.as_str()))
+      .ok_or_else(|| not_found(did_url))?;
+    let endpoint: &Url = match service.service_endpoint() {
+      ServiceEndpoint::One(url) => url,
+      ServiceEndpoint::Set(urls) => urls.head().ok_or_else(|| not_found(did_url))?,
+      ServiceEndpoint::Map(_) | ServiceEndpoint::Object(_) => return Err(not_found(did_url)),
+    };
+    return construct_endpoint(endpoint, params.relative_ref.as_deref(), did_url.fragment())
+      .map(DereferencedResource::ServiceEndpoint);
+  }
+
+  let Some(fragment) = did_url.fragment() else {
+    return Ok(DereferencedResource::Document(document));
+  };
+  if let Some(method) = core_document.resolve_method(fragment, None) {
+    return Ok(DereferencedResource::VerificationMethod(method.clone()));
+  }
+  core_document
+    .service()
+    .iter()
+    .find(|service| service.id().fragment() == Some(fragment))
+    .map(|service| DereferencedResource::Service(service.clone()))
+    .ok_or_else(|| not_found(did_url))
+}
+
+/// Constructs the URL of a service endpoint, following the
+/// [DID Resolution specification](https://w3c-ccg.github.io/did-resolution/#service-endpoint-construction).
+///
+/// The path of `relative_ref` is appended to the path of the `endpoint`, and its query and fragment replace those of
+/// the `endpoint`. The `fragment` of the DID URL is used if `relative_ref` does not have one.
+fn construct_endpoint(endpoint: &Url, relative_ref: Option<&str>, fragment: Option<&str>) -> Result<Url> {
+  let mut url: Url = endpoint.clone();
+
+  if let Some(relative_ref) = relative_ref {
+    let (relative_ref, relative_fragment): (&str, Option<&str>) = match relative_ref.split_once('#') {
+      Some((relative_ref, fragment)) => (relative_ref, Some(fragment)),
+      None => (relative_ref, None),
+    };
+    let (relative_path, relative_query): (&str, Option<&str>) = match relative_ref.split_once('?') {
+      Some((path, query)) => (path, Some(query)),
+      None => (relative_ref, None),
+    };
+
+    if !relative_path.is_empty() {
+      if url.cannot_be_a_base() {
+        return Err(invalid_did_url(
+          "the service endpoint cannot be combined with the relativeRef",
+        ));
+      }
+      let path: String = format!(
+        "{}/{}",
+        url.path().trim_end_matches('/'),
+        relative_path.trim_start_matches('/')
+      );
+      url.set_path(&path);
+    }
+    if relative_query.is_some() {
+      url.set_query(relative_query);
+    }
+    if relative_fragment.is_some() {
+      url.set_fragment(relative_fragment);
+    }
+  }
+
+  if url.fragment().is_none() && fragment.is_some() {
+    url.set_fragment(fragment);
+  }
+  Ok(url)
+}
+
+fn invalid_did_url(reason: &'static str) -> Error {
+  Error::new(ErrorCause::InvalidDIDUrl { reason })
+}
+
+fn not_found(did_url: &DIDUrl) -> Error {
+  Error::new(ErrorCause::ResourceNotFound {
+    did_url: did_url.to_string(),
+  })
+}
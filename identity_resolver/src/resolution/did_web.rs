@@ -0,0 +1,108 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Url;
+use identity_core::convert::FromJson;
+use identity_did::DIDWeb;
+use identity_document::document::CoreDocument;
+
+/// The maximum size of a did:web document fetched by the [`Resolver`](crate::Resolver), 1 MiB.
+pub const DID_WEB_MAX_DOCUMENT_SIZE: usize = 1_048_576;
+
+/// An HTTP client used to fetch did:web documents.
+///
+/// An implementation for [`reqwest::Client`] is provided with the `did-web-fetch` feature. Custom implementations can
+/// be used to configure the transport, or to serve documents locally in tests.
+#[async_trait::async_trait]
+pub trait DIDWebClient {
+  /// Fetches the body of the resource at `url` with a GET request.
+  ///
+  /// Implementations should return [`DIDWebError::NotFound`] if the resource does not exist, and must not return
+  /// bodies larger than [`DID_WEB_MAX_DOCUMENT_SIZE`].
+  async fn get(&self, url: &Url) -> Result<Vec<u8>, DIDWebError>;
+}
+
+/// Errors that can occur when resolving a did:web.
+#[derive(Debug, thiserror::Error, strum::IntoStaticStr)]
+#[non_exhaustive]
+pub enum DIDWebError {
+  /// Caused by the DID document not being found at its URL.
+  #[error("did:web document not found at {0}")]
+  NotFound(Url),
+  /// Caused by a failure of the HTTP client.
+  #[error("failed to fetch the did:web document")]
+  FetchError(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
+  /// Caused by a DID document exceeding [`DID_WEB_MAX_DOCUMENT_SIZE`].
+  #[error("the did:web document exceeds the maximum size of {DID_WEB_MAX_DOCUMENT_SIZE} bytes")]
+  DocumentTooLarge,
+  /// Caused by a fetched resource that is not a valid DID document.
+  #[error("invalid did:web document")]
+  InvalidDocument(#[source] identity_core::Error),
+  /// Caused by a fetched DID document whose id differs from the resolved DID.
+  #[error("the id of the did:web document does not match the DID: expected {expected}, found {actual}")]
+  IdMismatch {
+    /// The resolved DID.
+    expected: String,
+    /// The id of the fetched document.
+    actual: String,
+  },
+}
+
+/// Fetches the DID document of `did` with the given `client`, and checks that its id matches the DID.
+pub(super) async fn resolve_did_web<CLI>(client: &CLI, did: &DIDWeb) -> Result<CoreDocument, DIDWebError>
+where
+  CLI: DIDWebClient + ?Sized,
+{
+  let json: Vec<u8> = client.get(&did.url()).await?;
+  if json.len() > DID_WEB_MAX_DOCUMENT_SIZE {
+    return Err(DIDWebError::DocumentTooLarge);
+  }
+  let document: CoreDocument = CoreDocument::from_json_slice(&json).map_err(DIDWebError::InvalidDocument)?;
+  if document.id() != did.as_ref() {
+    return Err(DIDWebError::IdMismatch {
+      expected: did.to_string(),
+      actual: document.id().to_string(),
+    });
+  }
+  Ok(document)
+}
+
+#[cfg(feature = "did-web-fetch")]
+mod __reqwest_client {
+  use futures::StreamExt;
+  use identity_core::common::Url;
+  use reqwest::Client;
+  use reqwest::StatusCode;
+
+  use super::DIDWebClient;
+  use super::DIDWebError;
+  use super::DID_WEB_MAX_DOCUMENT_SIZE;
+
+  #[async_trait::async_trait]
+  impl DIDWebClient for Client {
+    async fn get(&self, url: &Url) -> Result<Vec<u8>, DIDWebError> {
+      let response = Client::get(self, url.as_str())
+        .send()
+        .await
+        .map_err(|err| DIDWebError::FetchError(Box::new(err)))?;
+      if response.status() == StatusCode::NOT_FOUND {
+        return Err(DIDWebError::NotFound(url.clone()));
+      }
+      let response = response
+        .error_for_status()
+        .map_err(|err| DIDWebError::FetchError(Box::new(err)))?;
+
+      // We use a stream so we can limit the size of the response.
+      let mut stream = response.bytes_stream();
+      let mut json: Vec<u8> = Vec::new();
+      while let Some(item) = stream.next().await {
+        let bytes = item.map_err(|err| DIDWebError::FetchError(Box::new(err)))?;
+        json.extend(bytes);
+        if json.len() > DID_WEB_MAX_DOCUMENT_SIZE {
+          return Err(DIDWebError::DocumentTooLarge);
+        }
+      }
+      Ok(json)
+    }
+  }
+}
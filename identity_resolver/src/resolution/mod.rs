@@ -1,7 +1,12 @@
 // Copyright 2020-2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+mod cache;
 mod commands;
+mod dereference;
+#[cfg(feature = "did-web")]
+mod did_web;
+mod resolution_result;
 mod resolver;
 #[cfg(test)]
 mod tests;
@@ -9,6 +14,18 @@ mod tests;
 use self::commands::SingleThreadedCommand;
 use identity_document::document::CoreDocument;
 
+pub use dereference::DereferencedResource;
+pub use dereference::DocumentVersion;
+#[cfg(feature = "did-web")]
+pub use did_web::DIDWebClient;
+#[cfg(feature = "did-web")]
+pub use did_web::DIDWebError;
+#[cfg(feature = "did-web")]
+pub use did_web::DID_WEB_MAX_DOCUMENT_SIZE;
+pub use resolution_result::DIDResolutionResult;
+pub use resolution_result::DocumentMetadata;
+pub use resolution_result::ResolutionErrorCode;
+pub use resolution_result::ResolutionMetadata;
 pub use resolver::Resolver;
 /// Alias for a [`Resolver`] that is not [`Send`] + [`Sync`].
 pub type SingleThreadedResolver<DOC = CoreDocument> = Resolver<DOC, SingleThreadedCommand<DOC>>;
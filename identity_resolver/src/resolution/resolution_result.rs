@@ -0,0 +1,183 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Object;
+use identity_core::common::Timestamp;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::Error;
+use crate::ErrorCause;
+
+/// The result of resolving a DID, consisting of the DID document and metadata about the document and the
+/// resolution process.
+///
+/// See the [DID Resolution specification](https://w3c-ccg.github.io/did-resolution/#did-resolution-result).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DIDResolutionResult<DOC> {
+  /// The resolved DID document, or `None` if the resolution was unsuccessful.
+  pub did_document: Option<DOC>,
+  /// Metadata about the resolved DID document.
+  pub did_document_metadata: DocumentMetadata,
+  /// Metadata about the resolution process.
+  pub did_resolution_metadata: ResolutionMetadata,
+}
+
+impl<DOC> DIDResolutionResult<DOC> {
+  /// Creates the result of a successful resolution of `document`.
+  pub fn new(document: DOC, document_metadata: DocumentMetadata) -> Self {
+    Self {
+      did_document: Some(document),
+      did_document_metadata: document_metadata,
+      did_resolution_metadata: ResolutionMetadata::default(),
+    }
+  }
+
+  /// Creates the result of an unsuccessful resolution with the given `error` code.
+  pub fn from_error(error: ResolutionErrorCode) -> Self {
+    Self {
+      did_document: None,
+      did_document_metadata: DocumentMetadata::default(),
+      did_resolution_metadata: ResolutionMetadata {
+        error: Some(error),
+        properties: Object::new(),
+      },
+    }
+  }
+
+  /// Returns `true` if the resolved DID document is marked as deactivated.
+  pub fn is_deactivated(&self) -> bool {
+    self.did_document_metadata.deactivated.unwrap_or(false)
+  }
+
+  /// Returns the error code of an unsuccessful resolution.
+  pub fn error(&self) -> Option<ResolutionErrorCode> {
+    self.did_resolution_metadata.error
+  }
+}
+
+/// Metadata about a resolved DID document.
+///
+/// See the [DID Core specification](https://www.w3.org/TR/did-core/#did-document-metadata).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DocumentMetadata {
+  /// The timestamp of the creation of the DID document.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub created: Option<Timestamp>,
+  /// The timestamp of the last update to the DID document.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub updated: Option<Timestamp>,
+  /// Whether the DID has been deactivated.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub deactivated: Option<bool>,
+  /// Additional method specific metadata.
+  #[serde(flatten)]
+  pub properties: Object,
+}
+
+/// Metadata about the DID resolution process.
+///
+/// See the [DID Core specification](https://www.w3.org/TR/did-core/#did-resolution-metadata).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResolutionMetadata {
+  /// The error code of an unsuccessful resolution.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub error: Option<ResolutionErrorCode>,
+  /// Additional resolution metadata.
+  #[serde(flatten)]
+  pub properties: Object,
+}
+
+/// Error codes of an unsuccessful DID resolution.
+///
+/// See the [DID Specification Registries](https://www.w3.org/TR/did-spec-registries/#error).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum ResolutionErrorCode {
+  /// The DID supplied to the resolution process does not conform to valid syntax.
+  InvalidDid,
+  /// The resolver was unable to find the DID document resulting from the resolution.
+  NotFound,
+  /// The resolver does not support the DID method of the DID supplied to the resolution process.
+  MethodNotSupported,
+  /// The DID URL supplied to the dereferencing process does not conform to valid syntax, or uses features the
+  /// resolver does not support.
+  InvalidDidUrl,
+  /// An unexpected error occurred during the resolution process.
+  InternalError,
+}
+
+impl From<&Error> for ResolutionErrorCode {
+  fn from(error: &Error) -> Self {
+    match error.error_cause() {
+      ErrorCause::DIDParsingError { .. } => Self::InvalidDid,
+      ErrorCause::UnsupportedMethodError { .. } | ErrorCause::UnsupportedNetwork(_) => Self::MethodNotSupported,
+      ErrorCause::UnsupportedVersion { .. } | ErrorCause::InvalidDIDUrl { .. } => Self::InvalidDidUrl,
+      ErrorCause::ResourceNotFound { .. } => Self::NotFound,
+      ErrorCause::HandlerError { source } => {
+        if let Some(error) = source.downcast_ref::<Error>() {
+          return Self::from(error);
+        }
+        #[cfg(feature = "iota")]
+        if is_iota_not_found(source.as_ref()) {
+          return Self::NotFound;
+        }
+        #[cfg(feature = "did-web")]
+        if let Some(crate::DIDWebError::NotFound(_)) = source.downcast_ref::<crate::DIDWebError>() {
+          return Self::NotFound;
+        }
+        Self::InternalError
+      }
+    }
+  }
+}
+
+/// Returns whether `error` signals that the Alias Output of an IOTA DID, or the requested version of it, does not
+/// exist.
+#[cfg(feature = "iota")]
+fn is_iota_not_found(error: &(dyn std::error::Error + Send + Sync + 'static)) -> bool {
+  use iota_sdk::client::node_api::error::Error as NodeApiError;
+  use iota_sdk::client::Error as ClientError;
+
+  matches!(
+    error.downcast_ref::<identity_iota_core::Error>(),
+    Some(
+      identity_iota_core::Error::DIDResolutionError(
+        ClientError::NoOutput(_) | ClientError::Node(NodeApiError::NotFound(_))
+      ) | identity_iota_core::Error::DIDVersionNotFound(_)
+        | identity_iota_core::Error::AliasOutputNotFound(_)
+    )
+  )
+}
+
+#[cfg(feature = "iota")]
+mod iota_metadata {
+  use identity_core::common::Value;
+  use identity_iota_core::IotaDocumentMetadata;
+
+  use super::DocumentMetadata;
+
+  impl From<&IotaDocumentMetadata> for DocumentMetadata {
+    fn from(metadata: &IotaDocumentMetadata) -> Self {
+      let mut properties = metadata.properties().clone();
+      if let Some(governor_address) = metadata.governor_address.as_ref() {
+        properties.insert("governorAddress".to_owned(), Value::String(governor_address.clone()));
+      }
+      if let Some(state_controller_address) = metadata.state_controller_address.as_ref() {
+        properties.insert(
+          "stateControllerAddress".to_owned(),
+          Value::String(state_controller_address.clone()),
+        );
+      }
+
+      Self {
+        created: metadata.created,
+        updated: metadata.updated,
+        deactivated: metadata.deactivated,
+        properties,
+      }
+    }
+  }
+}
@@ -4,20 +4,31 @@
 use core::future::Future;
 use futures::stream::FuturesUnordered;
 use futures::TryStreamExt;
+use identity_did::DIDUrl;
 use identity_did::DID;
 use std::collections::HashSet;
 
+use identity_core::common::Duration;
 use identity_document::document::CoreDocument;
 use std::collections::HashMap;
 use std::marker::PhantomData;
 
+use crate::DIDResolutionResult;
+use crate::DereferencedResource;
+use crate::DocumentMetadata;
+use crate::DocumentVersion;
 use crate::Error;
 use crate::ErrorCause;
+use crate::ResolutionErrorCode;
 use crate::Result;
 
+use super::cache::ResolutionCache;
 use super::commands::Command;
+use super::commands::Resolved;
 use super::commands::SendSyncCommand;
 use super::commands::SingleThreadedCommand;
+use super::dereference::dereference_resource;
+use super::dereference::DereferencingParams;
 
 /// Convenience type for resolving DID documents from different DID methods.   
 ///
@@ -25,17 +36,23 @@ use super::commands::SingleThreadedCommand;
 ///
 /// The resolver will only be able to resolve DID documents for methods it has been configured for. This is done by
 /// attaching method specific handlers with [`Self::attach_handler`](Self::attach_handler()).
+///
+/// # Caching
+///
+/// By default every resolution is delegated to the attached handler. Resolution results can be cached by calling
+/// [`Self::enable_cache`](Self::enable_cache()).
 pub struct Resolver<DOC = CoreDocument, CMD = SendSyncCommand<DOC>>
 where
-  CMD: for<'r> Command<'r, Result<DOC>>,
+  CMD: for<'r> Command<'r, Result<Resolved<DOC>>>,
 {
   command_map: HashMap<String, CMD>,
+  cache: Option<ResolutionCache<DOC>>,
   _required: PhantomData<DOC>,
 }
 
 impl<M, DOC> Resolver<DOC, M>
 where
-  M: for<'r> Command<'r, Result<DOC>>,
+  M: for<'r> Command<'r, Result<Resolved<DOC>>>,
 {
   /// Constructs a new [`Resolver`].
   ///
@@ -53,6 +70,7 @@ where
   pub fn new() -> Self {
     Self {
       command_map: HashMap::new(),
+      cache: None,
       _required: PhantomData::<DOC>,
     }
   }
@@ -90,6 +108,77 @@ where
   /// }
   /// ```
   pub async fn resolve<D: DID>(&self, did: &D) -> Result<DOC> {
+    self.resolve_document(did, None).await.map(|(document, _)| document)
+  }
+
+  /// Fetches the DID Document of the given DID together with the metadata about the document and the resolution
+  /// process, as defined by the [DID Resolution specification](https://w3c-ccg.github.io/did-resolution/#did-resolution-result).
+  ///
+  /// Unlike [`Self::resolve`](Self::resolve()), this method does not fail. An unsuccessful resolution is reported
+  /// through the `error` property of the resolution metadata instead:
+  /// - [`ResolutionErrorCode::InvalidDid`] if the handler could not parse the DID,
+  /// - [`ResolutionErrorCode::MethodNotSupported`] if the resolver has not been configured to handle the method or
+  ///   network of the DID,
+  /// - [`ResolutionErrorCode::NotFound`] if the DID document does not exist,
+  /// - [`ResolutionErrorCode::InternalError`] if the resolution failed for any other reason.
+  ///
+  /// The document metadata is populated by the handler, see
+  /// [`Self::attach_handler_with_metadata`](Self::attach_handler_with_metadata()). Documents resolved by the IOTA
+  /// handler carry the `created`, `updated` and `deactivated` properties of their `IotaDocumentMetadata`, such that
+  /// a deactivated IOTA DID can be detected with [`DIDResolutionResult::is_deactivated`].
+  pub async fn resolve_with_metadata<D: DID>(&self, did: &D) -> DIDResolutionResult<DOC> {
+    match self.resolve_document(did, None).await {
+      Ok((document, document_metadata)) => DIDResolutionResult::new(document, document_metadata),
+      Err(error) => DIDResolutionResult::from_error(ResolutionErrorCode::from(&error)),
+    }
+  }
+
+  /// Dereferences the given DID URL, following the
+  /// [DID URL Dereferencing](https://w3c-ccg.github.io/did-resolution/#dereferencing) algorithm.
+  ///
+  /// The DID of `did_url` is resolved first, then the resource identified by the rest of the DID URL is selected:
+  /// - `?service=<fragment>` returns the URL of the service endpoint with the given fragment, extended by the
+  ///   `relativeRef` parameter and the fragment of the DID URL, if any.
+  /// - A fragment returns the verification method or service with that fragment.
+  /// - Otherwise the DID document itself is returned.
+  ///
+  /// If the DID URL contains a `versionId` or `versionTime` parameter, the requested [`DocumentVersion`] is passed to
+  /// the handler, see [`Self::attach_versioned_handler`](Self::attach_versioned_handler()). Historical documents are
+  /// never cached.
+  ///
+  /// # Errors
+  ///
+  /// Besides the errors of [`Self::resolve`](Self::resolve()), fails with:
+  /// - [`ErrorCause::InvalidDIDUrl`] if the DID URL has a path, duplicate parameters or an invalid `versionTime`.
+  /// - [`ErrorCause::UnsupportedVersion`] if a version is requested from a handler that does not support history.
+  /// - [`ErrorCause::ResourceNotFound`] if the document has no resource matching the DID URL.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use identity_did::DIDUrl;
+  /// # use identity_document::document::CoreDocument;
+  /// # use identity_resolver::DereferencedResource;
+  /// # use identity_resolver::Resolver;
+  ///
+  /// async fn service_endpoint(resolver: &Resolver<CoreDocument>) -> Option<String> {
+  ///   let did_url: DIDUrl = DIDUrl::parse("did:foo:123?service=files&relativeRef=/resume.pdf").unwrap();
+  ///   match resolver.dereference(&did_url).await {
+  ///     Ok(DereferencedResource::ServiceEndpoint(url)) => Some(url.to_string()),
+  ///     _ => None,
+  ///   }
+  /// }
+  /// ```
+  pub async fn dereference(&self, did_url: &DIDUrl) -> Result<DereferencedResource<DOC>>
+  where
+    DOC: AsRef<CoreDocument>,
+  {
+    let mut params: DereferencingParams = DereferencingParams::parse(did_url)?;
+    let (document, _) = self.resolve_document(did_url.did(), params.version.take()).await?;
+    dereference_resource(document, did_url, params)
+  }
+
+  async fn resolve_document<D: DID>(&self, did: &D, version: Option<DocumentVersion>) -> Result<Resolved<DOC>> {
     let method: &str = did.method();
     let delegate: &M = self
       .command_map
@@ -99,7 +188,75 @@ where
       })
       .map_err(Error::new)?;
 
-    delegate.apply(did.as_str()).await
+    // Only the latest versions of documents are cached.
+    if version.is_some() {
+      return delegate.apply(did.as_str(), version).await;
+    }
+
+    if let Some(resolved) = self.cache.as_ref().and_then(|cache| cache.get(did.as_str())) {
+      return Ok(resolved);
+    }
+
+    let resolved: Resolved<DOC> = delegate.apply(did.as_str(), None).await?;
+    if let Some(cache) = self.cache.as_ref() {
+      cache.insert(did.as_str(), &resolved);
+    }
+    Ok(resolved)
+  }
+
+  /// Inserts the `command` handling the given `method`, evicting the cached results of the method.
+  fn insert_command(&mut self, method: String, command: M) {
+    if let Some(cache) = self.cache.as_ref() {
+      cache.remove_method(&method);
+    }
+    self.command_map.insert(method, command);
+  }
+
+  /// Enables caching of successful resolution results, replacing any previously cached results.
+  ///
+  /// Cached results are returned by all resolution methods, including
+  /// [`Self::resolve_multiple`](Self::resolve_multiple()), until they are older than `ttl`. At most `max_size`
+  /// results are cached at a time, once this limit is reached expired results are evicted first, followed by the
+  /// least recently used one. Failed resolutions are never cached.
+  ///
+  /// Attaching a handler evicts the cached results of the DID method it is attached for.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use identity_core::common::Duration;
+  /// # use identity_resolver::Resolver;
+  /// # use identity_document::document::CoreDocument;
+  ///
+  /// let mut resolver = Resolver::<CoreDocument>::new();
+  /// // Cache up to 1000 documents for 5 minutes each.
+  /// resolver.enable_cache(Duration::minutes(5), 1000);
+  /// ```
+  pub fn enable_cache(&mut self, ttl: Duration, max_size: usize)
+  where
+    DOC: Clone,
+  {
+    self.cache = Some(ResolutionCache::new(ttl, max_size));
+  }
+
+  /// Disables caching and drops all cached resolution results.
+  pub fn disable_cache(&mut self) {
+    self.cache = None;
+  }
+
+  /// Removes the cached resolution result of the given DID, such that it is resolved by the handler again the next
+  /// time it is requested.
+  pub fn invalidate<D: DID>(&self, did: &D) {
+    if let Some(cache) = self.cache.as_ref() {
+      cache.remove(did.as_str());
+    }
+  }
+
+  /// Removes all cached resolution results.
+  pub fn clear_cache(&self) {
+    if let Some(cache) = self.cache.as_ref() {
+      cache.clear();
+    }
   }
 
   /// Concurrently fetches the DID Documents of the multiple given DIDs.
@@ -183,8 +340,69 @@ impl<DOC: 'static> Resolver<DOC, SendSyncCommand<DOC>> {
     E: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
     DIDERR: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
   {
-    let command = SendSyncCommand::new(handler);
-    self.command_map.insert(method, command);
+    self.attach_handler_with_metadata(method, handler, |_: &DOCUMENT| DocumentMetadata::default());
+  }
+
+  /// Attach a new handler responsible for resolving DIDs of the given DID method, whose resolved documents carry
+  /// metadata.
+  ///
+  /// Equivalent to [`Self::attach_handler`](Self::attach_handler()), but additionally takes a `metadata` function
+  /// extracting the [`DocumentMetadata`] from the documents returned by the `handler`, which is exposed by
+  /// [`Resolver::resolve_with_metadata`].
+  pub fn attach_handler_with_metadata<D, F, Fut, DOCUMENT, E, DIDERR, META>(
+    &mut self,
+    method: String,
+    handler: F,
+    metadata: META,
+  ) where
+    D: DID + Send + for<'r> TryFrom<&'r str, Error = DIDERR> + 'static,
+    DOCUMENT: 'static + Into<DOC>,
+    F: Fn(D) -> Fut + 'static + Clone + Send + Sync,
+    Fut: Future<Output = std::result::Result<DOCUMENT, E>> + Send,
+    E: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    DIDERR: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    META: Fn(&DOCUMENT) -> DocumentMetadata + 'static + Clone + Send + Sync,
+  {
+    let command = SendSyncCommand::new(handler, metadata);
+    self.insert_command(method, command);
+  }
+
+  /// Attach a new handler responsible for resolving DIDs of the given DID method, which supports resolving historical
+  /// versions of DID documents.
+  ///
+  /// Equivalent to [`Self::attach_handler`](Self::attach_handler()), but the `handler` additionally receives the
+  /// [`DocumentVersion`] requested by the `versionId` or `versionTime` parameter of a DID URL passed to
+  /// [`Resolver::dereference`], or `None` if the latest version of the document is requested.
+  pub fn attach_versioned_handler<D, F, Fut, DOCUMENT, E, DIDERR>(&mut self, method: String, handler: F)
+  where
+    D: DID + Send + for<'r> TryFrom<&'r str, Error = DIDERR> + 'static,
+    DOCUMENT: 'static + Into<DOC>,
+    F: Fn(D, Option<DocumentVersion>) -> Fut + 'static + Clone + Send + Sync,
+    Fut: Future<Output = std::result::Result<DOCUMENT, E>> + Send,
+    E: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    DIDERR: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+  {
+    self.attach_versioned_handler_with_metadata(method, handler, |_: &DOCUMENT| DocumentMetadata::default());
+  }
+
+  /// Equivalent to [`Self::attach_versioned_handler`](Self::attach_versioned_handler()), but additionally takes a
+  /// `metadata` function extracting the [`DocumentMetadata`] from the documents returned by the `handler`.
+  pub fn attach_versioned_handler_with_metadata<D, F, Fut, DOCUMENT, E, DIDERR, META>(
+    &mut self,
+    method: String,
+    handler: F,
+    metadata: META,
+  ) where
+    D: DID + Send + for<'r> TryFrom<&'r str, Error = DIDERR> + 'static,
+    DOCUMENT: 'static + Into<DOC>,
+    F: Fn(D, Option<DocumentVersion>) -> Fut + 'static + Clone + Send + Sync,
+    Fut: Future<Output = std::result::Result<DOCUMENT, E>> + Send,
+    E: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    DIDERR: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    META: Fn(&DOCUMENT) -> DocumentMetadata + 'static + Clone + Send + Sync,
+  {
+    let command = SendSyncCommand::new_versioned(handler, metadata);
+    self.insert_command(method, command);
   }
 }
 
@@ -242,13 +460,76 @@ impl<DOC: 'static> Resolver<DOC, SingleThreadedCommand<DOC>> {
     E: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
     DIDERR: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
   {
-    let command = SingleThreadedCommand::new(handler);
-    self.command_map.insert(method, command);
+    self.attach_handler_with_metadata(method, handler, |_: &DOCUMENT| DocumentMetadata::default());
+  }
+
+  /// Attach a new handler responsible for resolving DIDs of the given DID method, whose resolved documents carry
+  /// metadata.
+  ///
+  /// Equivalent to [`Self::attach_handler`](Self::attach_handler()), but additionally takes a `metadata` function
+  /// extracting the [`DocumentMetadata`] from the documents returned by the `handler`, which is exposed by
+  /// [`Resolver::resolve_with_metadata`].
+  pub fn attach_handler_with_metadata<D, F, Fut, DOCUMENT, E, DIDERR, META>(
+    &mut self,
+    method: String,
+    handler: F,
+    metadata: META,
+  ) where
+    D: DID + for<'r> TryFrom<&'r str, Error = DIDERR> + 'static,
+    DOCUMENT: 'static + Into<DOC>,
+    F: Fn(D) -> Fut + 'static + Clone,
+    Fut: Future<Output = std::result::Result<DOCUMENT, E>>,
+    E: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    DIDERR: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    META: Fn(&DOCUMENT) -> DocumentMetadata + 'static + Clone,
+  {
+    let command = SingleThreadedCommand::new(handler, metadata);
+    self.insert_command(method, command);
+  }
+
+  /// Attach a new handler responsible for resolving DIDs of the given DID method, which supports resolving historical
+  /// versions of DID documents.
+  ///
+  /// Equivalent to [`Self::attach_handler`](Self::attach_handler()), but the `handler` additionally receives the
+  /// [`DocumentVersion`] requested by the `versionId` or `versionTime` parameter of a DID URL passed to
+  /// [`Resolver::dereference`], or `None` if the latest version of the document is requested.
+  pub fn attach_versioned_handler<D, F, Fut, DOCUMENT, E, DIDERR>(&mut self, method: String, handler: F)
+  where
+    D: DID + for<'r> TryFrom<&'r str, Error = DIDERR> + 'static,
+    DOCUMENT: 'static + Into<DOC>,
+    F: Fn(D, Option<DocumentVersion>) -> Fut + 'static + Clone,
+    Fut: Future<Output = std::result::Result<DOCUMENT, E>>,
+    E: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    DIDERR: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+  {
+    self.attach_versioned_handler_with_metadata(method, handler, |_: &DOCUMENT| DocumentMetadata::default());
+  }
+
+  /// Equivalent to [`Self::attach_versioned_handler`](Self::attach_versioned_handler()), but additionally takes a
+  /// `metadata` function extracting the [`DocumentMetadata`] from the documents returned by the `handler`.
+  pub fn attach_versioned_handler_with_metadata<D, F, Fut, DOCUMENT, E, DIDERR, META>(
+    &mut self,
+    method: String,
+    handler: F,
+    metadata: META,
+  ) where
+    D: DID + for<'r> TryFrom<&'r str, Error = DIDERR> + 'static,
+    DOCUMENT: 'static + Into<DOC>,
+    F: Fn(D, Option<DocumentVersion>) -> Fut + 'static + Clone,
+    Fut: Future<Output = std::result::Result<DOCUMENT, E>>,
+    E: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    DIDERR: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    META: Fn(&DOCUMENT) -> DocumentMetadata + 'static + Clone,
+  {
+    let command = SingleThreadedCommand::new_versioned(handler, metadata);
+    self.insert_command(method, command);
   }
 }
 
 #[cfg(feature = "iota")]
 mod iota_handler {
+  use crate::DocumentMetadata;
+  use crate::DocumentVersion;
   use crate::ErrorCause;
 
   use super::Resolver;
@@ -265,6 +546,11 @@ mod iota_handler {
   {
     /// Convenience method for attaching a new handler responsible for resolving IOTA DIDs.
     ///
+    /// The handler supports the `versionId` and `versionTime` parameters of [`Resolver::dereference`], resolving the
+    /// DID document at the given state index of its Alias Output, or at the given time, respectively. This requires
+    /// a client with access to the history of Alias Outputs, see
+    /// [`IotaIdentityClientExt::resolve_did_history`].
+    ///
     /// See also [`attach_handler`](Self::attach_handler).
     pub fn attach_iota_handler<CLI>(&mut self, client: CLI)
     where
@@ -272,12 +558,14 @@ mod iota_handler {
     {
       let arc_client: Arc<CLI> = Arc::new(client);
 
-      let handler = move |did: IotaDID| {
+      let handler = move |did: IotaDID, version: Option<DocumentVersion>| {
         let future_client = arc_client.clone();
-        async move { future_client.resolve_did(&did).await }
+        async move { resolve_iota_did(future_client.as_ref(), &did, version).await }
       };
 
-      self.attach_handler(IotaDID::METHOD.to_owned(), handler);
+      self.attach_versioned_handler_with_metadata(IotaDID::METHOD.to_owned(), handler, |document: &IotaDocument| {
+        DocumentMetadata::from(&document.metadata)
+      });
     }
 
     /// Convenience method for attaching multiple handlers responsible for resolving IOTA DIDs
@@ -312,7 +600,7 @@ mod iota_handler {
     {
       let arc_clients = Arc::new(clients.into_iter().collect::<HashMap<&'static str, CLI>>());
 
-      let handler = move |did: IotaDID| {
+      let handler = move |did: IotaDID, version: Option<DocumentVersion>| {
         let future_client = arc_clients.clone();
         async move {
           let did_network = did.network_str();
@@ -322,21 +610,216 @@ mod iota_handler {
               .ok_or(crate::Error::new(ErrorCause::UnsupportedNetwork(
                 did_network.to_string(),
               )))?;
-          client
-            .resolve_did(&did)
+          resolve_iota_did(client, &did, version)
             .await
-            .map_err(|err| crate::Error::new(ErrorCause::HandlerError { source: Box::new(err) }))
+            .map_err(|err| crate::Error::new(ErrorCause::HandlerError { source: err }))
         }
       };
 
-      self.attach_handler(IotaDID::METHOD.to_owned(), handler);
+      self.attach_versioned_handler_with_metadata(IotaDID::METHOD.to_owned(), handler, |document: &IotaDocument| {
+        DocumentMetadata::from(&document.metadata)
+      });
+    }
+  }
+
+  /// Resolves the given `version` of the DID document of `did`, where a `versionId` is the state index of its Alias
+  /// Output.
+  async fn resolve_iota_did<CLI>(
+    client: &CLI,
+    did: &IotaDID,
+    version: Option<DocumentVersion>,
+  ) -> Result<IotaDocument, Box<dyn std::error::Error + Send + Sync + 'static>>
+  where
+    CLI: IotaIdentityClientExt + Send + Sync + 'static,
+  {
+    match version {
+      None => client.resolve_did(did).await.map_err(Into::into),
+      Some(DocumentVersion::Id(version_id)) => {
+        let state_index: u32 = version_id.parse().map_err(|_| {
+          crate::Error::new(ErrorCause::InvalidDIDUrl {
+            reason: "the versionId of an IOTA DID must be a state index",
+          })
+        })?;
+        client
+          .resolve_did_at_state_index(did, state_index)
+          .await
+          .map_err(Into::into)
+      }
+      Some(DocumentVersion::Time(timestamp)) => client.resolve_did_at_time(did, timestamp).await.map_err(Into::into),
+    }
+  }
+}
+
+#[cfg(feature = "did-key")]
+mod did_key_handler {
+  use super::Resolver;
+  use super::SingleThreadedCommand;
+  use identity_did::DIDKey;
+  use identity_document::document::CoreDocument;
+
+  impl<DOC> Resolver<DOC>
+  where
+    DOC: From<CoreDocument> + 'static,
+  {
+    /// Convenience method for attaching a handler resolving did:key DIDs, by expanding them into the DID document
+    /// they represent without any network access.
+    ///
+    /// See also [`CoreDocument::expand_did_key`] and [`attach_handler`](Self::attach_handler).
+    pub fn attach_did_key_handler(&mut self) {
+      let handler = |did: DIDKey| async move { CoreDocument::expand_did_key(did) };
+      self.attach_handler(DIDKey::METHOD.to_owned(), handler);
+    }
+  }
+
+  impl<DOC> Resolver<DOC, SingleThreadedCommand<DOC>>
+  where
+    DOC: From<CoreDocument> + 'static,
+  {
+    /// Convenience method for attaching a handler resolving did:key DIDs, by expanding them into the DID document
+    /// they represent without any network access.
+    ///
+    /// See also [`CoreDocument::expand_did_key`] and [`attach_handler`](Self::attach_handler).
+    pub fn attach_did_key_handler(&mut self) {
+      let handler = |did: DIDKey| async move { CoreDocument::expand_did_key(did) };
+      self.attach_handler(DIDKey::METHOD.to_owned(), handler);
+    }
+  }
+}
+
+mod did_jwk_handler {
+  use super::Resolver;
+  use super::SingleThreadedCommand;
+  use identity_did::DIDJwk;
+  use identity_document::document::CoreDocument;
+
+  impl<DOC> Resolver<DOC>
+  where
+    DOC: From<CoreDocument> + 'static,
+  {
+    /// Convenience method for attaching a handler resolving did:jwk DIDs, by expanding them into the DID document
+    /// they represent without any network access.
+    ///
+    /// See also [`CoreDocument::expand_did_jwk`] and [`attach_handler`](Self::attach_handler).
+    pub fn attach_did_jwk_handler(&mut self) {
+      let handler = |did: DIDJwk| async move { CoreDocument::expand_did_jwk(did) };
+      self.attach_handler(DIDJwk::METHOD.to_owned(), handler);
+    }
+  }
+
+  impl<DOC> Resolver<DOC, SingleThreadedCommand<DOC>>
+  where
+    DOC: From<CoreDocument> + 'static,
+  {
+    /// Convenience method for attaching a handler resolving did:jwk DIDs, by expanding them into the DID document
+    /// they represent without any network access.
+    ///
+    /// See also [`CoreDocument::expand_did_jwk`] and [`attach_handler`](Self::attach_handler).
+    pub fn attach_did_jwk_handler(&mut self) {
+      let handler = |did: DIDJwk| async move { CoreDocument::expand_did_jwk(did) };
+      self.attach_handler(DIDJwk::METHOD.to_owned(), handler);
+    }
+  }
+}
+
+mod did_peer_handler {
+  use super::Resolver;
+  use super::SingleThreadedCommand;
+  use identity_did::DIDPeer;
+  use identity_document::document::CoreDocument;
+
+  impl<DOC> Resolver<DOC>
+  where
+    DOC: From<CoreDocument> + 'static,
+  {
+    /// Convenience method for attaching a handler resolving numalgo 0 and numalgo 2 did:peer DIDs, by expanding them
+    /// into the DID document they represent without any network access.
+    ///
+    /// See also [`CoreDocument::expand_did_peer`] and [`attach_handler`](Self::attach_handler).
+    pub fn attach_did_peer_handler(&mut self) {
+      let handler = |did: DIDPeer| async move { CoreDocument::expand_did_peer(did) };
+      self.attach_handler(DIDPeer::METHOD.to_owned(), handler);
+    }
+  }
+
+  impl<DOC> Resolver<DOC, SingleThreadedCommand<DOC>>
+  where
+    DOC: From<CoreDocument> + 'static,
+  {
+    /// Convenience method for attaching a handler resolving numalgo 0 and numalgo 2 did:peer DIDs, by expanding them
+    /// into the DID document they represent without any network access.
+    ///
+    /// See also [`CoreDocument::expand_did_peer`] and [`attach_handler`](Self::attach_handler).
+    pub fn attach_did_peer_handler(&mut self) {
+      let handler = |did: DIDPeer| async move { CoreDocument::expand_did_peer(did) };
+      self.attach_handler(DIDPeer::METHOD.to_owned(), handler);
+    }
+  }
+}
+
+#[cfg(feature = "did-web")]
+mod did_web_handler {
+  use super::Resolver;
+  use super::SingleThreadedCommand;
+  use crate::resolution::did_web::resolve_did_web;
+  use crate::DIDWebClient;
+  use identity_did::DIDWeb;
+  use identity_document::document::CoreDocument;
+  use std::sync::Arc;
+
+  impl<DOC> Resolver<DOC>
+  where
+    DOC: From<CoreDocument> + 'static,
+  {
+    /// Convenience method for attaching a handler resolving did:web DIDs, by fetching their DID document with the
+    /// given HTTP `client`.
+    ///
+    /// Resolution fails if the id of the fetched document does not match the resolved DID.
+    ///
+    /// See also [`DIDWeb::url`] and [`attach_handler`](Self::attach_handler).
+    pub fn attach_did_web_handler<CLI>(&mut self, client: CLI)
+    where
+      CLI: DIDWebClient + Send + Sync + 'static,
+    {
+      let arc_client: Arc<CLI> = Arc::new(client);
+
+      let handler = move |did: DIDWeb| {
+        let future_client = arc_client.clone();
+        async move { resolve_did_web(future_client.as_ref(), &did).await }
+      };
+
+      self.attach_handler(DIDWeb::METHOD.to_owned(), handler);
+    }
+  }
+
+  impl<DOC> Resolver<DOC, SingleThreadedCommand<DOC>>
+  where
+    DOC: From<CoreDocument> + 'static,
+  {
+    /// Convenience method for attaching a handler resolving did:web DIDs, by fetching their DID document with the
+    /// given HTTP `client`.
+    ///
+    /// Resolution fails if the id of the fetched document does not match the resolved DID.
+    ///
+    /// See also [`DIDWeb::url`] and [`attach_handler`](Self::attach_handler).
+    pub fn attach_did_web_handler<CLI>(&mut self, client: CLI)
+    where
+      CLI: DIDWebClient + 'static,
+    {
+      let arc_client: Arc<CLI> = Arc::new(client);
+
+      let handler = move |did: DIDWeb| {
+        let future_client = arc_client.clone();
+        async move { resolve_did_web(future_client.as_ref(), &did).await }
+      };
+
+      self.attach_handler(DIDWeb::METHOD.to_owned(), handler);
     }
   }
 }
 
 impl<CMD, DOC> Default for Resolver<DOC, CMD>
 where
-  CMD: for<'r> Command<'r, Result<DOC>>,
+  CMD: for<'r> Command<'r, Result<Resolved<DOC>>>,
   DOC: AsRef<CoreDocument>,
 {
   fn default() -> Self {
@@ -346,18 +829,21 @@ where
 
 impl<CMD, DOC> std::fmt::Debug for Resolver<DOC, CMD>
 where
-  CMD: for<'r> Command<'r, Result<DOC>>,
+  CMD: for<'r> Command<'r, Result<Resolved<DOC>>>,
   DOC: AsRef<CoreDocument>,
 {
   fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
     f.debug_struct("Resolver")
       .field("command_map", &self.command_map)
+      .field("cache", &self.cache)
       .finish()
   }
 }
 
 #[cfg(test)]
 mod tests {
+  use identity_iota_core::block::address::Address;
+  use identity_iota_core::block::address::Ed25519Address;
   use identity_iota_core::block::output::AliasId;
   use identity_iota_core::block::output::AliasOutput;
   use identity_iota_core::block::output::OutputId;
@@ -366,6 +852,7 @@ mod tests {
   use identity_iota_core::IotaDocument;
   use identity_iota_core::IotaIdentityClient;
   use identity_iota_core::IotaIdentityClientExt;
+  use identity_iota_core::MemIdentityClient;
 
   use super::*;
 
@@ -392,6 +879,19 @@ mod tests {
         ))
       }
     }
+
+    async fn resolve_did_at_state_index(
+      &self,
+      did: &IotaDID,
+      state_index: u32,
+    ) -> identity_iota_core::Result<IotaDocument> {
+      match state_index {
+        0 => self.resolve_did(did).await,
+        _ => Err(identity_iota_core::Error::DIDVersionNotFound(format!(
+          "state index {state_index}"
+        ))),
+      }
+    }
   }
 
   #[tokio::test]
@@ -414,4 +914,83 @@ mod tests {
     let doc = resolver.resolve(&did2).await.unwrap();
     assert_eq!(doc.id(), &did2);
   }
+
+  #[tokio::test]
+  async fn test_resolve_with_metadata() {
+    let did = IotaDID::parse("did:iota:0x0101010101010101010101010101010101010101010101010101010101010101").unwrap();
+    let mut document = IotaDocument::new_with_id(did.clone());
+    document.metadata.deactivated = Some(true);
+    document.metadata.governor_address = Some("governor".to_owned());
+
+    let mut resolver = Resolver::<CoreDocument>::new();
+    resolver.attach_iota_handler(DummyClient(document.clone()));
+
+    let result = resolver.resolve_with_metadata(&did).await;
+    assert_eq!(result.did_document.as_ref(), Some(document.as_ref()));
+    assert!(result.is_deactivated());
+    assert_eq!(result.did_document_metadata.created, document.metadata.created);
+    assert_eq!(result.did_document_metadata.updated, document.metadata.updated);
+    assert_eq!(
+      result.did_document_metadata.properties.get("governorAddress"),
+      Some(&"governor".into())
+    );
+    assert!(result.error().is_none());
+
+    let missing_did =
+      IotaDID::parse("did:iota:0x0202020202020202020202020202020202020202020202020202020202020202").unwrap();
+    let result = resolver.resolve_with_metadata(&missing_did).await;
+    assert!(result.did_document.is_none());
+    assert_eq!(result.error(), Some(ResolutionErrorCode::NotFound));
+
+    let mut resolver = Resolver::<CoreDocument>::new();
+    resolver.attach_multiple_iota_handlers(vec![("smr", DummyClient(document))]);
+    let result = resolver.resolve_with_metadata(&did).await;
+    assert_eq!(result.error(), Some(ResolutionErrorCode::MethodNotSupported));
+  }
+
+  #[tokio::test]
+  async fn test_dereference_iota_version() {
+    let did = IotaDID::parse("did:iota:0x0101010101010101010101010101010101010101010101010101010101010101").unwrap();
+    let document = IotaDocument::new_with_id(did.clone());
+    let mut resolver = Resolver::<CoreDocument>::new();
+    resolver.attach_iota_handler(DummyClient(document.clone()));
+
+    let did_url = DIDUrl::parse(format!("{did}?versionId=0")).unwrap();
+    let resource = resolver.dereference(&did_url).await.unwrap();
+    assert!(matches!(resource, DereferencedResource::Document(doc) if &doc == document.as_ref()));
+
+    let did_url = DIDUrl::parse(format!("{did}?versionId=1")).unwrap();
+    let error = resolver.dereference(&did_url).await.unwrap_err();
+    assert_eq!(ResolutionErrorCode::from(&error), ResolutionErrorCode::NotFound);
+
+    let did_url = DIDUrl::parse(format!("{did}?versionId=latest")).unwrap();
+    let error = resolver.dereference(&did_url).await.unwrap_err();
+    assert_eq!(ResolutionErrorCode::from(&error), ResolutionErrorCode::InvalidDidUrl);
+  }
+
+  #[tokio::test]
+  async fn test_memory_client() {
+    let client: MemIdentityClient = MemIdentityClient::new();
+    let mut resolver = Resolver::<IotaDocument>::new();
+    resolver.attach_iota_handler(client.clone());
+
+    let address: Address = Address::Ed25519(Ed25519Address::new([3; 32]));
+    let document = IotaDocument::new(&client.network_name().await.unwrap());
+    let alias_output = client.new_did_output(address, document, None).await.unwrap();
+    let document = client.publish_did_output(alias_output).await.unwrap();
+    assert_eq!(resolver.resolve(document.id()).await.unwrap(), document);
+
+    let alias_output = client.deactivate_did_output(document.id()).await.unwrap();
+    client.publish_did_output(alias_output).await.unwrap();
+    let deactivated = resolver.resolve(document.id()).await.unwrap();
+    assert_eq!(deactivated.metadata.deactivated, Some(true));
+
+    let did_url = DIDUrl::parse(format!("{}?versionId=0", document.id())).unwrap();
+    let resource = resolver.dereference(&did_url).await.unwrap();
+    assert!(matches!(resource, DereferencedResource::Document(doc) if doc == document));
+
+    client.delete_did_output(document.id()).await.unwrap();
+    let result = resolver.resolve_with_metadata(document.id()).await;
+    assert_eq!(result.error(), Some(ResolutionErrorCode::NotFound));
+  }
 }
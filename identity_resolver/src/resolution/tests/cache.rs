@@ -0,0 +1,169 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use identity_core::common::Duration;
+use identity_did::CoreDID;
+use identity_document::document::CoreDocument;
+use identity_document::document::DocumentBuilder;
+
+use crate::Resolver;
+use crate::SingleThreadedResolver;
+
+/// Creates a resolver for the "foo" method whose handler counts how often it is called.
+fn counting_resolver() -> (Resolver<CoreDocument>, Arc<AtomicUsize>) {
+  let calls: Arc<AtomicUsize> = Arc::new(AtomicUsize::new(0));
+  let calls_clone: Arc<AtomicUsize> = calls.clone();
+
+  let mut resolver: Resolver<CoreDocument> = Resolver::new();
+  resolver.attach_handler("foo".to_owned(), move |did: CoreDID| {
+    calls_clone.fetch_add(1, Ordering::SeqCst);
+    async move { Ok::<_, std::io::Error>(DocumentBuilder::default().id(did).build().unwrap()) }
+  });
+  (resolver, calls)
+}
+
+fn did(method_id: &str) -> CoreDID {
+  CoreDID::parse(format!("did:foo:{method_id}")).unwrap()
+}
+
+#[tokio::test]
+async fn cache_disabled_by_default() {
+  let (resolver, calls) = counting_resolver();
+  resolver.resolve(&did("1")).await.unwrap();
+  resolver.resolve(&did("1")).await.unwrap();
+  assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn cache_hits_and_invalidation() {
+  let (mut resolver, calls) = counting_resolver();
+  resolver.enable_cache(Duration::minutes(5), 10);
+
+  let doc: CoreDocument = resolver.resolve(&did("1")).await.unwrap();
+  assert_eq!(doc.id(), &did("1"));
+  let doc: CoreDocument = resolver.resolve(&did("1")).await.unwrap();
+  assert_eq!(doc.id(), &did("1"));
+  assert!(resolver.resolve_with_metadata(&did("1")).await.did_document.is_some());
+  assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+  resolver.invalidate(&did("1"));
+  resolver.resolve(&did("1")).await.unwrap();
+  assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+  resolver.resolve(&did("2")).await.unwrap();
+  resolver.clear_cache();
+  resolver.resolve(&did("1")).await.unwrap();
+  resolver.resolve(&did("2")).await.unwrap();
+  assert_eq!(calls.load(Ordering::SeqCst), 5);
+
+  resolver.disable_cache();
+  resolver.resolve(&did("1")).await.unwrap();
+  assert_eq!(calls.load(Ordering::SeqCst), 6);
+}
+
+#[tokio::test]
+async fn cache_expiration() {
+  let (mut resolver, calls) = counting_resolver();
+  resolver.enable_cache(Duration::seconds(0), 10);
+
+  resolver.resolve(&did("1")).await.unwrap();
+  resolver.resolve(&did("1")).await.unwrap();
+  assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn cache_max_size_evicts_least_recently_used() {
+  let (mut resolver, calls) = counting_resolver();
+  resolver.enable_cache(Duration::minutes(5), 2);
+
+  resolver.resolve(&did("1")).await.unwrap();
+  resolver.resolve(&did("2")).await.unwrap();
+  // Use "1" such that "2" is the least recently used entry.
+  resolver.resolve(&did("1")).await.unwrap();
+  assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+  resolver.resolve(&did("3")).await.unwrap();
+  assert_eq!(calls.load(Ordering::SeqCst), 3);
+  resolver.resolve(&did("1")).await.unwrap();
+  resolver.resolve(&did("3")).await.unwrap();
+  assert_eq!(calls.load(Ordering::SeqCst), 3);
+  resolver.resolve(&did("2")).await.unwrap();
+  assert_eq!(calls.load(Ordering::SeqCst), 4);
+}
+
+#[tokio::test]
+async fn cache_failures_are_not_cached() {
+  let calls: Arc<AtomicUsize> = Arc::new(AtomicUsize::new(0));
+  let calls_clone: Arc<AtomicUsize> = calls.clone();
+  let mut resolver: Resolver<CoreDocument> = Resolver::new();
+  resolver.enable_cache(Duration::minutes(5), 10);
+  resolver.attach_handler("foo".to_owned(), move |_did: CoreDID| {
+    calls_clone.fetch_add(1, Ordering::SeqCst);
+    async move { Err::<CoreDocument, _>(std::io::Error::new(std::io::ErrorKind::Other, "failure")) }
+  });
+
+  assert!(resolver.resolve(&did("1")).await.is_err());
+  assert!(resolver.resolve(&did("1")).await.is_err());
+  assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn cache_resolve_multiple() {
+  let (mut resolver, calls) = counting_resolver();
+  resolver.enable_cache(Duration::minutes(5), 10);
+
+  resolver.resolve(&did("1")).await.unwrap();
+  let documents: HashMap<CoreDID, CoreDocument> = resolver
+    .resolve_multiple(&[did("1"), did("2"), did("3")])
+    .await
+    .unwrap();
+  assert_eq!(documents.len(), 3);
+  assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+  let documents: HashMap<CoreDID, CoreDocument> = resolver
+    .resolve_multiple(&[did("1"), did("2"), did("3")])
+    .await
+    .unwrap();
+  assert_eq!(documents.len(), 3);
+  assert_eq!(calls.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn cache_attaching_handler_evicts_method() {
+  let (mut resolver, calls) = counting_resolver();
+  resolver.enable_cache(Duration::minutes(5), 10);
+  resolver.resolve(&did("1")).await.unwrap();
+
+  let calls_clone: Arc<AtomicUsize> = calls.clone();
+  resolver.attach_handler("foo".to_owned(), move |did: CoreDID| {
+    calls_clone.fetch_add(1, Ordering::SeqCst);
+    async move { Ok::<_, std::io::Error>(DocumentBuilder::default().id(did).build().unwrap()) }
+  });
+  resolver.resolve(&did("1")).await.unwrap();
+  assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn cache_single_threaded() {
+  let calls: std::rc::Rc<std::cell::Cell<usize>> = Default::default();
+  let calls_clone = calls.clone();
+  let mut resolver: SingleThreadedResolver = SingleThreadedResolver::new();
+  resolver.attach_handler("foo".to_owned(), move |did: CoreDID| {
+    calls_clone.set(calls_clone.get() + 1);
+    async move { Ok::<_, std::io::Error>(DocumentBuilder::default().id(did).build().unwrap()) }
+  });
+  resolver.enable_cache(Duration::minutes(5), 10);
+
+  resolver.resolve(&did("1")).await.unwrap();
+  resolver.resolve_multiple(&[did("1"), did("2")]).await.unwrap();
+  assert_eq!(calls.get(), 2);
+
+  resolver.invalidate(&did("2"));
+  resolver.resolve(&did("2")).await.unwrap();
+  assert_eq!(calls.get(), 3);
+}
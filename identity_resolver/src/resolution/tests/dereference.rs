@@ -0,0 +1,239 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use identity_core::common::Duration;
+use identity_core::common::Timestamp;
+use identity_core::convert::FromJson;
+use identity_core::json;
+use identity_did::CoreDID;
+use identity_did::DIDUrl;
+use identity_document::document::CoreDocument;
+
+use crate::DereferencedResource;
+use crate::DocumentVersion;
+use crate::ErrorCause;
+use crate::Resolver;
+use crate::SingleThreadedResolver;
+
+/// Creates a document with a verification method and services for the given `did`, whose `version` property is
+/// set to the requested version.
+fn document(did: CoreDID, version: Option<DocumentVersion>) -> CoreDocument {
+  let version: String = match version {
+    None => "latest".to_owned(),
+    Some(DocumentVersion::Id(id)) => id,
+    Some(DocumentVersion::Time(time)) => time.to_rfc3339(),
+  };
+  CoreDocument::from_json_value(json!({
+    "id": did,
+    "version": version,
+    "verificationMethod": [{
+      "id": format!("{did}#key-1"),
+      "controller": did,
+      "type": "Ed25519VerificationKey2018",
+      "publicKeyMultibase": "zHyx62wPQGyvXCoihZq1BrbUjBRh2LuNxWiiqMkfAuSZr"
+    }],
+    "service": [
+      {
+        "id": format!("{did}#files"),
+        "type": "LinkedDomains",
+        "serviceEndpoint": "https://example.com/files/"
+      },
+      {
+        "id": format!("{did}#agents"),
+        "type": "DIDCommMessaging",
+        "serviceEndpoint": ["https://agent.example.com/?version=2", "https://backup.example.com"]
+      },
+      {
+        "id": format!("{did}#map"),
+        "type": "LinkedDomains",
+        "serviceEndpoint": { "origins": ["https://example.com"] }
+      }
+    ]
+  }))
+  .unwrap()
+}
+
+fn resolver() -> Resolver<CoreDocument> {
+  let mut resolver: Resolver<CoreDocument> = Resolver::new();
+  resolver.attach_versioned_handler(
+    "foo".to_owned(),
+    |did: CoreDID, version: Option<DocumentVersion>| async move { Ok::<_, std::io::Error>(document(did, version)) },
+  );
+  resolver
+}
+
+fn did_url(input: &str) -> DIDUrl {
+  DIDUrl::parse(input).unwrap()
+}
+
+fn version(document: &CoreDocument) -> &str {
+  document.properties()["version"].as_str().unwrap()
+}
+
+#[tokio::test]
+async fn dereference_document() {
+  let resolver: Resolver<CoreDocument> = resolver();
+  let resource = resolver.dereference(&did_url("did:foo:123")).await.unwrap();
+  let DereferencedResource::Document(document) = resource else {
+    panic!("expected a document");
+  };
+  assert_eq!(document.id().to_string(), "did:foo:123");
+  assert_eq!(version(&document), "latest");
+
+  // Unknown parameters are ignored.
+  let resource = resolver.dereference(&did_url("did:foo:123?foo=bar")).await.unwrap();
+  assert!(matches!(resource, DereferencedResource::Document(_)));
+}
+
+#[tokio::test]
+async fn dereference_fragment() {
+  let resolver: Resolver<CoreDocument> = resolver();
+
+  let resource = resolver.dereference(&did_url("did:foo:123#key-1")).await.unwrap();
+  let DereferencedResource::VerificationMethod(method) = resource else {
+    panic!("expected a verification method");
+  };
+  assert_eq!(method.id().to_string(), "did:foo:123#key-1");
+
+  let resource = resolver.dereference(&did_url("did:foo:123#files")).await.unwrap();
+  let DereferencedResource::Service(service) = resource else {
+    panic!("expected a service");
+  };
+  assert_eq!(service.id().to_string(), "did:foo:123#files");
+
+  let err = resolver.dereference(&did_url("did:foo:123#missing")).await.unwrap_err();
+  assert!(matches!(err.error_cause(), ErrorCause::ResourceNotFound { did_url } if did_url == "did:foo:123#missing"));
+}
+
+#[tokio::test]
+async fn dereference_service_endpoint() {
+  let resolver: Resolver<CoreDocument> = resolver();
+
+  let cases: [(&str, &str); 5] = [
+    ("did:foo:123?service=files", "https://example.com/files/"),
+    (
+      "did:foo:123?service=files&relativeRef=/resume.pdf",
+      "https://example.com/files/resume.pdf",
+    ),
+    (
+      "did:foo:123?service=files&relativeRef=/docs/cv.pdf#page=2",
+      "https://example.com/files/docs/cv.pdf#page=2",
+    ),
+    (
+      "did:foo:123?service=agents&relativeRef=/inbox",
+      "https://agent.example.com/inbox?version=2",
+    ),
+    (
+      "did:foo:123?service=agents#degree",
+      "https://agent.example.com/?version=2#degree",
+    ),
+  ];
+  for (input, expected) in cases {
+    let resource = resolver.dereference(&did_url(input)).await.unwrap();
+    let DereferencedResource::ServiceEndpoint(url) = resource else {
+      panic!("expected a service endpoint for {input}");
+    };
+    assert_eq!(url.as_str(), expected, "{input}");
+  }
+
+  for input in ["did:foo:123?service=missing", "did:foo:123?service=map"] {
+    let err = resolver.dereference(&did_url(input)).await.unwrap_err();
+    assert!(matches!(err.error_cause(), ErrorCause::ResourceNotFound { .. }));
+  }
+}
+
+#[tokio::test]
+async fn dereference_version() {
+  let resolver: Resolver<CoreDocument> = resolver();
+
+  let resource = resolver.dereference(&did_url("did:foo:123?versionId=4")).await.unwrap();
+  let DereferencedResource::Document(versioned_document) = resource else {
+    panic!("expected a document");
+  };
+  assert_eq!(version(&versioned_document), "4");
+
+  let resource = resolver
+    .dereference(&did_url("did:foo:123?versionTime=2023-01-01T00:00:00Z#key-1"))
+    .await
+    .unwrap();
+  assert!(matches!(resource, DereferencedResource::VerificationMethod(_)));
+
+  let mut single_threaded_resolver: SingleThreadedResolver = SingleThreadedResolver::new();
+  single_threaded_resolver.attach_versioned_handler("foo".to_owned(), |did: CoreDID, version| async move {
+    Ok::<_, std::io::Error>(document(did, version))
+  });
+  let resource = single_threaded_resolver
+    .dereference(&did_url("did:foo:123?versionTime=2023-01-01T00:00:00Z"))
+    .await
+    .unwrap();
+  let DereferencedResource::Document(versioned_document) = resource else {
+    panic!("expected a document");
+  };
+  assert_eq!(
+    version(&versioned_document),
+    Timestamp::parse("2023-01-01T00:00:00Z").unwrap().to_rfc3339()
+  );
+}
+
+#[tokio::test]
+async fn dereference_version_unsupported() {
+  let mut resolver: Resolver<CoreDocument> = Resolver::new();
+  resolver.attach_handler("foo".to_owned(), |did: CoreDID| async move {
+    Ok::<_, std::io::Error>(document(did, None))
+  });
+
+  assert!(resolver.dereference(&did_url("did:foo:123#key-1")).await.is_ok());
+  let err = resolver
+    .dereference(&did_url("did:foo:123?versionId=1"))
+    .await
+    .unwrap_err();
+  assert!(matches!(err.error_cause(), ErrorCause::UnsupportedVersion { method } if method == "foo"));
+}
+
+#[tokio::test]
+async fn dereference_invalid_did_url() {
+  let resolver: Resolver<CoreDocument> = resolver();
+
+  for input in [
+    "did:foo:123/path",
+    "did:foo:123?versionId=1&versionTime=2023-01-01T00:00:00Z",
+    "did:foo:123?versionTime=yesterday",
+    "did:foo:123?service=files&service=agents",
+    "did:foo:123?relativeRef=/resume.pdf",
+  ] {
+    let err = resolver.dereference(&did_url(input)).await.unwrap_err();
+    assert!(matches!(err.error_cause(), ErrorCause::InvalidDIDUrl { .. }), "{input}");
+  }
+
+  let err = resolver.dereference(&did_url("did:bar:123")).await.unwrap_err();
+  assert!(matches!(err.error_cause(), ErrorCause::UnsupportedMethodError { .. }));
+}
+
+#[tokio::test]
+async fn versions_are_not_cached() {
+  let calls: Arc<AtomicUsize> = Arc::new(AtomicUsize::new(0));
+  let calls_clone: Arc<AtomicUsize> = calls.clone();
+
+  let mut resolver: Resolver<CoreDocument> = Resolver::new();
+  resolver.attach_versioned_handler("foo".to_owned(), move |did: CoreDID, version| {
+    calls_clone.fetch_add(1, Ordering::SeqCst);
+    async move { Ok::<_, std::io::Error>(document(did, version)) }
+  });
+  resolver.enable_cache(Duration::minutes(5), 10);
+
+  let did: CoreDID = CoreDID::parse("did:foo:123").unwrap();
+  assert_eq!(version(&resolver.resolve(&did).await.unwrap()), "latest");
+  for _ in 0..2 {
+    let resource = resolver.dereference(&did_url("did:foo:123?versionId=1")).await.unwrap();
+    let DereferencedResource::Document(document) = resource else {
+      panic!("expected a document");
+    };
+    assert_eq!(version(&document), "1");
+  }
+  assert!(resolver.dereference(&did_url("did:foo:123#key-1")).await.is_ok());
+  assert_eq!(calls.load(Ordering::SeqCst), 3);
+}
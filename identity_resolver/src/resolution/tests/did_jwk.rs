@@ -0,0 +1,48 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_did::CoreDID;
+use identity_did::DIDJwk;
+use identity_document::document::CoreDocument;
+
+use crate::ErrorCause;
+use crate::ResolutionErrorCode;
+use crate::Resolver;
+use crate::SingleThreadedResolver;
+
+const DID_JWKS: [&str; 2] = [
+  "did:jwk:eyJjcnYiOiJQLTI1NiIsImt0eSI6IkVDIiwieCI6ImFjYklRaXVNczNpOF91c3pFakoydHBUdFJNNEVVM3l6OTFQSDZDZEgyVjAiLCJ5IjoiX0tjeUxqOXZXTXB0bm1LdG00NkdxRHo4d2Y3NEk1TEtncmwyR3pIM25TRSJ9",
+  "did:jwk:eyJrdHkiOiJPS1AiLCJjcnYiOiJYMjU1MTkiLCJ1c2UiOiJlbmMiLCJ4IjoiM3A3YmZYdDl3YlRUVzJIQzdPUTFOei1EUThoYmVHZE5yZngtRkctSUswOCJ9",
+];
+
+#[tokio::test]
+async fn resolve_did_jwk() {
+  let mut resolver: Resolver<CoreDocument> = Resolver::new();
+  resolver.attach_did_jwk_handler();
+  let mut single_threaded_resolver: SingleThreadedResolver = SingleThreadedResolver::new();
+  single_threaded_resolver.attach_did_jwk_handler();
+
+  for did in DID_JWKS {
+    let did: DIDJwk = DIDJwk::parse(did).unwrap();
+    let expected: CoreDocument = CoreDocument::expand_did_jwk(did.clone()).unwrap();
+
+    let document: CoreDocument = resolver.resolve(&did).await.unwrap();
+    assert_eq!(document, expected);
+    assert_eq!(document.id(), did.as_ref());
+    assert_eq!(single_threaded_resolver.resolve(&did).await.unwrap(), expected);
+  }
+}
+
+#[tokio::test]
+async fn resolve_invalid_did_jwk() {
+  let mut resolver: Resolver<CoreDocument> = Resolver::new();
+  resolver.attach_did_jwk_handler();
+
+  let did: CoreDID = CoreDID::parse("did:jwk:eyJrdHkiOiJPS1AifQ").unwrap();
+  let err = resolver.resolve(&did).await.unwrap_err();
+  assert!(matches!(err.error_cause(), ErrorCause::DIDParsingError { .. }));
+  assert_eq!(
+    resolver.resolve_with_metadata(&did).await.error(),
+    Some(ResolutionErrorCode::InvalidDid)
+  );
+}
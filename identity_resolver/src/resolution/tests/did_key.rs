@@ -0,0 +1,50 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_did::CoreDID;
+use identity_did::DIDKey;
+use identity_document::document::CoreDocument;
+
+use crate::ErrorCause;
+use crate::ResolutionErrorCode;
+use crate::Resolver;
+use crate::SingleThreadedResolver;
+
+const DID_KEYS: [&str; 4] = [
+  "did:key:z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp",
+  "did:key:z6LSeu9HkTHSfLLeUs2nnzUSNedgDUevfNQgQjQC23ZCit6F",
+  "did:key:zDnaerx9CtbPJ1q36T5Ln5wYt3MQYeGRG5ehnPAmxcf5mDZpv",
+  "did:key:zQ3shptjE6JwdkeKN4fcpnYQY3m9Cet3NiHdAfpvSUZBFoKBj",
+];
+
+#[tokio::test]
+async fn resolve_did_key() {
+  let mut resolver: Resolver<CoreDocument> = Resolver::new();
+  resolver.attach_did_key_handler();
+  let mut single_threaded_resolver: SingleThreadedResolver = SingleThreadedResolver::new();
+  single_threaded_resolver.attach_did_key_handler();
+
+  for did in DID_KEYS {
+    let did: DIDKey = DIDKey::parse(did).unwrap();
+    let expected: CoreDocument = CoreDocument::expand_did_key(did.clone()).unwrap();
+
+    let document: CoreDocument = resolver.resolve(&did).await.unwrap();
+    assert_eq!(document, expected);
+    assert_eq!(document.id(), did.as_ref());
+    assert_eq!(single_threaded_resolver.resolve(&did).await.unwrap(), expected);
+  }
+}
+
+#[tokio::test]
+async fn resolve_invalid_did_key() {
+  let mut resolver: Resolver<CoreDocument> = Resolver::new();
+  resolver.attach_did_key_handler();
+
+  let did: CoreDID = CoreDID::parse("did:key:z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDoo").unwrap();
+  let err = resolver.resolve(&did).await.unwrap_err();
+  assert!(matches!(err.error_cause(), ErrorCause::DIDParsingError { .. }));
+  assert_eq!(
+    resolver.resolve_with_metadata(&did).await.error(),
+    Some(ResolutionErrorCode::InvalidDid)
+  );
+}
@@ -0,0 +1,49 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_did::CoreDID;
+use identity_did::DIDPeer;
+use identity_document::document::CoreDocument;
+
+use crate::ErrorCause;
+use crate::ResolutionErrorCode;
+use crate::Resolver;
+use crate::SingleThreadedResolver;
+
+const DID_PEERS: [&str; 2] = [
+  "did:peer:0z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK",
+  "did:peer:2.Ez6LSbysY2xFMRpGMhb7tFTLMpeuPRaqaWM1yECx2AtzE3KCc.Vz6MkqRYqQiSgvZQdnBytw86Qbs2ZWUkGv22od935YF4s8M7V.SeyJ0IjoiZG0iLCJzIjoiaHR0cHM6Ly9leGFtcGxlLmNvbS9lbmRwb2ludCJ9",
+];
+
+#[tokio::test]
+async fn resolve_did_peer() {
+  let mut resolver: Resolver<CoreDocument> = Resolver::new();
+  resolver.attach_did_peer_handler();
+  let mut single_threaded_resolver: SingleThreadedResolver = SingleThreadedResolver::new();
+  single_threaded_resolver.attach_did_peer_handler();
+
+  for did in DID_PEERS {
+    let did: DIDPeer = DIDPeer::parse(did).unwrap();
+    let expected: CoreDocument = CoreDocument::expand_did_peer(did.clone()).unwrap();
+
+    let document: CoreDocument = resolver.resolve(&did).await.unwrap();
+    assert_eq!(document, expected);
+    assert_eq!(document.id(), did.as_ref());
+    assert_eq!(single_threaded_resolver.resolve(&did).await.unwrap(), expected);
+  }
+}
+
+#[tokio::test]
+async fn resolve_invalid_did_peer() {
+  let mut resolver: Resolver<CoreDocument> = Resolver::new();
+  resolver.attach_did_peer_handler();
+
+  // Numalgo 1 is not statically resolvable.
+  let did: CoreDID = CoreDID::parse("did:peer:1zQmZMygzYqNwU6Uhmewx5Xepf2VLp5S4HLSwwgf2aiKZuwa").unwrap();
+  let err = resolver.resolve(&did).await.unwrap_err();
+  assert!(matches!(err.error_cause(), ErrorCause::DIDParsingError { .. }));
+  assert_eq!(
+    resolver.resolve_with_metadata(&did).await.error(),
+    Some(ResolutionErrorCode::InvalidDid)
+  );
+}
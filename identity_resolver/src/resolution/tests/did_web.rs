@@ -0,0 +1,142 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use identity_core::common::Url;
+use identity_core::convert::ToJson;
+use identity_did::CoreDID;
+use identity_did::DIDWeb;
+use identity_document::document::CoreDocument;
+
+use crate::DIDWebClient;
+use crate::DIDWebError;
+use crate::ErrorCause;
+use crate::ResolutionErrorCode;
+use crate::Resolver;
+use crate::SingleThreadedResolver;
+use crate::DID_WEB_MAX_DOCUMENT_SIZE;
+
+/// Serves documents from memory instead of fetching them over HTTP.
+#[derive(Default)]
+struct MockClient {
+  resources: HashMap<String, Vec<u8>>,
+}
+
+impl MockClient {
+  fn with_document(mut self, did: &str, document: &CoreDocument) -> Self {
+    let url: Url = DIDWeb::parse(did).unwrap().url();
+    self
+      .resources
+      .insert(url.into_string(), document.to_json_vec().unwrap());
+    self
+  }
+}
+
+#[async_trait::async_trait]
+impl DIDWebClient for MockClient {
+  async fn get(&self, url: &Url) -> Result<Vec<u8>, DIDWebError> {
+    self
+      .resources
+      .get(url.as_str())
+      .cloned()
+      .ok_or_else(|| DIDWebError::NotFound(url.clone()))
+  }
+}
+
+fn document(did: &str) -> CoreDocument {
+  CoreDocument::builder(Default::default())
+    .id(CoreDID::parse(did).unwrap())
+    .build()
+    .unwrap()
+}
+
+#[tokio::test]
+async fn resolve_did_web() {
+  let dids: [&str; 3] = [
+    "did:web:example.com",
+    "did:web:example.com:user:alice",
+    "did:web:localhost%3A8443",
+  ];
+  let client = || {
+    dids.iter().fold(MockClient::default(), |client, did| {
+      client.with_document(did, &document(did))
+    })
+  };
+
+  let mut resolver: Resolver<CoreDocument> = Resolver::new();
+  resolver.attach_did_web_handler(client());
+  let mut single_threaded_resolver: SingleThreadedResolver = SingleThreadedResolver::new();
+  single_threaded_resolver.attach_did_web_handler(client());
+
+  for did in dids {
+    let did: DIDWeb = DIDWeb::parse(did).unwrap();
+    let resolved: CoreDocument = resolver.resolve(&did).await.unwrap();
+    assert_eq!(resolved.id(), did.as_ref());
+    assert_eq!(single_threaded_resolver.resolve(&did).await.unwrap(), resolved);
+  }
+}
+
+#[tokio::test]
+async fn resolve_did_web_not_found() {
+  let mut resolver: Resolver<CoreDocument> = Resolver::new();
+  resolver.attach_did_web_handler(MockClient::default());
+
+  let did: DIDWeb = DIDWeb::parse("did:web:example.com").unwrap();
+  let err = resolver.resolve(&did).await.unwrap_err();
+  let ErrorCause::HandlerError { source } = err.error_cause() else {
+    panic!("unexpected error cause: {err}");
+  };
+  assert!(matches!(
+    source.downcast_ref::<DIDWebError>(),
+    Some(DIDWebError::NotFound(_))
+  ));
+  assert_eq!(
+    resolver.resolve_with_metadata(&did).await.error(),
+    Some(ResolutionErrorCode::NotFound)
+  );
+}
+
+#[tokio::test]
+async fn resolve_did_web_id_mismatch() {
+  let client: MockClient = MockClient::default().with_document("did:web:example.com", &document("did:web:example.org"));
+  let mut resolver: Resolver<CoreDocument> = Resolver::new();
+  resolver.attach_did_web_handler(client);
+
+  let err = resolver
+    .resolve(&DIDWeb::parse("did:web:example.com").unwrap())
+    .await
+    .unwrap_err();
+  let ErrorCause::HandlerError { source } = err.error_cause() else {
+    panic!("unexpected error cause: {err}");
+  };
+  assert!(matches!(
+    source.downcast_ref::<DIDWebError>(),
+    Some(DIDWebError::IdMismatch { .. })
+  ));
+}
+
+#[tokio::test]
+async fn resolve_did_web_invalid_document() {
+  let url: Url = DIDWeb::parse("did:web:example.com").unwrap().url();
+  let mut client: MockClient = MockClient::default();
+  client.resources.insert(url.to_string(), b"{}".to_vec());
+  client.resources.insert(
+    url.join("/large/did.json").unwrap().into_string(),
+    vec![b' '; DID_WEB_MAX_DOCUMENT_SIZE + 1],
+  );
+  let mut resolver: Resolver<CoreDocument> = Resolver::new();
+  resolver.attach_did_web_handler(client);
+
+  for (did, expected) in [
+    ("did:web:example.com", "InvalidDocument"),
+    ("did:web:example.com:large", "DocumentTooLarge"),
+  ] {
+    let err = resolver.resolve(&DIDWeb::parse(did).unwrap()).await.unwrap_err();
+    let ErrorCause::HandlerError { source } = err.error_cause() else {
+      panic!("unexpected error cause: {err}");
+    };
+    let error: &DIDWebError = source.downcast_ref::<DIDWebError>().unwrap();
+    assert_eq!(<&str>::from(error), expected);
+  }
+}
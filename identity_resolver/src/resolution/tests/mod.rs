@@ -2,5 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::resolver::*;
+mod cache;
+mod dereference;
+mod did_jwk;
+#[cfg(feature = "did-key")]
+mod did_key;
+mod did_peer;
+#[cfg(feature = "did-web")]
+mod did_web;
 mod resolution;
 mod send_sync;
@@ -6,6 +6,10 @@ use std::error::Error;
 use std::fmt::Debug;
 use std::str::FromStr;
 
+use identity_core::common::Value;
+use identity_core::convert::FromJson;
+use identity_core::convert::ToJson;
+use identity_core::json;
 use identity_did::BaseDIDUrl;
 use identity_did::CoreDID;
 use identity_did::Error as DIDError;
@@ -13,8 +17,11 @@ use identity_did::DID;
 use identity_document::document::CoreDocument;
 use identity_document::document::DocumentBuilder;
 
+use crate::DIDResolutionResult;
+use crate::DocumentMetadata;
 use crate::Error as ResolverError;
 use crate::ErrorCause;
+use crate::ResolutionErrorCode;
 use crate::Resolver;
 
 /// A very simple handler
@@ -279,3 +286,86 @@ async fn resolve_multiple() {
   assert_eq!(resolved_dids.len(), 1);
   assert_eq!(resolved_dids.get(&did_1).unwrap().id(), &did_1);
 }
+
+// ===========================================================================
+// Resolution with metadata.
+// ===========================================================================
+
+#[tokio::test]
+async fn resolve_with_metadata() {
+  #[derive(Debug, thiserror::Error)]
+  #[error("resolution failed")]
+  struct ResolutionError;
+  async fn failing_handler(_did: CoreDID) -> std::result::Result<CoreDocument, ResolutionError> {
+    Err(ResolutionError)
+  }
+  async fn foo_handler(did: FooDID) -> std::result::Result<CoreDocument, std::io::Error> {
+    mock_handler(did.as_ref().clone()).await
+  }
+
+  let mut resolver: Resolver<FooDocument> = Resolver::new();
+  resolver.attach_handler("foo".to_owned(), foo_handler);
+  resolver.attach_handler("bar".to_owned(), failing_handler);
+  resolver.attach_handler_with_metadata("baz".to_owned(), mock_handler, |_: &CoreDocument| DocumentMetadata {
+    deactivated: Some(true),
+    ..Default::default()
+  });
+
+  let did: CoreDID = CoreDID::parse("did:foo:12345").unwrap();
+  let result: DIDResolutionResult<FooDocument> = resolver.resolve_with_metadata(&did).await;
+  assert_eq!(result.did_document.unwrap().as_ref().id(), &did);
+  assert_eq!(result.did_document_metadata, DocumentMetadata::default());
+  assert_eq!(result.did_resolution_metadata.error, None);
+
+  let did: CoreDID = CoreDID::parse("did:baz:1234").unwrap();
+  let result: DIDResolutionResult<FooDocument> = resolver.resolve_with_metadata(&did).await;
+  assert!(result.did_document.is_some());
+  assert!(result.is_deactivated());
+
+  for (did, code) in [
+    ("did:foo:1234", ResolutionErrorCode::InvalidDid),
+    ("did:bar:1234", ResolutionErrorCode::InternalError),
+    ("did:qux:1234", ResolutionErrorCode::MethodNotSupported),
+  ] {
+    let did: CoreDID = CoreDID::parse(did).unwrap();
+    let result: DIDResolutionResult<FooDocument> = resolver.resolve_with_metadata(&did).await;
+    assert!(result.did_document.is_none());
+    assert!(!result.is_deactivated());
+    assert_eq!(result.error(), Some(code));
+  }
+}
+
+#[test]
+fn resolution_result_serialization() {
+  let did: CoreDID = CoreDID::parse("did:foo:1234").unwrap();
+  let result: DIDResolutionResult<CoreDocument> = DIDResolutionResult::new(
+    core_document(did),
+    DocumentMetadata {
+      deactivated: Some(true),
+      ..Default::default()
+    },
+  );
+  assert_eq!(
+    result.to_json_value().unwrap(),
+    json!({
+      "didDocument": { "id": "did:foo:1234" },
+      "didDocumentMetadata": { "deactivated": true },
+      "didResolutionMetadata": {},
+    })
+  );
+
+  let result: DIDResolutionResult<CoreDocument> = DIDResolutionResult::from_error(ResolutionErrorCode::NotFound);
+  let json: Value = result.to_json_value().unwrap();
+  assert_eq!(
+    json,
+    json!({
+      "didDocument": null,
+      "didDocumentMetadata": {},
+      "didResolutionMetadata": { "error": "notFound" },
+    })
+  );
+  assert_eq!(
+    DIDResolutionResult::<CoreDocument>::from_json_value(json).unwrap(),
+    result
+  );
+}
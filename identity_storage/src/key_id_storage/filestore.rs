@@ -0,0 +1,134 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::fmt::Debug;
+use core::fmt::Formatter;
+use std::collections::HashMap;
+use std::path::Path;
+
+use async_trait::async_trait;
+use identity_verification::jose::jwu;
+use tokio::sync::Mutex;
+use tokio::sync::MutexGuard;
+use tokio::sync::RwLockWriteGuard;
+
+use crate::key_id_storage::key_id_storage::KeyIdStorage;
+use crate::key_id_storage::key_id_storage::KeyIdStorageResult;
+use crate::key_id_storage::key_id_storage_error::KeyIdStorageError;
+use crate::key_id_storage::key_id_storage_error::KeyIdStorageErrorKind;
+use crate::key_id_storage::memstore::KeyIdMemstore;
+use crate::key_id_storage::memstore::KeyIdStore;
+use crate::key_id_storage::method_digest::MethodDigest;
+use crate::key_storage::encrypted_file::Argon2Params;
+use crate::key_storage::encrypted_file::EncryptedFile;
+use crate::key_storage::shared::Shared;
+use crate::key_storage::KeyId;
+
+/// The on-disk representation of the key id store, keyed by the base64url-encoded packed [`MethodDigest`].
+type SerializedKeyIdStore = HashMap<String, KeyId>;
+
+/// A [`KeyIdStorage`] implementation that keeps the mapping from [`MethodDigest`] to [`KeyId`] in a file
+/// encrypted under a password.
+///
+/// The file is encrypted in the same way as the file of a [`JwkFileStore`](crate::key_storage::JwkFileStore)
+/// and every modification rewrites the entire file atomically.
+pub struct KeyIdFileStore {
+  memstore: KeyIdMemstore,
+  file: EncryptedFile,
+  // Serializes modifications such that the file always reflects the latest state.
+  write_lock: Mutex<()>,
+}
+
+impl KeyIdFileStore {
+  /// Opens the storage file at `path` and decrypts it with `password`, or creates a new, empty storage file
+  /// if it does not exist.
+  ///
+  /// New files are encrypted with a key derived using the default [`Argon2Params`].
+  ///
+  /// # Errors
+  ///
+  /// Fails with [`Unauthenticated`](KeyIdStorageErrorKind::Unauthenticated) if the file cannot be decrypted
+  /// with `password`.
+  pub fn open(path: impl AsRef<Path>, password: &str) -> KeyIdStorageResult<Self> {
+    Self::open_with_params(path, password, Argon2Params::default())
+  }
+
+  /// Opens the storage file at `path` like [`KeyIdFileStore::open`], deriving the key of newly created files with
+  /// the given [`Argon2Params`].
+  pub fn open_with_params(path: impl AsRef<Path>, password: &str, params: Argon2Params) -> KeyIdStorageResult<Self> {
+    let (file, serialized): (EncryptedFile, SerializedKeyIdStore) =
+      EncryptedFile::open(path.as_ref(), password, params)?;
+
+    let key_id_store: KeyIdStore = serialized
+      .into_iter()
+      .map(|(method_digest, key_id)| {
+        let method_digest: Vec<u8> = jwu::decode_b64(&method_digest).map_err(|err| {
+          KeyIdStorageError::new(KeyIdStorageErrorKind::SerializationError)
+            .with_custom_message("unable to decode method digest")
+            .with_source(err)
+        })?;
+        Ok((MethodDigest::unpack(method_digest)?, key_id))
+      })
+      .collect::<KeyIdStorageResult<_>>()?;
+
+    Ok(Self {
+      memstore: KeyIdMemstore {
+        key_id_store: Shared::new(key_id_store),
+      },
+      file,
+      write_lock: Mutex::new(()),
+    })
+  }
+
+  /// Returns the number of items contained in the [`KeyIdFileStore`].
+  pub async fn count(&self) -> usize {
+    self.memstore.count().await
+  }
+
+  /// Returns a snapshot of the key ids to restore in case persisting a modification fails.
+  async fn begin_write(&self) -> (MutexGuard<'_, ()>, KeyIdStore) {
+    let guard: MutexGuard<'_, ()> = self.write_lock.lock().await;
+    let snapshot: KeyIdStore = self.memstore.key_id_store.read().await.clone();
+    (guard, snapshot)
+  }
+
+  /// Writes the current key ids to the file, restoring `snapshot` in memory if that fails.
+  async fn persist(&self, snapshot: KeyIdStore) -> KeyIdStorageResult<()> {
+    let mut key_id_store: RwLockWriteGuard<'_, KeyIdStore> = self.memstore.key_id_store.write().await;
+    let serialized: SerializedKeyIdStore = key_id_store
+      .iter()
+      .map(|(method_digest, key_id)| (jwu::encode_b64(method_digest.pack()), key_id.clone()))
+      .collect();
+
+    self.file.write(&serialized).map_err(|err| {
+      *key_id_store = snapshot;
+      KeyIdStorageError::from(err)
+    })
+  }
+}
+
+#[cfg_attr(not(feature = "send-sync-storage"), async_trait(?Send))]
+#[cfg_attr(feature = "send-sync-storage", async_trait)]
+impl KeyIdStorage for KeyIdFileStore {
+  async fn insert_key_id(&self, key: MethodDigest, value: KeyId) -> KeyIdStorageResult<()> {
+    let (_guard, snapshot) = self.begin_write().await;
+    self.memstore.insert_key_id(key, value).await?;
+    self.persist(snapshot).await
+  }
+
+  async fn get_key_id(&self, key: &MethodDigest) -> KeyIdStorageResult<KeyId> {
+    self.memstore.get_key_id(key).await
+  }
+
+  async fn delete_key_id(&self, key: &MethodDigest) -> KeyIdStorageResult<()> {
+    let (_guard, snapshot) = self.begin_write().await;
+    self.memstore.delete_key_id(key).await?;
+    self.persist(snapshot).await
+  }
+}
+
+impl Debug for KeyIdFileStore {
+  fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+    f.debug_struct("KeyIdFileStore").finish_non_exhaustive()
+  }
+}
@@ -14,12 +14,12 @@ use tokio::sync::RwLockWriteGuard;
 use super::key_id_storage::KeyIdStorageResult;
 use super::method_digest::MethodDigest;
 
-type KeyIdStore = HashMap<MethodDigest, KeyId>;
+pub(crate) type KeyIdStore = HashMap<MethodDigest, KeyId>;
 
 /// An insecure, in-memory [`KeyIdStorage`] implementation that serves as an example and may be used in tests.
 #[derive(Debug)]
 pub struct KeyIdMemstore {
-  key_id_store: Shared<KeyIdStore>,
+  pub(crate) key_id_store: Shared<KeyIdStore>,
 }
 
 impl KeyIdMemstore {
@@ -13,14 +13,22 @@ mod key_id_storage;
 mod key_id_storage_error;
 mod method_digest;
 
+#[cfg(feature = "filestore")]
+mod filestore;
 #[cfg(feature = "memstore")]
 mod memstore;
+#[cfg(feature = "sqlite")]
+mod sqlite;
 
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "filestore")]
+pub use filestore::*;
 pub use key_id_storage::*;
 pub use key_id_storage_error::*;
 #[cfg(feature = "memstore")]
 pub use memstore::*;
 pub use method_digest::*;
+#[cfg(feature = "sqlite")]
+pub use sqlite::*;
@@ -0,0 +1,218 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+
+use async_trait::async_trait;
+use rusqlite::Connection;
+use rusqlite::ErrorCode;
+use rusqlite::OptionalExtension;
+use rusqlite::Transaction;
+
+use crate::key_id_storage::key_id_storage::KeyIdStorage;
+use crate::key_id_storage::key_id_storage::KeyIdStorageResult;
+use crate::key_id_storage::key_id_storage_error::KeyIdStorageError;
+use crate::key_id_storage::key_id_storage_error::KeyIdStorageErrorKind;
+use crate::key_id_storage::method_digest::MethodDigest;
+use crate::key_storage::KeyId;
+
+/// The schema migrations, where the migration at index `i` upgrades the schema from version `i` to `i + 1`.
+///
+/// Migrations must never be modified once released, changes to the schema require appending a new migration.
+const MIGRATIONS: &[&str] = &["CREATE TABLE identity_key_ids (
+    tenant TEXT NOT NULL,
+    method_digest BLOB NOT NULL,
+    key_id TEXT NOT NULL,
+    PRIMARY KEY (tenant, method_digest)
+  ) WITHOUT ROWID;"];
+
+/// A [`KeyIdStorage`] implementation backed by a SQLite database.
+///
+/// Every [`KeyIdSqliteStore`] is bound to a tenant, which namespaces the stored key ids, such that a single database
+/// can be shared by many tenants without their mappings interfering with each other. Handles for further tenants
+/// sharing the same connection can be obtained with [`KeyIdSqliteStore::with_tenant`].
+///
+/// The schema is created and upgraded when the store is opened. Applied migrations are recorded in the
+/// `identity_key_id_migrations` table.
+#[derive(Debug, Clone)]
+pub struct KeyIdSqliteStore {
+  connection: Arc<Mutex<Connection>>,
+  tenant: String,
+}
+
+impl KeyIdSqliteStore {
+  /// Opens the SQLite database at `path`, creating it if it does not exist, and applies pending migrations.
+  pub fn open(path: impl AsRef<Path>, tenant: impl Into<String>) -> KeyIdStorageResult<Self> {
+    let connection: Connection = Connection::open(path).map_err(|err| {
+      KeyIdStorageError::new(KeyIdStorageErrorKind::Unavailable)
+        .with_custom_message("unable to open sqlite database")
+        .with_source(err)
+    })?;
+    Self::from_connection(connection, tenant)
+  }
+
+  /// Opens a new in-memory SQLite database and applies the migrations.
+  pub fn open_in_memory(tenant: impl Into<String>) -> KeyIdStorageResult<Self> {
+    let connection: Connection = Connection::open_in_memory().map_err(|err| {
+      KeyIdStorageError::new(KeyIdStorageErrorKind::Unavailable)
+        .with_custom_message("unable to open sqlite database")
+        .with_source(err)
+    })?;
+    Self::from_connection(connection, tenant)
+  }
+
+  /// Creates a [`KeyIdSqliteStore`] from an existing `connection` and applies pending migrations.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the schema of the database is newer than the one supported by this version of the library.
+  pub fn from_connection(mut connection: Connection, tenant: impl Into<String>) -> KeyIdStorageResult<Self> {
+    migrate(&mut connection)?;
+    Ok(Self {
+      connection: Arc::new(Mutex::new(connection)),
+      tenant: tenant.into(),
+    })
+  }
+
+  /// Returns a [`KeyIdSqliteStore`] for `tenant` that shares the database connection with `self`.
+  pub fn with_tenant(&self, tenant: impl Into<String>) -> Self {
+    Self {
+      connection: Arc::clone(&self.connection),
+      tenant: tenant.into(),
+    }
+  }
+
+  /// Returns the tenant whose key ids are accessed through this store.
+  pub fn tenant(&self) -> &str {
+    &self.tenant
+  }
+
+  /// Returns the number of key ids stored for the tenant.
+  pub async fn count(&self) -> KeyIdStorageResult<usize> {
+    let connection: MutexGuard<'_, Connection> = self.connection()?;
+    connection
+      .query_row(
+        "SELECT COUNT(*) FROM identity_key_ids WHERE tenant = ?1",
+        [&self.tenant],
+        |row| row.get::<_, i64>(0),
+      )
+      .map(|count| count as usize)
+      .map_err(sqlite_error)
+  }
+
+  fn connection(&self) -> KeyIdStorageResult<MutexGuard<'_, Connection>> {
+    self.connection.lock().map_err(|_| {
+      KeyIdStorageError::new(KeyIdStorageErrorKind::Unspecified).with_custom_message("sqlite connection lock poisoned")
+    })
+  }
+}
+
+#[cfg_attr(not(feature = "send-sync-storage"), async_trait(?Send))]
+#[cfg_attr(feature = "send-sync-storage", async_trait)]
+impl KeyIdStorage for KeyIdSqliteStore {
+  async fn insert_key_id(&self, key: MethodDigest, value: KeyId) -> KeyIdStorageResult<()> {
+    let connection: MutexGuard<'_, Connection> = self.connection()?;
+    connection
+      .execute(
+        "INSERT INTO identity_key_ids (tenant, method_digest, key_id) VALUES (?1, ?2, ?3)",
+        (&self.tenant, key.pack(), value.as_str()),
+      )
+      .map(|_| ())
+      .map_err(|err| match err.sqlite_error_code() {
+        Some(ErrorCode::ConstraintViolation) => {
+          KeyIdStorageError::new(KeyIdStorageErrorKind::KeyIdAlreadyExists).with_source(err)
+        }
+        _ => sqlite_error(err),
+      })
+  }
+
+  async fn get_key_id(&self, key: &MethodDigest) -> KeyIdStorageResult<KeyId> {
+    let connection: MutexGuard<'_, Connection> = self.connection()?;
+    connection
+      .query_row(
+        "SELECT key_id FROM identity_key_ids WHERE tenant = ?1 AND method_digest = ?2",
+        (&self.tenant, key.pack()),
+        |row| row.get::<_, String>(0),
+      )
+      .optional()
+      .map_err(sqlite_error)?
+      .map(KeyId::new)
+      .ok_or_else(|| KeyIdStorageError::new(KeyIdStorageErrorKind::KeyIdNotFound))
+  }
+
+  async fn delete_key_id(&self, key: &MethodDigest) -> KeyIdStorageResult<()> {
+    let connection: MutexGuard<'_, Connection> = self.connection()?;
+    let deleted: usize = connection
+      .execute(
+        "DELETE FROM identity_key_ids WHERE tenant = ?1 AND method_digest = ?2",
+        (&self.tenant, key.pack()),
+      )
+      .map_err(sqlite_error)?;
+
+    if deleted == 0 {
+      return Err(KeyIdStorageError::new(KeyIdStorageErrorKind::KeyIdNotFound));
+    }
+    Ok(())
+  }
+}
+
+/// Applies all migrations that have not been applied to the database yet.
+fn migrate(connection: &mut Connection) -> KeyIdStorageResult<()> {
+  let transaction: Transaction<'_> = connection.transaction().map_err(sqlite_error)?;
+  transaction
+    .execute(
+      "CREATE TABLE IF NOT EXISTS identity_key_id_migrations (
+        version INTEGER PRIMARY KEY NOT NULL,
+        applied_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+      )",
+      (),
+    )
+    .map_err(sqlite_error)?;
+
+  let version: usize = transaction
+    .query_row(
+      "SELECT COALESCE(MAX(version), 0) FROM identity_key_id_migrations",
+      (),
+      |row| row.get::<_, i64>(0),
+    )
+    .map_err(sqlite_error)? as usize;
+
+  if version > MIGRATIONS.len() {
+    return Err(
+      KeyIdStorageError::new(KeyIdStorageErrorKind::Unspecified).with_custom_message(format!(
+        "database schema version {version} is newer than the supported version {}",
+        MIGRATIONS.len()
+      )),
+    );
+  }
+
+  for (index, migration) in MIGRATIONS.iter().enumerate().skip(version) {
+    transaction.execute_batch(migration).map_err(sqlite_error)?;
+    transaction
+      .execute(
+        "INSERT INTO identity_key_id_migrations (version) VALUES (?1)",
+        [index as i64 + 1],
+      )
+      .map_err(sqlite_error)?;
+  }
+
+  transaction.commit().map_err(sqlite_error)
+}
+
+fn sqlite_error(err: rusqlite::Error) -> KeyIdStorageError {
+  let kind: KeyIdStorageErrorKind = match err.sqlite_error_code() {
+    Some(ErrorCode::DatabaseBusy | ErrorCode::DatabaseLocked | ErrorCode::SystemIoFailure) => {
+      KeyIdStorageErrorKind::RetryableIOFailure
+    }
+    Some(ErrorCode::CannotOpen | ErrorCode::ReadOnly | ErrorCode::PermissionDenied) => {
+      KeyIdStorageErrorKind::Unavailable
+    }
+    _ => KeyIdStorageErrorKind::Unspecified,
+  };
+  KeyIdStorageError::new(kind)
+    .with_custom_message("sqlite operation failed")
+    .with_source(err)
+}
@@ -0,0 +1,104 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::PathBuf;
+
+use identity_core::convert::FromJson;
+use identity_document::document::CoreDocument;
+use identity_document::verifiable::JwsVerificationOptions;
+use identity_eddsa_verifier::EdDSAJwsVerifier;
+use identity_verification::jws::JwsAlgorithm;
+use identity_verification::MethodScope;
+
+use crate::key_id_storage::filestore::KeyIdFileStore;
+use crate::key_id_storage::key_id_storage::KeyIdStorage;
+use crate::key_id_storage::method_digest::MethodDigest;
+use crate::key_id_storage::tests::utils::test_storage_operations;
+use crate::key_id_storage::KeyIdStorageErrorKind;
+use crate::key_storage::tests::utils::temp_file_path;
+use crate::key_storage::tests::utils::TEST_ARGON2_PARAMS;
+use crate::key_storage::JwkFileStore;
+use crate::key_storage::JwkMemStore;
+use crate::key_storage::KeyId;
+use crate::storage::tests::test_utils::create_verification_method;
+use crate::JwkDocumentExt;
+use crate::JwsSignatureOptions;
+use crate::Storage;
+
+const PASSWORD: &str = "secure_password";
+
+fn open_store(path: &PathBuf) -> KeyIdFileStore {
+  KeyIdFileStore::open_with_params(path, PASSWORD, TEST_ARGON2_PARAMS).unwrap()
+}
+
+#[tokio::test]
+async fn test_filestore() {
+  test_storage_operations(open_store(&temp_file_path())).await;
+}
+
+#[tokio::test]
+async fn test_filestore_persistence() {
+  let path: PathBuf = temp_file_path();
+  let method_digest: MethodDigest = MethodDigest::new(&create_verification_method()).unwrap();
+  let deleted: MethodDigest = MethodDigest::new(&create_verification_method()).unwrap();
+
+  let store: KeyIdFileStore = open_store(&path);
+  store
+    .insert_key_id(method_digest.clone(), KeyId::new("keyid"))
+    .await
+    .unwrap();
+  store
+    .insert_key_id(deleted.clone(), KeyId::new("deleted"))
+    .await
+    .unwrap();
+  store.delete_key_id(&deleted).await.unwrap();
+  drop(store);
+
+  let store: KeyIdFileStore = open_store(&path);
+  assert_eq!(store.count().await, 1);
+  assert_eq!(store.get_key_id(&method_digest).await.unwrap(), KeyId::new("keyid"));
+  let err = store.get_key_id(&deleted).await.unwrap_err();
+  assert!(matches!(err.kind(), KeyIdStorageErrorKind::KeyIdNotFound));
+
+  let err = KeyIdFileStore::open(&path, "incorrect_password").unwrap_err();
+  assert!(matches!(err.kind(), KeyIdStorageErrorKind::Unauthenticated));
+}
+
+#[tokio::test]
+async fn test_filestore_with_storage() {
+  let jwk_path: PathBuf = temp_file_path();
+  let key_id_path: PathBuf = temp_file_path();
+  let open_storage = || {
+    Storage::new(
+      JwkFileStore::open_with_params(&jwk_path, PASSWORD, TEST_ARGON2_PARAMS).unwrap(),
+      open_store(&key_id_path),
+    )
+  };
+
+  let mut document: CoreDocument =
+    CoreDocument::from_json(r#"{"id": "did:bar:Hyx62wPQGyvXCoihZq1BrbUjBRh2LuNxWiiqMkfAuSZr"}"#).unwrap();
+  let fragment: String = document
+    .generate_method(
+      &open_storage(),
+      JwkMemStore::ED25519_KEY_TYPE,
+      JwsAlgorithm::EdDSA,
+      None,
+      MethodScope::VerificationMethod,
+    )
+    .await
+    .unwrap();
+
+  // The key and its key id are still available after reopening the storage.
+  let jws = document
+    .create_jws(&open_storage(), &fragment, b"test", &JwsSignatureOptions::default())
+    .await
+    .unwrap();
+  document
+    .verify_jws(
+      jws.as_str(),
+      None,
+      &EdDSAJwsVerifier::default(),
+      &JwsVerificationOptions::default(),
+    )
+    .unwrap();
+}
@@ -1,5 +1,9 @@
 // Copyright 2020-2023 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+#[cfg(feature = "filestore")]
+mod filestore;
 mod memstore;
+#[cfg(feature = "sqlite")]
+mod sqlite;
 mod utils;
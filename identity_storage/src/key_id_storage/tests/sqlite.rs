@@ -0,0 +1,79 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use rusqlite::Connection;
+
+use crate::key_id_storage::key_id_storage::KeyIdStorage;
+use crate::key_id_storage::method_digest::MethodDigest;
+use crate::key_id_storage::sqlite::KeyIdSqliteStore;
+use crate::key_id_storage::tests::utils::test_storage_operations;
+use crate::key_id_storage::KeyIdStorageErrorKind;
+use crate::key_storage::KeyId;
+use crate::storage::tests::test_utils::create_verification_method;
+
+#[tokio::test]
+async fn test_sqlite() {
+  let store: KeyIdSqliteStore = KeyIdSqliteStore::open_in_memory("tenant").unwrap();
+  test_storage_operations(store).await;
+}
+
+#[tokio::test]
+async fn test_sqlite_tenant_isolation() {
+  let tenant_a: KeyIdSqliteStore = KeyIdSqliteStore::open_in_memory("tenant-a").unwrap();
+  let tenant_b: KeyIdSqliteStore = tenant_a.with_tenant("tenant-b");
+  let method_digest: MethodDigest = MethodDigest::new(&create_verification_method()).unwrap();
+
+  tenant_a
+    .insert_key_id(method_digest.clone(), KeyId::new("key-a"))
+    .await
+    .unwrap();
+
+  // The same method digest can be stored independently by each tenant.
+  let err = tenant_b.get_key_id(&method_digest).await.unwrap_err();
+  assert!(matches!(err.kind(), KeyIdStorageErrorKind::KeyIdNotFound));
+  tenant_b
+    .insert_key_id(method_digest.clone(), KeyId::new("key-b"))
+    .await
+    .unwrap();
+
+  assert_eq!(tenant_a.get_key_id(&method_digest).await.unwrap(), KeyId::new("key-a"));
+  assert_eq!(tenant_b.get_key_id(&method_digest).await.unwrap(), KeyId::new("key-b"));
+
+  tenant_a.delete_key_id(&method_digest).await.unwrap();
+  assert_eq!(tenant_a.count().await.unwrap(), 0);
+  assert_eq!(tenant_b.count().await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn test_sqlite_persistence_and_migrations() {
+  let mut path = std::env::temp_dir();
+  path.push(format!("test_sqlite_key_id_storage_{}.sqlite", rand::random::<u64>()));
+  let method_digest: MethodDigest = MethodDigest::new(&create_verification_method()).unwrap();
+
+  let store: KeyIdSqliteStore = KeyIdSqliteStore::open(&path, "tenant").unwrap();
+  store
+    .insert_key_id(method_digest.clone(), KeyId::new("keyid"))
+    .await
+    .unwrap();
+  drop(store);
+
+  // Reopening the database does not apply migrations again.
+  let store: KeyIdSqliteStore = KeyIdSqliteStore::open(&path, "tenant").unwrap();
+  assert_eq!(store.get_key_id(&method_digest).await.unwrap(), KeyId::new("keyid"));
+  drop(store);
+
+  let connection: Connection = Connection::open(&path).unwrap();
+  let versions: i64 = connection
+    .query_row("SELECT COUNT(*) FROM identity_key_id_migrations", (), |row| row.get(0))
+    .unwrap();
+  assert_eq!(versions, 1);
+
+  // INVALID: Databases with a newer schema are rejected.
+  connection
+    .execute("INSERT INTO identity_key_id_migrations (version) VALUES (1000)", ())
+    .unwrap();
+  let err = KeyIdSqliteStore::from_connection(connection, "tenant").unwrap_err();
+  assert!(matches!(err.kind(), KeyIdStorageErrorKind::Unspecified));
+
+  std::fs::remove_file(&path).unwrap();
+}
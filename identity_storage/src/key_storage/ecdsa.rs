@@ -0,0 +1,139 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_verification::jose::jwk::EcCurve;
+use identity_verification::jose::jwk::Jwk;
+use identity_verification::jose::jwk::JwkParamsEc;
+use identity_verification::jose::jwu;
+
+use crate::key_storage::KeyStorageError;
+use crate::key_storage::KeyStorageErrorKind;
+use crate::key_storage::KeyStorageResult;
+
+/// Generates a new private key on the given `curve` and encodes it as a [`Jwk`].
+pub(crate) fn generate_jwk(curve: EcCurve) -> KeyStorageResult<Jwk> {
+  let mut rng = rand::rngs::OsRng;
+
+  match curve {
+    EcCurve::P256 => {
+      let secret_key = p256::SecretKey::random(&mut rng);
+      let encoded_point = p256::elliptic_curve::sec1::ToEncodedPoint::to_encoded_point(&secret_key.public_key(), false);
+      Ok(encode_jwk(curve, &secret_key.to_bytes(), encoded_point.as_bytes()))
+    }
+    EcCurve::P384 => {
+      let secret_key = p384::SecretKey::random(&mut rng);
+      let encoded_point = p384::elliptic_curve::sec1::ToEncodedPoint::to_encoded_point(&secret_key.public_key(), false);
+      Ok(encode_jwk(curve, &secret_key.to_bytes(), encoded_point.as_bytes()))
+    }
+    EcCurve::Secp256K1 => {
+      let secret_key = k256::SecretKey::random(&mut rng);
+      let encoded_point = k256::elliptic_curve::sec1::ToEncodedPoint::to_encoded_point(&secret_key.public_key(), false);
+      Ok(encode_jwk(curve, &secret_key.to_bytes(), encoded_point.as_bytes()))
+    }
+    other => Err(
+      KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
+        .with_custom_message(format!("{other} not supported")),
+    ),
+  }
+}
+
+/// Encodes the private key with scalar `secret_key` on the given `curve` as a [`Jwk`].
+pub(crate) fn from_secret_key(curve: EcCurve, secret_key: &[u8]) -> KeyStorageResult<Jwk> {
+  let invalid_key = |err| {
+    KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+      .with_custom_message(format!("invalid {curve} private key"))
+      .with_source(err)
+  };
+
+  match curve {
+    EcCurve::P256 => {
+      let secret_key = p256::SecretKey::from_slice(secret_key).map_err(invalid_key)?;
+      let encoded_point = p256::elliptic_curve::sec1::ToEncodedPoint::to_encoded_point(&secret_key.public_key(), false);
+      Ok(encode_jwk(curve, &secret_key.to_bytes(), encoded_point.as_bytes()))
+    }
+    EcCurve::P384 => {
+      let secret_key = p384::SecretKey::from_slice(secret_key).map_err(invalid_key)?;
+      let encoded_point = p384::elliptic_curve::sec1::ToEncodedPoint::to_encoded_point(&secret_key.public_key(), false);
+      Ok(encode_jwk(curve, &secret_key.to_bytes(), encoded_point.as_bytes()))
+    }
+    EcCurve::Secp256K1 => {
+      let secret_key = k256::SecretKey::from_slice(secret_key).map_err(invalid_key)?;
+      let encoded_point = k256::elliptic_curve::sec1::ToEncodedPoint::to_encoded_point(&secret_key.public_key(), false);
+      Ok(encode_jwk(curve, &secret_key.to_bytes(), encoded_point.as_bytes()))
+    }
+    other => Err(
+      KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
+        .with_custom_message(format!("{other} not supported")),
+    ),
+  }
+}
+
+/// Signs `data` with the private key contained in `jwk`.
+///
+/// The signature is the concatenation of the `r` and `s` values as required by
+/// [RFC 7518 section 3.4](https://www.rfc-editor.org/rfc/rfc7518#section-3.4). The hash function is determined by
+/// the curve of the key, i.e. SHA-256 for `P-256` and `secp256k1` and SHA-384 for `P-384`.
+pub(crate) fn sign(jwk: &Jwk, data: &[u8]) -> KeyStorageResult<Vec<u8>> {
+  let params: &JwkParamsEc = jwk.try_ec_params().map_err(|err| {
+    KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
+      .with_custom_message("expected a Jwk with Ec params")
+      .with_source(err)
+  })?;
+  let curve: EcCurve = params
+    .try_ec_curve()
+    .map_err(|err| KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType).with_source(err))?;
+  let sk: Vec<u8> = params
+    .d
+    .as_deref()
+    .map(jwu::decode_b64)
+    .ok_or_else(|| {
+      KeyStorageError::new(KeyStorageErrorKind::Unspecified).with_custom_message("expected Jwk `d` param to be present")
+    })?
+    .map_err(|err| {
+      KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+        .with_custom_message("unable to decode `d` param")
+        .with_source(err)
+    })?;
+
+  let invalid_key = |err| {
+    KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+      .with_custom_message(format!("invalid {curve} private key"))
+      .with_source(err)
+  };
+
+  match curve {
+    EcCurve::P256 => {
+      let signing_key = p256::ecdsa::SigningKey::from_slice(&sk).map_err(invalid_key)?;
+      let signature: p256::ecdsa::Signature = p256::ecdsa::signature::Signer::sign(&signing_key, data);
+      Ok(signature.to_vec())
+    }
+    EcCurve::P384 => {
+      let signing_key = p384::ecdsa::SigningKey::from_slice(&sk).map_err(invalid_key)?;
+      let signature: p384::ecdsa::Signature = p384::ecdsa::signature::Signer::sign(&signing_key, data);
+      Ok(signature.to_vec())
+    }
+    EcCurve::Secp256K1 => {
+      let signing_key = k256::ecdsa::SigningKey::from_slice(&sk).map_err(invalid_key)?;
+      let signature: k256::ecdsa::Signature = k256::ecdsa::signature::Signer::sign(&signing_key, data);
+      Ok(signature.to_vec())
+    }
+    other => Err(
+      KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
+        .with_custom_message(format!("{other} not supported")),
+    ),
+  }
+}
+
+/// Encodes a private key on `curve` given its scalar and its uncompressed SEC1 encoded public point.
+fn encode_jwk(curve: EcCurve, secret_key: &[u8], encoded_point: &[u8]) -> Jwk {
+  // Strip the 0x04 tag of the uncompressed encoding and split the remainder into both coordinates.
+  let coordinates: &[u8] = encoded_point.get(1..).unwrap_or_default();
+  let (x, y) = coordinates.split_at(coordinates.len() / 2);
+
+  let mut params = JwkParamsEc::new();
+  params.crv = curve.name().to_owned();
+  params.x = jwu::encode_b64(x);
+  params.y = jwu::encode_b64(y);
+  params.d = Some(jwu::encode_b64(secret_key));
+  Jwk::from_params(params)
+}
@@ -0,0 +1,271 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crypto::ciphers::chacha::XChaCha20Poly1305;
+use crypto::ciphers::traits::Aead;
+use identity_verification::jose::jwu;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde::Serialize;
+use zeroize::Zeroizing;
+
+use crate::key_id_storage::KeyIdStorageError;
+use crate::key_id_storage::KeyIdStorageErrorKind;
+use crate::key_storage::KeyStorageError;
+use crate::key_storage::KeyStorageErrorKind;
+
+/// Version of the format of encrypted storage files.
+const FILE_FORMAT_VERSION: u8 = 1;
+
+/// Length in bytes of the random salt used to derive the file encryption key.
+const SALT_LENGTH: usize = 16;
+
+/// Length in bytes of the file encryption key.
+const KEY_LENGTH: usize = 32;
+
+/// Parameters of the Argon2id derivation of the file encryption key from a password.
+///
+/// The parameters are only used when a new storage file is created. Existing files are always opened
+/// with the parameters they were created with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Argon2Params {
+  memory_cost: u32,
+  time_cost: u32,
+  parallelism: u32,
+}
+
+impl Argon2Params {
+  /// Creates a new set of Argon2id parameters.
+  ///
+  /// - `memory_cost`: the amount of memory to use in KiB.
+  /// - `time_cost`: the number of passes over the memory.
+  /// - `parallelism`: the number of lanes.
+  pub const fn new(memory_cost: u32, time_cost: u32, parallelism: u32) -> Self {
+    Self {
+      memory_cost,
+      time_cost,
+      parallelism,
+    }
+  }
+
+  /// Returns the amount of memory to use in KiB.
+  pub fn memory_cost(&self) -> u32 {
+    self.memory_cost
+  }
+
+  /// Returns the number of passes over the memory.
+  pub fn time_cost(&self) -> u32 {
+    self.time_cost
+  }
+
+  /// Returns the number of lanes.
+  pub fn parallelism(&self) -> u32 {
+    self.parallelism
+  }
+}
+
+impl Default for Argon2Params {
+  /// The minimum parameters recommended by OWASP: 19 MiB of memory, 2 iterations and a parallelism of 1.
+  fn default() -> Self {
+    Self::new(19 * 1024, 2, 1)
+  }
+}
+
+/// Key derivation parameters stored in clear in the header of a storage file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Kdf {
+  salt: String,
+  #[serde(flatten)]
+  params: Argon2Params,
+}
+
+/// The on-disk representation of an encrypted storage file.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Envelope {
+  version: u8,
+  kdf: Kdf,
+  nonce: String,
+  ciphertext: String,
+}
+
+/// A file whose content is encrypted with XChaCha20-Poly1305 under a key derived from a password.
+///
+/// Every write replaces the entire file atomically, such that the file is never left in a partially written state.
+pub(crate) struct EncryptedFile {
+  path: PathBuf,
+  kdf: Kdf,
+  key: Zeroizing<[u8; KEY_LENGTH]>,
+}
+
+impl EncryptedFile {
+  /// Opens the file at `path` and decrypts its content, or creates a new file with `T::default()` as its content
+  /// if it does not exist yet.
+  pub(crate) fn open<T>(path: &Path, password: &str, params: Argon2Params) -> Result<(Self, T), EncryptedFileError>
+  where
+    T: Serialize + DeserializeOwned + Default,
+  {
+    if !path.try_exists().map_err(EncryptedFileError::Io)? {
+      let mut salt: [u8; SALT_LENGTH] = [0; SALT_LENGTH];
+      crypto::utils::rand::fill(&mut salt).map_err(|_| EncryptedFileError::Random)?;
+      let kdf: Kdf = Kdf {
+        salt: jwu::encode_b64(salt),
+        params,
+      };
+      let file: Self = Self::new(path.to_owned(), password, kdf)?;
+      let content: T = T::default();
+      file.write(&content)?;
+      return Ok((file, content));
+    }
+
+    let envelope: Envelope = serde_json::from_slice(&std::fs::read(path).map_err(EncryptedFileError::Io)?)
+      .map_err(EncryptedFileError::Serialization)?;
+    if envelope.version != FILE_FORMAT_VERSION {
+      return Err(EncryptedFileError::UnsupportedVersion(envelope.version));
+    }
+
+    let file: Self = Self::new(path.to_owned(), password, envelope.kdf)?;
+    let nonce: Vec<u8> = jwu::decode_b64(&envelope.nonce).map_err(|_| EncryptedFileError::Malformed)?;
+    let ciphertext: Vec<u8> = jwu::decode_b64(&envelope.ciphertext).map_err(|_| EncryptedFileError::Malformed)?;
+    let len: usize = ciphertext
+      .len()
+      .checked_sub(XChaCha20Poly1305::TAG_LENGTH)
+      .ok_or(EncryptedFileError::Malformed)?;
+    let (ciphertext, tag) = ciphertext.split_at(len);
+
+    let mut plaintext: Zeroizing<Vec<u8>> = Zeroizing::new(vec![0; len]);
+    XChaCha20Poly1305::try_decrypt(file.key.as_ref(), &nonce, &[], &mut plaintext, ciphertext, tag)
+      .map_err(|_| EncryptedFileError::Decryption)?;
+    let content: T = serde_json::from_slice(&plaintext).map_err(EncryptedFileError::Serialization)?;
+
+    Ok((file, content))
+  }
+
+  fn new(path: PathBuf, password: &str, kdf: Kdf) -> Result<Self, EncryptedFileError> {
+    let salt: Vec<u8> = jwu::decode_b64(&kdf.salt).map_err(|_| EncryptedFileError::Malformed)?;
+    let config = argon2::Config {
+      variant: argon2::Variant::Argon2id,
+      version: argon2::Version::Version13,
+      mem_cost: kdf.params.memory_cost,
+      time_cost: kdf.params.time_cost,
+      lanes: kdf.params.parallelism,
+      hash_length: KEY_LENGTH as u32,
+      ..Default::default()
+    };
+    let derived_key: Zeroizing<Vec<u8>> = argon2::hash_raw(password.as_bytes(), &salt, &config)
+      .map(Zeroizing::new)
+      .map_err(EncryptedFileError::KeyDerivation)?;
+
+    let mut key: Zeroizing<[u8; KEY_LENGTH]> = Zeroizing::new([0; KEY_LENGTH]);
+    key.copy_from_slice(&derived_key);
+
+    Ok(Self { path, kdf, key })
+  }
+
+  /// Encrypts `content` and atomically replaces the file with it.
+  pub(crate) fn write<T: Serialize>(&self, content: &T) -> Result<(), EncryptedFileError> {
+    let plaintext: Zeroizing<Vec<u8>> =
+      Zeroizing::new(serde_json::to_vec(content).map_err(EncryptedFileError::Serialization)?);
+    let nonce = XChaCha20Poly1305::random_nonce().map_err(|_| EncryptedFileError::Random)?;
+    let mut ciphertext: Vec<u8> = vec![0; plaintext.len() + XChaCha20Poly1305::TAG_LENGTH];
+    let (buffer, tag) = ciphertext.split_at_mut(plaintext.len());
+    XChaCha20Poly1305::try_encrypt(self.key.as_ref(), &nonce, &[], &plaintext, buffer, tag)
+      .map_err(|_| EncryptedFileError::Encryption)?;
+
+    let envelope: Envelope = Envelope {
+      version: FILE_FORMAT_VERSION,
+      kdf: self.kdf.clone(),
+      nonce: jwu::encode_b64(nonce),
+      ciphertext: jwu::encode_b64(ciphertext),
+    };
+    let bytes: Vec<u8> = serde_json::to_vec(&envelope).map_err(EncryptedFileError::Serialization)?;
+
+    self.write_atomically(&bytes).map_err(EncryptedFileError::Io)
+  }
+
+  /// Writes `bytes` to a temporary file next to the target and renames it over the target.
+  fn write_atomically(&self, bytes: &[u8]) -> std::io::Result<()> {
+    let mut tmp_path: PathBuf = self.path.clone();
+    let mut file_name = tmp_path.file_name().unwrap_or_default().to_owned();
+    file_name.push(".tmp");
+    tmp_path.set_file_name(file_name);
+
+    let mut options: OpenOptions = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+      use std::os::unix::fs::OpenOptionsExt;
+      options.mode(0o600);
+    }
+
+    let result: std::io::Result<()> = options.open(&tmp_path).and_then(|mut file: File| {
+      file.write_all(bytes)?;
+      file.sync_all()
+    });
+    if let Err(err) = result.and_then(|_| std::fs::rename(&tmp_path, &self.path)) {
+      let _ = std::fs::remove_file(&tmp_path);
+      return Err(err);
+    }
+
+    // Persist the rename itself, which is only possible on platforms that allow opening directories.
+    #[cfg(unix)]
+    if let Some(parent) = self.path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+      File::open(parent)?.sync_all()?;
+    }
+
+    Ok(())
+  }
+}
+
+/// Errors that can occur when reading or writing an [`EncryptedFile`].
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum EncryptedFileError {
+  #[error("unable to access the storage file")]
+  Io(#[source] std::io::Error),
+  #[error("unable to (de)serialize the storage file")]
+  Serialization(#[source] serde_json::Error),
+  #[error("the storage file is malformed")]
+  Malformed,
+  #[error("unsupported storage file version {0}")]
+  UnsupportedVersion(u8),
+  #[error("unable to derive the file encryption key")]
+  KeyDerivation(#[source] argon2::Error),
+  #[error("unable to decrypt the storage file, the password may be incorrect or the file may have been tampered with")]
+  Decryption,
+  #[error("unable to encrypt the storage file")]
+  Encryption,
+  #[error("unable to generate randomness")]
+  Random,
+}
+
+impl From<EncryptedFileError> for KeyStorageError {
+  fn from(err: EncryptedFileError) -> Self {
+    let kind: KeyStorageErrorKind = match &err {
+      EncryptedFileError::Io(_) => KeyStorageErrorKind::RetryableIOFailure,
+      EncryptedFileError::Serialization(_) | EncryptedFileError::Malformed => KeyStorageErrorKind::SerializationError,
+      EncryptedFileError::Decryption => KeyStorageErrorKind::Unauthenticated,
+      _ => KeyStorageErrorKind::Unspecified,
+    };
+    KeyStorageError::new(kind).with_source(err)
+  }
+}
+
+impl From<EncryptedFileError> for KeyIdStorageError {
+  fn from(err: EncryptedFileError) -> Self {
+    let kind: KeyIdStorageErrorKind = match &err {
+      EncryptedFileError::Io(_) => KeyIdStorageErrorKind::RetryableIOFailure,
+      EncryptedFileError::Serialization(_) | EncryptedFileError::Malformed => KeyIdStorageErrorKind::SerializationError,
+      EncryptedFileError::Decryption => KeyIdStorageErrorKind::Unauthenticated,
+      _ => KeyIdStorageErrorKind::Unspecified,
+    };
+    KeyIdStorageError::new(kind).with_source(err)
+  }
+}
@@ -0,0 +1,153 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::fmt::Debug;
+use core::fmt::Formatter;
+use std::path::Path;
+
+use async_trait::async_trait;
+use identity_verification::jose::jwk::Jwk;
+use identity_verification::jose::jws::JwsAlgorithm;
+use tokio::sync::Mutex;
+use tokio::sync::MutexGuard;
+use tokio::sync::RwLockWriteGuard;
+
+use super::encrypted_file::Argon2Params;
+use super::encrypted_file::EncryptedFile;
+use super::jwk_gen_output::JwkGenOutput;
+use super::memstore::shared::Shared;
+use super::memstore::JwkKeyStore;
+use super::JwkMemStore;
+use super::JwkStorage;
+use super::JwkStorageExport;
+use super::KeyId;
+use super::KeyStorageError;
+use super::KeyStorageResult;
+use super::KeyType;
+use super::KeyWrappingKey;
+use super::WrappedJwk;
+
+/// A [`JwkStorage`] implementation that keeps its keys in a file encrypted under a password.
+///
+/// The file encryption key is derived from the password with Argon2id and the content is encrypted with
+/// XChaCha20-Poly1305. Every modification rewrites the entire file atomically.
+///
+/// Supports the same key types as [`JwkMemStore`], which are exposed as associated constants of that type.
+pub struct JwkFileStore {
+  memstore: JwkMemStore,
+  file: EncryptedFile,
+  // Serializes modifications such that the file always reflects the latest state.
+  write_lock: Mutex<()>,
+}
+
+impl JwkFileStore {
+  /// Opens the storage file at `path` and decrypts it with `password`, or creates a new, empty storage file
+  /// if it does not exist.
+  ///
+  /// New files are encrypted with a key derived using the default [`Argon2Params`].
+  ///
+  /// # Errors
+  ///
+  /// Fails with [`Unauthenticated`](super::KeyStorageErrorKind::Unauthenticated) if the file cannot be decrypted
+  /// with `password`.
+  pub fn open(path: impl AsRef<Path>, password: &str) -> KeyStorageResult<Self> {
+    Self::open_with_params(path, password, Argon2Params::default())
+  }
+
+  /// Opens the storage file at `path` like [`JwkFileStore::open`], deriving the key of newly created files with
+  /// the given [`Argon2Params`].
+  pub fn open_with_params(path: impl AsRef<Path>, password: &str, params: Argon2Params) -> KeyStorageResult<Self> {
+    let (file, jwk_store): (EncryptedFile, JwkKeyStore) = EncryptedFile::open(path.as_ref(), password, params)?;
+
+    Ok(Self {
+      memstore: JwkMemStore {
+        jwk_store: Shared::new(jwk_store),
+      },
+      file,
+      write_lock: Mutex::new(()),
+    })
+  }
+
+  /// Returns the number of items contained in the [`JwkFileStore`].
+  pub async fn count(&self) -> usize {
+    self.memstore.count().await
+  }
+
+  /// Returns a snapshot of the keys to restore in case persisting a modification fails.
+  async fn begin_write(&self) -> (MutexGuard<'_, ()>, JwkKeyStore) {
+    let guard: MutexGuard<'_, ()> = self.write_lock.lock().await;
+    let snapshot: JwkKeyStore = self.memstore.jwk_store.read().await.clone();
+    (guard, snapshot)
+  }
+
+  /// Writes the current keys to the file, restoring `snapshot` in memory if that fails.
+  async fn persist(&self, snapshot: JwkKeyStore) -> KeyStorageResult<()> {
+    let mut jwk_store: RwLockWriteGuard<'_, JwkKeyStore> = self.memstore.jwk_store.write().await;
+    self.file.write(&*jwk_store).map_err(|err| {
+      *jwk_store = snapshot;
+      KeyStorageError::from(err)
+    })
+  }
+}
+
+// Refer to the `JwkStorage` interface docs for high-level documentation of the individual methods.
+#[cfg_attr(not(feature = "send-sync-storage"), async_trait(?Send))]
+#[cfg_attr(feature = "send-sync-storage", async_trait)]
+impl JwkStorage for JwkFileStore {
+  async fn generate(&self, key_type: KeyType, alg: JwsAlgorithm) -> KeyStorageResult<JwkGenOutput> {
+    let (_guard, snapshot) = self.begin_write().await;
+    let output: JwkGenOutput = self.memstore.generate(key_type, alg).await?;
+    self.persist(snapshot).await?;
+    Ok(output)
+  }
+
+  async fn insert(&self, jwk: Jwk) -> KeyStorageResult<KeyId> {
+    let (_guard, snapshot) = self.begin_write().await;
+    let key_id: KeyId = self.memstore.insert(jwk).await?;
+    self.persist(snapshot).await?;
+    Ok(key_id)
+  }
+
+  async fn sign(&self, key_id: &KeyId, data: &[u8], public_key: &Jwk) -> KeyStorageResult<Vec<u8>> {
+    self.memstore.sign(key_id, data, public_key).await
+  }
+
+  async fn delete(&self, key_id: &KeyId) -> KeyStorageResult<()> {
+    let (_guard, snapshot) = self.begin_write().await;
+    self.memstore.delete(key_id).await?;
+    self.persist(snapshot).await
+  }
+
+  async fn exists(&self, key_id: &KeyId) -> KeyStorageResult<bool> {
+    self.memstore.exists(key_id).await
+  }
+
+  async fn get_public_key(&self, key_id: &KeyId) -> KeyStorageResult<Jwk> {
+    self.memstore.get_public_key(key_id).await
+  }
+}
+
+#[cfg_attr(not(feature = "send-sync-storage"), async_trait(?Send))]
+#[cfg_attr(feature = "send-sync-storage", async_trait)]
+impl JwkStorageExport for JwkFileStore {
+  async fn list(&self) -> KeyStorageResult<Vec<KeyId>> {
+    self.memstore.list().await
+  }
+
+  async fn export(&self, key_id: &KeyId, wrapping_key: &KeyWrappingKey) -> KeyStorageResult<WrappedJwk> {
+    self.memstore.export(key_id, wrapping_key).await
+  }
+
+  async fn import(&self, wrapped_jwk: &WrappedJwk, wrapping_key: &KeyWrappingKey) -> KeyStorageResult<KeyId> {
+    let (_guard, snapshot) = self.begin_write().await;
+    let key_id: KeyId = self.memstore.import(wrapped_jwk, wrapping_key).await?;
+    self.persist(snapshot).await?;
+    Ok(key_id)
+  }
+}
+
+impl Debug for JwkFileStore {
+  fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+    f.debug_struct("JwkFileStore").finish_non_exhaustive()
+  }
+}
@@ -1,6 +1,7 @@
 // Copyright 2020-2023 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use identity_did::DIDJwk;
 use identity_verification::jose::jwk::Jwk;
 
 use super::KeyId;
@@ -20,4 +21,16 @@ impl JwkGenOutput {
   pub fn new(key_id: KeyId, jwk: Jwk) -> Self {
     Self { key_id, jwk }
   }
+
+  /// Returns the did:jwk encoding the public part of the generated JWK.
+  ///
+  /// See also [`Storage::insert_did_jwk`](crate::storage::Storage::insert_did_jwk), which additionally makes the key
+  /// usable for signing with the expanded DID document.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the generated JWK is a symmetric key.
+  pub fn did_jwk(&self) -> Result<DIDJwk, identity_did::Error> {
+    DIDJwk::new(&self.jwk)
+  }
 }
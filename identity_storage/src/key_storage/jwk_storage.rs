@@ -3,6 +3,7 @@
 
 use crate::key_storage::KeyId;
 use crate::key_storage::KeyStorageError;
+use crate::key_storage::KeyStorageErrorKind;
 use crate::key_storage::KeyType;
 use async_trait::async_trait;
 use identity_verification::jose::jwk::Jwk;
@@ -61,4 +62,18 @@ pub trait JwkStorage: storage_sub_trait::StorageSendSyncMaybe {
 
   /// Returns `true` if the key with the given `key_id` exists in storage, `false` otherwise.
   async fn exists(&self, key_id: &KeyId) -> KeyStorageResult<bool>;
+
+  /// Returns the public key corresponding to the key identified by `key_id`.
+  ///
+  /// If the corresponding key does not exist in storage, a [`KeyStorageError`] with kind
+  /// [`KeyNotFound`](crate::key_storage::KeyStorageErrorKind::KeyNotFound) must be returned.
+  ///
+  /// The default implementation returns an error, for storages that cannot retrieve public keys.
+  async fn get_public_key(&self, key_id: &KeyId) -> KeyStorageResult<Jwk> {
+    let _ = key_id;
+    Err(
+      KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+        .with_custom_message("the storage does not support retrieving public keys"),
+    )
+  }
 }
@@ -0,0 +1,257 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::fmt::Debug;
+use core::fmt::Formatter;
+use core::num::NonZeroU32;
+
+use async_trait::async_trait;
+#[cfg(feature = "memstore")]
+use crypto::ciphers::aes_kw::Aes256Kw;
+use identity_verification::jose::jwk::Jwk;
+use identity_verification::jose::jwu;
+use serde::Deserialize;
+use serde::Serialize;
+use zeroize::Zeroizing;
+
+use crate::key_storage::JwkStorage;
+use crate::key_storage::KeyId;
+use crate::key_storage::KeyStorageError;
+use crate::key_storage::KeyStorageErrorKind;
+use crate::key_storage::KeyStorageResult;
+
+/// Length in bytes of a key-encryption key.
+pub const KEY_ENCRYPTION_KEY_LENGTH: usize = 32;
+
+/// Number of PBKDF2 iterations used when deriving a key-encryption key from a password.
+const PBKDF2_ITERATIONS: u32 = 210_000;
+
+/// Length in bytes of the random salt used when deriving a key-encryption key from a password.
+const PBKDF2_SALT_LENGTH: usize = 16;
+
+/// Extension of [`JwkStorage`] for storages that support enumerating their keys and moving
+/// key material in and out of the storage.
+///
+/// Private keys never leave the storage in plaintext. They are exported as a [`WrappedJwk`], i.e. wrapped with
+/// AES-256 Key Wrap ([RFC 3394](https://www.rfc-editor.org/rfc/rfc3394)) under a [`KeyWrappingKey`], which allows to
+/// migrate keys between different [`JwkStorage`] implementations.
+#[cfg_attr(not(feature = "send-sync-storage"), async_trait(?Send))]
+#[cfg_attr(feature = "send-sync-storage", async_trait)]
+pub trait JwkStorageExport: JwkStorage {
+  /// Returns the identifiers of all keys contained in the storage.
+  async fn list(&self) -> KeyStorageResult<Vec<KeyId>>;
+
+  /// Exports the key identified by `key_id` with its private key material wrapped under `wrapping_key`.
+  ///
+  /// If the corresponding key does not exist in storage, a [`KeyStorageError`] with kind
+  /// [`KeyNotFound`](crate::key_storage::KeyStorageErrorKind::KeyNotFound) must be returned.
+  async fn export(&self, key_id: &KeyId, wrapping_key: &KeyWrappingKey) -> KeyStorageResult<WrappedJwk>;
+
+  /// Imports a key previously exported with [`JwkStorageExport::export`] by unwrapping it with `wrapping_key`.
+  ///
+  /// The key is stored under the [`KeyId`] it was exported with, such that mappings from verification methods
+  /// to key ids remain valid after a migration. Importing a key whose [`KeyId`] already exists in the storage
+  /// must fail.
+  async fn import(&self, wrapped_jwk: &WrappedJwk, wrapping_key: &KeyWrappingKey) -> KeyStorageResult<KeyId>;
+}
+
+/// The secret under which exported private keys are wrapped.
+pub enum KeyWrappingKey {
+  /// A password from which the key-encryption key is derived using PBKDF2-HMAC-SHA512.
+  Password(Zeroizing<String>),
+  /// A raw AES-256 key-encryption key.
+  Kek(Zeroizing<[u8; KEY_ENCRYPTION_KEY_LENGTH]>),
+}
+
+impl KeyWrappingKey {
+  /// Creates a [`KeyWrappingKey`] from a password.
+  pub fn password(password: impl Into<String>) -> Self {
+    Self::Password(Zeroizing::new(password.into()))
+  }
+
+  /// Creates a [`KeyWrappingKey`] from a raw AES-256 key-encryption key.
+  pub fn kek(kek: [u8; KEY_ENCRYPTION_KEY_LENGTH]) -> Self {
+    Self::Kek(Zeroizing::new(kek))
+  }
+
+  /// Derives the key-encryption key for a new export.
+  ///
+  /// Returns the key-encryption key along with the key derivation parameters that need to be stored alongside
+  /// the wrapped key, which are only present if the key-encryption key was derived from a password.
+  pub fn derive_for_export(
+    &self,
+  ) -> KeyStorageResult<(Zeroizing<[u8; KEY_ENCRYPTION_KEY_LENGTH]>, Option<Pbkdf2Params>)> {
+    match self {
+      Self::Password(_) => {
+        let mut salt: [u8; PBKDF2_SALT_LENGTH] = [0; PBKDF2_SALT_LENGTH];
+        crypto::utils::rand::fill(&mut salt)
+          .map_err(|err| KeyStorageError::new(KeyStorageErrorKind::RetryableIOFailure).with_source(err))?;
+        let kdf: Pbkdf2Params = Pbkdf2Params {
+          salt: jwu::encode_b64(salt),
+          iterations: PBKDF2_ITERATIONS,
+        };
+        let kek = self.derive_for_import(Some(&kdf))?;
+        Ok((kek, Some(kdf)))
+      }
+      Self::Kek(kek) => Ok((kek.clone(), None)),
+    }
+  }
+
+  /// Derives the key-encryption key with which a key exported using the key derivation parameters `kdf`
+  /// was wrapped.
+  pub fn derive_for_import(
+    &self,
+    kdf: Option<&Pbkdf2Params>,
+  ) -> KeyStorageResult<Zeroizing<[u8; KEY_ENCRYPTION_KEY_LENGTH]>> {
+    match (self, kdf) {
+      (Self::Password(password), Some(kdf)) => {
+        let salt: Vec<u8> = jwu::decode_b64(&kdf.salt).map_err(|err| {
+          KeyStorageError::new(KeyStorageErrorKind::SerializationError)
+            .with_custom_message("unable to decode pbkdf2 salt")
+            .with_source(err)
+        })?;
+        let iterations: NonZeroU32 = NonZeroU32::new(kdf.iterations).ok_or_else(|| {
+          KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+            .with_custom_message("pbkdf2 iteration count must not be zero")
+        })?;
+        let mut kek: Zeroizing<[u8; KEY_ENCRYPTION_KEY_LENGTH]> = Zeroizing::new([0; KEY_ENCRYPTION_KEY_LENGTH]);
+        crypto::keys::pbkdf::PBKDF2_HMAC_SHA512(password.as_bytes(), &salt, iterations, kek.as_mut());
+        Ok(kek)
+      }
+      (Self::Kek(kek), None) => Ok(kek.clone()),
+      (Self::Password(_), None) => Err(
+        KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+          .with_custom_message("key was not wrapped under a password-derived key"),
+      ),
+      (Self::Kek(_), Some(_)) => Err(
+        KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+          .with_custom_message("key was wrapped under a password-derived key"),
+      ),
+    }
+  }
+}
+
+impl Debug for KeyWrappingKey {
+  fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+    match self {
+      Self::Password(_) => f.write_str("KeyWrappingKey::Password(..)"),
+      Self::Kek(_) => f.write_str("KeyWrappingKey::Kek(..)"),
+    }
+  }
+}
+
+/// Parameters of the PBKDF2-HMAC-SHA512 derivation of a key-encryption key from a password.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Pbkdf2Params {
+  salt: String,
+  iterations: u32,
+}
+
+impl Pbkdf2Params {
+  /// Returns the base64url-encoded salt.
+  pub fn salt(&self) -> &str {
+    &self.salt
+  }
+
+  /// Returns the number of iterations.
+  pub fn iterations(&self) -> u32 {
+    self.iterations
+  }
+}
+
+/// A key exported from a [`JwkStorageExport`] implementation.
+///
+/// Contains the public key in clear and the private key material wrapped under a [`KeyWrappingKey`].
+/// The private key material is the raw secret key, e.g. the `d` parameter of the corresponding private JWK.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WrappedJwk {
+  key_id: KeyId,
+  public_key: Jwk,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  kdf: Option<Pbkdf2Params>,
+  wrapped_key: String,
+}
+
+impl WrappedJwk {
+  /// Creates a new [`WrappedJwk`].
+  ///
+  /// # Errors
+  ///
+  /// Fails if `public_key` contains private key components.
+  pub fn new(
+    key_id: KeyId,
+    public_key: Jwk,
+    kdf: Option<Pbkdf2Params>,
+    wrapped_key: impl AsRef<[u8]>,
+  ) -> KeyStorageResult<Self> {
+    if !public_key.is_public() {
+      return Err(
+        KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+          .with_custom_message("expected a Jwk without private key components"),
+      );
+    }
+
+    Ok(Self {
+      key_id,
+      public_key,
+      kdf,
+      wrapped_key: jwu::encode_b64(wrapped_key),
+    })
+  }
+
+  /// Returns the [`KeyId`] under which the key was stored.
+  pub fn key_id(&self) -> &KeyId {
+    &self.key_id
+  }
+
+  /// Returns the public key.
+  pub fn public_key(&self) -> &Jwk {
+    &self.public_key
+  }
+
+  /// Returns the parameters used to derive the key-encryption key, if it was derived from a password.
+  pub fn kdf(&self) -> Option<&Pbkdf2Params> {
+    self.kdf.as_ref()
+  }
+
+  /// Returns the wrapped private key material.
+  pub fn wrapped_key(&self) -> KeyStorageResult<Vec<u8>> {
+    jwu::decode_b64(&self.wrapped_key).map_err(|err| {
+      KeyStorageError::new(KeyStorageErrorKind::SerializationError)
+        .with_custom_message("unable to decode wrapped key")
+        .with_source(err)
+    })
+  }
+}
+
+/// Wraps `secret_key` under `kek` using AES-256 Key Wrap.
+#[cfg(feature = "memstore")]
+pub(crate) fn wrap_secret_key(kek: &[u8], secret_key: &[u8]) -> KeyStorageResult<Vec<u8>> {
+  let mut wrapped_key: Vec<u8> = vec![0; secret_key.len() + Aes256Kw::BLOCK];
+  Aes256Kw::new(kek)
+    .wrap_key(secret_key, &mut wrapped_key)
+    .map_err(|err| {
+      KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+        .with_custom_message("unable to wrap key")
+        .with_source(err)
+    })?;
+  Ok(wrapped_key)
+}
+
+/// Unwraps `wrapped_key` with `kek` using AES-256 Key Wrap.
+#[cfg(feature = "memstore")]
+pub(crate) fn unwrap_secret_key(kek: &[u8], wrapped_key: &[u8]) -> KeyStorageResult<Zeroizing<Vec<u8>>> {
+  let unwrap_error =
+    || KeyStorageError::new(KeyStorageErrorKind::Unspecified).with_custom_message("unable to unwrap key");
+
+  let len: usize = wrapped_key
+    .len()
+    .checked_sub(Aes256Kw::BLOCK)
+    .ok_or_else(unwrap_error)?;
+  let mut secret_key: Zeroizing<Vec<u8>> = Zeroizing::new(vec![0; len]);
+  Aes256Kw::new(kek)
+    .unwrap_key(wrapped_key, &mut secret_key)
+    .map_err(|err| unwrap_error().with_source(err))?;
+  Ok(secret_key)
+}
@@ -8,32 +8,43 @@ use std::str::FromStr;
 
 use async_trait::async_trait;
 use crypto::signatures::ed25519::SecretKey;
+use identity_verification::jose::jwk::EcCurve;
+use identity_verification::jose::jwk::EcxCurve;
 use identity_verification::jose::jwk::EdCurve;
 use identity_verification::jose::jwk::Jwk;
 use identity_verification::jose::jwk::JwkType;
 use identity_verification::jose::jws::JwsAlgorithm;
+use identity_verification::jose::jwu;
 use rand::distributions::DistString;
 use shared::Shared;
 use tokio::sync::RwLockReadGuard;
 use tokio::sync::RwLockWriteGuard;
+use zeroize::Zeroizing;
 
+use super::ecdsa;
 use super::ed25519::encode_jwk;
 use super::ed25519::expand_secret_jwk;
 use super::jwk_gen_output::JwkGenOutput;
+use super::jwk_storage_export::unwrap_secret_key;
+use super::jwk_storage_export::wrap_secret_key;
+use super::x25519;
+use super::JwkStorageExport;
 use super::KeyId;
 use super::KeyStorageError;
 use super::KeyStorageErrorKind;
 use super::KeyStorageResult;
 use super::KeyType;
+use super::KeyWrappingKey;
+use super::WrappedJwk;
 use crate::key_storage::JwkStorage;
 
 /// The map from key ids to JWKs.
-type JwkKeyStore = HashMap<KeyId, Jwk>;
+pub(crate) type JwkKeyStore = HashMap<KeyId, Jwk>;
 
 /// An insecure, in-memory [`JwkStorage`] implementation that serves as an example and may be used in tests.
 #[derive(Debug)]
 pub struct JwkMemStore {
-  jwk_store: Shared<JwkKeyStore>,
+  pub(crate) jwk_store: Shared<JwkKeyStore>,
 }
 
 impl JwkMemStore {
@@ -59,19 +70,25 @@ impl JwkStorage for JwkMemStore {
 
     check_key_alg_compatibility(key_type, alg)?;
 
-    let (private_key, public_key) = match key_type {
+    let mut jwk: Jwk = match key_type {
       MemStoreKeyType::Ed25519 => {
         let private_key = SecretKey::generate()
           .map_err(|err| KeyStorageError::new(KeyStorageErrorKind::RetryableIOFailure).with_source(err))?;
         let public_key = private_key.public_key();
-        (private_key, public_key)
+        encode_jwk(&private_key, &public_key)
       }
+      MemStoreKeyType::P256 => ecdsa::generate_jwk(EcCurve::P256)?,
+      MemStoreKeyType::P384 => ecdsa::generate_jwk(EcCurve::P384)?,
+      MemStoreKeyType::Secp256K1 => ecdsa::generate_jwk(EcCurve::Secp256K1)?,
+      MemStoreKeyType::X25519 => x25519::generate_jwk()?,
     };
 
     let kid: KeyId = random_key_id();
 
-    let mut jwk: Jwk = encode_jwk(&private_key, &public_key);
-    jwk.set_alg(alg.name());
+    // X25519 keys are only used for key agreement and are therefore not bound to a signature algorithm.
+    if key_type != MemStoreKeyType::X25519 {
+      jwk.set_alg(alg.name());
+    }
     jwk.set_kid(jwk.thumbprint_sha256_b64());
     let public_jwk: Jwk = jwk.to_public().expect("should only panic if kty == oct");
 
@@ -91,13 +108,15 @@ impl JwkStorage for JwkMemStore {
       );
     }
 
-    match jwk.alg() {
-      Some(alg) => {
+    match (key_type, jwk.alg()) {
+      // Key agreement keys may carry any `alg`, since it does not refer to a signature algorithm.
+      (MemStoreKeyType::X25519, _) => (),
+      (_, Some(alg)) => {
         let alg: JwsAlgorithm = JwsAlgorithm::from_str(alg)
           .map_err(|err| KeyStorageError::new(KeyStorageErrorKind::UnsupportedSignatureAlgorithm).with_source(err))?;
         check_key_alg_compatibility(key_type, alg)?;
       }
-      None => {
+      (_, None) => {
         return Err(
           KeyStorageError::new(KeyStorageErrorKind::UnsupportedSignatureAlgorithm)
             .with_custom_message("expected a Jwk with an `alg` parameter"),
@@ -105,13 +124,6 @@ impl JwkStorage for JwkMemStore {
       }
     }
 
-    if jwk.alg().is_none() {
-      return Err(
-        KeyStorageError::new(KeyStorageErrorKind::UnsupportedSignatureAlgorithm)
-          .with_custom_message("expected a Jwk with an `alg` parameter"),
-      );
-    }
-
     let key_id: KeyId = random_key_id();
 
     let mut jwk_store: RwLockWriteGuard<'_, JwkKeyStore> = self.jwk_store.write().await;
@@ -132,23 +144,12 @@ impl JwkStorage for JwkMemStore {
         JwsAlgorithm::from_str(alg_str).map_err(|_| KeyStorageErrorKind::UnsupportedSignatureAlgorithm)
       })?;
 
-    // Check that `kty` is `Okp` and `crv = Ed25519`.
-    match alg {
-      JwsAlgorithm::EdDSA => {
-        let okp_params = public_key.try_okp_params().map_err(|err| {
-          KeyStorageError::new(KeyStorageErrorKind::Unspecified)
-            .with_custom_message(format!("expected a Jwk with Okp params in order to sign with {alg}"))
-            .with_source(err)
-        })?;
-        if okp_params.crv != EdCurve::Ed25519.name() {
-          return Err(
-            KeyStorageError::new(KeyStorageErrorKind::Unspecified).with_custom_message(format!(
-              "expected Jwk with Okp {} crv in order to sign with {alg}",
-              EdCurve::Ed25519
-            )),
-          );
-        }
-      }
+    // Check that the public key is of the type required by `alg`.
+    let key_type: MemStoreKeyType = match alg {
+      JwsAlgorithm::EdDSA => MemStoreKeyType::Ed25519,
+      JwsAlgorithm::ES256 => MemStoreKeyType::P256,
+      JwsAlgorithm::ES384 => MemStoreKeyType::P384,
+      JwsAlgorithm::ES256K => MemStoreKeyType::Secp256K1,
       other => {
         return Err(
           KeyStorageError::new(KeyStorageErrorKind::UnsupportedSignatureAlgorithm)
@@ -156,13 +157,36 @@ impl JwkStorage for JwkMemStore {
         );
       }
     };
+    if MemStoreKeyType::try_from(public_key).ok() != Some(key_type) {
+      return Err(
+        KeyStorageError::new(KeyStorageErrorKind::Unspecified).with_custom_message(format!(
+          "expected a Jwk of key type {key_type} in order to sign with {alg}"
+        )),
+      );
+    }
 
     // Obtain the corresponding private key and sign `data`.
     let jwk: &Jwk = jwk_store
       .get(key_id)
       .ok_or_else(|| KeyStorageError::new(KeyStorageErrorKind::KeyNotFound))?;
-    let secret_key = expand_secret_jwk(jwk)?;
-    Ok(secret_key.sign(data).to_bytes().to_vec())
+    if MemStoreKeyType::try_from(jwk)? != key_type {
+      return Err(
+        KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
+          .with_custom_message(format!("the stored key cannot be used to sign with {alg}")),
+      );
+    }
+
+    match key_type {
+      MemStoreKeyType::Ed25519 => {
+        let secret_key = expand_secret_jwk(jwk)?;
+        Ok(secret_key.sign(data).to_bytes().to_vec())
+      }
+      MemStoreKeyType::P256 | MemStoreKeyType::P384 | MemStoreKeyType::Secp256K1 => ecdsa::sign(jwk, data),
+      MemStoreKeyType::X25519 => Err(
+        KeyStorageError::new(KeyStorageErrorKind::UnsupportedSignatureAlgorithm)
+          .with_custom_message("X25519 keys cannot be used for signing"),
+      ),
+    }
   }
 
   async fn delete(&self, key_id: &KeyId) -> KeyStorageResult<()> {
@@ -178,23 +202,137 @@ impl JwkStorage for JwkMemStore {
     let jwk_store: RwLockReadGuard<'_, JwkKeyStore> = self.jwk_store.read().await;
     Ok(jwk_store.contains_key(key_id))
   }
+
+  async fn get_public_key(&self, key_id: &KeyId) -> KeyStorageResult<Jwk> {
+    let jwk_store: RwLockReadGuard<'_, JwkKeyStore> = self.jwk_store.read().await;
+    jwk_store
+      .get(key_id)
+      .map(|jwk| jwk.to_public().expect("should only panic if kty == oct"))
+      .ok_or_else(|| KeyStorageError::new(KeyStorageErrorKind::KeyNotFound))
+  }
+}
+
+#[cfg_attr(not(feature = "send-sync-storage"), async_trait(?Send))]
+#[cfg_attr(feature = "send-sync-storage", async_trait)]
+impl JwkStorageExport for JwkMemStore {
+  async fn list(&self) -> KeyStorageResult<Vec<KeyId>> {
+    let jwk_store: RwLockReadGuard<'_, JwkKeyStore> = self.jwk_store.read().await;
+    Ok(jwk_store.keys().cloned().collect())
+  }
+
+  async fn export(&self, key_id: &KeyId, wrapping_key: &KeyWrappingKey) -> KeyStorageResult<WrappedJwk> {
+    let jwk_store: RwLockReadGuard<'_, JwkKeyStore> = self.jwk_store.read().await;
+    let jwk: &Jwk = jwk_store
+      .get(key_id)
+      .ok_or_else(|| KeyStorageError::new(KeyStorageErrorKind::KeyNotFound))?;
+
+    let secret_key: Zeroizing<Vec<u8>> = decode_secret_key(jwk)?;
+    let (kek, kdf) = wrapping_key.derive_for_export()?;
+    let wrapped_key: Vec<u8> = wrap_secret_key(kek.as_ref(), &secret_key)?;
+    let public_key: Jwk = jwk.to_public().expect("should only panic if kty == oct");
+
+    WrappedJwk::new(key_id.clone(), public_key, kdf, wrapped_key)
+  }
+
+  async fn import(&self, wrapped_jwk: &WrappedJwk, wrapping_key: &KeyWrappingKey) -> KeyStorageResult<KeyId> {
+    let public_key: &Jwk = wrapped_jwk.public_key();
+    let key_type: MemStoreKeyType = MemStoreKeyType::try_from(public_key)?;
+    let key_id: KeyId = wrapped_jwk.key_id().clone();
+    if self.exists(&key_id).await? {
+      return Err(
+        KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+          .with_custom_message(format!("a key with id `{key_id}` already exists")),
+      );
+    }
+
+    let kek = wrapping_key.derive_for_import(wrapped_jwk.kdf())?;
+    let secret_key: Zeroizing<Vec<u8>> = unwrap_secret_key(kek.as_ref(), &wrapped_jwk.wrapped_key()?)?;
+
+    let mut jwk: Jwk = match key_type {
+      MemStoreKeyType::Ed25519 => {
+        let secret_key: [u8; SecretKey::LENGTH] = secret_key.as_slice().try_into().map_err(|_| {
+          KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+            .with_custom_message(format!("expected key of length {}", SecretKey::LENGTH))
+        })?;
+        let private_key: SecretKey = SecretKey::from_bytes(&secret_key);
+        encode_jwk(&private_key, &private_key.public_key())
+      }
+      MemStoreKeyType::P256 => ecdsa::from_secret_key(EcCurve::P256, &secret_key)?,
+      MemStoreKeyType::P384 => ecdsa::from_secret_key(EcCurve::P384, &secret_key)?,
+      MemStoreKeyType::Secp256K1 => ecdsa::from_secret_key(EcCurve::Secp256K1, &secret_key)?,
+      MemStoreKeyType::X25519 => x25519::from_secret_key(&secret_key)?,
+    };
+
+    // The public key is not covered by the key wrapping, so it must be checked against the unwrapped key.
+    if jwk.thumbprint_sha256_b64() != public_key.thumbprint_sha256_b64() {
+      return Err(
+        KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+          .with_custom_message("the unwrapped private key does not match the exported public key"),
+      );
+    }
+    if let Some(alg) = public_key.alg() {
+      jwk.set_alg(alg);
+    }
+    if let Some(kid) = public_key.kid() {
+      jwk.set_kid(kid);
+    }
+
+    let mut jwk_store: RwLockWriteGuard<'_, JwkKeyStore> = self.jwk_store.write().await;
+    // The key may have been inserted concurrently while unwrapping.
+    if jwk_store.contains_key(&key_id) {
+      return Err(
+        KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+          .with_custom_message(format!("a key with id `{key_id}` already exists")),
+      );
+    }
+    jwk_store.insert(key_id.clone(), jwk);
+
+    Ok(key_id)
+  }
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 enum MemStoreKeyType {
   Ed25519,
+  P256,
+  P384,
+  Secp256K1,
+  X25519,
 }
 
 impl JwkMemStore {
   const ED25519_KEY_TYPE_STR: &'static str = "Ed25519";
   /// The Ed25519 key type.
   pub const ED25519_KEY_TYPE: KeyType = KeyType::from_static_str(Self::ED25519_KEY_TYPE_STR);
+
+  const P256_KEY_TYPE_STR: &'static str = "P-256";
+  /// The P-256 key type, used with [`JwsAlgorithm::ES256`].
+  pub const P256_KEY_TYPE: KeyType = KeyType::from_static_str(Self::P256_KEY_TYPE_STR);
+
+  const P384_KEY_TYPE_STR: &'static str = "P-384";
+  /// The P-384 key type, used with [`JwsAlgorithm::ES384`].
+  pub const P384_KEY_TYPE: KeyType = KeyType::from_static_str(Self::P384_KEY_TYPE_STR);
+
+  const SECP256K1_KEY_TYPE_STR: &'static str = "secp256k1";
+  /// The secp256k1 key type, used with [`JwsAlgorithm::ES256K`].
+  pub const SECP256K1_KEY_TYPE: KeyType = KeyType::from_static_str(Self::SECP256K1_KEY_TYPE_STR);
+
+  const X25519_KEY_TYPE_STR: &'static str = "X25519";
+  /// The X25519 key type.
+  ///
+  /// X25519 keys can only be used for key agreement, so they must be generated with [`JwsAlgorithm::NONE`]
+  /// and cannot be used for signing.
+  pub const X25519_KEY_TYPE: KeyType = KeyType::from_static_str(Self::X25519_KEY_TYPE_STR);
 }
 
 impl MemStoreKeyType {
   const fn name(&self) -> &'static str {
     match self {
-      MemStoreKeyType::Ed25519 => "Ed25519",
+      MemStoreKeyType::Ed25519 => JwkMemStore::ED25519_KEY_TYPE_STR,
+      MemStoreKeyType::P256 => JwkMemStore::P256_KEY_TYPE_STR,
+      MemStoreKeyType::P384 => JwkMemStore::P384_KEY_TYPE_STR,
+      MemStoreKeyType::Secp256K1 => JwkMemStore::SECP256K1_KEY_TYPE_STR,
+      MemStoreKeyType::X25519 => JwkMemStore::X25519_KEY_TYPE_STR,
     }
   }
 }
@@ -211,6 +349,10 @@ impl TryFrom<&KeyType> for MemStoreKeyType {
   fn try_from(value: &KeyType) -> Result<Self, Self::Error> {
     match value.as_str() {
       JwkMemStore::ED25519_KEY_TYPE_STR => Ok(MemStoreKeyType::Ed25519),
+      JwkMemStore::P256_KEY_TYPE_STR => Ok(MemStoreKeyType::P256),
+      JwkMemStore::P384_KEY_TYPE_STR => Ok(MemStoreKeyType::P384),
+      JwkMemStore::SECP256K1_KEY_TYPE_STR => Ok(MemStoreKeyType::Secp256K1),
+      JwkMemStore::X25519_KEY_TYPE_STR => Ok(MemStoreKeyType::X25519),
       _ => Err(KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)),
     }
   }
@@ -227,9 +369,12 @@ impl TryFrom<&Jwk> for MemStoreKeyType {
             .with_custom_message("expected Okp parameters for a JWK with `kty` Okp")
             .with_source(err)
         })?;
+        if okp_params.try_ecx_curve().ok() == Some(EcxCurve::X25519) {
+          return Ok(MemStoreKeyType::X25519);
+        }
         match okp_params.try_ed_curve().map_err(|err| {
           KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
-            .with_custom_message("only Ed curves are supported for signing")
+            .with_custom_message("only Ed curves and X25519 are supported for Okp keys")
             .with_source(err)
         })? {
           EdCurve::Ed25519 => Ok(MemStoreKeyType::Ed25519),
@@ -239,6 +384,26 @@ impl TryFrom<&Jwk> for MemStoreKeyType {
           ),
         }
       }
+      JwkType::Ec => {
+        let ec_params = jwk.try_ec_params().map_err(|err| {
+          KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
+            .with_custom_message("expected Ec parameters for a JWK with `kty` Ec")
+            .with_source(err)
+        })?;
+        match ec_params.try_ec_curve().map_err(|err| {
+          KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
+            .with_custom_message("unsupported Ec curve")
+            .with_source(err)
+        })? {
+          EcCurve::P256 => Ok(MemStoreKeyType::P256),
+          EcCurve::P384 => Ok(MemStoreKeyType::P384),
+          EcCurve::Secp256K1 => Ok(MemStoreKeyType::Secp256K1),
+          curve => Err(
+            KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
+              .with_custom_message(format!("{curve} not supported")),
+          ),
+        }
+      }
       other => Err(
         KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
           .with_custom_message(format!("Jwk `kty` {other} not supported")),
@@ -258,10 +423,34 @@ fn random_key_id() -> KeyId {
   KeyId::new(rand::distributions::Alphanumeric.sample_string(&mut rand::thread_rng(), 32))
 }
 
+/// Decodes the `d` parameter of a private Okp or Ec `jwk`.
+fn decode_secret_key(jwk: &Jwk) -> KeyStorageResult<Zeroizing<Vec<u8>>> {
+  let d: Option<&str> = match jwk.kty() {
+    JwkType::Okp => jwk.try_okp_params().ok().and_then(|params| params.d.as_deref()),
+    JwkType::Ec => jwk.try_ec_params().ok().and_then(|params| params.d.as_deref()),
+    _ => None,
+  };
+
+  d.ok_or_else(|| {
+    KeyStorageError::new(KeyStorageErrorKind::Unspecified).with_custom_message("expected Jwk `d` param to be present")
+  })
+  .and_then(|d| {
+    jwu::decode_b64(d).map(Zeroizing::new).map_err(|err| {
+      KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+        .with_custom_message("unable to decode `d` param")
+        .with_source(err)
+    })
+  })
+}
+
 /// Check that the key type can be used with the algorithm.
 fn check_key_alg_compatibility(key_type: MemStoreKeyType, alg: JwsAlgorithm) -> KeyStorageResult<()> {
   match (key_type, alg) {
     (MemStoreKeyType::Ed25519, JwsAlgorithm::EdDSA) => Ok(()),
+    (MemStoreKeyType::P256, JwsAlgorithm::ES256) => Ok(()),
+    (MemStoreKeyType::P384, JwsAlgorithm::ES384) => Ok(()),
+    (MemStoreKeyType::Secp256K1, JwsAlgorithm::ES256K) => Ok(()),
+    (MemStoreKeyType::X25519, JwsAlgorithm::NONE) => Ok(()),
     (key_type, alg) => Err(
       KeyStorageError::new(crate::key_storage::KeyStorageErrorKind::KeyAlgorithmMismatch)
         .with_custom_message(format!("`cannot use key type `{key_type}` with algorithm `{alg}`")),
@@ -362,7 +551,7 @@ mod tests {
     let store: JwkMemStore = JwkMemStore::new();
 
     let mut ec_params = JwkParamsEc::new();
-    ec_params.crv = EcCurve::P256.name().to_owned();
+    ec_params.crv = EcCurve::P521.name().to_owned();
     ec_params.x = "".to_owned();
     ec_params.y = "".to_owned();
     ec_params.d = Some("".to_owned());
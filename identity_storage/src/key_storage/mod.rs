@@ -6,21 +6,37 @@
 //! This module provides the [`JwkStorage`] trait that
 //! abstracts over storages that store JSON Web Keys.
 
+#[cfg(feature = "memstore")]
+mod ecdsa;
 #[cfg(feature = "memstore")]
 mod ed25519;
+#[cfg(feature = "filestore")]
+pub(crate) mod encrypted_file;
+#[cfg(feature = "filestore")]
+mod filestore;
 mod jwk_gen_output;
 mod jwk_storage;
+#[cfg(feature = "jwk-storage-export")]
+mod jwk_storage_export;
 mod key_id;
 mod key_storage_error;
 mod key_type;
 #[cfg(feature = "memstore")]
 mod memstore;
+#[cfg(feature = "memstore")]
+mod x25519;
 
 #[cfg(test)]
 pub(crate) mod tests;
 
+#[cfg(feature = "filestore")]
+pub use encrypted_file::Argon2Params;
+#[cfg(feature = "filestore")]
+pub use filestore::*;
 pub use jwk_gen_output::*;
 pub use jwk_storage::*;
+#[cfg(feature = "jwk-storage-export")]
+pub use jwk_storage_export::*;
 pub use key_id::*;
 pub use key_storage_error::*;
 pub use key_type::*;
@@ -0,0 +1,161 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::PathBuf;
+
+use super::utils::temp_file_path;
+use super::utils::test_export_and_import;
+use super::utils::test_generate_and_sign;
+use super::utils::test_generate_and_sign_with_alg;
+use super::utils::test_incompatible_key_alg;
+use super::utils::test_incompatible_key_type;
+use super::utils::test_insertion;
+use super::utils::test_key_exists;
+use super::utils::TEST_ARGON2_PARAMS;
+use crate::key_storage::JwkFileStore;
+use crate::key_storage::JwkMemStore;
+use crate::key_storage::JwkStorage;
+use crate::key_storage::JwkStorageExport;
+use crate::key_storage::KeyStorageErrorKind;
+use crate::key_storage::KeyWrappingKey;
+use identity_verification::jws::JwsAlgorithm;
+
+const PASSWORD: &str = "secure_password";
+
+fn open_store(path: &PathBuf) -> JwkFileStore {
+  JwkFileStore::open_with_params(path, PASSWORD, TEST_ARGON2_PARAMS).unwrap()
+}
+
+#[tokio::test]
+async fn insert() {
+  test_insertion(open_store(&temp_file_path())).await;
+}
+
+#[tokio::test]
+async fn incompatible_key_alg() {
+  test_incompatible_key_alg(open_store(&temp_file_path())).await;
+}
+
+#[tokio::test]
+async fn incompatible_key_types() {
+  test_incompatible_key_type(open_store(&temp_file_path())).await;
+}
+
+#[tokio::test]
+async fn generate_and_sign() {
+  test_generate_and_sign(open_store(&temp_file_path())).await;
+}
+
+#[tokio::test]
+async fn key_exists() {
+  test_key_exists(open_store(&temp_file_path())).await;
+}
+
+#[tokio::test]
+async fn generate_and_sign_all_algorithms() {
+  for (key_type, alg) in [
+    (JwkMemStore::ED25519_KEY_TYPE, JwsAlgorithm::EdDSA),
+    (JwkMemStore::P256_KEY_TYPE, JwsAlgorithm::ES256),
+    (JwkMemStore::P384_KEY_TYPE, JwsAlgorithm::ES384),
+    (JwkMemStore::SECP256K1_KEY_TYPE, JwsAlgorithm::ES256K),
+  ] {
+    test_generate_and_sign_with_alg(open_store(&temp_file_path()), key_type, alg).await;
+  }
+}
+
+#[tokio::test]
+async fn export_and_import() {
+  test_export_and_import(
+    JwkMemStore::new(),
+    open_store(&temp_file_path()),
+    JwkMemStore::ED25519_KEY_TYPE,
+    JwsAlgorithm::EdDSA,
+    KeyWrappingKey::kek([42; 32]),
+  )
+  .await;
+}
+
+#[tokio::test]
+async fn persistence() {
+  let path: PathBuf = temp_file_path();
+  let store: JwkFileStore = open_store(&path);
+  assert!(path.exists());
+
+  let generate = store
+    .generate(JwkMemStore::ED25519_KEY_TYPE, JwsAlgorithm::EdDSA)
+    .await
+    .unwrap();
+  let deleted = store
+    .generate(JwkMemStore::SECP256K1_KEY_TYPE, JwsAlgorithm::ES256K)
+    .await
+    .unwrap();
+  store.delete(&deleted.key_id).await.unwrap();
+  let expected = store.sign(&generate.key_id, b"test", &generate.jwk).await.unwrap();
+  drop(store);
+
+  // The temporary file used for atomic writes must not be left behind.
+  let mut tmp_path = path.clone().into_os_string();
+  tmp_path.push(".tmp");
+  assert!(!PathBuf::from(tmp_path).exists());
+
+  let store: JwkFileStore = open_store(&path);
+  assert_eq!(store.count().await, 1);
+  assert!(!store.exists(&deleted.key_id).await.unwrap());
+  assert_eq!(store.get_public_key(&generate.key_id).await.unwrap(), generate.jwk);
+  let signature = store.sign(&generate.key_id, b"test", &generate.jwk).await.unwrap();
+  assert_eq!(signature, expected);
+}
+
+#[tokio::test]
+async fn incorrect_password() {
+  let path: PathBuf = temp_file_path();
+  let store: JwkFileStore = open_store(&path);
+  store
+    .generate(JwkMemStore::ED25519_KEY_TYPE, JwsAlgorithm::EdDSA)
+    .await
+    .unwrap();
+  drop(store);
+
+  // The key derivation parameters of an existing file take precedence over the given ones.
+  let err = JwkFileStore::open(&path, "incorrect_password").unwrap_err();
+  assert!(matches!(err.kind(), KeyStorageErrorKind::Unauthenticated));
+}
+
+#[tokio::test]
+async fn tampered_file() {
+  let path: PathBuf = temp_file_path();
+  drop(open_store(&path));
+
+  let mut envelope: serde_json::Value = serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+  let mut ciphertext = identity_verification::jose::jwu::decode_b64(envelope["ciphertext"].as_str().unwrap()).unwrap();
+  ciphertext[0] ^= 1;
+  envelope["ciphertext"] = identity_verification::jose::jwu::encode_b64(ciphertext).into();
+  std::fs::write(&path, serde_json::to_vec(&envelope).unwrap()).unwrap();
+
+  let err = JwkFileStore::open_with_params(&path, PASSWORD, TEST_ARGON2_PARAMS).unwrap_err();
+  assert!(matches!(err.kind(), KeyStorageErrorKind::Unauthenticated));
+
+  std::fs::write(&path, b"not a storage file").unwrap();
+  let err = JwkFileStore::open_with_params(&path, PASSWORD, TEST_ARGON2_PARAMS).unwrap_err();
+  assert!(matches!(err.kind(), KeyStorageErrorKind::SerializationError));
+}
+
+#[tokio::test]
+async fn failed_write_is_rolled_back() {
+  let mut path: PathBuf = temp_file_path();
+  std::fs::create_dir(&path).unwrap();
+  path.push("keys.json");
+  let store: JwkFileStore = open_store(&path);
+
+  // Make the file unwritable by removing its directory.
+  std::fs::remove_file(&path).unwrap();
+  std::fs::remove_dir(path.parent().unwrap()).unwrap();
+
+  let err = store
+    .generate(JwkMemStore::ED25519_KEY_TYPE, JwsAlgorithm::EdDSA)
+    .await
+    .unwrap_err();
+  assert!(matches!(err.kind(), KeyStorageErrorKind::RetryableIOFailure));
+  assert_eq!(store.count().await, 0);
+  assert!(store.list().await.unwrap().is_empty());
+}
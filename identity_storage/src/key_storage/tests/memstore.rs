@@ -1,12 +1,20 @@
 // Copyright 2020-2023 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use super::utils::test_export_and_import;
 use super::utils::test_generate_and_sign;
+use super::utils::test_generate_and_sign_with_alg;
 use super::utils::test_incompatible_key_alg;
 use super::utils::test_incompatible_key_type;
 use super::utils::test_insertion;
 use super::utils::test_key_exists;
 use crate::key_storage::JwkMemStore;
+use crate::key_storage::JwkStorage;
+use crate::key_storage::JwkStorageExport;
+use crate::key_storage::KeyStorageErrorKind;
+use crate::key_storage::KeyWrappingKey;
+use crate::key_storage::WrappedJwk;
+use identity_verification::jws::JwsAlgorithm;
 
 #[tokio::test]
 async fn insert() {
@@ -37,3 +45,195 @@ async fn key_exists() {
   let store: JwkMemStore = JwkMemStore::new();
   test_key_exists(store).await;
 }
+
+#[tokio::test]
+async fn generate_and_sign_all_algorithms() {
+  for (key_type, alg) in [
+    (JwkMemStore::ED25519_KEY_TYPE, JwsAlgorithm::EdDSA),
+    (JwkMemStore::P256_KEY_TYPE, JwsAlgorithm::ES256),
+    (JwkMemStore::P384_KEY_TYPE, JwsAlgorithm::ES384),
+    (JwkMemStore::SECP256K1_KEY_TYPE, JwsAlgorithm::ES256K),
+  ] {
+    let store: JwkMemStore = JwkMemStore::new();
+    test_generate_and_sign_with_alg(store, key_type, alg).await;
+  }
+}
+
+#[tokio::test]
+async fn x25519_key_agreement_keys() {
+  let store: JwkMemStore = JwkMemStore::new();
+
+  // INVALID: X25519 keys cannot be bound to a signature algorithm.
+  let err = store
+    .generate(JwkMemStore::X25519_KEY_TYPE, JwsAlgorithm::EdDSA)
+    .await
+    .unwrap_err();
+  assert!(matches!(err.kind(), KeyStorageErrorKind::KeyAlgorithmMismatch));
+
+  let generate = store
+    .generate(JwkMemStore::X25519_KEY_TYPE, JwsAlgorithm::NONE)
+    .await
+    .unwrap();
+  assert!(generate.jwk.alg().is_none());
+  assert!(generate.jwk.is_public());
+
+  // INVALID: X25519 keys cannot be used for signing.
+  let mut public_key = generate.jwk.clone();
+  public_key.set_alg(JwsAlgorithm::EdDSA.name());
+  let err = store.sign(&generate.key_id, b"test", &public_key).await.unwrap_err();
+  assert!(matches!(err.kind(), KeyStorageErrorKind::Unspecified));
+}
+
+#[tokio::test]
+async fn sign_with_mismatched_key() {
+  let store: JwkMemStore = JwkMemStore::new();
+  let p256 = store
+    .generate(JwkMemStore::P256_KEY_TYPE, JwsAlgorithm::ES256)
+    .await
+    .unwrap();
+  let secp256k1 = store
+    .generate(JwkMemStore::SECP256K1_KEY_TYPE, JwsAlgorithm::ES256K)
+    .await
+    .unwrap();
+
+  // INVALID: The public key must match the algorithm.
+  let mut public_key = p256.jwk.clone();
+  public_key.set_alg(JwsAlgorithm::ES256K.name());
+  let err = store.sign(&p256.key_id, b"test", &public_key).await.unwrap_err();
+  assert!(matches!(err.kind(), KeyStorageErrorKind::Unspecified));
+
+  // INVALID: The stored key must match the public key.
+  let err = store.sign(&p256.key_id, b"test", &secp256k1.jwk).await.unwrap_err();
+  assert!(matches!(err.kind(), KeyStorageErrorKind::UnsupportedKeyType));
+}
+
+#[tokio::test]
+async fn export_and_import_all_algorithms() {
+  for (key_type, alg) in [
+    (JwkMemStore::ED25519_KEY_TYPE, JwsAlgorithm::EdDSA),
+    (JwkMemStore::P256_KEY_TYPE, JwsAlgorithm::ES256),
+    (JwkMemStore::P384_KEY_TYPE, JwsAlgorithm::ES384),
+    (JwkMemStore::SECP256K1_KEY_TYPE, JwsAlgorithm::ES256K),
+  ] {
+    test_export_and_import(
+      JwkMemStore::new(),
+      JwkMemStore::new(),
+      key_type,
+      alg,
+      KeyWrappingKey::kek([42; 32]),
+    )
+    .await;
+  }
+}
+
+#[tokio::test]
+async fn export_and_import_with_password() {
+  let source: JwkMemStore = JwkMemStore::new();
+  let target: JwkMemStore = JwkMemStore::new();
+  let wrapping_key: KeyWrappingKey = KeyWrappingKey::password("secure_password");
+
+  let generate = source
+    .generate(JwkMemStore::ED25519_KEY_TYPE, JwsAlgorithm::EdDSA)
+    .await
+    .unwrap();
+  let wrapped_jwk: WrappedJwk = source.export(&generate.key_id, &wrapping_key).await.unwrap();
+  assert!(wrapped_jwk.kdf().is_some());
+
+  // INVALID: Unwrapping with the wrong password must fail.
+  let err = target
+    .import(&wrapped_jwk, &KeyWrappingKey::password("wrong_password"))
+    .await
+    .unwrap_err();
+  assert!(matches!(err.kind(), KeyStorageErrorKind::Unspecified));
+
+  // INVALID: Unwrapping a password-wrapped key with a key-encryption key must fail.
+  let err = target
+    .import(&wrapped_jwk, &KeyWrappingKey::kek([42; 32]))
+    .await
+    .unwrap_err();
+  assert!(matches!(err.kind(), KeyStorageErrorKind::Unspecified));
+
+  let key_id = target.import(&wrapped_jwk, &wrapping_key).await.unwrap();
+  let signature = target.sign(&key_id, b"test", &generate.jwk).await.unwrap();
+  let expected = source.sign(&generate.key_id, b"test", &generate.jwk).await.unwrap();
+  assert_eq!(signature, expected);
+}
+
+#[tokio::test]
+async fn export_and_import_x25519() {
+  let source: JwkMemStore = JwkMemStore::new();
+  let target: JwkMemStore = JwkMemStore::new();
+  let wrapping_key: KeyWrappingKey = KeyWrappingKey::kek([42; 32]);
+
+  let generate = source
+    .generate(JwkMemStore::X25519_KEY_TYPE, JwsAlgorithm::NONE)
+    .await
+    .unwrap();
+  let wrapped_jwk: WrappedJwk = source.export(&generate.key_id, &wrapping_key).await.unwrap();
+  let key_id = target.import(&wrapped_jwk, &wrapping_key).await.unwrap();
+
+  let reexported: WrappedJwk = target.export(&key_id, &wrapping_key).await.unwrap();
+  assert_eq!(reexported.public_key(), &generate.jwk);
+}
+
+#[tokio::test]
+async fn import_with_mismatched_public_key() {
+  let source: JwkMemStore = JwkMemStore::new();
+  let wrapping_key: KeyWrappingKey = KeyWrappingKey::kek([42; 32]);
+
+  let generate = source
+    .generate(JwkMemStore::ED25519_KEY_TYPE, JwsAlgorithm::EdDSA)
+    .await
+    .unwrap();
+  let other = source
+    .generate(JwkMemStore::ED25519_KEY_TYPE, JwsAlgorithm::EdDSA)
+    .await
+    .unwrap();
+  let wrapped_jwk: WrappedJwk = source.export(&generate.key_id, &wrapping_key).await.unwrap();
+
+  // INVALID: The public key of the export is not covered by the key wrapping and must be checked on import.
+  let mut tampered: serde_json::Value = serde_json::to_value(&wrapped_jwk).unwrap();
+  tampered["publicKey"] = serde_json::to_value(&other.jwk).unwrap();
+  let tampered: WrappedJwk = serde_json::from_value(tampered).unwrap();
+
+  let target: JwkMemStore = JwkMemStore::new();
+  let err = target.import(&tampered, &wrapping_key).await.unwrap_err();
+  assert!(matches!(err.kind(), KeyStorageErrorKind::Unspecified));
+  assert!(target.list().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn export_missing_key() {
+  let store: JwkMemStore = JwkMemStore::new();
+  let err = store
+    .export(
+      &crate::key_storage::KeyId::new("non-existent-id"),
+      &KeyWrappingKey::kek([42; 32]),
+    )
+    .await
+    .unwrap_err();
+  assert!(matches!(err.kind(), KeyStorageErrorKind::KeyNotFound));
+}
+
+#[tokio::test]
+async fn get_public_key() {
+  let store: JwkMemStore = JwkMemStore::new();
+  for (key_type, alg) in [
+    (JwkMemStore::ED25519_KEY_TYPE, JwsAlgorithm::EdDSA),
+    (JwkMemStore::P256_KEY_TYPE, JwsAlgorithm::ES256),
+    (JwkMemStore::P384_KEY_TYPE, JwsAlgorithm::ES384),
+    (JwkMemStore::SECP256K1_KEY_TYPE, JwsAlgorithm::ES256K),
+    (JwkMemStore::X25519_KEY_TYPE, JwsAlgorithm::NONE),
+  ] {
+    let generate = store.generate(key_type, alg).await.unwrap();
+    let public_key = store.get_public_key(&generate.key_id).await.unwrap();
+    assert_eq!(public_key, generate.jwk);
+    assert!(public_key.is_public());
+  }
+
+  let err = store
+    .get_public_key(&crate::key_storage::KeyId::new("non-existent-id"))
+    .await
+    .unwrap_err();
+  assert!(matches!(err.kind(), KeyStorageErrorKind::KeyNotFound));
+}
@@ -2,9 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::key_storage::JwkStorage;
+use crate::key_storage::JwkStorageExport;
 use crate::key_storage::KeyId;
 use crate::key_storage::KeyStorageErrorKind;
 use crate::key_storage::KeyType;
+use crate::key_storage::KeyWrappingKey;
+use crate::key_storage::WrappedJwk;
 use crypto::signatures::ed25519::PublicKey;
 use crypto::signatures::ed25519::SecretKey;
 use crypto::signatures::ed25519::Signature;
@@ -15,6 +18,8 @@ use identity_verification::jose::jwu;
 use identity_verification::jwk::EcCurve;
 use identity_verification::jwk::JwkParamsEc;
 use identity_verification::jws::JwsAlgorithm;
+use identity_verification::jws::JwsVerifier;
+use identity_verification::jws::VerificationInput;
 
 pub(crate) async fn test_insertion(store: impl JwkStorage) {
   let (private_key, public_key) = generate_ed25519();
@@ -45,7 +50,7 @@ pub(crate) async fn test_incompatible_key_alg(store: impl JwkStorage) {
 
 pub(crate) async fn test_incompatible_key_type(store: impl JwkStorage) {
   let mut ec_params = JwkParamsEc::new();
-  ec_params.crv = EcCurve::P256.name().to_owned();
+  ec_params.crv = EcCurve::P521.name().to_owned();
   ec_params.x = "".to_owned();
   ec_params.y = "".to_owned();
   ec_params.d = Some("".to_owned());
@@ -73,6 +78,73 @@ pub(crate) async fn test_generate_and_sign(store: impl JwkStorage) {
   store.delete(&key_id).await.unwrap();
 }
 
+pub(crate) async fn test_generate_and_sign_with_alg(store: impl JwkStorage, key_type: KeyType, alg: JwsAlgorithm) {
+  let test_msg: &[u8] = b"test";
+
+  let generate = store.generate(key_type, alg).await.unwrap();
+  assert_eq!(generate.jwk.alg(), Some(alg.name()));
+  assert!(generate.jwk.is_public());
+
+  let signature = store.sign(&generate.key_id, test_msg, &generate.jwk).await.unwrap();
+
+  let input = VerificationInput {
+    alg,
+    signing_input: test_msg.into(),
+    decoded_signature: signature.into(),
+  };
+  identity_ecdsa_verifier::CompositeJwsVerifier::default()
+    .verify(input, &generate.jwk)
+    .unwrap();
+
+  let key_id: KeyId = generate.key_id;
+  assert!(store.exists(&key_id).await.unwrap());
+  store.delete(&key_id).await.unwrap();
+}
+
+pub(crate) async fn test_export_and_import(
+  source: impl JwkStorageExport,
+  target: impl JwkStorageExport,
+  key_type: KeyType,
+  alg: JwsAlgorithm,
+  wrapping_key: KeyWrappingKey,
+) {
+  let test_msg: &[u8] = b"test";
+
+  let generate = source.generate(key_type, alg).await.unwrap();
+  assert!(source.list().await.unwrap().contains(&generate.key_id));
+
+  let wrapped_jwk: WrappedJwk = source.export(&generate.key_id, &wrapping_key).await.unwrap();
+  assert_eq!(wrapped_jwk.key_id(), &generate.key_id);
+  assert_eq!(wrapped_jwk.public_key(), &generate.jwk);
+
+  // INVALID: Unwrapping with a different key must fail.
+  let err = target
+    .import(&wrapped_jwk, &KeyWrappingKey::kek([7; 32]))
+    .await
+    .unwrap_err();
+  assert!(matches!(err.kind(), KeyStorageErrorKind::Unspecified));
+  assert!(!target.exists(&generate.key_id).await.unwrap());
+
+  // VALID: The key is imported under its original key id and can be used for signing.
+  let key_id: KeyId = target.import(&wrapped_jwk, &wrapping_key).await.unwrap();
+  assert_eq!(key_id, generate.key_id);
+  assert_eq!(target.list().await.unwrap(), vec![key_id.clone()]);
+
+  let signature = target.sign(&key_id, test_msg, &generate.jwk).await.unwrap();
+  let input = VerificationInput {
+    alg,
+    signing_input: test_msg.into(),
+    decoded_signature: signature.into(),
+  };
+  identity_ecdsa_verifier::CompositeJwsVerifier::default()
+    .verify(input, &generate.jwk)
+    .unwrap();
+
+  // INVALID: Importing a key id that already exists must fail.
+  let err = target.import(&wrapped_jwk, &wrapping_key).await.unwrap_err();
+  assert!(matches!(err.kind(), KeyStorageErrorKind::Unspecified));
+}
+
 pub(crate) async fn test_key_exists(store: impl JwkStorage) {
   assert!(!store.exists(&KeyId::new("non-existent-id")).await.unwrap());
 }
@@ -94,3 +166,20 @@ pub(crate) fn generate_ed25519() -> (SecretKey, PublicKey) {
   let public_key = private_key.public_key();
   (private_key, public_key)
 }
+
+/// Cheap key derivation parameters, such that opening file stores in tests is fast.
+#[cfg(feature = "filestore")]
+pub(crate) const TEST_ARGON2_PARAMS: crate::key_storage::Argon2Params = crate::key_storage::Argon2Params::new(8, 1, 1);
+
+/// Returns a path to a file that does not exist yet in the temporary directory.
+#[cfg(feature = "filestore")]
+pub(crate) fn temp_file_path() -> std::path::PathBuf {
+  use rand::distributions::DistString;
+
+  let mut file = std::env::temp_dir();
+  file.push("test_filestores");
+  std::fs::create_dir_all(&file).unwrap();
+  file.push(rand::distributions::Alphanumeric.sample_string(&mut rand::thread_rng(), 32));
+  file.set_extension("json");
+  file
+}
@@ -0,0 +1,37 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crypto::keys::x25519::SecretKey;
+use identity_verification::jose::jwk::EcxCurve;
+use identity_verification::jose::jwk::Jwk;
+use identity_verification::jose::jwk::JwkParamsOkp;
+use identity_verification::jose::jwu;
+
+use crate::key_storage::KeyStorageError;
+use crate::key_storage::KeyStorageErrorKind;
+use crate::key_storage::KeyStorageResult;
+
+/// Generates a new X25519 private key and encodes it as a [`Jwk`].
+pub(crate) fn generate_jwk() -> KeyStorageResult<Jwk> {
+  let private_key: SecretKey = SecretKey::generate()
+    .map_err(|err| KeyStorageError::new(KeyStorageErrorKind::RetryableIOFailure).with_source(err))?;
+  Ok(encode_jwk(&private_key))
+}
+
+/// Encodes the X25519 private key `secret_key` as a [`Jwk`].
+pub(crate) fn from_secret_key(secret_key: &[u8]) -> KeyStorageResult<Jwk> {
+  let private_key: SecretKey = SecretKey::try_from_slice(secret_key).map_err(|err| {
+    KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+      .with_custom_message("invalid X25519 private key")
+      .with_source(err)
+  })?;
+  Ok(encode_jwk(&private_key))
+}
+
+fn encode_jwk(private_key: &SecretKey) -> Jwk {
+  let mut params = JwkParamsOkp::new();
+  params.x = jwu::encode_b64(private_key.public_key().to_bytes());
+  params.d = Some(jwu::encode_b64(private_key.to_bytes()));
+  params.crv = EcxCurve::X25519.name().to_owned();
+  Jwk::from_params(params)
+}
@@ -0,0 +1,76 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_did::DIDJwk;
+use identity_did::DIDUrl;
+use identity_did::DID;
+use identity_document::document::CoreDocument;
+use identity_verification::jose::jws::JwsAlgorithm;
+use identity_verification::VerificationMethod;
+
+use super::jwk_document_ext::try_undo_key_generation;
+use super::JwkStorageDocumentError as Error;
+use super::Storage;
+use super::StorageResult;
+
+use crate::key_id_storage::KeyIdStorage;
+use crate::key_id_storage::MethodDigest;
+use crate::key_storage::JwkGenOutput;
+use crate::key_storage::JwkStorage;
+use crate::key_storage::KeyType;
+
+impl<K, I> Storage<K, I>
+where
+  K: JwkStorage,
+  I: KeyIdStorage,
+{
+  /// Generates new key material in the key storage and returns the DID document of the did:jwk encoding its public
+  /// key.
+  ///
+  /// The key can be used for signing with the returned document through the
+  /// [`JwkDocumentExt`](crate::storage::JwkDocumentExt) API, using the fragment
+  /// [`CoreDocument::DID_JWK_FRAGMENT`]. See also [`Storage::insert_did_jwk`].
+  pub async fn generate_did_jwk(&self, key_type: KeyType, alg: JwsAlgorithm) -> StorageResult<CoreDocument> {
+    let output: JwkGenOutput = <K as JwkStorage>::generate(self.key_storage(), key_type, alg)
+      .await
+      .map_err(Error::KeyStorageError)?;
+
+    match self.insert_did_jwk(&output).await {
+      Ok(document) => Ok(document),
+      Err(error) => Err(try_undo_key_generation(self, &output.key_id, error).await),
+    }
+  }
+
+  /// Returns the DID document of the did:jwk encoding the public key of a previously generated `output`, and stores
+  /// the key identifier of its verification method in the key id storage.
+  ///
+  /// The key can then be used for signing with the returned document through the
+  /// [`JwkDocumentExt`](crate::storage::JwkDocumentExt) API, using the fragment
+  /// [`CoreDocument::DID_JWK_FRAGMENT`]. Since did:jwk documents are derived from the DID itself, holders and
+  /// verifiers can resolve them without any network access.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the JWK of `output` cannot be encoded as a did:jwk, or if a key identifier is already stored for the
+  /// same verification method.
+  pub async fn insert_did_jwk(&self, output: &JwkGenOutput) -> StorageResult<CoreDocument> {
+    let did: DIDJwk = output
+      .did_jwk()
+      .map_err(|err| Error::DIDJwkConstructionError(err.into()))?;
+    let document: CoreDocument =
+      CoreDocument::expand_did_jwk(did).map_err(|err| Error::DIDJwkConstructionError(err.into()))?;
+
+    let method_id: DIDUrl = document
+      .id()
+      .clone()
+      .join(format!("#{}", CoreDocument::DID_JWK_FRAGMENT))
+      .map_err(|err| Error::DIDJwkConstructionError(err.into()))?;
+    let method: &VerificationMethod = document.resolve_method(&method_id, None).ok_or(Error::MethodNotFound)?;
+    let method_digest: MethodDigest = MethodDigest::new(method).map_err(Error::MethodDigestConstructionError)?;
+    <I as KeyIdStorage>::insert_key_id(self.key_id_storage(), method_digest, output.key_id.clone())
+      .await
+      .map_err(Error::KeyIdStorageError)?;
+
+    Ok(document)
+  }
+}
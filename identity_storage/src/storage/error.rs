@@ -30,6 +30,9 @@ pub enum JwkStorageDocumentError {
   /// Caused by a failure to construct a verification method.
   #[error("method generation failed: unable to create a valid verification method")]
   VerificationMethodConstructionError(#[source] identity_verification::Error),
+  /// Caused by a failure to construct a did:jwk or its DID document.
+  #[error("unable to create did:jwk")]
+  DIDJwkConstructionError(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
   /// Caused by an encoding error.
   #[error("could not produce jwt: encoding error")]
   EncodingError(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
@@ -39,6 +42,10 @@ pub enum JwkStorageDocumentError {
   /// Caused by a failure during (de)serialization of JWS claims.
   #[error("could not produce JWS payload from the given claims: serialization failed")]
   ClaimsSerializationError(#[source] identity_credential::Error),
+  /// Caused by a failure to produce a Data Integrity proof.
+  #[cfg(feature = "data-integrity")]
+  #[error("could not produce data integrity proof")]
+  DataIntegrityProofError(#[source] identity_credential::data_integrity::DataIntegrityError),
   /// Caused by a failure to undo a failed storage operation.
   #[error("storage operation failed after altering state. Unable to undo operation(s): {message}")]
   UndoOperationFailed {
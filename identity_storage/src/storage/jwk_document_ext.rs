@@ -3,6 +3,7 @@
 
 use super::JwkStorageDocumentError as Error;
 use super::JwsSignatureOptions;
+use super::MethodRotationOptions;
 use super::Storage;
 
 use crate::key_id_storage::KeyIdStorage;
@@ -14,21 +15,40 @@ use crate::key_storage::KeyId;
 use crate::key_storage::KeyStorageResult;
 use crate::key_storage::KeyType;
 
+#[cfg(feature = "data-integrity")]
+use std::borrow::Cow;
+
 use async_trait::async_trait;
 use identity_core::common::Object;
 use identity_credential::credential::Credential;
 use identity_credential::credential::Jws;
 use identity_credential::credential::Jwt;
+#[cfg(feature = "data-integrity")]
+use identity_credential::credential::Proof;
+#[cfg(feature = "data-integrity")]
+use identity_credential::data_integrity::DataIntegrityProof;
+#[cfg(feature = "data-integrity")]
+use identity_credential::data_integrity::DataIntegrityProofOptions;
+#[cfg(feature = "data-integrity")]
+use identity_credential::data_integrity::EddsaJcs2022;
+#[cfg(feature = "data-integrity")]
+use identity_credential::data_integrity::ProofPurpose;
 use identity_credential::presentation::JwtPresentationOptions;
 use identity_credential::presentation::Presentation;
 use identity_did::DIDUrl;
 use identity_document::document::CoreDocument;
+#[cfg(feature = "data-integrity")]
+use identity_verification::jose::jwk::EdCurve;
+#[cfg(feature = "data-integrity")]
+use identity_verification::jose::jwk::Jwk;
 use identity_verification::jose::jws::CompactJwsEncoder;
 use identity_verification::jose::jws::CompactJwsEncodingOptions;
 use identity_verification::jose::jws::JwsAlgorithm;
 use identity_verification::jose::jws::JwsHeader;
 use identity_verification::jws::CharSet;
 use identity_verification::MethodData;
+use identity_verification::MethodRef;
+use identity_verification::MethodRelationship;
 use identity_verification::MethodScope;
 use identity_verification::VerificationMethod;
 use serde::de::DeserializeOwned;
@@ -79,6 +99,34 @@ pub trait JwkDocumentExt: private::Sealed {
     K: JwkStorage,
     I: KeyIdStorage;
 
+  /// Rotate the key material of the method identified by the given `id`.
+  ///
+  /// New key material is generated in the given `storage` and a new verification method with the corresponding public
+  /// key material replaces the old method in every verification relationship the old method was part of.
+  ///
+  /// - If no fragment is given the `kid` of the generated JWK is used, if it is set, otherwise an error is returned.
+  ///   The fragment of the old method may be reused unless the old method is retired.
+  /// - If [`MethodRotationOptions::retire_until`] is set, the old method and its key material are retained in a
+  ///   retired state. Otherwise they are purged like with [`JwkDocumentExt::purge_method`].
+  ///
+  /// If any step fails, the document and the storage are reverted to their previous state, including the removal of
+  /// the generated key material. [`JwkStorageDocumentError::UndoOperationFailed`](Error::UndoOperationFailed) is
+  /// returned if reverting the storage fails.
+  ///
+  /// The fragment of the new method is returned.
+  async fn rotate_method<K, I>(
+    &mut self,
+    storage: &Storage<K, I>,
+    id: &DIDUrl,
+    key_type: KeyType,
+    alg: JwsAlgorithm,
+    fragment: Option<&str>,
+    options: &MethodRotationOptions,
+  ) -> StorageResult<String>
+  where
+    K: JwkStorage,
+    I: KeyIdStorage;
+
   /// Sign the arbitrary `payload` according to `options` with the storage backed private key corresponding to the
   /// public key material in the verification method identified by the given `fragment.
   ///
@@ -135,6 +183,45 @@ pub trait JwkDocumentExt: private::Sealed {
     I: KeyIdStorage,
     T: ToOwned<Owned = T> + Serialize + DeserializeOwned + Sync,
     CRED: ToOwned<Owned = CRED> + Serialize + DeserializeOwned + Clone + Sync;
+
+  /// Produces a [Data Integrity](https://www.w3.org/TR/vc-data-integrity/) proof for the given `credential` using
+  /// the [`eddsa-jcs-2022`](https://www.w3.org/TR/vc-di-eddsa/#eddsa-jcs-2022) cryptosuite.
+  ///
+  /// The proof has the `assertionMethod` purpose and is signed by the private key backed by the `storage`
+  /// corresponding to the Ed25519 public key material in the method identified by `fragment`. It is computed over
+  /// the credential without its `proof` property, and should be set as its new `proof`.
+  #[cfg(feature = "data-integrity")]
+  async fn create_credential_data_integrity_proof<K, I, T>(
+    &self,
+    credential: &Credential<T>,
+    storage: &Storage<K, I>,
+    fragment: &str,
+    options: &DataIntegrityProofOptions,
+  ) -> StorageResult<Proof>
+  where
+    K: JwkStorage,
+    I: KeyIdStorage,
+    T: Serialize + Sync;
+
+  /// Produces a [Data Integrity](https://www.w3.org/TR/vc-data-integrity/) proof for the given `presentation` using
+  /// the [`eddsa-jcs-2022`](https://www.w3.org/TR/vc-di-eddsa/#eddsa-jcs-2022) cryptosuite.
+  ///
+  /// The proof has the `authentication` purpose and is signed by the private key backed by the `storage`
+  /// corresponding to the Ed25519 public key material in the method identified by `fragment`. It is computed over
+  /// the presentation without its `proof` property, and should be set as its new `proof`.
+  #[cfg(feature = "data-integrity")]
+  async fn create_presentation_data_integrity_proof<K, I, CRED, T>(
+    &self,
+    presentation: &Presentation<CRED, T>,
+    storage: &Storage<K, I>,
+    fragment: &str,
+    options: &DataIntegrityProofOptions,
+  ) -> StorageResult<Proof>
+  where
+    K: JwkStorage,
+    I: KeyIdStorage,
+    T: Serialize + Sync,
+    CRED: Serialize + Sync;
 }
 
 mod private {
@@ -300,12 +387,154 @@ macro_rules! purge_method_for_document_type {
   };
 }
 
+macro_rules! rotate_method_for_document_type {
+  ($t:ty, $name:ident) => {
+    async fn $name<K, I>(
+      document: &mut $t,
+      storage: &Storage<K, I>,
+      id: &DIDUrl,
+      key_type: KeyType,
+      alg: JwsAlgorithm,
+      fragment: Option<&str>,
+      options: &MethodRotationOptions,
+    ) -> StorageResult<String>
+    where
+      K: JwkStorage,
+      I: KeyIdStorage,
+    {
+      // Obtain the method to rotate and the key id of its key material before altering any state.
+      let old_method: VerificationMethod = document.resolve_method(id, None).ok_or(Error::MethodNotFound)?.clone();
+      let old_method_digest: MethodDigest =
+        MethodDigest::new(&old_method).map_err(Error::MethodDigestConstructionError)?;
+      let old_key_id: KeyId = <I as KeyIdStorage>::get_key_id(&storage.key_id_storage(), &old_method_digest)
+        .await
+        .map_err(Error::KeyIdStorageError)?;
+      let (embedded_in, referenced_in) = method_relationships(document.as_ref(), old_method.id());
+
+      let JwkGenOutput { key_id, jwk } = <K as JwkStorage>::generate(&storage.key_storage(), key_type, alg)
+        .await
+        .map_err(Error::KeyStorageError)?;
+
+      let new_method: VerificationMethod = match VerificationMethod::new_from_jwk(document.id().clone(), jwk, fragment)
+        .map_err(Error::VerificationMethodConstructionError)
+      {
+        Ok(method) => method,
+        Err(source) => {
+          return Err(try_undo_key_generation(storage, &key_id, source).await);
+        }
+      };
+      let new_method_digest: MethodDigest =
+        match MethodDigest::new(&new_method).map_err(Error::MethodDigestConstructionError) {
+          Ok(digest) => digest,
+          Err(source) => {
+            return Err(try_undo_key_generation(storage, &key_id, source).await);
+          }
+        };
+      let new_method_id: DIDUrl = new_method.id().clone();
+
+      // The fragment is always set on a method, so this error will never occur.
+      let fragment: String = new_method_id
+        .fragment()
+        .ok_or(identity_verification::Error::MissingIdFragment)
+        .map_err(Error::VerificationMethodConstructionError)?
+        .to_owned();
+
+      // Replace the old method in the document. The previous state is restored if this fails midway.
+      let backup: $t = document.clone();
+      let replacement: StorageResult<()> = (|| {
+        document.remove_method(old_method.id());
+
+        // An embedded method can only be kept embedded if it is not referenced anywhere else.
+        match (embedded_in, referenced_in.is_empty()) {
+          (Some(relationship), true) => document
+            .insert_method(new_method, MethodScope::VerificationRelationship(relationship))
+            .map_err(|_| Error::FragmentAlreadyExists)?,
+          _ => {
+            document
+              .insert_method(new_method, MethodScope::VerificationMethod)
+              .map_err(|_| Error::FragmentAlreadyExists)?;
+            for relationship in embedded_in.into_iter().chain(referenced_in) {
+              document
+                .attach_method_relationship(&new_method_id, relationship)
+                .map_err(|_| Error::MethodNotFound)?;
+            }
+          }
+        }
+
+        if let Some(retire_until) = options.retire_until {
+          let mut retired_method: VerificationMethod = old_method.clone();
+          retired_method.properties_mut().insert(
+            MethodRotationOptions::RETIRED_UNTIL_PROPERTY.to_owned(),
+            retire_until.to_rfc3339().into(),
+          );
+          document
+            .insert_method(retired_method, MethodScope::VerificationMethod)
+            .map_err(|_| Error::FragmentAlreadyExists)?;
+        }
+
+        Ok(())
+      })();
+
+      if let Err(error) = replacement {
+        *document = backup;
+        return Err(try_undo_key_generation(storage, &key_id, error).await);
+      }
+
+      // Insert the generated `KeyId` into storage under the digest of the new method.
+      if let Err(error) =
+        <I as KeyIdStorage>::insert_key_id(&storage.key_id_storage(), new_method_digest.clone(), key_id.clone())
+          .await
+          .map_err(Error::KeyIdStorageError)
+      {
+        *document = backup;
+        return Err(try_undo_key_generation(storage, &key_id, error).await);
+      }
+
+      // A retired method keeps its key material.
+      if options.retire_until.is_some() {
+        return Ok(fragment);
+      }
+
+      // Delete the old key id before the old key, as only the former can be undone.
+      if let Err(error) = <I as KeyIdStorage>::delete_key_id(&storage.key_id_storage(), &old_method_digest)
+        .await
+        .map_err(Error::KeyIdStorageError)
+      {
+        *document = backup;
+        return Err(try_undo_method_rotation(storage, &new_method_digest, &key_id, error).await);
+      }
+
+      if let Err(error) = <K as JwkStorage>::delete(&storage.key_storage(), &old_key_id)
+        .await
+        .map_err(Error::KeyStorageError)
+      {
+        *document = backup;
+        if let Err(key_id_insertion_error) =
+          <I as KeyIdStorage>::insert_key_id(&storage.key_id_storage(), old_method_digest.clone(), old_key_id.clone())
+            .await
+            .map_err(Error::KeyIdStorageError)
+        {
+          return Err(Error::UndoOperationFailed {
+            message: format!("cannot revert key id deletion: this results in stray key with key id: {old_key_id}"),
+            source: Box::new(error),
+            undo_error: Some(Box::new(key_id_insertion_error)),
+          });
+        }
+        return Err(try_undo_method_rotation(storage, &new_method_digest, &key_id, error).await);
+      }
+
+      Ok(fragment)
+    }
+  };
+}
+
 // ====================================================================================================================
 // CoreDocument
 // ====================================================================================================================
 
 generate_method_for_document_type!(CoreDocument, generate_method_core_document);
 purge_method_for_document_type!(CoreDocument, purge_method_core_document);
+rotate_method_for_document_type!(CoreDocument, rotate_method_core_document);
 
 #[cfg_attr(not(feature = "send-sync-storage"), async_trait(?Send))]
 #[cfg_attr(feature = "send-sync-storage", async_trait)]
@@ -333,6 +562,22 @@ impl JwkDocumentExt for CoreDocument {
     purge_method_core_document(self, storage, id).await
   }
 
+  async fn rotate_method<K, I>(
+    &mut self,
+    storage: &Storage<K, I>,
+    id: &DIDUrl,
+    key_type: KeyType,
+    alg: JwsAlgorithm,
+    fragment: Option<&str>,
+    options: &MethodRotationOptions,
+  ) -> StorageResult<String>
+  where
+    K: JwkStorage,
+    I: KeyIdStorage,
+  {
+    rotate_method_core_document(self, storage, id, key_type, alg, fragment, options).await
+  }
+
   async fn create_jws<K, I>(
     &self,
     storage: &Storage<K, I>,
@@ -500,12 +745,117 @@ impl JwkDocumentExt for CoreDocument {
       .await
       .map(|jws| Jwt::new(jws.into()))
   }
+
+  #[cfg(feature = "data-integrity")]
+  async fn create_credential_data_integrity_proof<K, I, T>(
+    &self,
+    credential: &Credential<T>,
+    storage: &Storage<K, I>,
+    fragment: &str,
+    options: &DataIntegrityProofOptions,
+  ) -> StorageResult<Proof>
+  where
+    K: JwkStorage,
+    I: KeyIdStorage,
+    T: Serialize + Sync,
+  {
+    create_eddsa_jcs_2022_proof(
+      self,
+      credential,
+      storage,
+      fragment,
+      ProofPurpose::AssertionMethod,
+      options,
+    )
+    .await
+  }
+
+  #[cfg(feature = "data-integrity")]
+  async fn create_presentation_data_integrity_proof<K, I, CRED, T>(
+    &self,
+    presentation: &Presentation<CRED, T>,
+    storage: &Storage<K, I>,
+    fragment: &str,
+    options: &DataIntegrityProofOptions,
+  ) -> StorageResult<Proof>
+  where
+    K: JwkStorage,
+    I: KeyIdStorage,
+    T: Serialize + Sync,
+    CRED: Serialize + Sync,
+  {
+    create_eddsa_jcs_2022_proof(
+      self,
+      presentation,
+      storage,
+      fragment,
+      ProofPurpose::Authentication,
+      options,
+    )
+    .await
+  }
+}
+
+#[cfg(feature = "data-integrity")]
+/// Signs `document` with the `eddsa-jcs-2022` cryptosuite using the key material of the method identified by
+/// `fragment` in `signer`.
+async fn create_eddsa_jcs_2022_proof<K, I, T>(
+  signer: &CoreDocument,
+  document: &T,
+  storage: &Storage<K, I>,
+  fragment: &str,
+  proof_purpose: ProofPurpose,
+  options: &DataIntegrityProofOptions,
+) -> StorageResult<Proof>
+where
+  K: JwkStorage,
+  I: KeyIdStorage,
+  T: Serialize + ?Sized,
+{
+  // Obtain the method corresponding to the given fragment.
+  let method: &VerificationMethod = signer.resolve_method(fragment, None).ok_or(Error::MethodNotFound)?;
+  let MethodData::PublicKeyJwk(ref jwk) = method.data() else {
+    return Err(Error::NotPublicKeyJwk);
+  };
+  // The cryptosuite only supports Ed25519 keys, whose `alg` is optional.
+  let is_ed25519: bool = jwk
+    .try_okp_params()
+    .map_or(false, |params| params.crv == EdCurve::Ed25519.name());
+  if !is_ed25519 || jwk.alg().map_or(false, |alg| alg != JwsAlgorithm::EdDSA.name()) {
+    return Err(Error::InvalidJwsAlgorithm);
+  }
+  // Storages select the signature algorithm from the `alg` of the public key.
+  let jwk: Cow<'_, Jwk> = if jwk.alg().is_some() {
+    Cow::Borrowed(jwk)
+  } else {
+    let mut jwk: Jwk = jwk.clone();
+    jwk.set_alg(JwsAlgorithm::EdDSA.name());
+    Cow::Owned(jwk)
+  };
+
+  let (unsecured_document, _) = EddsaJcs2022::unsecure(document).map_err(Error::DataIntegrityProofError)?;
+  let mut proof: DataIntegrityProof =
+    EddsaJcs2022::create_proof_configuration(&unsecured_document, method.id(), proof_purpose, options)
+      .map_err(Error::DataIntegrityProofError)?;
+  let hash_data = EddsaJcs2022::hash_data(&unsecured_document, &proof).map_err(Error::DataIntegrityProofError)?;
+
+  // Get the key identifier corresponding to the given method from the KeyId storage.
+  let method_digest: MethodDigest = MethodDigest::new(method).map_err(Error::MethodDigestConstructionError)?;
+  let key_id = <I as KeyIdStorage>::get_key_id(storage.key_id_storage(), &method_digest)
+    .await
+    .map_err(Error::KeyIdStorageError)?;
+  let signature = <K as JwkStorage>::sign(storage.key_storage(), &key_id, &hash_data, &jwk)
+    .await
+    .map_err(Error::KeyStorageError)?;
+
+  proof.proof_value = Some(EddsaJcs2022::encode_proof_value(&signature));
+  Ok(proof.into())
 }
 
 /// Attempt to revert key generation. If this succeeds the original `source_error` is returned,
 /// otherwise [`JwkStorageDocumentError::UndoOperationFailed`] is returned with the `source_error` attached as
 /// `source`.
-async fn try_undo_key_generation<K, I>(storage: &Storage<K, I>, key_id: &KeyId, source_error: Error) -> Error
+pub(super) async fn try_undo_key_generation<K, I>(storage: &Storage<K, I>, key_id: &KeyId, source_error: Error) -> Error
 where
   K: JwkStorage,
   I: KeyIdStorage,
@@ -522,6 +872,62 @@ where
   }
 }
 
+/// Attempt to revert a method rotation by deleting the key id stored under `method_digest` and the key material
+/// generated for the new method. If this succeeds the original `source_error` is returned, otherwise
+/// [`JwkStorageDocumentError::UndoOperationFailed`] is returned with the `source_error` attached as `source`.
+async fn try_undo_method_rotation<K, I>(
+  storage: &Storage<K, I>,
+  method_digest: &MethodDigest,
+  key_id: &KeyId,
+  source_error: Error,
+) -> Error
+where
+  K: JwkStorage,
+  I: KeyIdStorage,
+{
+  if let Err(err) = <I as KeyIdStorage>::delete_key_id(storage.key_id_storage(), method_digest).await {
+    Error::UndoOperationFailed {
+      message: format!(
+        "unable to delete stray key id stored under packed method digest: {:?}",
+        method_digest.pack()
+      ),
+      source: Box::new(source_error),
+      undo_error: Some(Box::new(Error::KeyIdStorageError(err))),
+    }
+  } else {
+    try_undo_key_generation(storage, key_id, source_error).await
+  }
+}
+
+/// Returns the relationship in which the method identified by `id` is embedded, if any, and the relationships in
+/// which it is referenced.
+fn method_relationships(document: &CoreDocument, id: &DIDUrl) -> (Option<MethodRelationship>, Vec<MethodRelationship>) {
+  let mut embedded_in: Option<MethodRelationship> = None;
+  let mut referenced_in: Vec<MethodRelationship> = Vec::new();
+
+  for (relationship, method_refs) in [
+    (MethodRelationship::Authentication, document.authentication()),
+    (MethodRelationship::AssertionMethod, document.assertion_method()),
+    (MethodRelationship::KeyAgreement, document.key_agreement()),
+    (
+      MethodRelationship::CapabilityDelegation,
+      document.capability_delegation(),
+    ),
+    (
+      MethodRelationship::CapabilityInvocation,
+      document.capability_invocation(),
+    ),
+  ] {
+    match method_refs.iter().find(|method_ref| method_ref.id() == id) {
+      Some(MethodRef::Embed(_)) => embedded_in = Some(relationship),
+      Some(MethodRef::Refer(_)) => referenced_in.push(relationship),
+      None => (),
+    }
+  }
+
+  (embedded_in, referenced_in)
+}
+
 // ====================================================================================================================
 // IotaDocument
 // ====================================================================================================================
@@ -533,6 +939,7 @@ mod iota_document {
 
   generate_method_for_document_type!(IotaDocument, generate_method_iota_document);
   purge_method_for_document_type!(IotaDocument, purge_method_iota_document);
+  rotate_method_for_document_type!(IotaDocument, rotate_method_iota_document);
 
   #[cfg_attr(not(feature = "send-sync-storage"), async_trait(?Send))]
   #[cfg_attr(feature = "send-sync-storage", async_trait)]
@@ -560,6 +967,22 @@ mod iota_document {
       purge_method_iota_document(self, storage, id).await
     }
 
+    async fn rotate_method<K, I>(
+      &mut self,
+      storage: &Storage<K, I>,
+      id: &DIDUrl,
+      key_type: KeyType,
+      alg: JwsAlgorithm,
+      fragment: Option<&str>,
+      options: &MethodRotationOptions,
+    ) -> StorageResult<String>
+    where
+      K: JwkStorage,
+      I: KeyIdStorage,
+    {
+      rotate_method_iota_document(self, storage, id, key_type, alg, fragment, options).await
+    }
+
     async fn create_jws<K, I>(
       &self,
       storage: &Storage<K, I>,
@@ -614,5 +1037,44 @@ mod iota_document {
         .create_presentation_jwt(presentation, storage, fragment, options, jwt_options)
         .await
     }
+
+    #[cfg(feature = "data-integrity")]
+    async fn create_credential_data_integrity_proof<K, I, T>(
+      &self,
+      credential: &Credential<T>,
+      storage: &Storage<K, I>,
+      fragment: &str,
+      options: &DataIntegrityProofOptions,
+    ) -> StorageResult<Proof>
+    where
+      K: JwkStorage,
+      I: KeyIdStorage,
+      T: Serialize + Sync,
+    {
+      self
+        .core_document()
+        .create_credential_data_integrity_proof(credential, storage, fragment, options)
+        .await
+    }
+
+    #[cfg(feature = "data-integrity")]
+    async fn create_presentation_data_integrity_proof<K, I, CRED, T>(
+      &self,
+      presentation: &Presentation<CRED, T>,
+      storage: &Storage<K, I>,
+      fragment: &str,
+      options: &DataIntegrityProofOptions,
+    ) -> StorageResult<Proof>
+    where
+      K: JwkStorage,
+      I: KeyIdStorage,
+      T: Serialize + Sync,
+      CRED: Serialize + Sync,
+    {
+      self
+        .core_document()
+        .create_presentation_data_integrity_proof(presentation, storage, fragment, options)
+        .await
+    }
   }
 }
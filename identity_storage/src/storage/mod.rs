@@ -3,14 +3,17 @@
 
 //! This module provides a type wrapping a key and key id storage.
 
+mod did_jwk;
 mod error;
 mod jwk_document_ext;
+mod rotation_options;
 mod signature_options;
 #[cfg(all(test, feature = "memstore"))]
 pub(crate) mod tests;
 
 pub use error::*;
 pub use jwk_document_ext::*;
+pub use rotation_options::*;
 pub use signature_options::*;
 
 /// A type wrapping a key and key id storage, typically used with [`JwkStorage`](crate::key_storage::JwkStorage) and
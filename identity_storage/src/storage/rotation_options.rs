@@ -0,0 +1,38 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Timestamp;
+
+/// Options for rotating the key material of a verification method with
+/// [`JwkDocumentExt::rotate_method`](crate::storage::JwkDocumentExt::rotate_method).
+#[non_exhaustive]
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize, Eq, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+#[serde(default)]
+pub struct MethodRotationOptions {
+  /// If set, the old method is retired instead of being purged: it is kept in the document as a general purpose
+  /// verification method without any verification relationships, and its key material is kept in storage. The
+  /// [`MethodRotationOptions::RETIRED_UNTIL_PROPERTY`] of the retired method is set to the given timestamp, marking
+  /// the end of the grace period during which signatures produced with the old key may still be verified.
+  ///
+  /// Retired methods are not removed automatically and should be purged with
+  /// [`JwkDocumentExt::purge_method`](crate::storage::JwkDocumentExt::purge_method) once the grace period is over.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub retire_until: Option<Timestamp>,
+}
+
+impl MethodRotationOptions {
+  /// The property set on a retired method, holding the end of its grace period.
+  pub const RETIRED_UNTIL_PROPERTY: &'static str = "expires";
+
+  /// Creates a new [`MethodRotationOptions`].
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Replace the value of the `retire_until` field.
+  pub fn retire_until(mut self, value: Timestamp) -> Self {
+    self.retire_until = Some(value);
+    self
+  }
+}
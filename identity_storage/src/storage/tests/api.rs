@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use identity_core::common::Object;
+use identity_core::common::Timestamp;
 use identity_core::common::Url;
 use identity_core::convert::FromJson;
 use identity_credential::credential::Credential;
@@ -11,6 +12,7 @@ use identity_did::DIDUrl;
 use identity_did::DID;
 use identity_document::document::CoreDocument;
 use identity_document::verifiable::JwsVerificationOptions;
+use identity_ecdsa_verifier::CompositeJwsVerifier;
 use identity_eddsa_verifier::EdDSAJwsVerifier;
 use identity_verification::jose::jws::JwsAlgorithm;
 use identity_verification::jwk::Jwk;
@@ -21,7 +23,11 @@ use identity_verification::MethodScope;
 
 use crate::key_id_storage::KeyIdMemstore;
 use crate::key_storage::JwkMemStore;
+use crate::key_storage::KeyType;
+use crate::storage::tests::test_utils::SIGNATURE_ALGORITHMS;
+use crate::storage::JwkStorageDocumentError;
 use crate::storage::JwsSignatureOptions;
+use crate::storage::MethodRotationOptions;
 
 use crate::storage::JwkDocumentExt;
 use crate::Storage;
@@ -48,17 +54,15 @@ fn setup() -> (CoreDocument, MemStorage) {
 }
 
 async fn setup_with_method() -> (CoreDocument, MemStorage, String) {
+  setup_with_method_and_alg(JwkMemStore::ED25519_KEY_TYPE, JwsAlgorithm::EdDSA).await
+}
+
+async fn setup_with_method_and_alg(key_type: KeyType, alg: JwsAlgorithm) -> (CoreDocument, MemStorage, String) {
   let mut mock_document = CoreDocument::from_json(MOCK_DOCUMENT_JSON).unwrap();
   let storage = Storage::new(JwkMemStore::new(), KeyIdMemstore::new());
 
   let method_fragment: String = mock_document
-    .generate_method(
-      &storage,
-      JwkMemStore::ED25519_KEY_TYPE,
-      JwsAlgorithm::EdDSA,
-      None,
-      MethodScope::VerificationMethod,
-    )
+    .generate_method(&storage, key_type, alg, None, MethodScope::VerificationMethod)
     .await
     .unwrap();
 
@@ -107,20 +111,27 @@ async fn generation() {
 
 #[tokio::test]
 async fn create_jws() {
-  let (document, storage, fragment) = setup_with_method().await;
+  for (key_type, alg) in SIGNATURE_ALGORITHMS {
+    let (document, storage, fragment) = setup_with_method_and_alg(key_type, alg).await;
 
-  let payload: &[u8] = b"test";
-  let signature_options: JwsSignatureOptions = JwsSignatureOptions::new();
-  let verification_options: JwsVerificationOptions = JwsVerificationOptions::new();
+    let payload: &[u8] = b"test";
+    let signature_options: JwsSignatureOptions = JwsSignatureOptions::new();
+    let verification_options: JwsVerificationOptions = JwsVerificationOptions::new();
 
-  let jws: Jws = document
-    .create_jws(&storage, &fragment, payload, &signature_options)
-    .await
-    .unwrap();
+    let jws: Jws = document
+      .create_jws(&storage, &fragment, payload, &signature_options)
+      .await
+      .unwrap();
 
-  assert!(document
-    .verify_jws(jws.as_str(), None, &EdDSAJwsVerifier::default(), &verification_options)
-    .is_ok());
+    assert!(document
+      .verify_jws(
+        jws.as_str(),
+        None,
+        &CompositeJwsVerifier::default(),
+        &verification_options
+      )
+      .is_ok());
+  }
 }
 
 #[tokio::test]
@@ -380,6 +391,209 @@ async fn purging() {
   assert_eq!(storage.key_storage().count().await, 0);
 }
 
+#[tokio::test]
+async fn rotation() {
+  let (mut document, storage) = setup();
+  let old_fragment: String = document
+    .generate_method(
+      &storage,
+      JwkMemStore::ED25519_KEY_TYPE,
+      JwsAlgorithm::EdDSA,
+      None,
+      MethodScope::VerificationMethod,
+    )
+    .await
+    .unwrap();
+  let old_method_id: DIDUrl = document.resolve_method(&old_fragment, None).unwrap().id().to_owned();
+  for relationship in [MethodRelationship::Authentication, MethodRelationship::AssertionMethod] {
+    document
+      .attach_method_relationship(&old_method_id, relationship)
+      .unwrap();
+  }
+
+  let new_fragment: String = document
+    .rotate_method(
+      &storage,
+      &old_method_id,
+      JwkMemStore::P256_KEY_TYPE,
+      JwsAlgorithm::ES256,
+      Some("#key-2"),
+      &MethodRotationOptions::new(),
+    )
+    .await
+    .unwrap();
+  assert_eq!(new_fragment, "key-2");
+
+  // The old method and its key material are gone.
+  assert!(document.resolve_method(&old_fragment, None).is_none());
+  assert_eq!(storage.key_id_storage().count().await, 1);
+  assert_eq!(storage.key_storage().count().await, 1);
+
+  // The new method took over all relationships of the old method.
+  assert!(document
+    .resolve_method(&new_fragment, Some(MethodScope::VerificationMethod))
+    .is_some());
+  for scope in [MethodScope::authentication(), MethodScope::assertion_method()] {
+    assert!(document.resolve_method(&new_fragment, Some(scope)).is_some());
+  }
+
+  let jws: Jws = document
+    .create_jws(&storage, &new_fragment, b"test", &JwsSignatureOptions::new())
+    .await
+    .unwrap();
+  assert!(document
+    .verify_jws(
+      jws.as_str(),
+      None,
+      &CompositeJwsVerifier::default(),
+      &JwsVerificationOptions::new()
+    )
+    .is_ok());
+}
+
+#[tokio::test]
+async fn rotation_of_embedded_method() {
+  let (mut document, storage) = setup();
+  let old_fragment: String = document
+    .generate_method(
+      &storage,
+      JwkMemStore::ED25519_KEY_TYPE,
+      JwsAlgorithm::EdDSA,
+      Some("#key-1"),
+      MethodScope::assertion_method(),
+    )
+    .await
+    .unwrap();
+  let old_method_id: DIDUrl = document.resolve_method(&old_fragment, None).unwrap().id().to_owned();
+
+  // The fragment of the old method can be reused.
+  let new_fragment: String = document
+    .rotate_method(
+      &storage,
+      &old_method_id,
+      JwkMemStore::ED25519_KEY_TYPE,
+      JwsAlgorithm::EdDSA,
+      Some(&old_fragment),
+      &MethodRotationOptions::new(),
+    )
+    .await
+    .unwrap();
+  assert_eq!(new_fragment, old_fragment);
+
+  assert!(document
+    .resolve_method(&new_fragment, Some(MethodScope::assertion_method()))
+    .is_some());
+  assert!(document
+    .resolve_method(&new_fragment, Some(MethodScope::VerificationMethod))
+    .is_none());
+  assert_eq!(storage.key_id_storage().count().await, 1);
+  assert_eq!(storage.key_storage().count().await, 1);
+}
+
+#[tokio::test]
+async fn rotation_with_retirement() {
+  let (mut document, storage) = setup();
+  let old_fragment: String = document
+    .generate_method(
+      &storage,
+      JwkMemStore::ED25519_KEY_TYPE,
+      JwsAlgorithm::EdDSA,
+      None,
+      MethodScope::assertion_method(),
+    )
+    .await
+    .unwrap();
+  let old_method_id: DIDUrl = document.resolve_method(&old_fragment, None).unwrap().id().to_owned();
+
+  let retire_until: Timestamp = Timestamp::parse("2030-01-01T00:00:00Z").unwrap();
+  let new_fragment: String = document
+    .rotate_method(
+      &storage,
+      &old_method_id,
+      JwkMemStore::ED25519_KEY_TYPE,
+      JwsAlgorithm::EdDSA,
+      None,
+      &MethodRotationOptions::new().retire_until(retire_until),
+    )
+    .await
+    .unwrap();
+
+  // The retired method is kept without any relationships, along with its key material.
+  let retired_method = document
+    .resolve_method(&old_fragment, Some(MethodScope::VerificationMethod))
+    .unwrap();
+  assert_eq!(
+    retired_method
+      .properties()
+      .get(MethodRotationOptions::RETIRED_UNTIL_PROPERTY)
+      .unwrap(),
+    &retire_until.to_rfc3339()
+  );
+  assert!(document
+    .resolve_method(&old_fragment, Some(MethodScope::assertion_method()))
+    .is_none());
+  assert!(document
+    .resolve_method(&new_fragment, Some(MethodScope::assertion_method()))
+    .is_some());
+  assert_eq!(storage.key_id_storage().count().await, 2);
+  assert_eq!(storage.key_storage().count().await, 2);
+
+  // The retired method can be purged once the grace period is over.
+  document.purge_method(&storage, &old_method_id).await.unwrap();
+  assert_eq!(storage.key_id_storage().count().await, 1);
+  assert_eq!(storage.key_storage().count().await, 1);
+}
+
+#[tokio::test]
+async fn rotation_rollback() {
+  let (mut document, storage) = setup();
+  let old_fragment: String = document
+    .generate_method(
+      &storage,
+      JwkMemStore::ED25519_KEY_TYPE,
+      JwsAlgorithm::EdDSA,
+      None,
+      MethodScope::assertion_method(),
+    )
+    .await
+    .unwrap();
+  let old_method_id: DIDUrl = document.resolve_method(&old_fragment, None).unwrap().id().to_owned();
+  let original_document: CoreDocument = document.clone();
+
+  // INVALID: The new fragment is already in use by another method.
+  let err = document
+    .rotate_method(
+      &storage,
+      &old_method_id,
+      JwkMemStore::ED25519_KEY_TYPE,
+      JwsAlgorithm::EdDSA,
+      Some("#root"),
+      &MethodRotationOptions::new(),
+    )
+    .await
+    .unwrap_err();
+  assert!(matches!(err, JwkStorageDocumentError::FragmentAlreadyExists));
+
+  // INVALID: A retired method cannot share its fragment with the new method.
+  let err = document
+    .rotate_method(
+      &storage,
+      &old_method_id,
+      JwkMemStore::ED25519_KEY_TYPE,
+      JwsAlgorithm::EdDSA,
+      Some(&old_fragment),
+      &MethodRotationOptions::new().retire_until(Timestamp::now_utc()),
+    )
+    .await
+    .unwrap_err();
+  assert!(matches!(err, JwkStorageDocumentError::FragmentAlreadyExists));
+
+  // Neither the document nor the storage were altered.
+  assert_eq!(document, original_document);
+  assert_eq!(storage.key_id_storage().count().await, 1);
+  assert_eq!(storage.key_storage().count().await, 1);
+}
+
 #[cfg(feature = "iota-document")]
 mod iota_document_tests {
   // Write a single test for the IotaDocument case just to check that it works
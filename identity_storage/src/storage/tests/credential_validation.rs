@@ -20,7 +20,7 @@ use identity_did::DID;
 use identity_document::document::CoreDocument;
 use identity_document::service::Service;
 use identity_document::verifiable::JwsVerificationOptions;
-use identity_eddsa_verifier::EdDSAJwsVerifier;
+use identity_ecdsa_verifier::CompositeJwsVerifier;
 use once_cell::sync::Lazy;
 
 use crate::storage::tests::test_utils;
@@ -29,8 +29,8 @@ use crate::storage::tests::test_utils::Setup;
 use crate::storage::JwkDocumentExt;
 use crate::storage::JwsSignatureOptions;
 
-static JWT_CREDENTIAL_VALIDATOR_ED25519: Lazy<JwtCredentialValidator<EdDSAJwsVerifier>> =
-  Lazy::new(|| JwtCredentialValidator::with_signature_verifier(EdDSAJwsVerifier::default()));
+static JWT_CREDENTIAL_VALIDATOR: Lazy<JwtCredentialValidator<CompositeJwsVerifier>> =
+  Lazy::new(|| JwtCredentialValidator::with_signature_verifier(CompositeJwsVerifier::default()));
 
 async fn invalid_expiration_or_issuance_date_impl<T>(setup: Setup<T, T>)
 where
@@ -72,7 +72,7 @@ where
       .earliest_expiry_date(expires_on_or_after);
 
     // validate and extract the nested error according to our expectations
-    let validation_errors = JWT_CREDENTIAL_VALIDATOR_ED25519
+    let validation_errors = JWT_CREDENTIAL_VALIDATOR
       .validate::<_, Object>(&jws, &issuer_doc, &options, FailFast::FirstError)
       .unwrap_err()
       .validation_errors;
@@ -95,7 +95,7 @@ where
       .earliest_expiry_date(expires_on_or_after);
 
     // validate and extract the nested error according to our expectations
-    let validation_errors = JWT_CREDENTIAL_VALIDATOR_ED25519
+    let validation_errors = JWT_CREDENTIAL_VALIDATOR
       .validate::<_, Object>(&jws, &issuer_doc, &options, FailFast::FirstError)
       .unwrap_err()
       .validation_errors;
@@ -111,8 +111,14 @@ where
 
 #[tokio::test]
 async fn invalid_expiration_or_issuance_date() {
-  invalid_expiration_or_issuance_date_impl(test_utils::setup_coredocument(None, None).await).await;
-  invalid_expiration_or_issuance_date_impl(test_utils::setup_iotadocument(None, None).await).await;
+  for (key_type, alg) in test_utils::SIGNATURE_ALGORITHMS {
+    invalid_expiration_or_issuance_date_impl(
+      test_utils::setup_coredocument_with_alg(key_type.clone(), alg, None, None).await,
+    )
+    .await;
+    invalid_expiration_or_issuance_date_impl(test_utils::setup_iotadocument_with_alg(key_type, alg, None, None).await)
+      .await;
+  }
 }
 
 async fn full_validation_impl<T>(setup: Setup<T, T>)
@@ -150,15 +156,17 @@ where
   let options = JwtCredentialValidationOptions::default()
     .latest_issuance_date(issued_on_or_before)
     .earliest_expiry_date(expires_on_or_after);
-  assert!(JWT_CREDENTIAL_VALIDATOR_ED25519
+  assert!(JWT_CREDENTIAL_VALIDATOR
     .validate::<_, Object>(&jwt, &issuer_doc, &options, FailFast::FirstError)
     .is_ok());
 }
 
 #[tokio::test]
 async fn full_validation() {
-  full_validation_impl(test_utils::setup_coredocument(None, None).await).await;
-  full_validation_impl(test_utils::setup_iotadocument(None, None).await).await;
+  for (key_type, alg) in test_utils::SIGNATURE_ALGORITHMS {
+    full_validation_impl(test_utils::setup_coredocument_with_alg(key_type.clone(), alg, None, None).await).await;
+    full_validation_impl(test_utils::setup_iotadocument_with_alg(key_type, alg, None, None).await).await;
+  }
 }
 
 async fn matches_issuer_did_unrelated_issuer_impl<T>(setup: Setup<T, T>)
@@ -189,7 +197,7 @@ where
 
   // the credential was not signed by this issuer
   // check that `verify_signature` returns the expected error
-  let error = JWT_CREDENTIAL_VALIDATOR_ED25519
+  let error = JWT_CREDENTIAL_VALIDATOR
     .verify_signature::<_, Object>(&jwt, &[&subject_doc], &JwsVerificationOptions::default())
     .unwrap_err();
 
@@ -199,7 +207,7 @@ where
   let options = JwtCredentialValidationOptions::default();
 
   // validate and extract the nested error according to our expectations
-  let validation_errors = JWT_CREDENTIAL_VALIDATOR_ED25519
+  let validation_errors = JWT_CREDENTIAL_VALIDATOR
     .validate::<_, Object>(&jwt, &subject_doc, &options, FailFast::FirstError)
     .unwrap_err()
     .validation_errors;
@@ -214,8 +222,14 @@ where
 
 #[tokio::test]
 async fn matches_issuer_did_unrelated_issuer() {
-  matches_issuer_did_unrelated_issuer_impl(test_utils::setup_coredocument(None, None).await).await;
-  matches_issuer_did_unrelated_issuer_impl(test_utils::setup_iotadocument(None, None).await).await;
+  for (key_type, alg) in test_utils::SIGNATURE_ALGORITHMS {
+    matches_issuer_did_unrelated_issuer_impl(
+      test_utils::setup_coredocument_with_alg(key_type.clone(), alg, None, None).await,
+    )
+    .await;
+    matches_issuer_did_unrelated_issuer_impl(test_utils::setup_iotadocument_with_alg(key_type, alg, None, None).await)
+      .await;
+  }
 }
 
 async fn verify_invalid_signature_impl<T>(setup: Setup<T, T>, other_setup: Setup<T, T>, fragment: &'static str)
@@ -252,7 +266,7 @@ where
     .await
     .unwrap();
 
-  let err = JWT_CREDENTIAL_VALIDATOR_ED25519
+  let err = JWT_CREDENTIAL_VALIDATOR
     .verify_signature::<_, Object>(&jwt, &[&issuer_doc], &JwsVerificationOptions::default())
     .unwrap_err();
 
@@ -268,7 +282,7 @@ where
     .earliest_expiry_date(expires_on_or_after);
 
   // validate and extract the nested error according to our expectations
-  let validation_errors = JWT_CREDENTIAL_VALIDATOR_ED25519
+  let validation_errors = JWT_CREDENTIAL_VALIDATOR
     .validate::<_, Object>(&jwt, &issuer_doc, &options, FailFast::FirstError)
     .unwrap_err()
     .validation_errors;
@@ -286,18 +300,20 @@ where
 async fn verify_invalid_signature() {
   // Ensure the fragment is the same on both documents so we can produce the signature verification error.
   let fragment = "signing-key";
-  verify_invalid_signature_impl(
-    test_utils::setup_coredocument(Some(fragment), None).await,
-    test_utils::setup_coredocument(Some(fragment), None).await,
-    fragment,
-  )
-  .await;
-  verify_invalid_signature_impl(
-    test_utils::setup_iotadocument(Some(fragment), None).await,
-    test_utils::setup_iotadocument(Some(fragment), None).await,
-    fragment,
-  )
-  .await;
+  for (key_type, alg) in test_utils::SIGNATURE_ALGORITHMS {
+    verify_invalid_signature_impl(
+      test_utils::setup_coredocument_with_alg(key_type.clone(), alg, Some(fragment), None).await,
+      test_utils::setup_coredocument_with_alg(key_type.clone(), alg, Some(fragment), None).await,
+      fragment,
+    )
+    .await;
+    verify_invalid_signature_impl(
+      test_utils::setup_iotadocument_with_alg(key_type.clone(), alg, Some(fragment), None).await,
+      test_utils::setup_iotadocument_with_alg(key_type, alg, Some(fragment), None).await,
+      fragment,
+    )
+    .await;
+  }
 }
 
 fn check_status_impl<T, F>(setup: Setup<T, T>, insert_service: F)
@@ -378,12 +394,14 @@ where
 // Note: We don't test `IotaDocument` because it (intentionally) doesn't implement RevocationDocumentExt.
 #[tokio::test]
 async fn check_status() {
-  check_status_impl(
-    test_utils::setup_coredocument(None, None).await,
-    |document: &mut CoreDocument, service: Service| {
-      document.insert_service(service).unwrap();
-    },
-  );
+  for (key_type, alg) in test_utils::SIGNATURE_ALGORITHMS {
+    check_status_impl(
+      test_utils::setup_coredocument_with_alg(key_type, alg, None, None).await,
+      |document: &mut CoreDocument, service: Service| {
+        document.insert_service(service).unwrap();
+      },
+    );
+  }
 }
 
 async fn full_validation_fail_fast_impl<T, U>(setup: Setup<T, U>)
@@ -425,14 +443,14 @@ where
     .latest_issuance_date(issued_on_or_before)
     .earliest_expiry_date(expires_on_or_after);
 
-  let validation_errors = JWT_CREDENTIAL_VALIDATOR_ED25519
+  let validation_errors = JWT_CREDENTIAL_VALIDATOR
     .validate::<_, Object>(&jws, &issuer_doc, &options, FailFast::FirstError)
     .unwrap_err()
     .validation_errors;
 
   assert!(validation_errors.len() == 1);
 
-  let validation_errors = JWT_CREDENTIAL_VALIDATOR_ED25519
+  let validation_errors = JWT_CREDENTIAL_VALIDATOR
     .validate::<_, Object>(&jws, &issuer_doc, &options, FailFast::AllErrors)
     .unwrap_err()
     .validation_errors;
@@ -442,6 +460,9 @@ where
 
 #[tokio::test]
 async fn full_validation_fail_fast() {
-  full_validation_fail_fast_impl(test_utils::setup_coredocument(None, None).await).await;
-  full_validation_fail_fast_impl(test_utils::setup_iotadocument(None, None).await).await;
+  for (key_type, alg) in test_utils::SIGNATURE_ALGORITHMS {
+    full_validation_fail_fast_impl(test_utils::setup_coredocument_with_alg(key_type.clone(), alg, None, None).await)
+      .await;
+    full_validation_fail_fast_impl(test_utils::setup_iotadocument_with_alg(key_type, alg, None, None).await).await;
+  }
 }
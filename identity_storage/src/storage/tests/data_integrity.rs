@@ -0,0 +1,324 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Object;
+use identity_core::common::Timestamp;
+use identity_core::common::Value;
+use identity_core::convert::ToJson;
+use identity_credential::credential::Credential;
+use identity_credential::credential::Proof;
+use identity_credential::data_integrity::DataIntegrityError;
+use identity_credential::data_integrity::DataIntegrityProof;
+use identity_credential::data_integrity::DataIntegrityProofOptions;
+use identity_credential::data_integrity::EddsaJcs2022;
+use identity_credential::data_integrity::EddsaJcs2022Verifier;
+use identity_credential::data_integrity::ProofPurpose;
+use identity_credential::presentation::Presentation;
+use identity_credential::presentation::PresentationBuilder;
+use identity_credential::validator::CredentialValidationOptions;
+use identity_credential::validator::FailFast;
+use identity_credential::validator::LinkedDataCredentialValidator;
+use identity_credential::validator::ValidationError;
+use identity_did::DID;
+use identity_eddsa_verifier::EdDSAJwsVerifier;
+use identity_verification::jose::jwk::Jwk;
+use identity_verification::jose::jws::JwsAlgorithm;
+use identity_verification::MethodScope;
+use identity_verification::VerificationMethod;
+
+use crate::key_id_storage::KeyIdStorage;
+use crate::key_id_storage::MethodDigest;
+use crate::key_storage::JwkGenOutput;
+use crate::key_storage::JwkMemStore;
+use crate::key_storage::JwkStorage;
+use crate::storage::tests::test_utils;
+use crate::storage::tests::test_utils::CredentialSetup;
+use crate::storage::tests::test_utils::Setup;
+use crate::JwkDocumentExt;
+use crate::JwkStorageDocumentError;
+
+#[tokio::test]
+async fn credential_proof_roundtrip() {
+  let Setup {
+    issuer_doc,
+    subject_doc,
+    issuer_storage,
+    issuer_method_fragment,
+    ..
+  } = test_utils::setup_coredocument_with_alg(JwkMemStore::ED25519_KEY_TYPE, JwsAlgorithm::EdDSA, None, None).await;
+  let CredentialSetup { mut credential, .. } =
+    test_utils::generate_credential(&issuer_doc, &[&subject_doc], None, None);
+
+  let options = DataIntegrityProofOptions::new().created(Timestamp::parse("2023-02-24T23:36:38Z").unwrap());
+  credential.proof = Some(
+    issuer_doc
+      .create_credential_data_integrity_proof(&credential, &issuer_storage, &issuer_method_fragment, &options)
+      .await
+      .unwrap(),
+  );
+
+  let proof: DataIntegrityProof = DataIntegrityProof::try_from(credential.proof.as_ref().unwrap()).unwrap();
+  assert_eq!(proof.cryptosuite, EddsaJcs2022::CRYPTOSUITE);
+  assert_eq!(proof.proof_purpose, ProofPurpose::AssertionMethod);
+  assert_eq!(proof.created, options.created.map(|created| created.to_rfc3339()));
+  assert_eq!(
+    proof.verification_method,
+    issuer_doc
+      .resolve_method(issuer_method_fragment.as_str(), None)
+      .unwrap()
+      .id()
+      .to_string()
+  );
+
+  // The credential is verified as it is received by the verifier.
+  let mut credential_json: Value = credential.to_json_value().unwrap();
+  let verifier = EddsaJcs2022Verifier::with_signature_verifier(EdDSAJwsVerifier::default());
+  assert_eq!(
+    verifier.verify_credential(&credential_json, &[&issuer_doc]).unwrap(),
+    proof
+  );
+
+  // The proof does not verify against another issuer.
+  assert!(matches!(
+    verifier
+      .verify_credential(&credential_json, &[&subject_doc])
+      .unwrap_err(),
+    DataIntegrityError::SignerMismatch
+  ));
+
+  // Any change to the credential invalidates the proof.
+  credential_json["id"] = Value::from("https://example.edu/credentials/3733");
+  assert!(matches!(
+    verifier
+      .verify_credential(&credential_json, &[&issuer_doc])
+      .unwrap_err(),
+    DataIntegrityError::Signature(_)
+  ));
+
+  // Credentials without a proof fail.
+  credential_json.as_object_mut().unwrap().remove("proof");
+  assert!(matches!(
+    verifier
+      .verify_credential(&credential_json, &[&issuer_doc])
+      .unwrap_err(),
+    DataIntegrityError::MissingProof
+  ));
+}
+
+#[tokio::test]
+async fn presentation_proof_roundtrip() {
+  let Setup {
+    issuer_doc,
+    mut subject_doc,
+    subject_storage,
+    ..
+  } = test_utils::setup_coredocument_with_alg(JwkMemStore::ED25519_KEY_TYPE, JwsAlgorithm::EdDSA, None, None).await;
+  let CredentialSetup { credential, .. } = test_utils::generate_credential(&issuer_doc, &[&subject_doc], None, None);
+
+  // Presentation proofs must be created with a method authorized for authentication.
+  let fragment: String = subject_doc
+    .generate_method(
+      &subject_storage,
+      JwkMemStore::ED25519_KEY_TYPE,
+      JwsAlgorithm::EdDSA,
+      None,
+      MethodScope::authentication(),
+    )
+    .await
+    .unwrap();
+
+  let mut presentation: Presentation<Credential> =
+    PresentationBuilder::new(subject_doc.id().to_url().into(), Object::new())
+      .credential(credential)
+      .build()
+      .unwrap();
+  presentation.proof = Some(
+    subject_doc
+      .create_presentation_data_integrity_proof(
+        &presentation,
+        &subject_storage,
+        &fragment,
+        &DataIntegrityProofOptions::new()
+          .challenge("475a7984-1bb5-4c4c-a56f-822bccd46440")
+          .domain("https://verifier.example"),
+      )
+      .await
+      .unwrap(),
+  );
+
+  let presentation: Value = presentation.to_json_value().unwrap();
+  let verifier = EddsaJcs2022Verifier::with_signature_verifier(EdDSAJwsVerifier::default());
+  let proof: DataIntegrityProof = verifier
+    .verify_presentation(
+      &presentation,
+      &[&subject_doc],
+      Some("475a7984-1bb5-4c4c-a56f-822bccd46440"),
+      Some("https://verifier.example"),
+    )
+    .unwrap();
+  assert_eq!(proof.proof_purpose, ProofPurpose::Authentication);
+
+  assert!(matches!(
+    verifier
+      .verify_presentation(&presentation, &[&subject_doc], Some("other-challenge"), None)
+      .unwrap_err(),
+    DataIntegrityError::ChallengeMismatch
+  ));
+  assert!(matches!(
+    verifier
+      .verify_presentation(&presentation, &[&subject_doc], None, Some("https://other.example"))
+      .unwrap_err(),
+    DataIntegrityError::DomainMismatch
+  ));
+
+  // A presentation proof is not valid as an assertion.
+  assert!(matches!(
+    verifier
+      .verify(&presentation, &[&subject_doc], ProofPurpose::AssertionMethod)
+      .unwrap_err(),
+    DataIntegrityError::ProofPurposeMismatch
+  ));
+}
+
+#[tokio::test]
+async fn proof_requires_ed25519_key() {
+  let Setup {
+    issuer_doc,
+    subject_doc,
+    issuer_storage,
+    issuer_method_fragment,
+    ..
+  } = test_utils::setup_coredocument_with_alg(JwkMemStore::P256_KEY_TYPE, JwsAlgorithm::ES256, None, None).await;
+  let CredentialSetup { credential, .. } = test_utils::generate_credential(&issuer_doc, &[&subject_doc], None, None);
+
+  assert!(matches!(
+    issuer_doc
+      .create_credential_data_integrity_proof(
+        &credential,
+        &issuer_storage,
+        &issuer_method_fragment,
+        &DataIntegrityProofOptions::default(),
+      )
+      .await
+      .unwrap_err(),
+    JwkStorageDocumentError::InvalidJwsAlgorithm
+  ));
+}
+
+#[tokio::test]
+async fn proof_with_ed25519_key_without_alg() {
+  let Setup {
+    mut issuer_doc,
+    subject_doc,
+    issuer_storage,
+    ..
+  } = test_utils::setup_coredocument_with_alg(JwkMemStore::ED25519_KEY_TYPE, JwsAlgorithm::EdDSA, None, None).await;
+  let CredentialSetup { credential, .. } = test_utils::generate_credential(&issuer_doc, &[&subject_doc], None, None);
+
+  // Insert a method whose public key does not declare an `alg`.
+  let JwkGenOutput { key_id, jwk } = issuer_storage
+    .key_storage()
+    .generate(JwkMemStore::ED25519_KEY_TYPE, JwsAlgorithm::EdDSA)
+    .await
+    .unwrap();
+  let method: VerificationMethod = VerificationMethod::new_from_jwk(
+    issuer_doc.id().clone(),
+    Jwk::from_params(jwk.params().clone()),
+    Some("no-alg"),
+  )
+  .unwrap();
+  assert!(method.data().try_public_key_jwk().unwrap().alg().is_none());
+  issuer_storage
+    .key_id_storage()
+    .insert_key_id(MethodDigest::new(&method).unwrap(), key_id)
+    .await
+    .unwrap();
+  issuer_doc
+    .insert_method(method, MethodScope::assertion_method())
+    .unwrap();
+
+  let proof: Proof = issuer_doc
+    .create_credential_data_integrity_proof(
+      &credential,
+      &issuer_storage,
+      "no-alg",
+      &DataIntegrityProofOptions::default(),
+    )
+    .await
+    .unwrap();
+  let mut credential: Credential = credential;
+  credential.proof = Some(proof);
+
+  let verifier = EddsaJcs2022Verifier::with_signature_verifier(EdDSAJwsVerifier::default());
+  assert!(verifier
+    .verify_credential(&credential.to_json_value().unwrap(), &[&issuer_doc])
+    .is_ok());
+}
+
+#[tokio::test]
+async fn validate_credential_with_embedded_proof() {
+  let Setup {
+    issuer_doc,
+    subject_doc,
+    issuer_storage,
+    issuer_method_fragment,
+    ..
+  } = test_utils::setup_coredocument_with_alg(JwkMemStore::ED25519_KEY_TYPE, JwsAlgorithm::EdDSA, None, None).await;
+  let CredentialSetup { mut credential, .. } = test_utils::generate_credential(
+    &issuer_doc,
+    &[&subject_doc],
+    None,
+    Some(Timestamp::parse("2100-01-01T00:00:00Z").unwrap()),
+  );
+  credential.proof = Some(
+    issuer_doc
+      .create_credential_data_integrity_proof(
+        &credential,
+        &issuer_storage,
+        &issuer_method_fragment,
+        &DataIntegrityProofOptions::default(),
+      )
+      .await
+      .unwrap(),
+  );
+
+  let credential: Value = credential.to_json_value().unwrap();
+
+  let validator = LinkedDataCredentialValidator::with_proof_suite(EddsaJcs2022Verifier::with_signature_verifier(
+    EdDSAJwsVerifier::default(),
+  ));
+  validator
+    .validate::<_, Object>(
+      &credential,
+      &issuer_doc,
+      &CredentialValidationOptions::default(),
+      FailFast::FirstError,
+    )
+    .unwrap();
+
+  // The proof is verified against the given issuer document.
+  let errors: Vec<ValidationError> = validator
+    .validate::<_, Object>(
+      &credential,
+      &subject_doc,
+      &CredentialValidationOptions::default(),
+      FailFast::AllErrors,
+    )
+    .unwrap_err()
+    .validation_errors;
+  assert!(matches!(errors.as_slice(), [ValidationError::DocumentMismatch { .. }]));
+
+  // A tampered credential fails proof verification.
+  let mut tampered: Value = credential.clone();
+  tampered["id"] = Value::from("https://example.edu/credentials/3733");
+  let errors: Vec<ValidationError> = validator
+    .validate::<_, Object>(
+      &tampered,
+      &issuer_doc,
+      &CredentialValidationOptions::default(),
+      FailFast::AllErrors,
+    )
+    .unwrap_err()
+    .validation_errors;
+  assert!(matches!(errors.as_slice(), [ValidationError::ProofVerification { .. }]));
+}
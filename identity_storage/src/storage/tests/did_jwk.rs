@@ -0,0 +1,80 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Duration;
+use identity_core::common::Object;
+use identity_credential::credential::Jwt;
+use identity_credential::validator::FailFast;
+use identity_credential::validator::JwtCredentialValidationOptions;
+use identity_credential::validator::JwtCredentialValidator;
+use identity_did::DIDJwk;
+use identity_document::document::CoreDocument;
+use identity_ecdsa_verifier::CompositeJwsVerifier;
+use identity_verification::jose::jws::JwsAlgorithm;
+
+use crate::key_id_storage::KeyIdMemstore;
+use crate::key_storage::JwkGenOutput;
+use crate::key_storage::JwkMemStore;
+use crate::key_storage::JwkStorage;
+use crate::storage::tests::test_utils;
+use crate::storage::tests::test_utils::CredentialSetup;
+use crate::storage::JwkDocumentExt;
+use crate::storage::JwkStorageDocumentError;
+use crate::storage::JwsSignatureOptions;
+use crate::Storage;
+
+#[tokio::test]
+async fn issue_and_verify_with_did_jwk() {
+  for (key_type, alg) in test_utils::SIGNATURE_ALGORITHMS {
+    let issuer_storage = Storage::new(JwkMemStore::new(), KeyIdMemstore::new());
+    let issuer_doc: CoreDocument = issuer_storage.generate_did_jwk(key_type.clone(), alg).await.unwrap();
+    let holder_storage = Storage::new(JwkMemStore::new(), KeyIdMemstore::new());
+    let holder_doc: CoreDocument = holder_storage.generate_did_jwk(key_type, alg).await.unwrap();
+
+    let CredentialSetup {
+      credential,
+      expiration_date,
+      ..
+    } = test_utils::generate_credential(&issuer_doc, &[&holder_doc], None, None);
+    let jwt: Jwt = issuer_doc
+      .create_credential_jwt(
+        &credential,
+        &issuer_storage,
+        CoreDocument::DID_JWK_FRAGMENT,
+        &JwsSignatureOptions::default(),
+        None,
+      )
+      .await
+      .unwrap();
+
+    // The verifier only needs the DID of the issuer to obtain its document.
+    let issuer_did: DIDJwk = DIDJwk::try_from_core(issuer_doc.id().clone()).unwrap();
+    let resolved_issuer_doc: CoreDocument = CoreDocument::expand_did_jwk(issuer_did).unwrap();
+    assert_eq!(resolved_issuer_doc, issuer_doc);
+
+    let options = JwtCredentialValidationOptions::default()
+      .earliest_expiry_date(expiration_date.checked_sub(Duration::hours(1)).unwrap());
+    JwtCredentialValidator::with_signature_verifier(CompositeJwsVerifier::default())
+      .validate::<_, Object>(&jwt, &resolved_issuer_doc, &options, FailFast::FirstError)
+      .unwrap();
+  }
+}
+
+#[tokio::test]
+async fn insert_did_jwk_of_existing_key() {
+  let storage = Storage::new(JwkMemStore::new(), KeyIdMemstore::new());
+  let output: JwkGenOutput = storage
+    .key_storage()
+    .generate(JwkMemStore::ED25519_KEY_TYPE, JwsAlgorithm::EdDSA)
+    .await
+    .unwrap();
+
+  let document: CoreDocument = storage.insert_did_jwk(&output).await.unwrap();
+  assert_eq!(document.id(), output.did_jwk().unwrap().as_ref());
+
+  // The key id of the method is already stored.
+  assert!(matches!(
+    storage.insert_did_jwk(&output).await.unwrap_err(),
+    JwkStorageDocumentError::KeyIdStorageError(_)
+  ));
+}
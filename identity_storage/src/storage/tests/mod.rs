@@ -4,6 +4,10 @@
 mod api;
 mod credential_jws;
 mod credential_validation;
+#[cfg(feature = "data-integrity")]
+mod data_integrity;
+mod did_jwk;
 mod kb_jwt;
+mod presentation_exchange;
 mod presentation_validation;
 pub(crate) mod test_utils;
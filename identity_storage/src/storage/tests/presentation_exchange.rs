@@ -0,0 +1,218 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Object;
+use identity_core::common::Timestamp;
+use identity_core::convert::FromJson;
+use identity_credential::credential::Jwt;
+use identity_credential::presentation::JwtPresentationOptions;
+use identity_credential::presentation::Presentation;
+use identity_credential::presentation::PresentationBuilder;
+use identity_credential::presentation_exchange::ClaimFormat;
+use identity_credential::presentation_exchange::InputDescriptorMapping;
+use identity_credential::presentation_exchange::InputDescriptorMatch;
+use identity_credential::presentation_exchange::PresentationDefinition;
+use identity_credential::presentation_exchange::PresentationExchangeError;
+use identity_credential::presentation_exchange::PresentationExchangeValidationOptions;
+use identity_credential::presentation_exchange::PresentationExchangeValidator;
+use identity_credential::presentation_exchange::PresentationSubmission;
+use identity_credential::validator::DecodedJwtPresentation;
+use identity_did::DID;
+use identity_document::document::CoreDocument;
+use identity_eddsa_verifier::EdDSAJwsVerifier;
+use identity_verification::jws::JwsAlgorithm;
+use identity_verification::jwu;
+use serde_json::json;
+use serde_json::Value;
+
+use crate::key_storage::JwkMemStore;
+use crate::storage::tests::test_utils::generate_credential;
+use crate::storage::tests::test_utils::setup_coredocument_with_alg;
+use crate::storage::tests::test_utils::CredentialSetup;
+use crate::storage::tests::test_utils::MemStorage;
+use crate::storage::tests::test_utils::Setup;
+use crate::JwkDocumentExt;
+use crate::JwsSignatureOptions;
+
+fn degree_definition(degree: &str) -> PresentationDefinition {
+  PresentationDefinition::from_json_value(json!({
+    "id": "university_degree_definition",
+    "input_descriptors": [
+      {
+        "id": "university_degree",
+        "format": { "jwt_vc_json": { "alg": ["EdDSA"] } },
+        "constraints": {
+          "fields": [
+            {
+              "path": ["$.vc.credentialSubject.degree.type"],
+              "filter": { "type": "string", "const": degree }
+            }
+          ]
+        }
+      }
+    ]
+  }))
+  .unwrap()
+}
+
+async fn create_presentation_jwt(
+  holder_doc: &CoreDocument,
+  holder_storage: &MemStorage,
+  holder_method_fragment: &str,
+  credential_jwt: Jwt,
+) -> Jwt {
+  let presentation: Presentation<Jwt> = PresentationBuilder::new(holder_doc.id().to_url().into(), Object::new())
+    .credential(credential_jwt)
+    .build()
+    .unwrap();
+  holder_doc
+    .create_presentation_jwt(
+      &presentation,
+      holder_storage,
+      holder_method_fragment,
+      &JwsSignatureOptions::default(),
+      &JwtPresentationOptions::default(),
+    )
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn presentation_exchange_roundtrip() {
+  let Setup {
+    issuer_doc,
+    subject_doc,
+    issuer_storage,
+    subject_storage,
+    issuer_method_fragment,
+    subject_method_fragment,
+  } = setup_coredocument_with_alg(JwkMemStore::ED25519_KEY_TYPE, JwsAlgorithm::EdDSA, None, None).await;
+  let CredentialSetup { credential, .. } = generate_credential(
+    &issuer_doc,
+    &[&subject_doc],
+    None,
+    Some(Timestamp::parse("2100-01-01T00:00:00Z").unwrap()),
+  );
+  let credential_jwt: Jwt = issuer_doc
+    .create_credential_jwt(
+      &credential,
+      &issuer_storage,
+      &issuer_method_fragment,
+      &JwsSignatureOptions::default(),
+      None,
+    )
+    .await
+    .unwrap();
+
+  // The holder selects the credentials matching the definition.
+  let definition: PresentationDefinition = degree_definition("BachelorDegree");
+  let matches: Vec<InputDescriptorMatch> = definition.match_candidates(&[&credential_jwt]).unwrap();
+  assert_eq!(matches[0].candidates, [0]);
+  // The credential itself is in an unknown format and lacks the JWT claims the definition's paths select.
+  assert!(definition.match_candidates(&[credential]).unwrap()[0]
+    .candidates
+    .is_empty());
+
+  let presentation_jwt: Jwt = create_presentation_jwt(
+    &subject_doc,
+    &subject_storage,
+    &subject_method_fragment,
+    credential_jwt.clone(),
+  )
+  .await;
+  let submission: PresentationSubmission = PresentationSubmission::new(
+    "submission",
+    definition.id.clone(),
+    vec![
+      InputDescriptorMapping::new("university_degree", ClaimFormat::JwtVp, "$").path_nested(
+        InputDescriptorMapping::new("university_degree", ClaimFormat::JwtVc, "$.vp.verifiableCredential[0]"),
+      ),
+    ],
+  );
+
+  // The verifier checks the presentation satisfies the definition.
+  let validator = PresentationExchangeValidator::with_signature_verifier(EdDSAJwsVerifier::default());
+  let decoded: DecodedJwtPresentation<Jwt> = validator
+    .validate::<_, _, Jwt, Object>(
+      &presentation_jwt,
+      &subject_doc,
+      &[&issuer_doc],
+      &definition,
+      &submission,
+      &PresentationExchangeValidationOptions::default(),
+    )
+    .unwrap();
+  assert_eq!(decoded.presentation.verifiable_credential[0], credential_jwt);
+
+  // The presentation must be signed by the holder.
+  assert!(matches!(
+    validator
+      .validate::<_, _, Jwt, Object>(
+        &presentation_jwt,
+        &issuer_doc,
+        &[&issuer_doc],
+        &definition,
+        &submission,
+        &PresentationExchangeValidationOptions::default(),
+      )
+      .unwrap_err(),
+    PresentationExchangeError::PresentationValidation(_)
+  ));
+
+  // The credential must be issued by a trusted issuer.
+  assert!(matches!(
+    validator
+      .validate::<_, _, Jwt, Object>(
+        &presentation_jwt,
+        &subject_doc,
+        &[&subject_doc],
+        &definition,
+        &submission,
+        &PresentationExchangeValidationOptions::default(),
+      )
+      .unwrap_err(),
+    PresentationExchangeError::CredentialValidation { .. }
+  ));
+
+  // The credential must satisfy the definition.
+  let other_definition: PresentationDefinition = degree_definition("MasterDegree");
+  assert!(matches!(
+    validator
+      .validate::<_, _, Jwt, Object>(
+        &presentation_jwt,
+        &subject_doc,
+        &[&issuer_doc],
+        &other_definition,
+        &submission,
+        &PresentationExchangeValidationOptions::default(),
+      )
+      .unwrap_err(),
+    PresentationExchangeError::InputDescriptorNotSatisfied(_)
+  ));
+
+  // A forged credential satisfying the definition is rejected.
+  let [header, claims, signature]: [&str; 3] = credential_jwt
+    .as_str()
+    .split('.')
+    .collect::<Vec<_>>()
+    .try_into()
+    .unwrap();
+  let mut claims: Value = serde_json::from_slice(&jwu::decode_b64(claims).unwrap()).unwrap();
+  claims["vc"]["credentialSubject"]["degree"]["type"] = json!("MasterDegree");
+  let forged_jwt: Jwt = Jwt::new(format!("{header}.{}.{signature}", jwu::encode_b64(claims.to_string())));
+  let forged_presentation_jwt: Jwt =
+    create_presentation_jwt(&subject_doc, &subject_storage, &subject_method_fragment, forged_jwt).await;
+  assert!(matches!(
+    validator
+      .validate::<_, _, Jwt, Object>(
+        &forged_presentation_jwt,
+        &subject_doc,
+        &[&issuer_doc],
+        &other_definition,
+        &submission,
+        &PresentationExchangeValidationOptions::default(),
+      )
+      .unwrap_err(),
+    PresentationExchangeError::CredentialValidation { .. }
+  ));
+}
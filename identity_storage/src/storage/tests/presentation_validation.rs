@@ -19,28 +19,31 @@ use identity_credential::validator::JwtValidationError;
 use identity_did::CoreDID;
 use identity_did::DID;
 use identity_document::document::CoreDocument;
-use identity_eddsa_verifier::EdDSAJwsVerifier;
+use identity_ecdsa_verifier::CompositeJwsVerifier;
 use identity_verification::jws::JwsAlgorithm;
 use identity_verification::MethodScope;
 use once_cell::sync::Lazy;
 
 use crate::key_storage::JwkMemStore;
 use crate::storage::tests::test_utils::generate_credential;
-use crate::storage::tests::test_utils::setup_coredocument;
-use crate::storage::tests::test_utils::setup_iotadocument;
+use crate::storage::tests::test_utils::setup_coredocument_with_alg;
+use crate::storage::tests::test_utils::setup_iotadocument_with_alg;
 use crate::storage::tests::test_utils::Setup;
+use crate::storage::tests::test_utils::SIGNATURE_ALGORITHMS;
 use crate::JwkDocumentExt;
 use crate::JwsSignatureOptions;
 
 use super::test_utils::CredentialSetup;
 
-static JWT_PRESENTATION_VALIDATOR_ED25519: Lazy<JwtPresentationValidator<EdDSAJwsVerifier>> =
-  Lazy::new(|| JwtPresentationValidator::with_signature_verifier(EdDSAJwsVerifier::default()));
+static JWT_PRESENTATION_VALIDATOR: Lazy<JwtPresentationValidator<CompositeJwsVerifier>> =
+  Lazy::new(|| JwtPresentationValidator::with_signature_verifier(CompositeJwsVerifier::default()));
 
 #[tokio::test]
 async fn test_valid_presentation() {
-  test_valid_presentation_impl(setup_coredocument(None, None).await).await;
-  test_valid_presentation_impl(setup_iotadocument(None, None).await).await;
+  for (key_type, alg) in SIGNATURE_ALGORITHMS {
+    test_valid_presentation_impl(setup_coredocument_with_alg(key_type.clone(), alg, None, None).await).await;
+    test_valid_presentation_impl(setup_iotadocument_with_alg(key_type, alg, None, None).await).await;
+  }
 }
 async fn test_valid_presentation_impl<T>(setup: Setup<T, T>)
 where
@@ -80,7 +83,7 @@ where
     .await
     .unwrap();
 
-  let decoded_presentation: DecodedJwtPresentation<Jwt> = JWT_PRESENTATION_VALIDATOR_ED25519
+  let decoded_presentation: DecodedJwtPresentation<Jwt> = JWT_PRESENTATION_VALIDATOR
     .validate::<_, Jwt, Object>(
       &presentation_jwt,
       &setup.subject_doc,
@@ -99,8 +102,10 @@ where
 
 #[tokio::test]
 async fn test_extract_holder() {
-  test_extract_holder_impl(setup_coredocument(None, None).await).await;
-  test_extract_holder_impl(setup_iotadocument(None, None).await).await;
+  for (key_type, alg) in SIGNATURE_ALGORITHMS {
+    test_extract_holder_impl(setup_coredocument_with_alg(key_type.clone(), alg, None, None).await).await;
+    test_extract_holder_impl(setup_iotadocument_with_alg(key_type, alg, None, None).await).await;
+  }
 }
 async fn test_extract_holder_impl<T>(setup: Setup<T, T>)
 where
@@ -142,7 +147,7 @@ where
   let holder = JwtPresentationValidatorUtils::extract_holder::<CoreDID>(&presentation_jwt).unwrap();
   assert_eq!(holder.to_url(), setup.subject_doc.as_ref().id().to_url());
 
-  let decoded_presentation: DecodedJwtPresentation<Jwt> = JWT_PRESENTATION_VALIDATOR_ED25519
+  let decoded_presentation: DecodedJwtPresentation<Jwt> = JWT_PRESENTATION_VALIDATOR
     .validate::<_, _, Object>(
       &presentation_jwt,
       &setup.subject_doc,
@@ -161,8 +166,10 @@ where
 // > Validation fails due to invalid signature since key material changed.
 #[tokio::test]
 async fn test_invalid_signature() {
-  test_invalid_signature_impl(setup_coredocument(None, None).await).await;
-  test_invalid_signature_impl(setup_iotadocument(None, None).await).await;
+  for (key_type, alg) in SIGNATURE_ALGORITHMS {
+    test_invalid_signature_impl(setup_coredocument_with_alg(key_type.clone(), alg, None, None).await).await;
+    test_invalid_signature_impl(setup_iotadocument_with_alg(key_type, alg, None, None).await).await;
+  }
 }
 async fn test_invalid_signature_impl<T>(mut setup: Setup<T, T>)
 where
@@ -222,7 +229,7 @@ where
     .await
     .unwrap();
 
-  let validation_error: JwtValidationError = JWT_PRESENTATION_VALIDATOR_ED25519
+  let validation_error: JwtValidationError = JWT_PRESENTATION_VALIDATOR
     .validate::<_, Jwt, Object>(
       &presentation_jwt,
       &setup.subject_doc,
@@ -243,8 +250,10 @@ where
 
 #[tokio::test]
 async fn expiration_date() {
-  expiration_date_impl(setup_coredocument(None, None).await).await;
-  expiration_date_impl(setup_iotadocument(None, None).await).await;
+  for (key_type, alg) in SIGNATURE_ALGORITHMS {
+    expiration_date_impl(setup_coredocument_with_alg(key_type.clone(), alg, None, None).await).await;
+    expiration_date_impl(setup_iotadocument_with_alg(key_type, alg, None, None).await).await;
+  }
 }
 async fn expiration_date_impl<T>(setup: Setup<T, T>)
 where
@@ -279,7 +288,7 @@ where
     .await
     .unwrap();
 
-  let validation_error: JwtValidationError = JWT_PRESENTATION_VALIDATOR_ED25519
+  let validation_error: JwtValidationError = JWT_PRESENTATION_VALIDATOR
     .validate::<_, Jwt, Object>(
       &presentation_jwt,
       &setup.subject_doc,
@@ -300,15 +309,17 @@ where
   validation_options =
     validation_options.earliest_expiry_date(Timestamp::now_utc().checked_sub(Duration::days(2)).unwrap());
 
-  JWT_PRESENTATION_VALIDATOR_ED25519
+  JWT_PRESENTATION_VALIDATOR
     .validate::<_, Jwt, Object>(&presentation_jwt, &setup.subject_doc, &validation_options)
     .unwrap();
 }
 
 #[tokio::test]
 async fn issuance_date() {
-  issuance_date_impl(setup_coredocument(None, None).await).await;
-  issuance_date_impl(setup_iotadocument(None, None).await).await;
+  for (key_type, alg) in SIGNATURE_ALGORITHMS {
+    issuance_date_impl(setup_coredocument_with_alg(key_type.clone(), alg, None, None).await).await;
+    issuance_date_impl(setup_iotadocument_with_alg(key_type, alg, None, None).await).await;
+  }
 }
 
 async fn issuance_date_impl<T>(setup: Setup<T, T>)
@@ -344,7 +355,7 @@ where
     .await
     .unwrap();
 
-  let validation_error: JwtValidationError = JWT_PRESENTATION_VALIDATOR_ED25519
+  let validation_error: JwtValidationError = JWT_PRESENTATION_VALIDATOR
     .validate::<_, Jwt, Object>(
       &presentation_jwt,
       &setup.subject_doc,
@@ -364,7 +375,7 @@ where
   validation_options =
     validation_options.latest_issuance_date(Timestamp::now_utc().checked_add(Duration::hours(2)).unwrap());
 
-  let validation_ok: bool = JWT_PRESENTATION_VALIDATOR_ED25519
+  let validation_ok: bool = JWT_PRESENTATION_VALIDATOR
     .validate::<_, Jwt, Object>(&presentation_jwt, &setup.subject_doc, &validation_options)
     .is_ok();
   assert!(validation_ok);
@@ -372,8 +383,10 @@ where
 
 #[tokio::test]
 async fn presentation_jws_error() {
-  presentation_jws_error_impl(setup_coredocument(None, None).await).await;
-  presentation_jws_error_impl(setup_iotadocument(None, None).await).await;
+  for (key_type, alg) in SIGNATURE_ALGORITHMS {
+    presentation_jws_error_impl(setup_coredocument_with_alg(key_type.clone(), alg, None, None).await).await;
+    presentation_jws_error_impl(setup_iotadocument_with_alg(key_type, alg, None, None).await).await;
+  }
 }
 
 async fn presentation_jws_error_impl<T>(setup: Setup<T, T>)
@@ -404,7 +417,7 @@ where
     .await
     .unwrap();
 
-  let validation_error: JwtValidationError = JWT_PRESENTATION_VALIDATOR_ED25519
+  let validation_error: JwtValidationError = JWT_PRESENTATION_VALIDATOR
     .validate::<_, Jwt, Object>(
       &presentation_jwt,
       &setup.subject_doc,
@@ -24,10 +24,19 @@ use serde_json::json;
 
 use crate::key_id_storage::KeyIdMemstore;
 use crate::key_storage::JwkMemStore;
+use crate::key_storage::KeyType;
 use crate::JwkDocumentExt;
 use crate::Storage;
 
-type MemStorage = Storage<JwkMemStore, KeyIdMemstore>;
+pub(super) type MemStorage = Storage<JwkMemStore, KeyIdMemstore>;
+
+/// The key types and signature algorithms supported by the [`JwkMemStore`].
+pub(crate) const SIGNATURE_ALGORITHMS: [(KeyType, JwsAlgorithm); 4] = [
+  (JwkMemStore::ED25519_KEY_TYPE, JwsAlgorithm::EdDSA),
+  (JwkMemStore::P256_KEY_TYPE, JwsAlgorithm::ES256),
+  (JwkMemStore::P384_KEY_TYPE, JwsAlgorithm::ES384),
+  (JwkMemStore::SECP256K1_KEY_TYPE, JwsAlgorithm::ES256K),
+];
 
 const ISSUER_DOCUMENT_JSON: &str = r#"
 {
@@ -73,14 +82,31 @@ pub(super) struct Setup<T: JwkDocumentExt, U: JwkDocumentExt> {
 pub(super) async fn setup_iotadocument(
   issuer_fragment: Option<&'static str>,
   subject_fragment: Option<&'static str>,
+) -> Setup<IotaDocument, IotaDocument> {
+  setup_iotadocument_with_alg(
+    JwkMemStore::ED25519_KEY_TYPE,
+    JwsAlgorithm::EdDSA,
+    issuer_fragment,
+    subject_fragment,
+  )
+  .await
+}
+
+pub(super) async fn setup_iotadocument_with_alg(
+  key_type: KeyType,
+  alg: JwsAlgorithm,
+  issuer_fragment: Option<&'static str>,
+  subject_fragment: Option<&'static str>,
 ) -> Setup<IotaDocument, IotaDocument> {
   let mut issuer_doc = IotaDocument::from_json(ISSUER_IOTA_DOCUMENT_JSON).unwrap();
   let mut subject_doc = IotaDocument::from_json(SUBJECT_IOTA_DOCUMENT_JSON).unwrap();
   let issuer_storage = Storage::new(JwkMemStore::new(), KeyIdMemstore::new());
   let subject_storage = Storage::new(JwkMemStore::new(), KeyIdMemstore::new());
 
-  let issuer_method_fragment: String = generate_method(&issuer_storage, &mut issuer_doc, issuer_fragment).await;
-  let subject_method_fragment: String = generate_method(&subject_storage, &mut subject_doc, subject_fragment).await;
+  let issuer_method_fragment: String =
+    generate_method(&issuer_storage, &mut issuer_doc, key_type.clone(), alg, issuer_fragment).await;
+  let subject_method_fragment: String =
+    generate_method(&subject_storage, &mut subject_doc, key_type, alg, subject_fragment).await;
 
   Setup {
     issuer_doc,
@@ -92,7 +118,9 @@ pub(super) async fn setup_iotadocument(
   }
 }
 
-pub(super) async fn setup_coredocument(
+pub(super) async fn setup_coredocument_with_alg(
+  key_type: KeyType,
+  alg: JwsAlgorithm,
   issuer_fragment: Option<&'static str>,
   subject_fragment: Option<&'static str>,
 ) -> Setup<CoreDocument, CoreDocument> {
@@ -101,8 +129,10 @@ pub(super) async fn setup_coredocument(
   let issuer_storage = Storage::new(JwkMemStore::new(), KeyIdMemstore::new());
   let subject_storage = Storage::new(JwkMemStore::new(), KeyIdMemstore::new());
 
-  let issuer_method_fragment: String = generate_method(&issuer_storage, &mut issuer_doc, issuer_fragment).await;
-  let subject_method_fragment: String = generate_method(&subject_storage, &mut subject_doc, subject_fragment).await;
+  let issuer_method_fragment: String =
+    generate_method(&issuer_storage, &mut issuer_doc, key_type.clone(), alg, issuer_fragment).await;
+  let subject_method_fragment: String =
+    generate_method(&subject_storage, &mut subject_doc, key_type, alg, subject_fragment).await;
 
   Setup {
     issuer_doc,
@@ -114,18 +144,18 @@ pub(super) async fn setup_coredocument(
   }
 }
 
-async fn generate_method<T>(storage: &MemStorage, document: &mut T, fragment: Option<&'static str>) -> String
+async fn generate_method<T>(
+  storage: &MemStorage,
+  document: &mut T,
+  key_type: KeyType,
+  alg: JwsAlgorithm,
+  fragment: Option<&'static str>,
+) -> String
 where
   T: JwkDocumentExt,
 {
   document
-    .generate_method(
-      storage,
-      JwkMemStore::ED25519_KEY_TYPE,
-      JwsAlgorithm::EdDSA,
-      fragment,
-      MethodScope::assertion_method(),
-    )
+    .generate_method(storage, key_type, alg, fragment, MethodScope::assertion_method())
     .await
     .unwrap()
 }
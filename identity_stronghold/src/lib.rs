@@ -2,9 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub(crate) mod ed25519;
+mod secp256k1;
 mod stronghold_jwk_storage;
+mod stronghold_jwk_storage_export;
 mod stronghold_key_id;
 #[cfg(test)]
 mod tests;
+mod x25519;
 
 pub use stronghold_jwk_storage::*;
@@ -0,0 +1,75 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_verification::jose::jwk::EcCurve;
+use identity_verification::jose::jwk::Jwk;
+use identity_verification::jose::jwk::JwkParamsEc;
+use identity_verification::jose::jwu;
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use zeroize::Zeroizing;
+
+use identity_storage::key_storage::KeyStorageError;
+use identity_storage::key_storage::KeyStorageErrorKind;
+use identity_storage::key_storage::KeyStorageResult;
+
+/// Length in bytes of a secp256k1 private key.
+const SECRET_KEY_LENGTH: usize = 32;
+
+pub(crate) fn expand_secret_jwk(jwk: &Jwk) -> KeyStorageResult<Zeroizing<Vec<u8>>> {
+  let params: &JwkParamsEc = jwk
+    .try_ec_params()
+    .map_err(|err| KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType).with_source(err))?;
+
+  if params
+    .try_ec_curve()
+    .map_err(|err| KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType).with_source(err))?
+    != EcCurve::Secp256K1
+  {
+    return Err(
+      KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
+        .with_custom_message(format!("expected a {} key", EcCurve::Secp256K1.name())),
+    );
+  }
+
+  let sk: Zeroizing<Vec<u8>> = params
+    .d
+    .as_deref()
+    .map(jwu::decode_b64)
+    .ok_or_else(|| {
+      KeyStorageError::new(KeyStorageErrorKind::Unspecified).with_custom_message("expected Jwk `d` param to be present")
+    })?
+    .map(Zeroizing::new)
+    .map_err(|err| {
+      KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+        .with_custom_message("unable to decode `d` param")
+        .with_source(err)
+    })?;
+
+  if sk.len() != SECRET_KEY_LENGTH {
+    return Err(
+      KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+        .with_custom_message(format!("expected key of length {SECRET_KEY_LENGTH}")),
+    );
+  }
+
+  Ok(sk)
+}
+
+/// Encodes a SEC1 encoded secp256k1 public key as a [`Jwk`].
+pub(crate) fn encode_public_jwk(public_key: &[u8]) -> KeyStorageResult<Jwk> {
+  let public_key: k256::PublicKey = k256::PublicKey::from_sec1_bytes(public_key).map_err(|err| {
+    KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+      .with_custom_message("invalid secp256k1 public key")
+      .with_source(err)
+  })?;
+  let encoded_point = public_key.to_encoded_point(false);
+  let (Some(x), Some(y)) = (encoded_point.x(), encoded_point.y()) else {
+    unreachable!("uncompressed points of public keys have both coordinates");
+  };
+
+  let mut params = JwkParamsEc::new();
+  params.crv = EcCurve::Secp256K1.name().to_owned();
+  params.x = jwu::encode_b64(x);
+  params.y = jwu::encode_b64(y);
+  Ok(Jwk::from_params(params))
+}
@@ -11,6 +11,8 @@ use identity_storage::KeyStorageError;
 use identity_storage::KeyStorageErrorKind;
 use identity_storage::KeyStorageResult;
 use identity_storage::KeyType;
+use identity_verification::jwk::EcCurve;
+use identity_verification::jwk::EcxCurve;
 use identity_verification::jwk::EdCurve;
 use identity_verification::jwk::Jwk;
 use identity_verification::jwk::JwkParamsOkp;
@@ -22,7 +24,10 @@ use iota_sdk::client::secret::SecretManager;
 use iota_stronghold::procedures::Ed25519Sign;
 use iota_stronghold::procedures::GenerateKey;
 use iota_stronghold::procedures::KeyType as ProceduresKeyType;
+use iota_stronghold::procedures::Secp256k1EcdsaFlavor;
+use iota_stronghold::procedures::Secp256k1EcdsaSign;
 use iota_stronghold::procedures::StrongholdProcedure;
+use iota_stronghold::procedures::X25519DiffieHellman;
 use iota_stronghold::Client;
 use iota_stronghold::ClientError;
 use iota_stronghold::Location;
@@ -34,14 +39,25 @@ use std::sync::Arc;
 use tokio::sync::MutexGuard;
 
 use crate::ed25519;
+use crate::secp256k1;
+use crate::stronghold_jwk_storage_export;
+use crate::x25519;
 
 const ED25519_KEY_TYPE_STR: &str = "Ed25519";
-static IDENTITY_VAULT_PATH: &str = "iota_identity_vault";
+const SECP256K1_KEY_TYPE_STR: &str = "secp256k1";
+const X25519_KEY_TYPE_STR: &str = "X25519";
+pub(crate) static IDENTITY_VAULT_PATH: &str = "iota_identity_vault";
 pub(crate) static IDENTITY_CLIENT_PATH: &[u8] = b"iota_identity_client";
 
 /// The Ed25519 key type.
 pub const ED25519_KEY_TYPE: &KeyType = &KeyType::from_static_str(ED25519_KEY_TYPE_STR);
 
+/// The secp256k1 key type.
+pub const SECP256K1_KEY_TYPE: &KeyType = &KeyType::from_static_str(SECP256K1_KEY_TYPE_STR);
+
+/// The X25519 key type, used for key agreement.
+pub const X25519_KEY_TYPE: &KeyType = &KeyType::from_static_str(X25519_KEY_TYPE_STR);
+
 /// Wrapper around a [`StrongholdSecretManager`] that implements the [`KeyIdStorage`](crate::KeyIdStorage)
 /// and [`JwkStorage`](crate::JwkStorage) interfaces.
 #[derive(Clone, Debug)]
@@ -68,33 +84,63 @@ impl StrongholdStorage {
 
   /// Retrieve the public key corresponding to `key_id`.
   pub async fn get_public_key(&self, key_id: &KeyId) -> KeyStorageResult<Jwk> {
+    <Self as JwkStorage>::get_public_key(self, key_id).await
+  }
+
+  /// Performs an X25519 key agreement between the stored key identified by `key_id` and the
+  /// `public_key` of the other party.
+  ///
+  /// The shared secret never leaves the vault. It is stored under the returned [`KeyId`], which can be
+  /// used as input to further Stronghold procedures and removed with [`JwkStorage::delete`].
+  pub async fn x25519_key_agreement(&self, key_id: &KeyId, public_key: &Jwk) -> KeyStorageResult<KeyId> {
+    let public_key: [u8; 32] = x25519::expand_public_jwk(public_key)?.to_bytes();
+
     let stronghold = self.get_stronghold().await;
     let client = get_client(&stronghold)?;
 
-    let location = Location::generic(
+    let private_key = Location::generic(
       IDENTITY_VAULT_PATH.as_bytes().to_vec(),
       key_id.to_string().as_bytes().to_vec(),
     );
+    let exists: bool = client.record_exists(&private_key).map_err(|err| {
+      KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+        .with_custom_message("stronghold client error")
+        .with_source(err)
+    })?;
+    if !exists {
+      return Err(KeyStorageError::new(KeyStorageErrorKind::KeyNotFound));
+    }
 
-    let public_key_procedure = iota_stronghold::procedures::PublicKey {
-      ty: ProceduresKeyType::Ed25519,
-      private_key: location,
-    };
+    let key_type: StrongholdKeyType = stronghold_jwk_storage_export::stored_key_type(&client, key_id)?;
+    if key_type != StrongholdKeyType::X25519 {
+      return Err(
+        KeyStorageError::new(KeyStorageErrorKind::KeyAlgorithmMismatch)
+          .with_custom_message(format!("cannot use key type `{key_type}` for key agreement")),
+      );
+    }
 
-    let procedure_result = client
-      .execute_procedure(StrongholdProcedure::PublicKey(public_key_procedure))
-      .map_err(|err| KeyStorageError::new(KeyStorageErrorKind::KeyNotFound).with_source(err))?;
+    let shared_key_id: KeyId = random_key_id();
+    let shared_key = Location::generic(
+      IDENTITY_VAULT_PATH.as_bytes().to_vec(),
+      shared_key_id.to_string().as_bytes().to_vec(),
+    );
 
-    let public_key: Vec<u8> = procedure_result.into();
+    let diffie_hellman_procedure = X25519DiffieHellman {
+      public_key,
+      private_key,
+      shared_key,
+    };
 
-    let mut params = JwkParamsOkp::new();
-    params.x = jwu::encode_b64(public_key);
-    params.crv = EdCurve::Ed25519.name().to_owned();
-    let mut jwk: Jwk = Jwk::from_params(params);
-    jwk.set_alg(JwsAlgorithm::EdDSA.name());
-    jwk.set_kid(jwk.thumbprint_sha256_b64());
+    client
+      .execute_procedure(StrongholdProcedure::X25519DiffieHellman(diffie_hellman_procedure))
+      .map_err(|err| {
+        KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+          .with_custom_message("stronghold X25519 key agreement procedure failed")
+          .with_source(err)
+      })?;
+    persist_changes(self, stronghold).await?;
 
-    Ok(jwk)
+    Ok(shared_key_id)
   }
 }
 
@@ -108,9 +154,7 @@ impl JwkStorage for StrongholdStorage {
     let key_type = StrongholdKeyType::try_from(&key_type)?;
     check_key_alg_compatibility(key_type, alg)?;
 
-    let keytype: ProceduresKeyType = match key_type {
-      StrongholdKeyType::Ed25519 => ProceduresKeyType::Ed25519,
-    };
+    let keytype: ProceduresKeyType = key_type.procedures_key_type();
 
     let key_id: KeyId = random_key_id();
     let location = Location::generic(
@@ -143,14 +187,15 @@ impl JwkStorage for StrongholdStorage {
           .with_custom_message("stronghold public key procedure failed")
           .with_source(err)
       })?;
-    persist_changes(self, stronghold).await?;
     let public_key: Vec<u8> = procedure_result.into();
+    let mut jwk: Jwk = encode_public_jwk(key_type, &public_key)?;
+    stronghold_jwk_storage_export::index_key_id(&client, &key_id, key_type)?;
+    persist_changes(self, stronghold).await?;
 
-    let mut params = JwkParamsOkp::new();
-    params.x = jwu::encode_b64(public_key);
-    params.crv = EdCurve::Ed25519.name().to_owned();
-    let mut jwk: Jwk = Jwk::from_params(params);
-    jwk.set_alg(alg.name());
+    // X25519 keys are used for key agreement and are therefore not bound to a signature algorithm.
+    if key_type != StrongholdKeyType::X25519 {
+      jwk.set_alg(alg.name());
+    }
     jwk.set_kid(jwk.thumbprint_sha256_b64());
 
     Ok(JwkGenOutput::new(key_id, jwk))
@@ -166,6 +211,7 @@ impl JwkStorage for StrongholdStorage {
     }
 
     match jwk.alg() {
+      _ if key_type == StrongholdKeyType::X25519 => {}
       Some(alg) => {
         let alg: JwsAlgorithm = JwsAlgorithm::from_str(alg)
           .map_err(|err| KeyStorageError::new(KeyStorageErrorKind::UnsupportedSignatureAlgorithm).with_source(err))?;
@@ -178,7 +224,11 @@ impl JwkStorage for StrongholdStorage {
         );
       }
     }
-    let secret_key = ed25519::expand_secret_jwk(&jwk)?;
+    let secret_key: zeroize::Zeroizing<Vec<u8>> = match key_type {
+      StrongholdKeyType::Ed25519 => zeroize::Zeroizing::from(ed25519::expand_secret_jwk(&jwk)?.to_bytes().to_vec()),
+      StrongholdKeyType::Secp256K1 => secp256k1::expand_secret_jwk(&jwk)?,
+      StrongholdKeyType::X25519 => zeroize::Zeroizing::from(x25519::expand_secret_jwk(&jwk)?.to_bytes().to_vec()),
+    };
     let key_id: KeyId = random_key_id();
 
     let location = Location::generic(
@@ -189,12 +239,13 @@ impl JwkStorage for StrongholdStorage {
     let client = get_client(&stronghold)?;
     client
       .vault(IDENTITY_VAULT_PATH.as_bytes())
-      .write_secret(location, zeroize::Zeroizing::from(secret_key.to_bytes().to_vec()))
+      .write_secret(location, secret_key)
       .map_err(|err| {
         KeyStorageError::new(KeyStorageErrorKind::Unspecified)
           .with_custom_message("stronghold write secret failed")
           .with_source(err)
       })?;
+    stronghold_jwk_storage_export::index_key_id(&client, &key_id, key_type)?;
     persist_changes(self, stronghold).await?;
 
     Ok(key_id)
@@ -209,8 +260,8 @@ impl JwkStorage for StrongholdStorage {
         JwsAlgorithm::from_str(alg_str).map_err(|_| KeyStorageErrorKind::UnsupportedSignatureAlgorithm)
       })?;
 
-    // Check that `kty` is `Okp` and `crv = Ed25519`.
-    match alg {
+    // Check that `kty` and `crv` of the public key match the algorithm.
+    let key_type: StrongholdKeyType = match alg {
       JwsAlgorithm::EdDSA => {
         let okp_params = public_key.try_okp_params().map_err(|err| {
           KeyStorageError::new(KeyStorageErrorKind::Unspecified)
@@ -225,6 +276,23 @@ impl JwkStorage for StrongholdStorage {
             )),
           );
         }
+        StrongholdKeyType::Ed25519
+      }
+      JwsAlgorithm::ES256K => {
+        let ec_params = public_key.try_ec_params().map_err(|err| {
+          KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+            .with_custom_message(format!("expected a Jwk with Ec params in order to sign with {alg}"))
+            .with_source(err)
+        })?;
+        if ec_params.crv != EcCurve::Secp256K1.name() {
+          return Err(
+            KeyStorageError::new(KeyStorageErrorKind::Unspecified).with_custom_message(format!(
+              "expected Jwk with Ec {} crv in order to sign with {alg}",
+              EcCurve::Secp256K1
+            )),
+          );
+        }
+        StrongholdKeyType::Secp256K1
       }
       other => {
         return Err(
@@ -238,21 +306,47 @@ impl JwkStorage for StrongholdStorage {
       IDENTITY_VAULT_PATH.as_bytes().to_vec(),
       key_id.to_string().as_bytes().to_vec(),
     );
-    let procedure: Ed25519Sign = Ed25519Sign {
-      private_key: location,
-      msg: data.to_vec(),
-    };
 
     let stronghold = self.get_stronghold().await;
     let client = get_client(&stronghold)?;
 
-    let signature: [u8; 64] = client.execute_procedure(procedure).map_err(|err| {
-      KeyStorageError::new(KeyStorageErrorKind::Unspecified)
-        .with_custom_message("stronghold Ed25519Sign procedure failed")
-        .with_source(err)
-    })?;
+    let stored_key_type: StrongholdKeyType = stronghold_jwk_storage_export::stored_key_type(&client, key_id)?;
+    if stored_key_type != key_type {
+      return Err(
+        KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
+          .with_custom_message(format!("cannot sign with a stored `{stored_key_type}` key using {alg}")),
+      );
+    }
 
-    Ok(signature.to_vec())
+    match key_type {
+      StrongholdKeyType::Ed25519 => {
+        let procedure: Ed25519Sign = Ed25519Sign {
+          private_key: location,
+          msg: data.to_vec(),
+        };
+        let signature: [u8; 64] = client.execute_procedure(procedure).map_err(|err| {
+          KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+            .with_custom_message("stronghold Ed25519Sign procedure failed")
+            .with_source(err)
+        })?;
+        Ok(signature.to_vec())
+      }
+      StrongholdKeyType::Secp256K1 => {
+        let procedure: Secp256k1EcdsaSign = Secp256k1EcdsaSign {
+          flavor: Secp256k1EcdsaFlavor::Sha256,
+          msg: data.to_vec(),
+          private_key: location,
+        };
+        let signature: [u8; 65] = client.execute_procedure(procedure).map_err(|err| {
+          KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+            .with_custom_message("stronghold Secp256k1EcdsaSign procedure failed")
+            .with_source(err)
+        })?;
+        // The recoverable signature is `r || s || v`, whereas JWS expects `r || s`.
+        Ok(signature[..64].to_vec())
+      }
+      StrongholdKeyType::X25519 => unreachable!("X25519 keys are rejected before"),
+    }
   }
 
   async fn delete(&self, key_id: &KeyId) -> KeyStorageResult<()> {
@@ -270,6 +364,7 @@ impl JwkStorage for StrongholdStorage {
     if !deleted {
       return Err(KeyStorageError::new(KeyStorageErrorKind::KeyNotFound));
     }
+    stronghold_jwk_storage_export::unindex_key_id(&client, key_id)?;
     persist_changes(self, stronghold).await?;
 
     Ok(())
@@ -289,10 +384,60 @@ impl JwkStorage for StrongholdStorage {
     })?;
     Ok(exists)
   }
+
+  async fn get_public_key(&self, key_id: &KeyId) -> KeyStorageResult<Jwk> {
+    let stronghold = self.get_stronghold().await;
+    let client = get_client(&stronghold)?;
+    public_key(&client, key_id)
+  }
+}
+
+/// Retrieve the public key corresponding to `key_id` from the given `client`.
+pub(crate) fn public_key(client: &Client, key_id: &KeyId) -> KeyStorageResult<Jwk> {
+  let location = Location::generic(
+    IDENTITY_VAULT_PATH.as_bytes().to_vec(),
+    key_id.to_string().as_bytes().to_vec(),
+  );
+
+  let key_type: StrongholdKeyType = stronghold_jwk_storage_export::stored_key_type(client, key_id)?;
+  let public_key_procedure = iota_stronghold::procedures::PublicKey {
+    ty: key_type.procedures_key_type(),
+    private_key: location,
+  };
+
+  let procedure_result = client
+    .execute_procedure(StrongholdProcedure::PublicKey(public_key_procedure))
+    .map_err(|err| KeyStorageError::new(KeyStorageErrorKind::KeyNotFound).with_source(err))?;
+
+  let public_key: Vec<u8> = procedure_result.into();
+
+  let mut jwk: Jwk = encode_public_jwk(key_type, &public_key)?;
+  match key_type {
+    StrongholdKeyType::Ed25519 => jwk.set_alg(JwsAlgorithm::EdDSA.name()),
+    StrongholdKeyType::Secp256K1 => jwk.set_alg(JwsAlgorithm::ES256K.name()),
+    StrongholdKeyType::X25519 => {}
+  }
+  jwk.set_kid(jwk.thumbprint_sha256_b64());
+
+  Ok(jwk)
+}
+
+/// Encodes the public key returned by the stronghold public key procedure as a [`Jwk`].
+fn encode_public_jwk(key_type: StrongholdKeyType, public_key: &[u8]) -> KeyStorageResult<Jwk> {
+  match key_type {
+    StrongholdKeyType::Ed25519 => {
+      let mut params = JwkParamsOkp::new();
+      params.x = jwu::encode_b64(public_key);
+      params.crv = EdCurve::Ed25519.name().to_owned();
+      Ok(Jwk::from_params(params))
+    }
+    StrongholdKeyType::Secp256K1 => secp256k1::encode_public_jwk(public_key),
+    StrongholdKeyType::X25519 => x25519::encode_public_jwk(public_key),
+  }
 }
 
 /// Generate a random alphanumeric string of len 32.
-fn random_key_id() -> KeyId {
+pub(crate) fn random_key_id() -> KeyId {
   KeyId::new(rand::distributions::Alphanumeric.sample_string(&mut rand::thread_rng(), 32))
 }
 
@@ -300,6 +445,8 @@ fn random_key_id() -> KeyId {
 fn check_key_alg_compatibility(key_type: StrongholdKeyType, alg: JwsAlgorithm) -> KeyStorageResult<()> {
   match (key_type, alg) {
     (StrongholdKeyType::Ed25519, JwsAlgorithm::EdDSA) => Ok(()),
+    (StrongholdKeyType::Secp256K1, JwsAlgorithm::ES256K) => Ok(()),
+    (StrongholdKeyType::X25519, JwsAlgorithm::NONE) => Ok(()),
     (key_type, alg) => Err(
       KeyStorageError::new(identity_storage::KeyStorageErrorKind::KeyAlgorithmMismatch)
         .with_custom_message(format!("cannot use key type `{key_type}` with algorithm `{alg}`")),
@@ -307,7 +454,7 @@ fn check_key_alg_compatibility(key_type: StrongholdKeyType, alg: JwsAlgorithm) -
   }
 }
 
-fn get_client(stronghold: &Stronghold) -> KeyStorageResult<Client> {
+pub(crate) fn get_client(stronghold: &Stronghold) -> KeyStorageResult<Client> {
   let client = stronghold.get_client(IDENTITY_CLIENT_PATH);
   match client {
     Ok(client) => Ok(client),
@@ -326,7 +473,7 @@ fn load_or_create_client(stronghold: &Stronghold) -> KeyStorageResult<Client> {
   }
 }
 
-async fn persist_changes(
+pub(crate) async fn persist_changes(
   secret_manager: &StrongholdStorage,
   stronghold: MutexGuard<'_, Stronghold>,
 ) -> KeyStorageResult<()> {
@@ -360,16 +507,29 @@ async fn persist_changes(
 }
 
 /// Key Types supported by the stronghold storage implementation.
-#[derive(Debug, Copy, Clone)]
-enum StrongholdKeyType {
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum StrongholdKeyType {
   Ed25519,
+  Secp256K1,
+  X25519,
 }
 
 impl StrongholdKeyType {
   /// String representation of the key type.
-  const fn name(&self) -> &'static str {
+  pub(crate) const fn name(&self) -> &'static str {
     match self {
-      StrongholdKeyType::Ed25519 => "Ed25519",
+      StrongholdKeyType::Ed25519 => ED25519_KEY_TYPE_STR,
+      StrongholdKeyType::Secp256K1 => SECP256K1_KEY_TYPE_STR,
+      StrongholdKeyType::X25519 => X25519_KEY_TYPE_STR,
+    }
+  }
+
+  /// The corresponding key type of the stronghold procedures.
+  fn procedures_key_type(&self) -> ProceduresKeyType {
+    match self {
+      StrongholdKeyType::Ed25519 => ProceduresKeyType::Ed25519,
+      StrongholdKeyType::Secp256K1 => ProceduresKeyType::Secp256k1Ecdsa,
+      StrongholdKeyType::X25519 => ProceduresKeyType::X25519,
     }
   }
 }
@@ -386,6 +546,8 @@ impl TryFrom<&KeyType> for StrongholdKeyType {
   fn try_from(value: &KeyType) -> Result<Self, Self::Error> {
     match value.as_str() {
       ED25519_KEY_TYPE_STR => Ok(StrongholdKeyType::Ed25519),
+      SECP256K1_KEY_TYPE_STR => Ok(StrongholdKeyType::Secp256K1),
+      X25519_KEY_TYPE_STR => Ok(StrongholdKeyType::X25519),
       _ => Err(KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)),
     }
   }
@@ -402,9 +564,12 @@ impl TryFrom<&Jwk> for StrongholdKeyType {
             .with_custom_message("expected Okp parameters for a JWK with `kty` Okp")
             .with_source(err)
         })?;
+        if okp_params.crv == EcxCurve::X25519.name() {
+          return Ok(StrongholdKeyType::X25519);
+        }
         match okp_params.try_ed_curve().map_err(|err| {
           KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
-            .with_custom_message("only Ed curves are supported for signing")
+            .with_custom_message("only Ed25519 and X25519 Okp curves are supported")
             .with_source(err)
         })? {
           EdCurve::Ed25519 => Ok(StrongholdKeyType::Ed25519),
@@ -414,6 +579,24 @@ impl TryFrom<&Jwk> for StrongholdKeyType {
           ),
         }
       }
+      JwkType::Ec => {
+        let ec_params = jwk.try_ec_params().map_err(|err| {
+          KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
+            .with_custom_message("expected Ec parameters for a JWK with `kty` Ec")
+            .with_source(err)
+        })?;
+        match ec_params.try_ec_curve().map_err(|err| {
+          KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
+            .with_custom_message("unknown Ec curve")
+            .with_source(err)
+        })? {
+          EcCurve::Secp256K1 => Ok(StrongholdKeyType::Secp256K1),
+          curve => Err(
+            KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
+              .with_custom_message(format!("{curve} not supported")),
+          ),
+        }
+      }
       other => Err(
         KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
           .with_custom_message(format!("Jwk `kty` {other} not supported")),
@@ -0,0 +1,235 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use async_trait::async_trait;
+use identity_storage::key_storage::JwkStorageExport;
+use identity_storage::key_storage::KeyWrappingKey;
+use identity_storage::key_storage::WrappedJwk;
+use identity_storage::KeyId;
+use identity_storage::KeyStorageError;
+use identity_storage::KeyStorageErrorKind;
+use identity_storage::KeyStorageResult;
+use identity_storage::KeyType;
+use identity_verification::jwk::Jwk;
+use iota_stronghold::procedures::AesKeyWrapCipher;
+use iota_stronghold::procedures::AesKeyWrapDecrypt;
+use iota_stronghold::procedures::AesKeyWrapEncrypt;
+use iota_stronghold::procedures::StrongholdProcedure;
+use iota_stronghold::Client;
+use iota_stronghold::Location;
+use zeroize::Zeroizing;
+
+use crate::stronghold_jwk_storage::get_client;
+use crate::stronghold_jwk_storage::persist_changes;
+use crate::stronghold_jwk_storage::public_key;
+use crate::stronghold_jwk_storage::random_key_id;
+use crate::stronghold_jwk_storage::StrongholdKeyType;
+use crate::stronghold_jwk_storage::IDENTITY_VAULT_PATH;
+use crate::StrongholdStorage;
+
+/// Prefix of the store entries that index the key ids of the keys in the vault.
+///
+/// The vault only stores hashes of record paths, so key ids need to be indexed separately in order to be listed.
+static KEY_ID_INDEX_PREFIX: &str = "iota_identity_key_id:";
+
+/// Lists, exports and imports the keys of a [`StrongholdStorage`].
+///
+/// Keys are wrapped and unwrapped inside Stronghold, so the private key material is never exposed in plaintext.
+///
+/// Only keys generated or inserted with a version of this library that indexes key ids are returned by
+/// [`JwkStorageExport::list`]. Keys created before can still be exported by their [`KeyId`].
+#[cfg_attr(not(feature = "send-sync-storage"), async_trait(?Send))]
+#[cfg_attr(feature = "send-sync-storage", async_trait)]
+impl JwkStorageExport for StrongholdStorage {
+  async fn list(&self) -> KeyStorageResult<Vec<KeyId>> {
+    let stronghold = self.get_stronghold().await;
+    let client = get_client(&stronghold)?;
+    let keys: Vec<Vec<u8>> = client.store().keys().map_err(|err| {
+      KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+        .with_custom_message("stronghold store error")
+        .with_source(err)
+    })?;
+
+    Ok(
+      keys
+        .into_iter()
+        .filter_map(|key| String::from_utf8(key).ok())
+        .filter_map(|key| key.strip_prefix(KEY_ID_INDEX_PREFIX).map(KeyId::new))
+        .collect(),
+    )
+  }
+
+  async fn export(&self, key_id: &KeyId, wrapping_key: &KeyWrappingKey) -> KeyStorageResult<WrappedJwk> {
+    let stronghold = self.get_stronghold().await;
+    let client = get_client(&stronghold)?;
+    let location = key_location(key_id);
+    if !record_exists(&client, &location)? {
+      return Err(KeyStorageError::new(KeyStorageErrorKind::KeyNotFound));
+    }
+
+    let public_key: Jwk = public_key(&client, key_id)?;
+    let (kek, kdf) = wrapping_key.derive_for_export()?;
+    let kek_id: KeyId = write_kek(&client, kek)?;
+
+    let wrap_key_procedure = AesKeyWrapEncrypt {
+      cipher: AesKeyWrapCipher::Aes256,
+      encryption_key: key_location(&kek_id),
+      wrap_key: location,
+    };
+    let procedure_result = client.execute_procedure(StrongholdProcedure::AesKeyWrapEncrypt(wrap_key_procedure));
+    delete_secret(&client, &kek_id)?;
+
+    let wrapped_key: Vec<u8> = procedure_result
+      .map_err(|err| {
+        KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+          .with_custom_message("stronghold aes key wrap procedure failed")
+          .with_source(err)
+      })?
+      .into();
+
+    WrappedJwk::new(key_id.clone(), public_key, kdf, wrapped_key)
+  }
+
+  async fn import(&self, wrapped_jwk: &WrappedJwk, wrapping_key: &KeyWrappingKey) -> KeyStorageResult<KeyId> {
+    let key_type: StrongholdKeyType = StrongholdKeyType::try_from(wrapped_jwk.public_key())?;
+    let key_id: KeyId = wrapped_jwk.key_id().clone();
+    let wrapped_key: Vec<u8> = wrapped_jwk.wrapped_key()?;
+
+    let stronghold = self.get_stronghold().await;
+    let client = get_client(&stronghold)?;
+    let location = key_location(&key_id);
+    if record_exists(&client, &location)? {
+      return Err(
+        KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+          .with_custom_message(format!("a key with id `{key_id}` already exists")),
+      );
+    }
+
+    let kek = wrapping_key.derive_for_import(wrapped_jwk.kdf())?;
+    let kek_id: KeyId = write_kek(&client, kek)?;
+
+    let unwrap_key_procedure = AesKeyWrapDecrypt {
+      cipher: AesKeyWrapCipher::Aes256,
+      decryption_key: key_location(&kek_id),
+      wrapped_key,
+      output: location,
+    };
+    let procedure_result = client.execute_procedure(StrongholdProcedure::AesKeyWrapDecrypt(unwrap_key_procedure));
+    delete_secret(&client, &kek_id)?;
+    procedure_result.map_err(|err| {
+      KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+        .with_custom_message("unable to unwrap key")
+        .with_source(err)
+    })?;
+
+    // The public key is not covered by the key wrapping, so it must be checked against the unwrapped key.
+    index_key_id(&client, &key_id, key_type)?;
+    let unwrapped_public_key: KeyStorageResult<Jwk> = public_key(&client, &key_id);
+    let public_key_matches: bool = unwrapped_public_key
+      .as_ref()
+      .map(|jwk| jwk.thumbprint_sha256_b64() == wrapped_jwk.public_key().thumbprint_sha256_b64())
+      .unwrap_or(false);
+    if !public_key_matches {
+      delete_secret(&client, &key_id)?;
+      unindex_key_id(&client, &key_id)?;
+      return Err(
+        KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+          .with_custom_message("the unwrapped private key does not match the exported public key"),
+      );
+    }
+
+    persist_changes(self, stronghold).await?;
+
+    Ok(key_id)
+  }
+}
+
+/// Adds `key_id` to the index of key ids, along with the type of the key.
+pub(crate) fn index_key_id(client: &Client, key_id: &KeyId, key_type: StrongholdKeyType) -> KeyStorageResult<()> {
+  client
+    .store()
+    .insert(index_key(key_id), key_type.name().as_bytes().to_vec(), None)
+    .map(|_| ())
+    .map_err(|err| {
+      KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+        .with_custom_message("stronghold store error")
+        .with_source(err)
+    })
+}
+
+/// Removes `key_id` from the index of key ids.
+pub(crate) fn unindex_key_id(client: &Client, key_id: &KeyId) -> KeyStorageResult<()> {
+  client.store().delete(&index_key(key_id)).map(|_| ()).map_err(|err| {
+    KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+      .with_custom_message("stronghold store error")
+      .with_source(err)
+  })
+}
+
+/// Returns the type of the key identified by `key_id`.
+///
+/// Keys that were not indexed were created when only Ed25519 keys were supported.
+pub(crate) fn stored_key_type(client: &Client, key_id: &KeyId) -> KeyStorageResult<StrongholdKeyType> {
+  let key_type: Option<Vec<u8>> = client.store().get(&index_key(key_id)).map_err(|err| {
+    KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+      .with_custom_message("stronghold store error")
+      .with_source(err)
+  })?;
+
+  match key_type.as_deref() {
+    None | Some(&[]) => Ok(StrongholdKeyType::Ed25519),
+    Some(key_type) => {
+      let key_type: &str = std::str::from_utf8(key_type).map_err(|err| {
+        KeyStorageError::new(KeyStorageErrorKind::SerializationError)
+          .with_custom_message("invalid key type in key id index")
+          .with_source(err)
+      })?;
+      StrongholdKeyType::try_from(&KeyType::new(key_type))
+    }
+  }
+}
+
+fn index_key(key_id: &KeyId) -> Vec<u8> {
+  format!("{KEY_ID_INDEX_PREFIX}{key_id}").into_bytes()
+}
+
+fn key_location(key_id: &KeyId) -> Location {
+  Location::generic(
+    IDENTITY_VAULT_PATH.as_bytes().to_vec(),
+    key_id.to_string().as_bytes().to_vec(),
+  )
+}
+
+fn record_exists(client: &Client, location: &Location) -> KeyStorageResult<bool> {
+  client.record_exists(location).map_err(|err| {
+    KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+      .with_custom_message("stronghold client error")
+      .with_source(err)
+  })
+}
+
+/// Writes the key-encryption key to a temporary record in the vault, such that it can be used by procedures.
+fn write_kek(client: &Client, kek: Zeroizing<[u8; 32]>) -> KeyStorageResult<KeyId> {
+  let kek_id: KeyId = random_key_id();
+  client
+    .vault(IDENTITY_VAULT_PATH.as_bytes())
+    .write_secret(key_location(&kek_id), Zeroizing::new(kek.to_vec()))
+    .map_err(|err| {
+      KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+        .with_custom_message("stronghold write secret failed")
+        .with_source(err)
+    })?;
+  Ok(kek_id)
+}
+
+fn delete_secret(client: &Client, key_id: &KeyId) -> KeyStorageResult<()> {
+  client
+    .vault(IDENTITY_VAULT_PATH.as_bytes())
+    .delete_secret(key_id.to_string().as_bytes())
+    .map(|_| ())
+    .map_err(|err| {
+      KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+        .with_custom_message("stronghold client error")
+        .with_source(err)
+    })
+}
@@ -2,5 +2,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod test_jwk_storage;
+mod test_jwk_storage_export;
 mod test_key_id_storage;
 pub(crate) mod utils;
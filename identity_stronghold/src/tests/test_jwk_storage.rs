@@ -3,8 +3,12 @@
 
 use std::path::PathBuf;
 
+use identity_ecdsa_verifier::EcDSAJwsVerifier;
 use identity_verification::jwk::Jwk;
 use identity_verification::jws::JwsAlgorithm;
+use identity_verification::jws::JwsVerifier;
+use identity_verification::jws::VerificationInput;
+use identity_verification::jwu;
 use iota_sdk::client::Password;
 
 use super::utils::create_stronghold_secret_manager;
@@ -12,6 +16,8 @@ use super::utils::create_temp_file;
 use crate::tests::utils::generate_ed25519;
 use crate::StrongholdStorage;
 use identity_storage::key_storage::JwkStorage;
+use identity_storage::key_storage::KeyId;
+use identity_storage::key_storage::KeyStorageErrorKind;
 use identity_storage::key_storage::KeyType;
 use iota_sdk::client::secret::stronghold::StrongholdSecretManager;
 
@@ -35,6 +41,12 @@ async fn retrieve() {
 
   let pub_key: Jwk = stronghold_storage.get_public_key(key_id).await.unwrap();
   assert_eq!(generate.jwk, pub_key);
+
+  let err = stronghold_storage
+    .get_public_key(&KeyId::new("non-existent-id"))
+    .await
+    .unwrap_err();
+  assert!(matches!(err.kind(), KeyStorageErrorKind::KeyNotFound));
 }
 
 #[tokio::test]
@@ -65,6 +77,209 @@ async fn key_exists() {
   jwk_storage_tests::test_key_exists(stronghold_storage).await;
 }
 
+#[tokio::test]
+async fn generate_and_sign_secp256k1() {
+  let stronghold_secret_manager = create_stronghold_secret_manager();
+  let stronghold_storage = StrongholdStorage::new(stronghold_secret_manager);
+  jwk_storage_tests::test_generate_and_sign_with_alg(
+    stronghold_storage,
+    crate::SECP256K1_KEY_TYPE,
+    JwsAlgorithm::ES256K,
+  )
+  .await;
+}
+
+#[tokio::test]
+async fn insert_secp256k1() {
+  let stronghold_secret_manager = create_stronghold_secret_manager();
+  let stronghold_storage = StrongholdStorage::new(stronghold_secret_manager);
+
+  let secret_key = k256::SecretKey::random(&mut rand::thread_rng());
+  let mut jwk: Jwk = crate::secp256k1::encode_public_jwk(&secret_key.public_key().to_sec1_bytes()).unwrap();
+  jwk.try_ec_params_mut().unwrap().d = Some(jwu::encode_b64(secret_key.to_bytes()));
+
+  // INVALID: Inserting a secp256k1 key with the EdDSA alg is not compatible.
+  jwk.set_alg(JwsAlgorithm::EdDSA.name());
+  let err = stronghold_storage.insert(jwk.clone()).await.unwrap_err();
+  assert!(matches!(err.kind(), KeyStorageErrorKind::KeyAlgorithmMismatch));
+
+  jwk.set_alg(JwsAlgorithm::ES256K.name());
+  let key_id: KeyId = stronghold_storage.insert(jwk.clone()).await.unwrap();
+
+  let mut public_key: Jwk = jwk.to_public().unwrap();
+  public_key.set_kid(public_key.thumbprint_sha256_b64());
+  assert_eq!(stronghold_storage.get_public_key(&key_id).await.unwrap(), public_key);
+
+  let signature = stronghold_storage.sign(&key_id, b"test", &public_key).await.unwrap();
+  let input = VerificationInput {
+    alg: JwsAlgorithm::ES256K,
+    signing_input: b"test".to_vec().into(),
+    decoded_signature: signature.into(),
+  };
+  EcDSAJwsVerifier::default().verify(input, &public_key).unwrap();
+}
+
+#[tokio::test]
+async fn x25519_key_agreement_keys() {
+  let stronghold_secret_manager = create_stronghold_secret_manager();
+  let stronghold_storage = StrongholdStorage::new(stronghold_secret_manager);
+
+  // INVALID: X25519 keys cannot be bound to a signature algorithm.
+  let err = stronghold_storage
+    .generate(crate::X25519_KEY_TYPE.clone(), JwsAlgorithm::EdDSA)
+    .await
+    .unwrap_err();
+  assert!(matches!(err.kind(), KeyStorageErrorKind::KeyAlgorithmMismatch));
+
+  let generate = stronghold_storage
+    .generate(crate::X25519_KEY_TYPE.clone(), JwsAlgorithm::NONE)
+    .await
+    .unwrap();
+  assert!(generate.jwk.alg().is_none());
+  assert!(generate.jwk.is_public());
+  assert_eq!(
+    stronghold_storage.get_public_key(&generate.key_id).await.unwrap(),
+    generate.jwk
+  );
+
+  // INVALID: X25519 keys cannot be used for signing.
+  let mut public_key = generate.jwk.clone();
+  public_key.set_alg(JwsAlgorithm::EdDSA.name());
+  let err = stronghold_storage
+    .sign(&generate.key_id, b"test", &public_key)
+    .await
+    .unwrap_err();
+  assert!(matches!(err.kind(), KeyStorageErrorKind::Unspecified));
+
+  // VALID: X25519 keys can be inserted without an `alg` parameter.
+  let secret_key = crypto::keys::x25519::SecretKey::generate().unwrap();
+  let mut jwk: Jwk = crate::x25519::encode_public_jwk(&secret_key.public_key().to_bytes()).unwrap();
+  jwk.try_okp_params_mut().unwrap().d = Some(jwu::encode_b64(secret_key.to_bytes()));
+  let key_id: KeyId = stronghold_storage.insert(jwk.clone()).await.unwrap();
+  let public_key: Jwk = stronghold_storage.get_public_key(&key_id).await.unwrap();
+  assert_eq!(public_key.thumbprint_sha256_b64(), jwk.thumbprint_sha256_b64());
+}
+
+#[tokio::test]
+async fn x25519_key_agreement() {
+  let stronghold_secret_manager = create_stronghold_secret_manager();
+  let stronghold_storage = StrongholdStorage::new(stronghold_secret_manager);
+
+  let alice = stronghold_storage
+    .generate(crate::X25519_KEY_TYPE.clone(), JwsAlgorithm::NONE)
+    .await
+    .unwrap();
+  let bob = stronghold_storage
+    .generate(crate::X25519_KEY_TYPE.clone(), JwsAlgorithm::NONE)
+    .await
+    .unwrap();
+  let eve = stronghold_storage
+    .generate(crate::X25519_KEY_TYPE.clone(), JwsAlgorithm::NONE)
+    .await
+    .unwrap();
+
+  // VALID: both parties derive the same shared secret.
+  let alice_secret: KeyId = stronghold_storage
+    .x25519_key_agreement(&alice.key_id, &bob.jwk)
+    .await
+    .unwrap();
+  let bob_secret: KeyId = stronghold_storage
+    .x25519_key_agreement(&bob.key_id, &alice.jwk)
+    .await
+    .unwrap();
+  let eve_secret: KeyId = stronghold_storage
+    .x25519_key_agreement(&eve.key_id, &alice.jwk)
+    .await
+    .unwrap();
+  assert_ne!(alice_secret, bob_secret);
+  assert_eq!(
+    shared_secret_mac(&stronghold_storage, &alice_secret).await,
+    shared_secret_mac(&stronghold_storage, &bob_secret).await
+  );
+  assert_ne!(
+    shared_secret_mac(&stronghold_storage, &alice_secret).await,
+    shared_secret_mac(&stronghold_storage, &eve_secret).await
+  );
+
+  stronghold_storage.delete(&alice_secret).await.unwrap();
+  assert!(!stronghold_storage.exists(&alice_secret).await.unwrap());
+
+  // INVALID: only X25519 keys can be used for key agreement.
+  let ed25519 = stronghold_storage
+    .generate(crate::ED25519_KEY_TYPE.clone(), JwsAlgorithm::EdDSA)
+    .await
+    .unwrap();
+  let err = stronghold_storage
+    .x25519_key_agreement(&ed25519.key_id, &bob.jwk)
+    .await
+    .unwrap_err();
+  assert!(matches!(err.kind(), KeyStorageErrorKind::KeyAlgorithmMismatch));
+
+  // INVALID: the public key of the other party must be an X25519 key.
+  let err = stronghold_storage
+    .x25519_key_agreement(&alice.key_id, &ed25519.jwk)
+    .await
+    .unwrap_err();
+  assert!(matches!(err.kind(), KeyStorageErrorKind::UnsupportedKeyType));
+
+  // INVALID: the key must exist.
+  let err = stronghold_storage
+    .x25519_key_agreement(&KeyId::new("nonexistent"), &bob.jwk)
+    .await
+    .unwrap_err();
+  assert!(matches!(err.kind(), KeyStorageErrorKind::KeyNotFound));
+}
+
+/// Computes a MAC keyed with the shared secret stored under `key_id`, so that secrets can be compared
+/// without leaving the vault.
+async fn shared_secret_mac(stronghold_storage: &StrongholdStorage, key_id: &KeyId) -> Vec<u8> {
+  let stronghold = stronghold_storage.get_stronghold().await;
+  let client = crate::stronghold_jwk_storage::get_client(&stronghold).unwrap();
+  let hmac = iota_stronghold::procedures::Hmac {
+    hash_type: iota_stronghold::procedures::Sha2Hash::Sha256,
+    msg: b"test".to_vec(),
+    key: iota_stronghold::Location::generic(
+      crate::stronghold_jwk_storage::IDENTITY_VAULT_PATH.as_bytes().to_vec(),
+      key_id.to_string().as_bytes().to_vec(),
+    ),
+  };
+  client
+    .execute_procedure(iota_stronghold::procedures::StrongholdProcedure::Hmac(hmac))
+    .unwrap()
+    .into()
+}
+
+#[tokio::test]
+async fn sign_with_mismatched_key() {
+  let stronghold_secret_manager = create_stronghold_secret_manager();
+  let stronghold_storage = StrongholdStorage::new(stronghold_secret_manager);
+
+  let ed25519 = stronghold_storage
+    .generate(crate::ED25519_KEY_TYPE.clone(), JwsAlgorithm::EdDSA)
+    .await
+    .unwrap();
+  let secp256k1 = stronghold_storage
+    .generate(crate::SECP256K1_KEY_TYPE.clone(), JwsAlgorithm::ES256K)
+    .await
+    .unwrap();
+
+  // INVALID: The public key must match the algorithm.
+  let mut public_key = ed25519.jwk.clone();
+  public_key.set_alg(JwsAlgorithm::ES256K.name());
+  let err = stronghold_storage
+    .sign(&ed25519.key_id, b"test", &public_key)
+    .await
+    .unwrap_err();
+  assert!(matches!(err.kind(), KeyStorageErrorKind::Unspecified));
+
+  // INVALID: The stored key must match the public key.
+  let err = stronghold_storage
+    .sign(&ed25519.key_id, b"test", &secp256k1.jwk)
+    .await
+    .unwrap_err();
+  assert!(matches!(err.kind(), KeyStorageErrorKind::UnsupportedKeyType));
+}
+
 // Tests the cases that require persisting to disk, generate, insert and delete.
 #[tokio::test]
 async fn write_to_disk() {
@@ -125,6 +340,7 @@ mod jwk_storage_tests {
   use crypto::signatures::ed25519::PublicKey;
   use crypto::signatures::ed25519::SecretKey;
   use crypto::signatures::ed25519::Signature;
+  use identity_ecdsa_verifier::EcDSAJwsVerifier;
   use identity_storage::key_storage::JwkStorage;
   use identity_storage::key_storage::KeyId;
   use identity_storage::key_storage::KeyStorageErrorKind;
@@ -136,6 +352,8 @@ mod jwk_storage_tests {
   use identity_verification::jwk::EcCurve;
   use identity_verification::jwk::JwkParamsEc;
   use identity_verification::jws::JwsAlgorithm;
+  use identity_verification::jws::JwsVerifier;
+  use identity_verification::jws::VerificationInput;
 
   use crate::ed25519;
 
@@ -196,6 +414,27 @@ mod jwk_storage_tests {
     store.delete(&key_id).await.unwrap();
   }
 
+  pub(crate) async fn test_generate_and_sign_with_alg(store: impl JwkStorage, key_type: &KeyType, alg: JwsAlgorithm) {
+    let test_msg: &[u8] = b"test";
+
+    let generate = store.generate(key_type.clone(), alg).await.unwrap();
+    assert_eq!(generate.jwk.alg(), Some(alg.name()));
+    assert!(generate.jwk.is_public());
+
+    let signature = store.sign(&generate.key_id, test_msg, &generate.jwk).await.unwrap();
+
+    let input = VerificationInput {
+      alg,
+      signing_input: test_msg.into(),
+      decoded_signature: signature.into(),
+    };
+    EcDSAJwsVerifier::default().verify(input, &generate.jwk).unwrap();
+
+    let key_id: KeyId = generate.key_id;
+    assert!(store.exists(&key_id).await.unwrap());
+    store.delete(&key_id).await.unwrap();
+  }
+
   pub(crate) async fn test_key_exists(store: impl JwkStorage) {
     assert!(!store.exists(&KeyId::new("non-existent-id")).await.unwrap());
   }
@@ -0,0 +1,191 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_ecdsa_verifier::EcDSAJwsVerifier;
+use identity_storage::key_storage::JwkMemStore;
+use identity_storage::key_storage::JwkStorage;
+use identity_storage::key_storage::JwkStorageExport;
+use identity_storage::key_storage::KeyWrappingKey;
+use identity_storage::key_storage::WrappedJwk;
+use identity_storage::KeyId;
+use identity_storage::KeyStorageErrorKind;
+use identity_storage::KeyType;
+use identity_verification::jws::JwsAlgorithm;
+use identity_verification::jws::JwsVerifier;
+use identity_verification::jws::VerificationInput;
+
+use super::utils::create_stronghold_secret_manager;
+use crate::StrongholdStorage;
+
+#[tokio::test]
+async fn list() {
+  let stronghold_storage = StrongholdStorage::new(create_stronghold_secret_manager());
+  assert!(stronghold_storage.list().await.unwrap().is_empty());
+
+  let generate = stronghold_storage
+    .generate(KeyType::new("Ed25519"), JwsAlgorithm::EdDSA)
+    .await
+    .unwrap();
+  let generate_other = stronghold_storage
+    .generate(KeyType::new("Ed25519"), JwsAlgorithm::EdDSA)
+    .await
+    .unwrap();
+
+  let mut key_ids: Vec<KeyId> = stronghold_storage.list().await.unwrap();
+  key_ids.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+  let mut expected: Vec<KeyId> = vec![generate.key_id.clone(), generate_other.key_id];
+  expected.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+  assert_eq!(key_ids, expected);
+
+  stronghold_storage.delete(&generate.key_id).await.unwrap();
+  assert!(!stronghold_storage.list().await.unwrap().contains(&generate.key_id));
+}
+
+#[tokio::test]
+async fn export_and_import() {
+  let source = StrongholdStorage::new(create_stronghold_secret_manager());
+  let target = StrongholdStorage::new(create_stronghold_secret_manager());
+  let wrapping_key: KeyWrappingKey = KeyWrappingKey::kek([42; 32]);
+
+  let generate = source
+    .generate(KeyType::new("Ed25519"), JwsAlgorithm::EdDSA)
+    .await
+    .unwrap();
+  let wrapped_jwk: WrappedJwk = source.export(&generate.key_id, &wrapping_key).await.unwrap();
+  assert_eq!(wrapped_jwk.public_key(), &generate.jwk);
+
+  // INVALID: Unwrapping with a different key must fail.
+  let err = target
+    .import(&wrapped_jwk, &KeyWrappingKey::kek([7; 32]))
+    .await
+    .unwrap_err();
+  assert!(matches!(err.kind(), KeyStorageErrorKind::Unspecified));
+  assert!(!target.exists(&generate.key_id).await.unwrap());
+
+  let key_id: KeyId = target.import(&wrapped_jwk, &wrapping_key).await.unwrap();
+  assert_eq!(key_id, generate.key_id);
+  assert_eq!(target.list().await.unwrap(), vec![key_id.clone()]);
+  assert_eq!(target.get_public_key(&key_id).await.unwrap(), generate.jwk);
+
+  let signature = target.sign(&key_id, b"test", &generate.jwk).await.unwrap();
+  let expected = source.sign(&generate.key_id, b"test", &generate.jwk).await.unwrap();
+  assert_eq!(signature, expected);
+
+  // INVALID: Importing a key id that already exists must fail.
+  let err = target.import(&wrapped_jwk, &wrapping_key).await.unwrap_err();
+  assert!(matches!(err.kind(), KeyStorageErrorKind::Unspecified));
+}
+
+#[tokio::test]
+async fn migrate_from_memstore() {
+  let memstore: JwkMemStore = JwkMemStore::new();
+  let stronghold_storage = StrongholdStorage::new(create_stronghold_secret_manager());
+  let wrapping_key: KeyWrappingKey = KeyWrappingKey::password("secure_password");
+
+  let generate = memstore
+    .generate(JwkMemStore::ED25519_KEY_TYPE, JwsAlgorithm::EdDSA)
+    .await
+    .unwrap();
+  // Keys of types not supported by Stronghold cannot be migrated.
+  let p256 = memstore
+    .generate(JwkMemStore::P256_KEY_TYPE, JwsAlgorithm::ES256)
+    .await
+    .unwrap();
+
+  for key_id in memstore.list().await.unwrap() {
+    let wrapped_jwk: WrappedJwk = memstore.export(&key_id, &wrapping_key).await.unwrap();
+    let result = stronghold_storage.import(&wrapped_jwk, &wrapping_key).await;
+    if key_id == p256.key_id {
+      assert!(matches!(
+        result.unwrap_err().kind(),
+        KeyStorageErrorKind::UnsupportedKeyType
+      ));
+    } else {
+      assert_eq!(result.unwrap(), key_id);
+    }
+  }
+
+  let signature = stronghold_storage
+    .sign(&generate.key_id, b"test", &generate.jwk)
+    .await
+    .unwrap();
+  let expected = memstore.sign(&generate.key_id, b"test", &generate.jwk).await.unwrap();
+  assert_eq!(signature, expected);
+}
+
+#[tokio::test]
+async fn export_to_memstore() {
+  let stronghold_storage = StrongholdStorage::new(create_stronghold_secret_manager());
+  let memstore: JwkMemStore = JwkMemStore::new();
+  let wrapping_key: KeyWrappingKey = KeyWrappingKey::kek([42; 32]);
+
+  let generate = stronghold_storage
+    .generate(KeyType::new("Ed25519"), JwsAlgorithm::EdDSA)
+    .await
+    .unwrap();
+  let wrapped_jwk: WrappedJwk = stronghold_storage
+    .export(&generate.key_id, &wrapping_key)
+    .await
+    .unwrap();
+  let key_id: KeyId = memstore.import(&wrapped_jwk, &wrapping_key).await.unwrap();
+
+  let signature = memstore.sign(&key_id, b"test", &generate.jwk).await.unwrap();
+  let expected = stronghold_storage
+    .sign(&generate.key_id, b"test", &generate.jwk)
+    .await
+    .unwrap();
+  assert_eq!(signature, expected);
+}
+
+#[tokio::test]
+async fn export_missing_key() {
+  let stronghold_storage = StrongholdStorage::new(create_stronghold_secret_manager());
+  let err = stronghold_storage
+    .export(&KeyId::new("non-existent-id"), &KeyWrappingKey::kek([42; 32]))
+    .await
+    .unwrap_err();
+  assert!(matches!(err.kind(), KeyStorageErrorKind::KeyNotFound));
+}
+
+#[tokio::test]
+async fn migrate_secp256k1_and_x25519_keys() {
+  let memstore: JwkMemStore = JwkMemStore::new();
+  let stronghold_storage = StrongholdStorage::new(create_stronghold_secret_manager());
+  let wrapping_key: KeyWrappingKey = KeyWrappingKey::kek([42; 32]);
+
+  let secp256k1 = memstore
+    .generate(JwkMemStore::SECP256K1_KEY_TYPE, JwsAlgorithm::ES256K)
+    .await
+    .unwrap();
+  let x25519 = memstore
+    .generate(JwkMemStore::X25519_KEY_TYPE, JwsAlgorithm::NONE)
+    .await
+    .unwrap();
+
+  for generate in [&secp256k1, &x25519] {
+    let wrapped_jwk: WrappedJwk = memstore.export(&generate.key_id, &wrapping_key).await.unwrap();
+    let key_id: KeyId = stronghold_storage.import(&wrapped_jwk, &wrapping_key).await.unwrap();
+    let public_key = stronghold_storage.get_public_key(&key_id).await.unwrap();
+    assert_eq!(public_key.thumbprint_sha256_b64(), generate.jwk.thumbprint_sha256_b64());
+  }
+
+  let signature = stronghold_storage
+    .sign(&secp256k1.key_id, b"test", &secp256k1.jwk)
+    .await
+    .unwrap();
+  let input = VerificationInput {
+    alg: JwsAlgorithm::ES256K,
+    signing_input: b"test".to_vec().into(),
+    decoded_signature: signature.into(),
+  };
+  EcDSAJwsVerifier::default().verify(input, &secp256k1.jwk).unwrap();
+
+  // Keys are exported again with their original public key.
+  let target: JwkMemStore = JwkMemStore::new();
+  let wrapped_jwk: WrappedJwk = stronghold_storage
+    .export(&secp256k1.key_id, &wrapping_key)
+    .await
+    .unwrap();
+  let key_id: KeyId = target.import(&wrapped_jwk, &wrapping_key).await.unwrap();
+  assert!(target.sign(&key_id, b"test", &secp256k1.jwk).await.is_ok());
+}
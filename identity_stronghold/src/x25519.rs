@@ -0,0 +1,86 @@
+// Copyright 2020-2023 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crypto::keys::x25519::PublicKey;
+use crypto::keys::x25519::SecretKey;
+use identity_verification::jose::jwk::EcxCurve;
+use identity_verification::jose::jwk::Jwk;
+use identity_verification::jose::jwk::JwkParamsOkp;
+use identity_verification::jose::jwu;
+
+use identity_storage::key_storage::KeyStorageError;
+use identity_storage::key_storage::KeyStorageErrorKind;
+use identity_storage::key_storage::KeyStorageResult;
+
+pub(crate) fn expand_secret_jwk(jwk: &Jwk) -> KeyStorageResult<SecretKey> {
+  let params: &JwkParamsOkp = x25519_params(jwk)?;
+
+  let sk: Vec<u8> = params
+    .d
+    .as_deref()
+    .map(jwu::decode_b64)
+    .ok_or_else(|| {
+      KeyStorageError::new(KeyStorageErrorKind::Unspecified).with_custom_message("expected Jwk `d` param to be present")
+    })?
+    .map_err(|err| {
+      KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+        .with_custom_message("unable to decode `d` param")
+        .with_source(err)
+    })?;
+
+  SecretKey::try_from_slice(&sk).map_err(|err| {
+    KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+      .with_custom_message("invalid X25519 private key")
+      .with_source(err)
+  })
+}
+
+/// Decodes the X25519 public key of `jwk`.
+pub(crate) fn expand_public_jwk(jwk: &Jwk) -> KeyStorageResult<PublicKey> {
+  let params: &JwkParamsOkp = x25519_params(jwk)?;
+
+  let pk: Vec<u8> = jwu::decode_b64(params.x.as_str()).map_err(|err| {
+    KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+      .with_custom_message("unable to decode `x` param")
+      .with_source(err)
+  })?;
+
+  PublicKey::try_from_slice(&pk).map_err(|err| {
+    KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+      .with_custom_message("invalid X25519 public key")
+      .with_source(err)
+  })
+}
+
+/// Encodes an X25519 public key as a [`Jwk`].
+pub(crate) fn encode_public_jwk(public_key: &[u8]) -> KeyStorageResult<Jwk> {
+  let public_key: PublicKey = PublicKey::try_from_slice(public_key).map_err(|err| {
+    KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+      .with_custom_message("invalid X25519 public key")
+      .with_source(err)
+  })?;
+
+  let mut params = JwkParamsOkp::new();
+  params.x = jwu::encode_b64(public_key.to_bytes());
+  params.crv = EcxCurve::X25519.name().to_owned();
+  Ok(Jwk::from_params(params))
+}
+
+fn x25519_params(jwk: &Jwk) -> KeyStorageResult<&JwkParamsOkp> {
+  let params: &JwkParamsOkp = jwk
+    .try_okp_params()
+    .map_err(|err| KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType).with_source(err))?;
+
+  if params
+    .try_ecx_curve()
+    .map_err(|err| KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType).with_source(err))?
+    != EcxCurve::X25519
+  {
+    return Err(
+      KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
+        .with_custom_message(format!("expected an {} key", EcxCurve::X25519.name())),
+    );
+  }
+
+  Ok(params)
+}
@@ -12,6 +12,7 @@ use crate::error::Result;
 const ED25519_VERIFICATION_KEY_2018_STR: &str = "Ed25519VerificationKey2018";
 const X25519_KEY_AGREEMENT_KEY_2019_STR: &str = "X25519KeyAgreementKey2019";
 const JSON_WEB_KEY_METHOD_TYPE: &str = "JsonWebKey";
+const MULTIKEY_METHOD_TYPE: &str = "Multikey";
 
 /// verification method types.
 #[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
@@ -25,6 +26,9 @@ impl MethodType {
   /// A verification method for use with JWT verification as prescribed by the [`Jwk`](::identity_jose::jwk::Jwk)
   /// in the [`publicKeyJwk`](crate::MethodData::PublicKeyJwk) entry.
   pub const JSON_WEB_KEY: Self = Self(Cow::Borrowed(JSON_WEB_KEY_METHOD_TYPE));
+  /// A verification method whose [`publicKeyMultibase`](crate::MethodData::PublicKeyMultibase) entry is a multicodec
+  /// prefixed public key, as prescribed by the [Multikey](https://www.w3.org/TR/controller-document/#multikey) format.
+  pub const MULTIKEY: Self = Self(Cow::Borrowed(MULTIKEY_METHOD_TYPE));
   /// Construct a custom method type.
   pub fn custom(type_: impl AsRef<str>) -> Self {
     Self(Cow::Owned(type_.as_ref().to_owned()))
@@ -58,6 +62,7 @@ impl FromStr for MethodType {
       ED25519_VERIFICATION_KEY_2018_STR => Ok(Self::ED25519_VERIFICATION_KEY_2018),
       X25519_KEY_AGREEMENT_KEY_2019_STR => Ok(Self::X25519_KEY_AGREEMENT_KEY_2019),
       JSON_WEB_KEY_METHOD_TYPE => Ok(Self::JSON_WEB_KEY),
+      MULTIKEY_METHOD_TYPE => Ok(Self::MULTIKEY),
       _ => Ok(Self(Cow::Owned(string.to_owned()))),
     }
   }
@@ -74,6 +79,8 @@ mod tests {
     for method_type in [
       MethodType::ED25519_VERIFICATION_KEY_2018,
       MethodType::X25519_KEY_AGREEMENT_KEY_2019,
+      MethodType::JSON_WEB_KEY,
+      MethodType::MULTIKEY,
     ] {
       let ser: Value = serde_json::to_value(method_type.clone()).unwrap();
       assert_eq!(ser.as_str().unwrap(), method_type.as_str());